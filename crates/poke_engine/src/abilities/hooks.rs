@@ -1,11 +1,13 @@
 use super::weather::{Terrain, Weather};
+use crate::battle::BattleSink;
 use crate::moves::{Move, MoveCategory, MoveId};
+use crate::species::SpeciesId;
 use crate::state::BattleState;
 use crate::state::Hazard;
 use crate::types::Type;
 
 /// Called when a Pokemon switches in (after hazards)
-pub type OnSwitchIn = fn(state: &mut BattleState, switched_idx: usize);
+pub type OnSwitchIn = fn(state: &mut BattleState, switched_idx: usize, events: &mut dyn BattleSink);
 
 /// Called during turn ordering to modify move priority
 pub type OnModifyPriority =
@@ -19,7 +21,13 @@ pub type OnModifyDamage =
     fn(state: &BattleState, attacker: usize, defender: usize, damage: u16) -> u16;
 
 /// Called after damage has been dealt
-pub type OnAfterDamage = fn(state: &mut BattleState, attacker: usize, defender: usize, damage: u16);
+pub type OnAfterDamage = fn(
+    state: &mut BattleState,
+    attacker: usize,
+    defender: usize,
+    damage: u16,
+    events: &mut dyn BattleSink,
+);
 
 /// Called when a stat boost is applied to modify the stage change
 pub type OnStatChange = fn(change: i8) -> i8;
@@ -111,7 +119,41 @@ pub type OnModifyMultiHit = fn(
     attacker: usize,
     defender: usize,
     move_id: MoveId,
-) -> Option<Vec<crate::damage::Modifier>>;
+) -> Option<crate::damage::ExtraHits<crate::damage::Modifier>>;
+
+/// What prompted a forme-change check.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FormeTrigger {
+    /// The Pokémon switched in
+    SwitchIn,
+    /// The Pokémon is about to switch out (not when it fainted)
+    SwitchOut,
+    /// The Pokémon is about to use a move (Stance Change)
+    BeforeMove(MoveId),
+    /// The Pokémon is about to take a hit from a damaging move. Changing
+    /// forme here means the ability took the hit instead (Disguise, Ice Face).
+    Hit(MoveId),
+    /// The Pokémon's move knocked out its target (Battle Bond)
+    Knockout,
+    /// End of turn (Zen Mode, Schooling, Power Construct, ...)
+    Residual,
+    /// The weather changed (Forecast, Ice Face)
+    WeatherChange,
+}
+
+/// A forme change decided by an ability.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FormeChange {
+    pub forme: SpeciesId,
+    /// Whether the forme survives switching out (Disguise, Power Construct)
+    pub permanent: bool,
+}
+
+/// Called to decide an in-battle forme change from HP, weather or the move
+/// used. Returns `None` to stay in the current forme; the battle applies
+/// the change.
+pub type OnFormeChange =
+    fn(state: &BattleState, entity: usize, trigger: FormeTrigger) -> Option<FormeChange>;
 
 // ============================================================================
 // AbilityHooks Struct
@@ -141,6 +183,8 @@ pub struct AbilityHooks {
     pub on_status_immunity: Option<OnStatusImmunity>,
     // Multi-hit hooks
     pub on_modify_multi_hit: Option<OnModifyMultiHit>,
+    // Forme-change hooks
+    pub on_forme_change: Option<OnFormeChange>,
 }
 
 impl AbilityHooks {
@@ -164,6 +208,7 @@ impl AbilityHooks {
         on_ignore_status_damage_reduction: None,
         on_status_immunity: None,
         on_modify_multi_hit: None,
+        on_forme_change: None,
     };
 
     /// Helper to set weather
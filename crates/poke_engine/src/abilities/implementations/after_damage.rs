@@ -0,0 +1,21 @@
+//! Abilities that react after the holder takes damage.
+
+use crate::battle::{BattleSink, Event};
+use crate::state::BattleState;
+
+/// Stamina: +1 Defense each time the holder is hit by a damaging move.
+pub fn stamina(
+    state: &mut BattleState,
+    _attacker: usize,
+    defender: usize,
+    damage: u16,
+    events: &mut dyn BattleSink,
+) {
+    if damage == 0 || state.is_fainted(defender) || state.boosts[defender][1] >= 6 {
+        return;
+    }
+    let ability = state.abilities[defender];
+    events.event(state, &Event::AbilityActivated { entity: defender, ability });
+    state.apply_stat_change(defender, 2, 1);
+    events.event(state, &Event::Boost { entity: defender, stat: 1, delta: 1 });
+}
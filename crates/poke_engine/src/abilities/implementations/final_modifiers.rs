@@ -179,6 +179,9 @@ mod tests {
             terrain: TerrainId::None,
             target: MoveTarget::Normal,
             multihit: (0, 0),
+            z_power: None,
+            gen: 9,
+            nonstandard: None,
         }
     }
 
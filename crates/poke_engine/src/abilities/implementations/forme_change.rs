@@ -0,0 +1,205 @@
+//! Forme-changing abilities.
+//!
+//! Called via `OnFormeChange`; each hook only picks the forme; the battle
+//! applies it with `BattleState::ability_forme_change`.
+
+use crate::abilities::hooks::{FormeChange, FormeTrigger};
+use crate::damage::generations::Weather;
+use crate::moves::{MoveCategory, MoveId};
+use crate::species::SpeciesId;
+use crate::state::BattleState;
+
+fn temporary(forme: SpeciesId) -> Option<FormeChange> {
+    Some(FormeChange {
+        forme,
+        permanent: false,
+    })
+}
+
+fn permanent(forme: SpeciesId) -> Option<FormeChange> {
+    Some(FormeChange {
+        forme,
+        permanent: true,
+    })
+}
+
+fn category(state: &BattleState, move_id: MoveId) -> MoveCategory {
+    move_id.data_in(state.generation).category
+}
+
+/// Stance Change: Blade Forme before attacking, Shield Forme before King's Shield
+pub fn stance_change(
+    state: &BattleState,
+    idx: usize,
+    trigger: FormeTrigger,
+) -> Option<FormeChange> {
+    let FormeTrigger::BeforeMove(move_id) = trigger else {
+        return None;
+    };
+    match state.species[idx] {
+        SpeciesId::AEGISLASHBLADE if move_id == MoveId::Kingsshield => {
+            temporary(SpeciesId::AEGISLASH)
+        }
+        SpeciesId::AEGISLASH if category(state, move_id) != MoveCategory::Status => {
+            temporary(SpeciesId::AEGISLASHBLADE)
+        }
+        _ => None,
+    }
+}
+
+/// Zen Mode: Zen Mode at the end of a turn at 1/2 HP or less, back above it
+pub fn zen_mode(state: &BattleState, idx: usize, trigger: FormeTrigger) -> Option<FormeChange> {
+    if trigger != FormeTrigger::Residual {
+        return None;
+    }
+    let low = state.hp[idx] <= state.max_hp[idx] / 2;
+    let forme = match state.species[idx] {
+        SpeciesId::DARMANITAN if low => SpeciesId::DARMANITANZEN,
+        SpeciesId::DARMANITANZEN if !low => SpeciesId::DARMANITAN,
+        SpeciesId::DARMANITANGALAR if low => SpeciesId::DARMANITANGALARZEN,
+        SpeciesId::DARMANITANGALARZEN if !low => SpeciesId::DARMANITANGALAR,
+        _ => return None,
+    };
+    temporary(forme)
+}
+
+/// Schooling: School Form above 1/4 HP from level 20
+pub fn schooling(state: &BattleState, idx: usize, trigger: FormeTrigger) -> Option<FormeChange> {
+    if !matches!(trigger, FormeTrigger::SwitchIn | FormeTrigger::Residual)
+        || state.species[idx].base() != SpeciesId::WISHIWASHI
+        || state.level[idx] < 20
+    {
+        return None;
+    }
+    if state.hp[idx] > state.max_hp[idx] / 4 {
+        temporary(SpeciesId::WISHIWASHISCHOOL)
+    } else {
+        temporary(SpeciesId::WISHIWASHI)
+    }
+}
+
+/// Shields Down: Meteor Form above 1/2 HP, its Core otherwise
+pub fn shields_down(state: &BattleState, idx: usize, trigger: FormeTrigger) -> Option<FormeChange> {
+    if !matches!(trigger, FormeTrigger::SwitchIn | FormeTrigger::Residual)
+        || state.species[idx].base() != SpeciesId::MINIOR
+    {
+        return None;
+    }
+    if state.hp[idx] > state.max_hp[idx] / 2 {
+        return temporary(SpeciesId::MINIORMETEOR);
+    }
+    // Cores come in several colours; go back to the one the Pokémon has.
+    match state.original_species[idx] {
+        SpeciesId::MINIORMETEOR => temporary(SpeciesId::MINIOR),
+        core => temporary(core),
+    }
+}
+
+/// Disguise: the first damaging hit only busts the disguise
+pub fn disguise(state: &BattleState, idx: usize, trigger: FormeTrigger) -> Option<FormeChange> {
+    match trigger {
+        FormeTrigger::Hit(move_id)
+            if state.species[idx] == SpeciesId::MIMIKYU
+                && category(state, move_id) != MoveCategory::Status =>
+        {
+            permanent(SpeciesId::MIMIKYUBUSTED)
+        }
+        _ => None,
+    }
+}
+
+/// Ice Face: the first physical hit only breaks the ice, which comes back
+/// when snow (or hail) starts or Eiscue switches in during it
+pub fn ice_face(state: &BattleState, idx: usize, trigger: FormeTrigger) -> Option<FormeChange> {
+    let snowing = matches!(
+        Weather::from_u8(state.weather),
+        Weather::Hail | Weather::Snow
+    );
+    match (state.species[idx], trigger) {
+        (SpeciesId::EISCUE, FormeTrigger::Hit(move_id))
+            if category(state, move_id) == MoveCategory::Physical =>
+        {
+            permanent(SpeciesId::EISCUENOICE)
+        }
+        (SpeciesId::EISCUENOICE, FormeTrigger::SwitchIn | FormeTrigger::WeatherChange)
+            if snowing =>
+        {
+            permanent(SpeciesId::EISCUE)
+        }
+        _ => None,
+    }
+}
+
+/// Power Construct: Complete Forme at the end of a turn at 1/2 HP or less
+pub fn power_construct(
+    state: &BattleState,
+    idx: usize,
+    trigger: FormeTrigger,
+) -> Option<FormeChange> {
+    let species = state.species[idx];
+    if trigger == FormeTrigger::Residual
+        && matches!(species, SpeciesId::ZYGARDE | SpeciesId::ZYGARDE10)
+        && state.hp[idx] <= state.max_hp[idx] / 2
+    {
+        permanent(SpeciesId::ZYGARDECOMPLETE)
+    } else {
+        None
+    }
+}
+
+/// Zero to Hero: Hero Form once Palafin switches out
+pub fn zero_to_hero(state: &BattleState, idx: usize, trigger: FormeTrigger) -> Option<FormeChange> {
+    if trigger == FormeTrigger::SwitchOut && state.species[idx] == SpeciesId::PALAFIN {
+        permanent(SpeciesId::PALAFINHERO)
+    } else {
+        None
+    }
+}
+
+/// Hunger Switch: alternates Full Belly and Hangry Mode every turn, except
+/// while Terastallized
+pub fn hunger_switch(
+    state: &BattleState,
+    idx: usize,
+    trigger: FormeTrigger,
+) -> Option<FormeChange> {
+    if trigger != FormeTrigger::Residual || state.terastallized[idx] {
+        return None;
+    }
+    match state.species[idx] {
+        SpeciesId::MORPEKO => temporary(SpeciesId::MORPEKOHANGRY),
+        SpeciesId::MORPEKOHANGRY => temporary(SpeciesId::MORPEKO),
+        _ => None,
+    }
+}
+
+/// Battle Bond: Ash-Greninja after knocking out a Pokémon. Gen 9 raises
+/// stats instead, which isn't modelled yet.
+pub fn battle_bond(state: &BattleState, idx: usize, trigger: FormeTrigger) -> Option<FormeChange> {
+    if trigger == FormeTrigger::Knockout
+        && state.species[idx] == SpeciesId::GRENINJABOND
+        && state.generation <= 8
+    {
+        permanent(SpeciesId::GRENINJAASH)
+    } else {
+        None
+    }
+}
+
+/// Forecast: Castform takes the forme of the weather
+pub fn forecast(state: &BattleState, idx: usize, trigger: FormeTrigger) -> Option<FormeChange> {
+    if !matches!(
+        trigger,
+        FormeTrigger::SwitchIn | FormeTrigger::WeatherChange
+    ) || state.species[idx].base() != SpeciesId::CASTFORM
+    {
+        return None;
+    }
+    let forme = match Weather::from_u8(state.weather) {
+        Weather::Sun | Weather::HarshSun => SpeciesId::CASTFORMSUNNY,
+        Weather::Rain | Weather::HeavyRain => SpeciesId::CASTFORMRAINY,
+        Weather::Hail | Weather::Snow => SpeciesId::CASTFORMSNOWY,
+        _ => SpeciesId::CASTFORM,
+    };
+    temporary(forme)
+}
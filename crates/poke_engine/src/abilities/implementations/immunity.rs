@@ -108,7 +108,7 @@ pub fn levitate_grounding(
     Some(false)
 }
 
-/// Magic Guard: Immune to hazard damage (Stealth Rock, Spikes)
+/// Magic Guard: Immune to hazard damage (Stealth Rock, Spikes, Steelsurge)
 pub fn magic_guard_hazard_immunity(
     _state: &BattleState,
     _entity: usize,
@@ -117,7 +117,9 @@ pub fn magic_guard_hazard_immunity(
     // Magic Guard prevents indirect damage, but not status or stat drops.
     // So it blocks SR and Spikes damage, but not Toxic Spikes (status) or Sticky Web (speed).
     match hazard {
-        crate::state::Hazard::StealthRock | crate::state::Hazard::Spikes => true,
+        crate::state::Hazard::StealthRock
+        | crate::state::Hazard::Spikes
+        | crate::state::Hazard::Steelsurge => true,
         crate::state::Hazard::ToxicSpikes | crate::state::Hazard::StickyWeb => false,
     }
 }
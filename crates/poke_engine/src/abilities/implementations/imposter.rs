@@ -0,0 +1,10 @@
+use crate::battle::{BattleSink, Event};
+use crate::state::BattleState;
+
+/// Imposter: Transform into the opposing Pokémon on switch-in.
+pub fn imposter(state: &mut BattleState, idx: usize, events: &mut dyn BattleSink) {
+    let target = state.active_index(1 - state.get_side(idx));
+    if state.transform(idx, target) {
+        events.event(state, &Event::Transform { user: idx, target });
+    }
+}
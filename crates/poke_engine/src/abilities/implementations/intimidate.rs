@@ -1,6 +1,8 @@
+use crate::abilities::AbilityId;
+use crate::battle::{BattleSink, Event};
 use crate::state::BattleState;
 
-pub fn intimidate(state: &mut BattleState, idx: usize) {
+pub fn intimidate(state: &mut BattleState, idx: usize, events: &mut dyn BattleSink) {
     // Determine opponent index (target active opponent)
     // Works for Singles. For Doubles, needs adjacency check.
     let side = if idx < 6 { 0 } else { 1 };
@@ -13,8 +15,16 @@ pub fn intimidate(state: &mut BattleState, idx: usize) {
     // TODO(TASK-E-PHASE2): Check Substitute
     // TODO(TASK-E-PHASE2): Check Mirror Armor (reflects stat drop)
 
+    let ability = AbilityId::Intimidate;
+    events.event(state, &Event::AbilityActivated { entity: idx, ability });
+
     // -1 Attack
     let current_boost = state.boosts[opponent_idx][0]; // 0 is Atk in boosts array
     let new_boost = (current_boost - 1).max(-6);
     state.boosts[opponent_idx][0] = new_boost;
+
+    let delta = new_boost - current_boost;
+    if delta != 0 {
+        events.event(state, &Event::Boost { entity: opponent_idx, stat: 0, delta });
+    }
 }
@@ -1,6 +1,9 @@
+pub mod after_damage;
 pub mod damage_modifiers;
 pub mod final_modifiers;
+pub mod forme_change;
 pub mod immunity;
+pub mod imposter;
 pub mod intimidate;
 pub mod multitype;
 pub mod parental_bond;
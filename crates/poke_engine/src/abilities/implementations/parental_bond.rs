@@ -1,4 +1,4 @@
-use crate::damage::Modifier;
+use crate::damage::{ExtraHits, Modifier};
 use crate::moves::{MoveId, MoveTarget};
 use crate::state::BattleState;
 
@@ -7,7 +7,7 @@ pub fn parental_bond(
     _attacker: usize,
     _defender: usize,
     move_id: MoveId,
-) -> Option<Vec<Modifier>> {
+) -> Option<ExtraHits<Modifier>> {
     let move_data = move_id.data();
 
     // Does not apply to moves that are already multi-hit
@@ -51,5 +51,5 @@ pub fn parental_bond(
         Modifier::new(1024) // 0.25x
     };
 
-    Some(vec![modifier])
+    Some([modifier].into_iter().collect())
 }
@@ -1,35 +1,71 @@
 use crate::state::BattleState;
 use crate::abilities::hooks::AbilityHooks;
 use crate::abilities::weather::{Weather, Terrain};
+use crate::battle::{BattleSink, Event};
+use crate::damage::generations::Weather as FieldWeather;
 
-pub fn drizzle(state: &mut BattleState, _idx: usize) {
-    AbilityHooks::set_weather(state, Weather::Rain, 5);
+fn start_weather(state: &mut BattleState, idx: usize, weather: Weather, events: &mut dyn BattleSink) {
+    // Primal weathers have no duration (0 = indefinite) and can only be
+    // replaced by another primal weather.
+    let is_primal = |w: u8| (Weather::HarshSun as u8..=Weather::StrongWinds as u8).contains(&w);
+    if is_primal(state.weather) && !is_primal(weather as u8) {
+        return;
+    }
+    let turns = if is_primal(weather as u8) { 0 } else { 5 };
+    AbilityHooks::set_weather(state, weather, turns);
+    let ability = state.abilities[idx];
+    events.event(state, &Event::AbilityActivated { entity: idx, ability });
+    let weather = FieldWeather::from_u8(weather as u8);
+    events.event(state, &Event::WeatherStart { weather });
 }
 
-pub fn drought(state: &mut BattleState, _idx: usize) {
-    AbilityHooks::set_weather(state, Weather::Sun, 5);
+fn start_terrain(state: &mut BattleState, idx: usize, terrain: Terrain, events: &mut dyn BattleSink) {
+    AbilityHooks::set_terrain(state, terrain, 5);
+    let ability = state.abilities[idx];
+    events.event(state, &Event::AbilityActivated { entity: idx, ability });
+    events.event(state, &Event::TerrainStart { terrain });
 }
 
-pub fn sand_stream(state: &mut BattleState, _idx: usize) {
-    AbilityHooks::set_weather(state, Weather::Sand, 5);
+pub fn drizzle(state: &mut BattleState, idx: usize, events: &mut dyn BattleSink) {
+    start_weather(state, idx, Weather::Rain, events);
 }
 
-pub fn snow_warning(state: &mut BattleState, _idx: usize) {
-    AbilityHooks::set_weather(state, Weather::Snow, 5);
+pub fn drought(state: &mut BattleState, idx: usize, events: &mut dyn BattleSink) {
+    start_weather(state, idx, Weather::Sun, events);
 }
 
-pub fn electric_surge(state: &mut BattleState, _idx: usize) {
-    AbilityHooks::set_terrain(state, Terrain::Electric, 5);
+pub fn sand_stream(state: &mut BattleState, idx: usize, events: &mut dyn BattleSink) {
+    start_weather(state, idx, Weather::Sand, events);
 }
 
-pub fn grassy_surge(state: &mut BattleState, _idx: usize) {
-    AbilityHooks::set_terrain(state, Terrain::Grassy, 5);
+pub fn snow_warning(state: &mut BattleState, idx: usize, events: &mut dyn BattleSink) {
+    start_weather(state, idx, Weather::Snow, events);
 }
 
-pub fn misty_surge(state: &mut BattleState, _idx: usize) {
-    AbilityHooks::set_terrain(state, Terrain::Misty, 5);
+pub fn desolate_land(state: &mut BattleState, idx: usize, events: &mut dyn BattleSink) {
+    start_weather(state, idx, Weather::HarshSun, events);
 }
 
-pub fn psychic_surge(state: &mut BattleState, _idx: usize) {
-    AbilityHooks::set_terrain(state, Terrain::Psychic, 5);
+pub fn primordial_sea(state: &mut BattleState, idx: usize, events: &mut dyn BattleSink) {
+    start_weather(state, idx, Weather::HeavyRain, events);
+}
+
+pub fn delta_stream(state: &mut BattleState, idx: usize, events: &mut dyn BattleSink) {
+    start_weather(state, idx, Weather::StrongWinds, events);
+}
+
+pub fn electric_surge(state: &mut BattleState, idx: usize, events: &mut dyn BattleSink) {
+    start_terrain(state, idx, Terrain::Electric, events);
+}
+
+pub fn grassy_surge(state: &mut BattleState, idx: usize, events: &mut dyn BattleSink) {
+    start_terrain(state, idx, Terrain::Grassy, events);
+}
+
+pub fn misty_surge(state: &mut BattleState, idx: usize, events: &mut dyn BattleSink) {
+    start_terrain(state, idx, Terrain::Misty, events);
+}
+
+pub fn psychic_surge(state: &mut BattleState, idx: usize, events: &mut dyn BattleSink) {
+    start_terrain(state, idx, Terrain::Psychic, events);
 }
@@ -8,7 +8,7 @@ pub mod registry;
 pub mod weather;
 pub mod implementations;
 
-pub use hooks::AbilityHooks;
+pub use hooks::{AbilityHooks, FormeChange, FormeTrigger};
 pub use registry::ABILITY_REGISTRY;
 pub use weather::{Weather, Terrain};
 
@@ -1,7 +1,7 @@
 use crate::abilities::hooks::AbilityHooks;
 use crate::abilities::implementations::{
-    damage_modifiers, final_modifiers, immunity, intimidate, multitype, parental_bond, priority,
-    speed, stat_modifiers, status, weather_setters,
+    after_damage, damage_modifiers, final_modifiers, forme_change, immunity, imposter, intimidate,
+    multitype, parental_bond, priority, speed, stat_modifiers, status, weather_setters,
 };
 use crate::abilities::AbilityId;
 
@@ -28,6 +28,19 @@ pub static ABILITY_REGISTRY: [Option<AbilityHooks>; AbilityId::COUNT] = {
         ..AbilityHooks::NONE
     });
 
+    registry[AbilityId::Desolateland as usize] = Some(AbilityHooks {
+        on_switch_in: Some(weather_setters::desolate_land),
+        ..AbilityHooks::NONE
+    });
+    registry[AbilityId::Primordialsea as usize] = Some(AbilityHooks {
+        on_switch_in: Some(weather_setters::primordial_sea),
+        ..AbilityHooks::NONE
+    });
+    registry[AbilityId::Deltastream as usize] = Some(AbilityHooks {
+        on_switch_in: Some(weather_setters::delta_stream),
+        ..AbilityHooks::NONE
+    });
+
     registry[AbilityId::Electricsurge as usize] = Some(AbilityHooks {
         on_switch_in: Some(weather_setters::electric_surge),
         ..AbilityHooks::NONE
@@ -45,6 +58,54 @@ pub static ABILITY_REGISTRY: [Option<AbilityHooks>; AbilityId::COUNT] = {
         ..AbilityHooks::NONE
     });
 
+    // =========================================================================
+    // Forme Changes
+    // =========================================================================
+    registry[AbilityId::Stancechange as usize] = Some(AbilityHooks {
+        on_forme_change: Some(forme_change::stance_change),
+        ..AbilityHooks::NONE
+    });
+    registry[AbilityId::Zenmode as usize] = Some(AbilityHooks {
+        on_forme_change: Some(forme_change::zen_mode),
+        ..AbilityHooks::NONE
+    });
+    registry[AbilityId::Schooling as usize] = Some(AbilityHooks {
+        on_forme_change: Some(forme_change::schooling),
+        ..AbilityHooks::NONE
+    });
+    registry[AbilityId::Shieldsdown as usize] = Some(AbilityHooks {
+        on_forme_change: Some(forme_change::shields_down),
+        ..AbilityHooks::NONE
+    });
+    registry[AbilityId::Disguise as usize] = Some(AbilityHooks {
+        on_forme_change: Some(forme_change::disguise),
+        ..AbilityHooks::NONE
+    });
+    registry[AbilityId::Iceface as usize] = Some(AbilityHooks {
+        on_forme_change: Some(forme_change::ice_face),
+        ..AbilityHooks::NONE
+    });
+    registry[AbilityId::Powerconstruct as usize] = Some(AbilityHooks {
+        on_forme_change: Some(forme_change::power_construct),
+        ..AbilityHooks::NONE
+    });
+    registry[AbilityId::Zerotohero as usize] = Some(AbilityHooks {
+        on_forme_change: Some(forme_change::zero_to_hero),
+        ..AbilityHooks::NONE
+    });
+    registry[AbilityId::Hungerswitch as usize] = Some(AbilityHooks {
+        on_forme_change: Some(forme_change::hunger_switch),
+        ..AbilityHooks::NONE
+    });
+    registry[AbilityId::Battlebond as usize] = Some(AbilityHooks {
+        on_forme_change: Some(forme_change::battle_bond),
+        ..AbilityHooks::NONE
+    });
+    registry[AbilityId::Forecast as usize] = Some(AbilityHooks {
+        on_forme_change: Some(forme_change::forecast),
+        ..AbilityHooks::NONE
+    });
+
     // =========================================================================
     // Priority Modifiers
     // =========================================================================
@@ -68,11 +129,23 @@ pub static ABILITY_REGISTRY: [Option<AbilityHooks>; AbilityId::COUNT] = {
         on_switch_in: Some(intimidate::intimidate),
         ..AbilityHooks::NONE
     });
+    registry[AbilityId::Imposter as usize] = Some(AbilityHooks {
+        on_switch_in: Some(imposter::imposter),
+        ..AbilityHooks::NONE
+    });
     registry[AbilityId::Multitype as usize] = Some(AbilityHooks {
         on_switch_in: Some(multitype::multitype_on_switch_in),
         ..AbilityHooks::NONE
     });
 
+    // =========================================================================
+    // After-damage Reactions
+    // =========================================================================
+    registry[AbilityId::Stamina as usize] = Some(AbilityHooks {
+        on_after_damage: Some(after_damage::stamina),
+        ..AbilityHooks::NONE
+    });
+
     // =========================================================================
     // Multi-hit Modifiers
     // =========================================================================
@@ -40,7 +40,7 @@ fn test_drizzle_hook() {
     let drizzle = AbilityId::Drizzle;
     if let Some(hook) = ABILITY_REGISTRY[drizzle as usize] {
         if let Some(on_switch_in) = hook.on_switch_in {
-            on_switch_in(&mut state, idx);
+            on_switch_in(&mut state, idx, &mut ());
         }
     }
 
@@ -69,3 +69,42 @@ fn test_prankster_hook() {
     let p2 = on_modify_priority(&state, idx, thunderbolt, 0);
     assert_eq!(p2, 0);
 }
+
+#[test]
+fn test_forme_change_hooks() {
+    use crate::species::SpeciesId;
+
+    let mut state = BattleState::new();
+    state.level[0] = 50;
+    state.max_hp[0] = 100;
+    state.hp[0] = 100;
+    let forme = |state: &BattleState, ability: AbilityId, trigger: FormeTrigger| {
+        let hook = ABILITY_REGISTRY[ability as usize].unwrap().on_forme_change.unwrap();
+        hook(state, 0, trigger).map(|change| change.forme)
+    };
+
+    // Schooling holds above 1/4 HP and only from level 20.
+    state.species[0] = SpeciesId::WISHIWASHI;
+    let school = Some(SpeciesId::WISHIWASHISCHOOL);
+    assert_eq!(forme(&state, AbilityId::Schooling, FormeTrigger::SwitchIn), school);
+    state.hp[0] = 25;
+    let solo = Some(SpeciesId::WISHIWASHI);
+    assert_eq!(forme(&state, AbilityId::Schooling, FormeTrigger::Residual), solo);
+    state.hp[0] = 100;
+    state.level[0] = 19;
+    assert_eq!(forme(&state, AbilityId::Schooling, FormeTrigger::SwitchIn), None);
+
+    // Forecast follows the weather.
+    state.species[0] = SpeciesId::CASTFORM;
+    state.weather = Weather::Rain as u8;
+    let rainy = Some(SpeciesId::CASTFORMRAINY);
+    assert_eq!(forme(&state, AbilityId::Forecast, FormeTrigger::WeatherChange), rainy);
+    assert_eq!(forme(&state, AbilityId::Forecast, FormeTrigger::Residual), None);
+
+    // Hunger Switch alternates every turn, but not while Terastallized.
+    state.species[0] = SpeciesId::MORPEKO;
+    let hangry = Some(SpeciesId::MORPEKOHANGRY);
+    assert_eq!(forme(&state, AbilityId::Hungerswitch, FormeTrigger::Residual), hangry);
+    state.terastallized[0] = true;
+    assert_eq!(forme(&state, AbilityId::Hungerswitch, FormeTrigger::Residual), None);
+}
@@ -25,3 +25,16 @@ pub enum Terrain {
     Misty = 3,
     Psychic = 4,
 }
+
+impl Terrain {
+    /// Convert from the raw u8 stored in `BattleState::terrain`.
+    pub fn from_u8(value: u8) -> Self {
+        match value {
+            1 => Terrain::Electric,
+            2 => Terrain::Grassy,
+            3 => Terrain::Misty,
+            4 => Terrain::Psychic,
+            _ => Terrain::None,
+        }
+    }
+}
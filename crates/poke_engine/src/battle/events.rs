@@ -0,0 +1,167 @@
+//! Typed battle events.
+//!
+//! Everything observable that happens during a battle is described by an
+//! `Event`. Events carry entity indices and IDs rather than strings, so
+//! subscribers (UIs, loggers, belief tracking) can match on them directly;
+//! `ProtocolWriter` is just one subscriber that renders them as text.
+
+use crate::abilities::{AbilityId, Terrain};
+use crate::damage::generations::Weather;
+use crate::items::ItemId;
+use crate::moves::MoveId;
+use crate::state::Status;
+use crate::types::Type;
+
+/// Why a Pokémon could not act this turn.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CantReason {
+    Sleep,
+    Freeze,
+    Paralysis,
+    Flinch,
+    /// Recharging after Hyper Beam
+    Recharge,
+    /// Held by Wrap and co. (Gen 1)
+    PartiallyTrapped,
+}
+
+/// What caused an HP change.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EffectSource {
+    /// The move currently being executed
+    Move,
+    /// Residual weather damage (Sandstorm, Hail)
+    Weather(Weather),
+    /// Residual status damage (Burn, Poison, Toxic)
+    Status(Status),
+    /// An entry hazard on switch-in (Stealth Rock, Spikes, G-Max Steelsurge)
+    Hazard(MoveId),
+    /// Recoil from the user's own move (Struggle)
+    Recoil,
+    /// An ability (Rough Skin, Volt Absorb, ...)
+    Ability(AbilityId),
+    /// A held item (Rocky Helmet, Sitrus Berry, ...)
+    Item(ItemId),
+    /// A move's lingering effect on a side (G-Max Wildfire, ...)
+    SideCondition(MoveId),
+    /// A protection punishing a contact move it blocked (Spiky Shield)
+    Protection(MoveId),
+    /// Residual damage from a partial trapping move (Wrap, Fire Spin, ...)
+    PartialTrap(MoveId),
+    /// Leech Seed's drain, on both the seeded Pokémon and the one it heals
+    LeechSeed,
+}
+
+/// A single observable battle event.
+///
+/// Events are emitted *after* the change they describe has been applied
+/// to the state, so sinks can read post-event HP, boosts, etc.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Event {
+    /// A new turn begins
+    TurnStart { turn: u16 },
+    /// A Pokémon entered the field
+    SwitchIn { entity: usize },
+    /// A Pokémon used a move
+    MoveUsed {
+        user: usize,
+        move_id: MoveId,
+        target: usize,
+    },
+    /// A Pokémon was prevented from acting
+    Cant { entity: usize, reason: CantReason },
+    /// A move missed its target
+    Miss { user: usize, target: usize },
+    /// A hit was critical
+    CriticalHit { target: usize },
+    /// Type effectiveness of a hit (4 = neutral, 0 = immune, 8 = 2x, ...)
+    Effectiveness { target: usize, effectiveness: u8 },
+    /// A multi-hit move finished after `hits` hits
+    HitCount { target: usize, hits: u8 },
+    /// A Pokémon lost HP
+    Damage {
+        target: usize,
+        amount: u16,
+        source: EffectSource,
+    },
+    /// A Pokémon restored HP
+    Heal {
+        target: usize,
+        amount: u16,
+        source: EffectSource,
+    },
+    /// A major status condition was inflicted
+    StatusInflicted { entity: usize, status: Status },
+    /// A major status condition was cured
+    StatusCured { entity: usize, status: Status },
+    /// A stat stage changed. `stat` indexes `BattleState::boosts` (0 = Atk ... 6 = Eva)
+    Boost { entity: usize, stat: usize, delta: i8 },
+    /// An ability announced itself (Intimidate, Drizzle, ...)
+    AbilityActivated { entity: usize, ability: AbilityId },
+    /// A held item was used up
+    ItemConsumed { entity: usize, item: ItemId },
+    /// A Pokémon Terastallized
+    Terastallize { entity: usize, tera_type: Type },
+    /// A Pokémon Dynamaxed
+    Dynamax { entity: usize },
+    /// A Pokémon's Dynamax ended
+    DynamaxEnd { entity: usize },
+    /// A Pokémon Mega Evolved with its held Mega Stone (`ItemId::None` for
+    /// Rayquaza's Dragon Ascent)
+    MegaEvolve { entity: usize, item: ItemId },
+    /// A Kyogre or Groudon underwent Primal Reversion on switch-in
+    PrimalReversion { entity: usize },
+    /// A Necrozma Ultra Bursted
+    UltraBurst { entity: usize },
+    /// A Pokémon Transformed into the target (Transform, Imposter)
+    Transform { user: usize, target: usize },
+    /// A Pokémon's Illusion was broken, revealing its real species
+    IllusionEnd { entity: usize },
+    /// An ability changed a Pokémon's forme (Stance Change, Disguise, ...)
+    FormeChange { entity: usize, permanent: bool },
+    /// A Pokémon used its side's Z-Power
+    ZPower { entity: usize },
+    /// A Z-Move hit through the target's protection for 1/4 damage
+    ZBrokeProtect { target: usize },
+    /// A Pokémon protected itself for the turn (Protect, Max Guard, ...)
+    Protect { entity: usize },
+    /// A move was blocked by the target's protection
+    Protected { target: usize },
+    /// A move failed (e.g. Low Kick against a Dynamaxed target)
+    Fail { user: usize },
+    /// A Pokémon must skip its next turn to recharge (Hyper Beam)
+    MustRecharge { entity: usize },
+    /// A partial trapping move (Wrap, Fire Spin, ...) caught the target
+    PartialTrap {
+        target: usize,
+        source: usize,
+        move_id: MoveId,
+    },
+    /// A partial trapping move released its target
+    PartialTrapEnd { target: usize, move_id: MoveId },
+    /// A Pokémon was seeded by Leech Seed
+    LeechSeed { target: usize },
+    /// Haze reset every stat stage on the field
+    ClearAllBoosts,
+    /// A Pokémon fainted
+    Faint { entity: usize },
+    /// Weather was set
+    WeatherStart { weather: Weather },
+    /// Weather continues at end of turn
+    WeatherUpkeep { weather: Weather },
+    /// Weather ended
+    WeatherEnd { weather: Weather },
+    /// Terrain was set
+    TerrainStart { terrain: Terrain },
+    /// Terrain was cleared (G-Max Wind Rage)
+    TerrainEnd { terrain: Terrain },
+    /// A move started a condition on a side (G-Max Wildfire, G-Max
+    /// Steelsurge, ...)
+    SideStart { side: usize, move_id: MoveId },
+    /// A side condition wore off
+    SideEnd { side: usize, move_id: MoveId },
+    /// End-of-turn residuals finished
+    Upkeep,
+    /// The battle ended. `None` is a tie.
+    Win { player: Option<usize> },
+}
@@ -0,0 +1,2745 @@
+//! Turn execution.
+//!
+//! Drives a `BattleState` through whole turns: action ordering, move
+//! execution, fainting and end-of-turn residuals. Randomness comes from an
+//! explicit `BattleRng`, and every observable step is reported to a
+//! `BattleSink`, so the same code path serves AI rollouts (`&mut ()`) and
+//! protocol logging (`ProtocolWriter`).
+//!
+//! Only singles is supported: each side has exactly one active Pokémon and
+//! every move targets the opposing active.
+
+pub mod events;
+pub mod protocol;
+pub mod rng;
+pub mod search;
+pub mod sink;
+pub mod snapshot;
+
+pub use events::{CantReason, EffectSource, Event};
+pub use protocol::ProtocolWriter;
+pub use rng::BattleRng;
+pub use search::{player_view, search_best_move, SearchOptions, SearchResult};
+pub use sink::BattleSink;
+pub use snapshot::BattleSnapshot;
+
+use alloc::vec::Vec;
+use core::{fmt, mem};
+
+use crate::abilities::{AbilityId, FormeTrigger, Terrain, ABILITY_REGISTRY};
+use crate::damage::generations::{Generation, Weather};
+use crate::damage::{
+    apply_modifier, calculate_damage, calculate_max_move_damage, calculate_priority,
+    calculate_z_move_damage, DamageContext, GenMechanics, Modifier,
+};
+use crate::items::{ItemId, ITEM_REGISTRY};
+use crate::moves::max::{
+    bypasses_protect, fails_against_dynamax, is_max_move, max_move_effect, max_move_for, MaxEffect,
+    SIDE_RESIDUALS,
+};
+use crate::moves::z::z_move_for;
+use crate::moves::{MoveCategory, MoveFlags, MoveId, ZPower};
+use crate::state::{
+    BattleState, SideConditions, Status, TurnOrder, Volatiles, BOOST_STATS, MAX_MOVES,
+    MAX_TEAM_SIZE,
+};
+use crate::types::Type;
+
+/// A player's choice for one turn.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Action {
+    /// Use the move in the given move slot (0-3)
+    Move(u8),
+    /// Terastallize, then use the move in the given move slot
+    Terastallize(u8),
+    /// Dynamax, then use the Max Move of the given move slot
+    Dynamax(u8),
+    /// Use the move in the given move slot as a Z-Move
+    ZMove(u8),
+    /// Mega Evolve, then use the move in the given move slot
+    MegaEvolve(u8),
+    /// Ultra Burst, then use the move in the given move slot
+    UltraBurst(u8),
+    /// Use the move in the given move slot in Strong Style (Legends: Arceus)
+    StrongStyle(u8),
+    /// Use the move in the given move slot in Agile Style (Legends: Arceus)
+    AgileStyle(u8),
+    /// Switch to the given team slot (0-5)
+    Switch(u8),
+    /// Do nothing (e.g. the side has no legal action)
+    Pass,
+}
+
+impl Action {
+    /// Parse a Showdown choice (`"move 1"`, `"move 1 terastallize"`,
+    /// `"move 1 dynamax"`, `"move 1 zmove"`, `"move 1 mega"`,
+    /// `"move 1 ultra"`, `"move 1 strong"`, `"move 1 agile"`, `"switch 3"`,
+    /// `"pass"`; 1-based).
+    pub fn from_choice(choice: &str) -> Option<Self> {
+        let mut words = choice.split_whitespace();
+        let action = match (words.next()?, words.next()) {
+            ("pass", None) => return Some(Action::Pass),
+            ("move", Some(n)) => {
+                let slot = n.parse::<u8>().ok()?.checked_sub(1)?;
+                match words.next() {
+                    None => return Some(Action::Move(slot)),
+                    Some("terastallize") => Action::Terastallize(slot),
+                    Some("dynamax") => Action::Dynamax(slot),
+                    Some("zmove") => Action::ZMove(slot),
+                    Some("mega") => Action::MegaEvolve(slot),
+                    Some("ultra") => Action::UltraBurst(slot),
+                    Some("strong") => Action::StrongStyle(slot),
+                    Some("agile") => Action::AgileStyle(slot),
+                    Some(_) => return None,
+                }
+            }
+            ("switch", Some(n)) => Action::Switch(n.parse::<u8>().ok()?.checked_sub(1)?),
+            _ => return None,
+        };
+        words.next().is_none().then_some(action)
+    }
+
+    /// Move slot this action uses, if it is a move.
+    pub fn move_slot(self) -> Option<u8> {
+        match self {
+            Action::Move(slot)
+            | Action::Terastallize(slot)
+            | Action::Dynamax(slot)
+            | Action::ZMove(slot)
+            | Action::MegaEvolve(slot)
+            | Action::UltraBurst(slot)
+            | Action::StrongStyle(slot)
+            | Action::AgileStyle(slot) => Some(slot),
+            Action::Switch(_) | Action::Pass => None,
+        }
+    }
+}
+
+/// Formats as a Showdown choice, the inverse of `Action::from_choice`.
+impl fmt::Display for Action {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Action::Move(slot) => write!(f, "move {}", slot + 1),
+            Action::Terastallize(slot) => write!(f, "move {} terastallize", slot + 1),
+            Action::Dynamax(slot) => write!(f, "move {} dynamax", slot + 1),
+            Action::ZMove(slot) => write!(f, "move {} zmove", slot + 1),
+            Action::MegaEvolve(slot) => write!(f, "move {} mega", slot + 1),
+            Action::UltraBurst(slot) => write!(f, "move {} ultra", slot + 1),
+            Action::StrongStyle(slot) => write!(f, "move {} strong", slot + 1),
+            Action::AgileStyle(slot) => write!(f, "move {} agile", slot + 1),
+            Action::Switch(slot) => write!(f, "switch {}", slot + 1),
+            Action::Pass => f.write_str("pass"),
+        }
+    }
+}
+
+/// Outcome of the battle so far.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BattleResult {
+    Ongoing,
+    /// The given player (0 or 1) won
+    Win(usize),
+    Tie,
+}
+
+/// Whether every Pokémon on a player's team has fainted.
+pub fn side_defeated(state: &BattleState, player: usize) -> bool {
+    (0..state.team_sizes[player] as usize)
+        .all(|slot| state.is_fainted(BattleState::entity_index(player, slot)))
+}
+
+/// Current battle outcome.
+pub fn battle_result(state: &BattleState) -> BattleResult {
+    match (side_defeated(state, 0), side_defeated(state, 1)) {
+        (true, true) => BattleResult::Tie,
+        (true, false) => BattleResult::Win(1),
+        (false, true) => BattleResult::Win(0),
+        (false, false) => BattleResult::Ongoing,
+    }
+}
+
+/// Whether a player's active Pokémon has fainted and a replacement is
+/// available, i.e. `replace_fainted` must be called before the next turn.
+pub fn needs_replacement(state: &BattleState, player: usize) -> bool {
+    state.is_fainted(state.active_index(player)) && !side_defeated(state, player)
+}
+
+/// Most actions `legal_actions` can return: every move plainly and in both
+/// Styles, plus a switch to each benched Pokémon.
+pub const MAX_LEGAL_ACTIONS: usize = 3 * MAX_MOVES + MAX_TEAM_SIZE - 1;
+
+/// Legal actions for a player before the next turn.
+///
+/// While a replacement is pending only switches are legal. With no usable
+/// move left, `Action::Move(0)` stands for Struggle. Every usable move is
+/// also offered with the one gimmick the active Pokémon can use, in order:
+/// both Styles when the format has Legends: Arceus mechanics, then
+/// `Action::Terastallize`, `Action::Dynamax`, `Action::ZMove` (for moves
+/// with a Z-Move), `Action::MegaEvolve` or `Action::UltraBurst`. A Pokémon
+/// recharging from Hyper Beam only has `Action::Move(0)`, and a Gen 1
+/// partial trapper only its trapping move. Returns an empty list once the
+/// battle is over, and never more than `MAX_LEGAL_ACTIONS`.
+pub fn legal_actions(state: &BattleState, player: usize) -> Vec<Action> {
+    let mut actions = Vec::new();
+    if battle_result(state) != BattleResult::Ongoing {
+        return actions;
+    }
+    let active = state.active_index(player);
+    let replacing = state.is_fainted(active);
+    let gen = Generation::from_num(state.generation);
+
+    if !replacing && state.volatiles[active].contains(Volatiles::MUST_RECHARGE) {
+        // The recharge turn can't be spent switching either
+        actions.push(Action::Move(0));
+        return actions;
+    }
+    let locked = if replacing { None } else { locked_slot(state, active) };
+    if let Some(slot) = locked {
+        actions.push(Action::Move(slot as u8));
+    } else if !replacing {
+        for slot in 0..MAX_MOVES {
+            if state.max_pp[active][slot] > 0 && state.pp[active][slot] > 0 {
+                actions.push(Action::Move(slot as u8));
+            }
+        }
+        if actions.is_empty() {
+            actions.push(Action::Move(0));
+        } else {
+            let slots: Vec<u8> = actions.iter().filter_map(|action| action.move_slot()).collect();
+            let z_slots: Vec<u8> = match state.can_z_move(active) {
+                true => slots
+                    .iter()
+                    .copied()
+                    .filter(|&slot| {
+                        z_move_for(state, active, state.moves[active][slot as usize]).is_some()
+                    })
+                    .collect(),
+                false => Vec::new(),
+            };
+            // One gimmick per Pokémon, which keeps the list within
+            // MAX_LEGAL_ACTIONS
+            if state.gimmicks.legends_arceus && !state.is_dynamaxed(active) {
+                actions.extend(slots.iter().copied().map(Action::StrongStyle));
+                actions.extend(slots.iter().copied().map(Action::AgileStyle));
+            } else if state.can_terastallize(active) {
+                actions.extend(slots.iter().copied().map(Action::Terastallize));
+            } else if state.can_dynamax(active) {
+                actions.extend(slots.iter().copied().map(Action::Dynamax));
+            } else if !z_slots.is_empty() {
+                actions.extend(z_slots.into_iter().map(Action::ZMove));
+            } else if state.can_mega_evolve(active) {
+                actions.extend(slots.iter().copied().map(Action::MegaEvolve));
+            } else if state.can_ultra_burst(active) {
+                actions.extend(slots.iter().copied().map(Action::UltraBurst));
+            }
+        }
+    }
+
+    // Gen 1 partial trapping stops the target's moves, not its switches
+    let mut trapping = Volatiles::TRAPPED;
+    if !gen.partial_trap_prevents_moves() {
+        trapping |= Volatiles::PARTIALLY_TRAPPED;
+    }
+    let trapped = state.volatiles[active].intersects(trapping)
+        && !state.defensive_types(active).contains(&Type::Ghost);
+    if replacing || !trapped {
+        for slot in 0..state.team_sizes[player] as usize {
+            let entity = BattleState::entity_index(player, slot);
+            if entity != active && !state.is_fainted(entity) {
+                actions.push(Action::Switch(slot as u8));
+            }
+        }
+    }
+    actions
+}
+
+/// Announce the leads, run their switch-in effects and start turn 1.
+///
+/// Build the teams with `PokemonConfig::place` so lead effects (weather,
+/// Intimidate, ...) run exactly once, here, faster lead first.
+pub fn start_battle<S: BattleSink>(state: &mut BattleState, rng: &mut BattleRng, sink: &mut S) {
+    for player in 0..2 {
+        let entity = state.active_index(player);
+        state.start_illusion(entity);
+        sink.event(state, &Event::SwitchIn { entity });
+    }
+    for player in order_players(state, [0, 0], rng) {
+        run_switch_in_hooks(state, state.active_index(player), sink);
+    }
+    state.turn = 1;
+    sink.event(state, &Event::TurnStart { turn: state.turn });
+}
+
+/// Bring in a replacement for a fainted active Pokémon between turns.
+pub fn replace_fainted<S: BattleSink>(
+    state: &mut BattleState,
+    player: usize,
+    slot: usize,
+    sink: &mut S,
+) {
+    switch_in(state, player, slot, sink);
+}
+
+/// Execute one full turn.
+///
+/// `actions[p]` is player `p`'s choice. Switches resolve first, then
+/// Mega Evolution, Ultra Burst, Terastallization and Dynamax, then moves in
+/// priority/speed order; speed ties are broken by `rng`. Up to Gen 7 the
+/// move order is fixed before a Mega Evolution or Ultra Burst changes speed.
+/// Does nothing once the battle is over.
+pub fn execute_turn<G: GenMechanics, S: BattleSink>(
+    gen: G,
+    state: &mut BattleState,
+    actions: [Action; 2],
+    rng: &mut BattleRng,
+    sink: &mut S,
+) {
+    if battle_result(state) != BattleResult::Ongoing {
+        return;
+    }
+
+    // Switches go first, faster side first.
+    let [first, second] = order_players(state, [0, 0], rng);
+    for player in [first, second] {
+        if let Action::Switch(slot) = actions[player] {
+            switch_in(state, player, slot as usize, sink);
+        }
+    }
+
+    let changes_forme = actions
+        .iter()
+        .any(|action| matches!(action, Action::MegaEvolve(_) | Action::UltraBurst(_)));
+    let locked_order =
+        (changes_forme && gen.generation() <= 7).then(|| move_order(state, actions, rng));
+
+    for player in order_players(state, [0, 0], rng) {
+        if let Action::MegaEvolve(_) = actions[player] {
+            let entity = state.active_index(player);
+            if state.can_mega_evolve(entity) {
+                let item = match state.items[entity].data().mega_stone {
+                    Some(_) => state.items[entity],
+                    None => ItemId::None,
+                };
+                state.mega_evolve(entity);
+                sink.event(state, &Event::MegaEvolve { entity, item });
+                run_ability_start(state, entity, sink);
+            }
+        }
+        if let Action::UltraBurst(_) = actions[player] {
+            let entity = state.active_index(player);
+            if state.can_ultra_burst(entity) {
+                state.ultra_burst(entity);
+                sink.event(state, &Event::UltraBurst { entity });
+                run_ability_start(state, entity, sink);
+            }
+        }
+        if let Action::Terastallize(_) = actions[player] {
+            let entity = state.active_index(player);
+            if state.can_terastallize(entity) {
+                state.terastallize(entity);
+                let tera_type = state.tera_type[entity];
+                sink.event(state, &Event::Terastallize { entity, tera_type });
+            }
+        }
+        if let Action::Dynamax(_) = actions[player] {
+            let entity = state.active_index(player);
+            if gen.has_dynamax() && state.can_dynamax(entity) {
+                state.dynamax(entity, gen.dynamax_hp_multiplier());
+                sink.event(state, &Event::Dynamax { entity });
+            }
+        }
+    }
+
+    let order = match locked_order {
+        Some(order) => order,
+        None => move_order(state, actions, rng),
+    };
+    for (i, player) in order.into_iter().enumerate() {
+        if actions[player].move_slot().is_some() {
+            let user = state.active_index(player);
+            if !state.is_fainted(user) {
+                let moves_last = order[i + 1..].iter().all(|&other| {
+                    actions[other].move_slot().is_none()
+                        || state.is_fainted(state.active_index(other))
+                });
+                let weather = state.weather;
+                run_move(gen, state, user, actions[player], moves_last, rng, sink);
+                after_weather_change(state, weather, sink);
+            }
+        }
+    }
+
+    end_of_turn(gen, state, sink);
+}
+
+/// A choice that `legal_actions` does not allow.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct IllegalAction {
+    pub player: usize,
+    pub action: Action,
+}
+
+impl fmt::Display for IllegalAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "p{}: illegal choice: {}", self.player + 1, self.action)
+    }
+}
+
+impl core::error::Error for IllegalAction {}
+
+/// Validate both players' choices and advance the battle.
+///
+/// This is the entry point for front ends that take choices from outside
+/// (REPL, RPC, bindings). While a replacement is pending, only the sides
+/// that must switch are checked and their switches are applied; the other
+/// side's choice is ignored, as in Showdown. Otherwise a full turn runs.
+/// Nothing changes if either choice is illegal.
+pub fn submit_actions<G: GenMechanics, S: BattleSink>(
+    gen: G,
+    state: &mut BattleState,
+    actions: [Action; 2],
+    rng: &mut BattleRng,
+    sink: &mut S,
+) -> Result<(), IllegalAction> {
+    let replacing = [needs_replacement(state, 0), needs_replacement(state, 1)];
+    let any_replacing = replacing.contains(&true);
+    for (player, &action) in actions.iter().enumerate() {
+        let must_choose = if any_replacing {
+            replacing[player]
+        } else {
+            battle_result(state) == BattleResult::Ongoing
+        };
+        if must_choose && !legal_actions(state, player).contains(&action) {
+            return Err(IllegalAction { player, action });
+        }
+    }
+
+    if any_replacing {
+        for (player, action) in actions.into_iter().enumerate() {
+            if let (true, Action::Switch(slot)) = (replacing[player], action) {
+                replace_fainted(state, player, slot as usize, sink);
+            }
+        }
+    } else {
+        execute_turn(gen, state, actions, rng, sink);
+    }
+    Ok(())
+}
+
+/// Move actually executed from a slot (Struggle when out of PP).
+fn selected_move(state: &BattleState, user: usize, slot: u8) -> MoveId {
+    let slot = slot as usize;
+    if slot < state.pp[user].len() && state.pp[user][slot] > 0 {
+        state.moves[user][slot]
+    } else {
+        MoveId::Struggle
+    }
+}
+
+/// Move executed from a slot: the selected move, its Max Move while
+/// Dynamaxed, or its Z-Move when `z_move` is chosen.
+fn executed_move(state: &BattleState, user: usize, slot: u8, z_move: bool) -> MoveId {
+    let base_move = selected_move(state, user, slot);
+    if state.is_dynamaxed(user) {
+        let target = state.active_index(1 - state.get_side(user));
+        max_move_for(state, user, target, base_move)
+    } else if z_move {
+        z_move_for(state, user, base_move).unwrap_or(base_move)
+    } else {
+        base_move
+    }
+}
+
+/// Order the players' moves by priority, then speed.
+fn move_order(state: &BattleState, actions: [Action; 2], rng: &mut BattleRng) -> [usize; 2] {
+    let mut priorities = [0i8; 2];
+    for player in 0..2 {
+        if let Some(slot) = actions[player].move_slot() {
+            let user = state.active_index(player);
+            let z_move = matches!(actions[player], Action::ZMove(_));
+            let move_id = executed_move(state, user, slot, z_move);
+            priorities[player] = calculate_priority(state, user, move_id);
+        }
+    }
+    // Agile Style moves first and Strong Style last among equal priorities
+    let styles = actions.map(style_order);
+    if priorities[0] == priorities[1] && styles[0] != styles[1] {
+        return if styles[0] < styles[1] { [0, 1] } else { [1, 0] };
+    }
+    order_players(state, priorities, rng)
+}
+
+/// Turn-order shift of a Legends: Arceus Style, lower moving first.
+fn style_order(action: Action) -> i8 {
+    match action {
+        Action::AgileStyle(_) => -1,
+        Action::StrongStyle(_) => 1,
+        _ => 0,
+    }
+}
+
+/// Order the two players' actions for the given priorities.
+fn order_players(state: &BattleState, priorities: [i8; 2], rng: &mut BattleRng) -> [usize; 2] {
+    let (a, b) = (state.active_index(0), state.active_index(1));
+    match state.compare_turn_order(a, priorities[0], b, priorities[1]) {
+        TurnOrder::First => [0, 1],
+        TurnOrder::Second => [1, 0],
+        TurnOrder::Tie if rng.chance(1, 2) => [1, 0],
+        TurnOrder::Tie => [0, 1],
+    }
+}
+
+#[inline]
+fn has_ability(state: &BattleState, entity: usize, ability: AbilityId) -> bool {
+    state.abilities[entity] == ability
+}
+
+/// Apply indirect or direct damage, reporting the hit and a resulting faint.
+fn deal_damage<S: BattleSink>(
+    state: &mut BattleState,
+    target: usize,
+    amount: u16,
+    source: EffectSource,
+    sink: &mut S,
+) {
+    if amount == 0 || state.is_fainted(target) {
+        return;
+    }
+    let amount = amount.min(state.hp[target]);
+    state.apply_damage(target, amount);
+    sink.event(state, &Event::Damage { target, amount, source });
+    if state.is_fainted(target) {
+        if state.is_dynamaxed(target) {
+            state.end_dynamax(target);
+        }
+        sink.event(state, &Event::Faint { entity: target });
+    }
+}
+
+/// Report every boost that differs from `before`.
+fn report_boosts<S: BattleSink>(
+    state: &BattleState,
+    entity: usize,
+    before: [i8; BOOST_STATS],
+    sink: &mut S,
+) {
+    for (stat, &old) in before.iter().enumerate() {
+        let delta = state.boosts[entity][stat] - old;
+        if delta != 0 {
+            sink.event(state, &Event::Boost { entity, stat, delta });
+        }
+    }
+}
+
+/// Swap a player's active Pokémon and run switch-in effects.
+fn switch_in<S: BattleSink>(state: &mut BattleState, player: usize, slot: usize, sink: &mut S) {
+    let incoming = BattleState::entity_index(player, slot);
+    let outgoing = state.active_index(player);
+    if slot >= state.team_sizes[player] as usize
+        || incoming == outgoing
+        || state.is_fainted(incoming)
+    {
+        return;
+    }
+
+    // Switching out clears boosts, volatiles, Dynamax and temporary formes;
+    // Toxic's counter restarts and partial trapping ends on both sides, as
+    // does a G-Max Terror trap on the opponent.
+    for entity in [outgoing, state.active_index(1 - player)] {
+        if state.partial_trap_turns[entity] > 0 {
+            end_partial_trap(state, entity, sink);
+        }
+    }
+    state.volatiles[state.active_index(1 - player)].remove(Volatiles::TRAPPED);
+    if state.is_dynamaxed(outgoing) {
+        state.end_dynamax(outgoing);
+        sink.event(state, &Event::DynamaxEnd { entity: outgoing });
+    }
+    if !state.is_fainted(outgoing) {
+        check_forme_change(state, outgoing, FormeTrigger::SwitchOut, sink);
+    }
+    state.revert_forme(outgoing);
+    state.boosts[outgoing] = [0; BOOST_STATS];
+    state.volatiles[outgoing] = Volatiles::empty();
+    state.reset_move_counter(outgoing);
+    state.protect_chain[outgoing] = 0;
+    if state.status[outgoing].intersects(Status::TOXIC | Status::POISON) {
+        state.status_counter[outgoing] = 0;
+    }
+
+    state.active[player] = incoming as u8;
+    state.start_illusion(incoming);
+    sink.event(state, &Event::SwitchIn { entity: incoming });
+
+    let status_before = state.status[incoming];
+    let boosts_before = state.boosts[incoming];
+    for (hazard, amount) in state.entry_hazard_damage(incoming) {
+        if amount == 0 {
+            continue;
+        }
+        state.apply_damage(incoming, amount);
+        let event = Event::Damage {
+            target: incoming,
+            amount,
+            source: EffectSource::Hazard(hazard),
+        };
+        sink.event(state, &event);
+        if state.is_fainted(incoming) {
+            sink.event(state, &Event::Faint { entity: incoming });
+            return;
+        }
+    }
+    state.apply_entry_hazard_effects(incoming);
+    if state.status[incoming] != status_before {
+        let status = state.status[incoming];
+        sink.event(state, &Event::StatusInflicted { entity: incoming, status });
+    }
+    report_boosts(state, incoming, boosts_before, sink);
+
+    run_switch_in_hooks(state, incoming, sink);
+}
+
+/// Run the ability and item switch-in hooks for an entity. Kyogre and
+/// Groudon undergo Primal Reversion first, so their new ability starts.
+fn run_switch_in_hooks<S: BattleSink>(state: &mut BattleState, entity: usize, sink: &mut S) {
+    if let Some(forme) = state.primal_forme(entity) {
+        state.apply_forme_change(entity, forme);
+        state.original_species[entity] = forme;
+        sink.event(state, &Event::PrimalReversion { entity });
+    }
+    run_ability_start(state, entity, sink);
+    let item = state.items[entity];
+    if let Some(Some(hooks)) = ITEM_REGISTRY.get(item as usize) {
+        if let Some(on_switch_in) = hooks.on_switch_in {
+            on_switch_in(state, entity, sink);
+        }
+    }
+    check_forme_change(state, entity, FormeTrigger::SwitchIn, sink);
+}
+
+/// Run an entity's ability switch-in hook, also used when a forme change
+/// gives it a new ability mid-battle.
+fn run_ability_start<S: BattleSink>(state: &mut BattleState, entity: usize, sink: &mut S) {
+    let weather = state.weather;
+    let ability = state.abilities[entity];
+    if let Some(Some(hooks)) = ABILITY_REGISTRY.get(ability as usize) {
+        if let Some(on_switch_in) = hooks.on_switch_in {
+            on_switch_in(state, entity, sink);
+        }
+    }
+    after_weather_change(state, weather, sink);
+}
+
+/// Run an entity's forme-change ability for `trigger`. Returns whether its
+/// forme changed.
+fn check_forme_change<S: BattleSink>(
+    state: &mut BattleState,
+    entity: usize,
+    trigger: FormeTrigger,
+    sink: &mut S,
+) -> bool {
+    let on_forme_change = match ABILITY_REGISTRY.get(state.abilities[entity] as usize) {
+        Some(Some(hooks)) => hooks.on_forme_change,
+        _ => None,
+    };
+    match on_forme_change.and_then(|hook| hook(state, entity, trigger)) {
+        Some(change) if change.forme != state.species[entity] => {
+            state.ability_forme_change(entity, change.forme, change.permanent);
+            sink.event(state, &Event::FormeChange { entity, permanent: change.permanent });
+            true
+        }
+        _ => false,
+    }
+}
+
+/// Let weather-dependent formes (Forecast, Ice Face) react if the weather
+/// differs from `weather_before`.
+fn after_weather_change<S: BattleSink>(state: &mut BattleState, weather_before: u8, sink: &mut S) {
+    if state.weather == weather_before {
+        return;
+    }
+    for player in 0..2 {
+        let entity = state.active_index(player);
+        if !state.is_fainted(entity) {
+            check_forme_change(state, entity, FormeTrigger::WeatherChange, sink);
+        }
+    }
+}
+
+/// Check sleep, freeze, partial trapping, paralysis and flinch. Returns
+/// false if the user cannot act this turn.
+fn can_act<G: GenMechanics, S: BattleSink>(
+    gen: G,
+    state: &mut BattleState,
+    user: usize,
+    move_id: MoveId,
+    rng: &mut BattleRng,
+    sink: &mut S,
+) -> bool {
+    let status = state.status[user];
+    if status == Status::SLEEP {
+        state.status_counter[user] = state.status_counter[user].saturating_sub(1);
+        if state.status_counter[user] > 0 {
+            sink.event(state, &Event::Cant { entity: user, reason: CantReason::Sleep });
+            return false;
+        }
+        state.status[user] = Status::NONE;
+        sink.event(state, &Event::StatusCured { entity: user, status: Status::SLEEP });
+        if !gen.moves_on_waking() {
+            return false;
+        }
+    } else if status == Status::FREEZE {
+        // Defrosting moves always thaw the user; otherwise it may thaw at
+        // the start of each turn, except in Gen 1
+        let (num, den) = gen.freeze_thaw_chance();
+        if move_id.data().flags.contains(MoveFlags::DEFROST) || (num > 0 && rng.chance(num, den)) {
+            state.status[user] = Status::NONE;
+            sink.event(state, &Event::StatusCured { entity: user, status: Status::FREEZE });
+        } else {
+            sink.event(state, &Event::Cant { entity: user, reason: CantReason::Freeze });
+            return false;
+        }
+    }
+
+    if gen.partial_trap_prevents_moves()
+        && state.volatiles[user].contains(Volatiles::PARTIALLY_TRAPPED)
+    {
+        sink.event(state, &Event::Cant { entity: user, reason: CantReason::PartiallyTrapped });
+        return false;
+    }
+
+    // Dynamaxed Pokémon can't flinch
+    if state.volatiles[user].contains(Volatiles::FLINCH) && !state.is_dynamaxed(user) {
+        sink.event(state, &Event::Cant { entity: user, reason: CantReason::Flinch });
+        return false;
+    }
+
+    if state.status[user] == Status::PARALYSIS && rng.chance(1, 4) {
+        sink.event(state, &Event::Cant { entity: user, reason: CantReason::Paralysis });
+        return false;
+    }
+
+    true
+}
+
+/// Accuracy check with accuracy/evasion stages: (3+n)/3 or 3/(3-n).
+fn accuracy_hits<G: GenMechanics>(
+    gen: G,
+    state: &BattleState,
+    user: usize,
+    target: usize,
+    accuracy: u8,
+    rng: &mut BattleRng,
+) -> bool {
+    if accuracy == 0 {
+        return true;
+    }
+    let stage = (state.boosts[user][5] - state.boosts[target][6]).clamp(-6, 6) as i32;
+    let (num, den) = if stage >= 0 { (3 + stage, 3) } else { (3, 3 - stage) };
+    if gen.has_one_in_256_miss() {
+        // Accuracy is out of 255 but the roll is out of 256
+        let threshold = (accuracy as i32 * 255 / 100 * num / den).min(255) as u32;
+        return rng.range(256) < threshold;
+    }
+    let threshold = (accuracy as i32 * num / den) as u32;
+    rng.range(100) < threshold
+}
+
+/// Number of hits for a multi-hit move.
+fn roll_hit_count(state: &BattleState, user: usize, multihit: (u8, u8), rng: &mut BattleRng) -> u8 {
+    match multihit {
+        (0, _) => 1,
+        (min, max) if min == max => min,
+        (2, 5) => {
+            if has_ability(state, user, AbilityId::Skilllink) {
+                return 5;
+            }
+            // Gen 5+: 35% 2 hits, 35% 3, 15% 4, 15% 5
+            match rng.range(20) {
+                0..=6 => 2,
+                7..=13 => 3,
+                14..=16 => 4,
+                _ => 5,
+            }
+        }
+        (min, max) => {
+            if has_ability(state, user, AbilityId::Skilllink) {
+                max
+            } else {
+                min + rng.range((max - min + 1) as u32) as u8
+            }
+        }
+    }
+}
+
+/// Roll for a critical hit with a move that does (`high_crit`) or doesn't
+/// have a raised critical hit ratio.
+fn roll_crit<G: GenMechanics>(
+    gen: G,
+    state: &BattleState,
+    user: usize,
+    target: usize,
+    high_crit: bool,
+    rng: &mut BattleRng,
+) -> bool {
+    if has_ability(state, target, AbilityId::Battlearmor)
+        || has_ability(state, target, AbilityId::Shellarmor)
+    {
+        return false;
+    }
+    let mut stage = 0u8;
+    if state.volatiles[user].contains(Volatiles::FOCUS_ENERGY) {
+        stage += 2;
+    }
+    if high_crit {
+        stage += 1;
+    }
+    if has_ability(state, user, AbilityId::Superluck) {
+        stage += 1;
+    }
+    if matches!(state.items[user], ItemId::Scopelens | ItemId::Razorclaw) {
+        stage += 1;
+    }
+    let base_speed = state.species[user].data_in(gen.generation()).base_stats[5];
+    let (numerator, denominator) = gen.crit_chance(stage, base_speed);
+    rng.chance(numerator, denominator)
+}
+
+/// Execute the move `action` selects for `user` against the opposing
+/// active: a Z-Move if the user's Z-Crystal allows it, or in a Style.
+/// `moves_last` is set when no one acts after the user this turn.
+fn run_move<G: GenMechanics, S: BattleSink>(
+    gen: G,
+    state: &mut BattleState,
+    user: usize,
+    action: Action,
+    moves_last: bool,
+    rng: &mut BattleRng,
+    sink: &mut S,
+) {
+    let Some(slot) = action.move_slot() else {
+        return;
+    };
+    let base_move = selected_move(state, user, slot);
+    let z_move = matches!(action, Action::ZMove(_))
+        && gen.has_z_moves()
+        && state.can_z_move(user)
+        && z_move_for(state, user, base_move).is_some();
+    let move_id = executed_move(state, user, slot, z_move);
+    let slot = slot as usize;
+    let dynamaxed = state.is_dynamaxed(user);
+    // Any other move (or a turn spent recharging) breaks a Protect chain
+    if !is_protection(move_id) {
+        state.protect_chain[user] = 0;
+    }
+    if state.volatiles[user].contains(Volatiles::MUST_RECHARGE) {
+        state.volatiles[user].remove(Volatiles::MUST_RECHARGE);
+        sink.event(state, &Event::Cant { entity: user, reason: CantReason::Recharge });
+        return;
+    }
+    if !can_act(gen, state, user, move_id, rng, sink) {
+        state.record_move_use(user, move_id, false);
+        return;
+    }
+    let target = state.active_index(1 - state.get_side(user));
+    // A Gen 1 partial trapper keeps hitting without PP or accuracy checks
+    let continuing = state.volatiles[user].contains(Volatiles::LOCKED_MOVE)
+        && state.partial_trap_turns[target] > 0;
+    if move_id != MoveId::Struggle && !continuing {
+        state.pp[user][slot] -= 1;
+    }
+    // The Z-Power is only spent once the user actually moves.
+    if z_move {
+        state.z_move_used[state.get_side(user)] = true;
+        sink.event(state, &Event::ZPower { entity: user });
+    }
+    // The damage calc reads the Style's power change from the volatile,
+    // which lasts until the end of the turn
+    if state.gimmicks.legends_arceus {
+        match action {
+            Action::StrongStyle(_) => state.volatiles[user].insert(Volatiles::STRONG_STYLE),
+            Action::AgileStyle(_) => state.volatiles[user].insert(Volatiles::AGILE_STYLE),
+            _ => {}
+        }
+    }
+
+    check_forme_change(state, user, FormeTrigger::BeforeMove(move_id), sink);
+    sink.event(state, &Event::MoveUsed { user, move_id, target });
+
+    let move_data = move_id.data_in(state.generation);
+    if move_data.category == MoveCategory::Status {
+        if z_move {
+            if let Some(effect) = move_data.z_power {
+                apply_z_effect(gen, state, user, effect, sink);
+            }
+        }
+        // TODO: Status move effects (stat changes, status infliction, field setup)
+        if is_protection(move_id) {
+            let protected = protect(gen, state, user, moves_last, rng);
+            let event = match protected {
+                true => Event::Protect { entity: user },
+                false => Event::Fail { user },
+            };
+            sink.event(state, &event);
+            state.record_move_use(user, move_id, protected);
+            return;
+        }
+        if move_id == MoveId::Transform {
+            if state.transform(user, target) {
+                sink.event(state, &Event::Transform { user, target });
+            } else {
+                sink.event(state, &Event::Fail { user });
+                state.record_move_use(user, move_id, false);
+                return;
+            }
+        }
+        if !run_status_effect(gen, state, user, target, move_id, rng, sink) {
+            state.record_move_use(user, move_id, false);
+            return;
+        }
+        state.record_move_use(user, move_id, true);
+        return;
+    }
+    if state.is_fainted(target) {
+        state.record_move_use(user, move_id, false);
+        return;
+    }
+    let mut broke_protect = false;
+    if state.volatiles[target].contains(Volatiles::PROTECT) && !bypasses_protect(move_id) {
+        if z_move {
+            sink.event(state, &Event::ZBrokeProtect { target });
+            broke_protect = true;
+        } else if move_data.flags.contains(MoveFlags::PROTECT) || is_max_move(move_id) {
+            sink.event(state, &Event::Protected { target });
+            if move_data.flags.contains(MoveFlags::CONTACT) {
+                protection_contact(gen, state, user, target, rng, sink);
+            }
+            state.record_move_use(user, move_id, false);
+            return;
+        }
+    }
+    if state.is_dynamaxed(target) && fails_against_dynamax(move_id) {
+        sink.event(state, &Event::Fail { user });
+        state.record_move_use(user, move_id, false);
+        return;
+    }
+    if !continuing && !accuracy_hits(gen, state, user, target, move_data.accuracy, rng) {
+        sink.event(state, &Event::Miss { user, target });
+        state.record_move_use(user, move_id, false);
+        return;
+    }
+
+    let hit_count = roll_hit_count(state, user, move_data.multihit, rng);
+    let mut hits = 0u8;
+    let mut total_damage = 0u16;
+    while hits < hit_count && !state.is_fainted(target) && !state.is_fainted(user) {
+        let high_crit = move_data.flags.contains(MoveFlags::HIGH_CRIT);
+        let is_crit = roll_crit(gen, state, user, target, high_crit, rng);
+        let result = if dynamaxed {
+            calculate_max_move_damage(gen, state, user, target, base_move, is_crit)
+        } else if z_move {
+            calculate_z_move_damage(gen, state, user, target, base_move, is_crit)
+        } else {
+            calculate_damage(gen, state, user, target, move_id, is_crit)
+        };
+        if result.effectiveness == 0 {
+            let event = Event::Effectiveness { target, effectiveness: 0 };
+            sink.event(state, &event);
+            state.record_move_use(user, move_id, false);
+            return;
+        }
+
+        // Disguise and Ice Face take the hit in the Pokémon's place; a busted
+        // Disguise still costs 1/8 max HP from Gen 8.
+        if check_forme_change(state, target, FormeTrigger::Hit(move_id), sink) {
+            if has_ability(state, target, AbilityId::Disguise) && gen.generation() >= 8 {
+                let chip = (state.max_hp[target] / 8).max(1);
+                let source = EffectSource::Ability(AbilityId::Disguise);
+                deal_damage(state, target, chip, source, sink);
+            }
+            hits += 1;
+            continue;
+        }
+
+        let hp_before = state.hp[target];
+        if result.is_crit {
+            sink.event(state, &Event::CriticalHit { target });
+        }
+        if hits == 0 {
+            let event = Event::Effectiveness { target, effectiveness: result.effectiveness };
+            sink.event(state, &event);
+        }
+        let mut damage = result.rolls[rng.range(16) as usize];
+        if broke_protect {
+            damage = apply_modifier(damage as u32, Modifier::QUARTER) as u16;
+        }
+        deal_damage(state, target, damage, EffectSource::Move, sink);
+        hits += 1;
+
+        // Extra hits from abilities such as Parental Bond
+        if let Some(extra) = &result.multi_hit_rolls {
+            for rolls in extra {
+                if state.is_fainted(target) {
+                    break;
+                }
+                let damage = rolls[rng.range(16) as usize];
+                deal_damage(state, target, damage, EffectSource::Move, sink);
+                hits += 1;
+            }
+        }
+        total_damage = total_damage.saturating_add(hp_before - state.hp[target]);
+    }
+    if hits > 1 {
+        sink.event(state, &Event::HitCount { target, hits });
+    }
+    // Any damaging hit breaks an Illusion.
+    if hits > 0 && state.illusion[target].is_some() {
+        state.illusion[target] = None;
+        sink.event(state, &Event::IllusionEnd { entity: target });
+    }
+
+    let ability = state.abilities[target];
+    if let Some(Some(hooks)) = ABILITY_REGISTRY.get(ability as usize) {
+        if let Some(on_after_damage) = hooks.on_after_damage {
+            on_after_damage(state, user, target, total_damage, sink);
+        }
+    }
+    let item = state.items[target];
+    if let Some(Some(hooks)) = ITEM_REGISTRY.get(item as usize) {
+        if let Some(on_after_damage) = hooks.on_after_damage {
+            on_after_damage(state, user, target, total_damage, sink);
+        }
+    }
+
+    if state.is_fainted(target) && !state.is_fainted(user) {
+        check_forme_change(state, user, FormeTrigger::Knockout, sink);
+    }
+
+    if dynamaxed {
+        if let Some(effect) = max_move_effect(move_id) {
+            apply_max_effect(gen, state, user, target, move_id, effect, rng, sink);
+        }
+    }
+
+    // Tera Stellar boosts each move type once; Stellar Tera Blast also
+    // lowers the user's Attack and Special Attack.
+    let move_type = DamageContext::new(gen, state, user, target, move_id, false).move_type;
+    state.consume_stellar_boost(user, move_type);
+    if move_id == MoveId::Terablast && move_type == Type::Stellar {
+        let before = state.boosts[user];
+        change_stat(gen, state, user, 1, -1);
+        change_stat(gen, state, user, 3, -1);
+        report_boosts(state, user, before, sink);
+    }
+
+    // Fire moves thaw a frozen target.
+    if move_type == Type::Fire
+        && state.status[target] == Status::FREEZE
+        && !state.is_fainted(target)
+    {
+        state.status[target] = Status::NONE;
+        sink.event(state, &Event::StatusCured { entity: target, status: Status::FREEZE });
+    }
+
+    if move_data.flags.contains(MoveFlags::PARTIALLY_TRAPS) && !continuing {
+        start_partial_trap(gen, state, user, target, move_id, rng, sink);
+    }
+
+    // Gen 1 skips the recharge turn when Hyper Beam KOs its target.
+    if move_data.flags.contains(MoveFlags::RECHARGE)
+        && !state.is_fainted(user)
+        && (gen.recharges_after_ko() || !state.is_fainted(target))
+    {
+        state.volatiles[user].insert(Volatiles::MUST_RECHARGE);
+        sink.event(state, &Event::MustRecharge { entity: user });
+    }
+
+    if move_id == MoveId::Struggle {
+        let recoil = gen.struggle_recoil(total_damage, state.max_hp[user]).max(1);
+        deal_damage(state, user, recoil, EffectSource::Recoil, sink);
+    }
+
+    state.record_move_use(user, move_id, true);
+}
+
+/// Apply a Max Move's secondary effect after it hits. Max Move weather
+/// can't replace primal weather, and G-Max side effects don't stack.
+#[allow(clippy::too_many_arguments)]
+fn apply_max_effect<G: GenMechanics, S: BattleSink>(
+    gen: G,
+    state: &mut BattleState,
+    user: usize,
+    target: usize,
+    move_id: MoveId,
+    effect: MaxEffect,
+    rng: &mut BattleRng,
+    sink: &mut S,
+) {
+    match effect {
+        MaxEffect::RaiseUser(stat) => {
+            let before = state.boosts[user];
+            change_stat(gen, state, user, stat + 1, 1);
+            report_boosts(state, user, before, sink);
+        }
+        MaxEffect::LowerTarget(stat) => {
+            if state.is_fainted(target) {
+                return;
+            }
+            let before = state.boosts[target];
+            change_stat(gen, state, target, stat + 1, -1);
+            report_boosts(state, target, before, sink);
+        }
+        MaxEffect::HarshlyLowerTarget(stat) => {
+            if state.is_fainted(target) {
+                return;
+            }
+            let before = state.boosts[target];
+            change_stat(gen, state, target, stat + 1, -2);
+            report_boosts(state, target, before, sink);
+        }
+        MaxEffect::Status(statuses) => {
+            if state.is_fainted(target) {
+                return;
+            }
+            let status = statuses[rng.range(statuses.len() as u32) as usize];
+            if status_immune(gen, state, target, move_id, status)
+                || (status == Status::SLEEP && sleep_clause_blocks(state, target))
+            {
+                return;
+            }
+            inflict_status(gen, state, target, status, rng, sink);
+        }
+        MaxEffect::Weather(weather) => {
+            let current = Weather::from_u8(state.weather);
+            if current == weather
+                || matches!(current, Weather::HarshSun | Weather::HeavyRain | Weather::StrongWinds)
+            {
+                return;
+            }
+            state.weather = weather as u8;
+            state.weather_turns = 5;
+            sink.event(state, &Event::WeatherStart { weather });
+        }
+        MaxEffect::Terrain(terrain) => {
+            if state.terrain == terrain as u8 {
+                return;
+            }
+            state.terrain = terrain as u8;
+            state.terrain_turns = 5;
+            sink.event(state, &Event::TerrainStart { terrain });
+        }
+        MaxEffect::SideResidual(spared) => {
+            let side = state.get_side(target);
+            match state.side_conditions[side].gmax_residual_turns(spared) {
+                Some(turns) if *turns == 0 => *turns = 4,
+                _ => return,
+            }
+            sink.event(state, &Event::SideStart { side, move_id });
+        }
+        MaxEffect::Steelsurge => {
+            let side = state.get_side(target);
+            if state.side_conditions[side].steelsurge {
+                return;
+            }
+            state.side_conditions[side].steelsurge = true;
+            sink.event(state, &Event::SideStart { side, move_id });
+        }
+        MaxEffect::StealthRock => {
+            let side = state.get_side(target);
+            if state.side_conditions[side].stealth_rock {
+                return;
+            }
+            state.side_conditions[side].stealth_rock = true;
+            sink.event(state, &Event::SideStart { side, move_id: MoveId::Stealthrock });
+        }
+        MaxEffect::AuroraVeil => {
+            let side = state.get_side(user);
+            if state.side_conditions[side].aurora_veil_turns > 0 {
+                return;
+            }
+            state.side_conditions[side].aurora_veil_turns = 5;
+            sink.event(state, &Event::SideStart { side, move_id: MoveId::Auroraveil });
+        }
+        MaxEffect::Defog => wind_rage(state, state.get_side(target), sink),
+        MaxEffect::Trap => {
+            if !state.is_fainted(target) {
+                state.volatiles[target].insert(Volatiles::TRAPPED);
+            }
+        }
+        MaxEffect::PartialTrap => start_partial_trap(gen, state, user, target, move_id, rng, sink),
+        MaxEffect::CureSide => {
+            let side = state.get_side(user);
+            for slot in 0..state.team_sizes[side] as usize {
+                let entity = BattleState::entity_index(side, slot);
+                let status = state.status[entity];
+                if status != Status::NONE && !state.is_fainted(entity) {
+                    state.status[entity] = Status::NONE;
+                    state.status_counter[entity] = 0;
+                    sink.event(state, &Event::StatusCured { entity, status });
+                }
+            }
+        }
+        MaxEffect::HealUser => {
+            let missing = state.max_hp[user] - state.hp[user];
+            let amount = (state.max_hp[user] / 6).min(missing);
+            if amount > 0 && !state.is_fainted(user) {
+                state.hp[user] += amount;
+                let event = Event::Heal { target: user, amount, source: EffectSource::Move };
+                sink.event(state, &event);
+            }
+        }
+        MaxEffect::Depletion => {
+            let last_move = state.last_move[target];
+            if let Some(slot) = state.moves[target].iter().position(|&m| m == last_move) {
+                state.pp[target][slot] = state.pp[target][slot].saturating_sub(2);
+            }
+        }
+    }
+}
+
+/// G-Max Wind Rage: clear screens and the like from `foe`'s side, entry
+/// hazards from both sides, and the terrain.
+fn wind_rage<S: BattleSink>(state: &mut BattleState, foe: usize, sink: &mut S) {
+    type Clear = fn(&mut SideConditions) -> bool;
+    let screens: [(MoveId, Clear); 5] = [
+        (MoveId::Reflect, |c| mem::take(&mut c.reflect_turns) > 0),
+        (MoveId::Lightscreen, |c| mem::take(&mut c.light_screen_turns) > 0),
+        (MoveId::Auroraveil, |c| mem::take(&mut c.aurora_veil_turns) > 0),
+        (MoveId::Safeguard, |c| mem::take(&mut c.safeguard_turns) > 0),
+        (MoveId::Mist, |c| mem::take(&mut c.mist_turns) > 0),
+    ];
+    let hazards: [(MoveId, Clear); 5] = [
+        (MoveId::Spikes, |c| mem::take(&mut c.spikes_layers) > 0),
+        (MoveId::Toxicspikes, |c| mem::take(&mut c.toxic_spikes_layers) > 0),
+        (MoveId::Stealthrock, |c| mem::take(&mut c.stealth_rock)),
+        (MoveId::Stickyweb, |c| mem::take(&mut c.sticky_web)),
+        (MoveId::Gmaxsteelsurge, |c| mem::take(&mut c.steelsurge)),
+    ];
+    for side in 0..2 {
+        let cleared = screens.iter().filter(|_| side == foe).chain(&hazards);
+        for &(move_id, clear) in cleared {
+            if clear(&mut state.side_conditions[side]) {
+                sink.event(state, &Event::SideEnd { side, move_id });
+            }
+        }
+    }
+    if state.terrain != 0 {
+        let terrain = Terrain::from_u8(state.terrain);
+        state.terrain = 0;
+        state.terrain_turns = 0;
+        sink.event(state, &Event::TerrainEnd { terrain });
+    }
+}
+
+/// Apply a Z-status move's Z-effect before the move's own effect.
+fn apply_z_effect<G: GenMechanics, S: BattleSink>(
+    gen: G,
+    state: &mut BattleState,
+    user: usize,
+    effect: ZPower,
+    sink: &mut S,
+) {
+    let before = state.boosts[user];
+    match effect {
+        ZPower::Boost(stages) => {
+            for (stat, &delta) in stages.iter().enumerate() {
+                change_stat(gen, state, user, stat + 1, delta);
+            }
+        }
+        ZPower::ClearNegativeBoost => {
+            for boost in state.boosts[user].iter_mut() {
+                *boost = (*boost).max(0);
+            }
+        }
+        ZPower::Heal => heal_fully(state, user, sink),
+        ZPower::Curse if state.types[user].contains(&Type::Ghost) => heal_fully(state, user, sink),
+        ZPower::Curse => change_stat(gen, state, user, 1, 1),
+        ZPower::Crit2 => state.volatiles[user].insert(Volatiles::FOCUS_ENERGY),
+        // Redirection only matters in doubles, and Memento and Parting Shot
+        // don't switch the user out yet.
+        ZPower::Redirect | ZPower::HealReplacement | ZPower::BasePower(_) => {}
+    }
+    report_boosts(state, user, before, sink);
+}
+
+/// Apply a stat stage change (`stat` 1-based, as in `apply_stat_change`).
+/// With a shared Special stat, Special Attack and Special Defense stages
+/// move together.
+fn change_stat<G: GenMechanics>(
+    gen: G,
+    state: &mut BattleState,
+    entity: usize,
+    stat: usize,
+    delta: i8,
+) {
+    state.apply_stat_change(entity, stat, delta);
+    if gen.shares_special_stat() && matches!(stat, 3 | 4) {
+        let (spa, spd) = (2, 3);
+        let stage = state.boosts[entity][stat - 1];
+        state.boosts[entity][spa] = stage;
+        state.boosts[entity][spd] = stage;
+    }
+}
+
+/// Effects of the status moves the battle models beyond Transform: Haze,
+/// Leech Seed and moves that only inflict a major status. Returns false if
+/// the move missed or failed.
+fn run_status_effect<G: GenMechanics, S: BattleSink>(
+    gen: G,
+    state: &mut BattleState,
+    user: usize,
+    target: usize,
+    move_id: MoveId,
+    rng: &mut BattleRng,
+    sink: &mut S,
+) -> bool {
+    if move_id == MoveId::Haze {
+        haze(gen, state, user, target, sink);
+        return true;
+    }
+    let inflicts = primary_status(move_id);
+    if move_id != MoveId::Leechseed && inflicts.is_none() {
+        return true;
+    }
+    if state.is_fainted(target) {
+        return false;
+    }
+    // King's Shield, Obstruct and Silk Trap only block damaging moves
+    let protection = state.last_move[target];
+    if state.volatiles[target].contains(Volatiles::PROTECT)
+        && !matches!(protection, MoveId::Kingsshield | MoveId::Obstruct | MoveId::Silktrap)
+    {
+        sink.event(state, &Event::Protected { target });
+        return false;
+    }
+    let accuracy = move_id.data_in(state.generation).accuracy;
+    if !accuracy_hits(gen, state, user, target, accuracy, rng) {
+        sink.event(state, &Event::Miss { user, target });
+        return false;
+    }
+    let landed = match inflicts {
+        Some(Status::SLEEP) if sleep_clause_blocks(state, target) => false,
+        Some(status) => {
+            !status_immune(gen, state, target, move_id, status)
+                && inflict_status(gen, state, target, status, rng, sink)
+        }
+        None => {
+            let seeded = state.volatiles[target].contains(Volatiles::LEECH_SEED)
+                || state.defensive_types(target).contains(&Type::Grass);
+            if !seeded {
+                state.volatiles[target].insert(Volatiles::LEECH_SEED);
+                sink.event(state, &Event::LeechSeed { target });
+            }
+            !seeded
+        }
+    };
+    if !landed {
+        sink.event(state, &Event::Fail { user });
+    }
+    landed
+}
+
+/// Whether Sleep Clause stops `target` from being put to sleep: one of its
+/// teammates is already asleep.
+fn sleep_clause_blocks(state: &BattleState, target: usize) -> bool {
+    let side = state.get_side(target);
+    state.sleep_clause
+        && (0..state.team_sizes[side] as usize)
+            .map(|slot| BattleState::entity_index(side, slot))
+            .any(|entity| {
+                entity != target
+                    && !state.is_fainted(entity)
+                    && state.status[entity] == Status::SLEEP
+            })
+}
+
+/// Major status inflicted by a status move that does nothing else.
+fn primary_status(move_id: MoveId) -> Option<Status> {
+    match move_id {
+        MoveId::Spore
+        | MoveId::Sleeppowder
+        | MoveId::Hypnosis
+        | MoveId::Sing
+        | MoveId::Lovelykiss => Some(Status::SLEEP),
+        MoveId::Thunderwave | MoveId::Stunspore | MoveId::Glare => Some(Status::PARALYSIS),
+        MoveId::Poisonpowder => Some(Status::POISON),
+        MoveId::Toxic => Some(Status::TOXIC),
+        MoveId::Willowisp => Some(Status::BURN),
+        _ => None,
+    }
+}
+
+/// Type immunity to a status move: Grass to powders (Gen 6+), Ground to
+/// Thunder Wave, and the types that can't get the status at all.
+fn status_immune<G: GenMechanics>(
+    gen: G,
+    state: &BattleState,
+    target: usize,
+    move_id: MoveId,
+    status: Status,
+) -> bool {
+    let types = state.defensive_types(target);
+    let powder = move_id.data_in(state.generation).flags.contains(MoveFlags::POWDER);
+    if (powder && gen.generation() >= 6 && types.contains(&Type::Grass))
+        || (move_id == MoveId::Thunderwave && types.contains(&Type::Ground))
+    {
+        return true;
+    }
+    match status {
+        Status::POISON | Status::TOXIC => {
+            types.contains(&Type::Poison) || types.contains(&Type::Steel)
+        }
+        Status::BURN => types.contains(&Type::Fire),
+        Status::PARALYSIS => gen.generation() >= 6 && types.contains(&Type::Electric),
+        _ => false,
+    }
+}
+
+/// Inflict a major status, rolling how long sleep lasts in this generation.
+fn inflict_status<G: GenMechanics, S: BattleSink>(
+    gen: G,
+    state: &mut BattleState,
+    entity: usize,
+    status: Status,
+    rng: &mut BattleRng,
+    sink: &mut S,
+) -> bool {
+    if !state.set_status(entity, status) {
+        return false;
+    }
+    let status = state.status[entity];
+    if status == Status::SLEEP {
+        let (min, max) = gen.sleep_turns();
+        let turns = min + rng.range((max - min + 1) as u32) as u8;
+        // can_act counts down before checking, so the turn spent waking up
+        // needs one more tick when the Pokémon may move on it
+        state.status_counter[entity] = turns + gen.moves_on_waking() as u8;
+    }
+    sink.event(state, &Event::StatusInflicted { entity, status });
+    true
+}
+
+/// Whether `move_id` is Protect or one of its variants.
+fn is_protection(move_id: MoveId) -> bool {
+    matches!(
+        move_id,
+        MoveId::Protect
+            | MoveId::Detect
+            | MoveId::Kingsshield
+            | MoveId::Spikyshield
+            | MoveId::Banefulbunker
+            | MoveId::Obstruct
+            | MoveId::Silktrap
+            | MoveId::Burningbulwark
+            | MoveId::Maxguard
+    )
+}
+
+/// Protect and its variants: shield the user for the rest of the turn.
+/// Fails if the user moves last, and gets less likely with each success
+/// in a row (`GenMechanics::protect_chain_odds`).
+fn protect<G: GenMechanics>(
+    gen: G,
+    state: &mut BattleState,
+    user: usize,
+    moves_last: bool,
+    rng: &mut BattleRng,
+) -> bool {
+    let (factor, cap) = gen.protect_chain_odds();
+    let chain = state.protect_chain[user] as u32;
+    if moves_last || (chain > 0 && !rng.chance(1, factor.saturating_pow(chain).min(cap))) {
+        state.protect_chain[user] = 0;
+        return false;
+    }
+    state.protect_chain[user] += 1;
+    state.volatiles[user].insert(Volatiles::PROTECT);
+    true
+}
+
+/// What a protection does to a Pokémon whose contact move it blocked. The
+/// protecting move is the target's last successful one.
+fn protection_contact<G: GenMechanics, S: BattleSink>(
+    gen: G,
+    state: &mut BattleState,
+    user: usize,
+    target: usize,
+    rng: &mut BattleRng,
+    sink: &mut S,
+) {
+    let protection = state.last_move[target];
+    let (stat, delta) = match protection {
+        MoveId::Spikyshield => {
+            let damage = (state.max_hp[user] / 8).max(1);
+            deal_damage(state, user, damage, EffectSource::Protection(protection), sink);
+            return;
+        }
+        MoveId::Banefulbunker | MoveId::Burningbulwark => {
+            let status = match protection {
+                MoveId::Banefulbunker => Status::POISON,
+                _ => Status::BURN,
+            };
+            if !state.is_fainted(user) && !status_immune(gen, state, user, protection, status) {
+                inflict_status(gen, state, user, status, rng, sink);
+            }
+            return;
+        }
+        // Gen 6-7 King's Shield lowered Attack by 2
+        MoveId::Kingsshield if gen.generation() <= 7 => (1, -2),
+        MoveId::Kingsshield => (1, -1),
+        MoveId::Obstruct => (2, -2),
+        MoveId::Silktrap => (5, -1),
+        _ => return,
+    };
+    let before = state.boosts[user];
+    change_stat(gen, state, user, stat, delta);
+    report_boosts(state, user, before, sink);
+}
+
+/// Haze: reset every stat stage on the field. With `haze_cures_status`
+/// (Gen 1) it also clears Leech Seed, Focus Energy and confusion, cures the
+/// target and downgrades the user's Toxic to poison, keeping its counter.
+fn haze<G: GenMechanics, S: BattleSink>(
+    gen: G,
+    state: &mut BattleState,
+    user: usize,
+    target: usize,
+    sink: &mut S,
+) {
+    for entity in [user, target] {
+        state.boosts[entity] = [0; BOOST_STATS];
+    }
+    sink.event(state, &Event::ClearAllBoosts);
+    if !gen.haze_cures_status() {
+        return;
+    }
+    for entity in [user, target] {
+        state.volatiles[entity]
+            .remove(Volatiles::LEECH_SEED | Volatiles::FOCUS_ENERGY | Volatiles::CONFUSION);
+    }
+    let status = state.status[target];
+    if status != Status::NONE && !state.is_fainted(target) {
+        state.status[target] = Status::NONE;
+        state.status_counter[target] = 0;
+        sink.event(state, &Event::StatusCured { entity: target, status });
+    }
+    if state.status[user] == Status::TOXIC {
+        state.status[user] = Status::POISON;
+        sink.event(state, &Event::StatusCured { entity: user, status: Status::TOXIC });
+        sink.event(state, &Event::StatusInflicted { entity: user, status: Status::POISON });
+    }
+}
+
+/// Catch `target` in a partial trapping move for the generation's number
+/// of turns. In Gen 1 the user is locked into the move meanwhile.
+fn start_partial_trap<G: GenMechanics, S: BattleSink>(
+    gen: G,
+    state: &mut BattleState,
+    user: usize,
+    target: usize,
+    move_id: MoveId,
+    rng: &mut BattleRng,
+    sink: &mut S,
+) {
+    if state.is_fainted(target) || state.partial_trap_turns[target] > 0 {
+        return;
+    }
+    let (min, max) = gen.partial_trap_turns();
+    state.partial_trap_turns[target] = min + rng.range((max - min + 1) as u32) as u8;
+    state.partial_trap_move[target] = move_id;
+    state.volatiles[target].insert(Volatiles::PARTIALLY_TRAPPED);
+    if gen.partial_trap_prevents_moves() {
+        state.volatiles[user].insert(Volatiles::LOCKED_MOVE);
+    }
+    sink.event(state, &Event::PartialTrap { target, source: user, move_id });
+}
+
+/// Release `target` from partial trapping and its trapper from the lock.
+fn end_partial_trap<S: BattleSink>(state: &mut BattleState, target: usize, sink: &mut S) {
+    state.partial_trap_turns[target] = 0;
+    state.volatiles[target].remove(Volatiles::PARTIALLY_TRAPPED);
+    let trapper = state.active_index(1 - state.get_side(target));
+    state.volatiles[trapper].remove(Volatiles::LOCKED_MOVE);
+    if !state.is_fainted(target) {
+        let move_id = state.partial_trap_move[target];
+        sink.event(state, &Event::PartialTrapEnd { target, move_id });
+    }
+}
+
+/// Move slot a Gen 1 partial trapper is locked into.
+fn locked_slot(state: &BattleState, user: usize) -> Option<usize> {
+    if !state.volatiles[user].contains(Volatiles::LOCKED_MOVE) {
+        return None;
+    }
+    let target = state.active_index(1 - state.get_side(user));
+    let move_id = state.partial_trap_move[target];
+    state.moves[user].iter().position(|&slot_move| slot_move == move_id)
+}
+
+/// Restore a Pokémon to full HP.
+fn heal_fully<S: BattleSink>(state: &mut BattleState, entity: usize, sink: &mut S) {
+    let amount = state.max_hp[entity] - state.hp[entity];
+    if amount > 0 {
+        state.hp[entity] = state.max_hp[entity];
+        sink.event(state, &Event::Heal { target: entity, amount, source: EffectSource::Move });
+    }
+}
+
+/// Weather residual damage: 1/16 max HP from Sandstorm or Hail.
+fn weather_residual<S: BattleSink>(state: &mut BattleState, weather: Weather, sink: &mut S) {
+    for player in 0..2 {
+        let entity = state.active_index(player);
+        if state.is_fainted(entity)
+            || has_ability(state, entity, AbilityId::Magicguard)
+            || has_ability(state, entity, AbilityId::Overcoat)
+        {
+            continue;
+        }
+        let types = state.defensive_types(entity);
+        let immune = match weather {
+            Weather::Sand => types
+                .iter()
+                .any(|t| matches!(t, Type::Rock | Type::Ground | Type::Steel)),
+            Weather::Hail => types.contains(&Type::Ice),
+            _ => true,
+        };
+        if !immune {
+            let damage = (state.max_hp[entity] / 16).max(1);
+            deal_damage(state, entity, damage, EffectSource::Weather(weather), sink);
+        }
+    }
+}
+
+/// G-Max Wildfire, Vine Lash, Cannonade and Volcalith residual damage:
+/// 1/6 of the non-Dynamax max HP to every Pokémon on the side that isn't of
+/// the spared type.
+fn gmax_residual<S: BattleSink>(state: &mut BattleState, sink: &mut S) {
+    for side in 0..2 {
+        let entity = state.active_index(side);
+        for (move_id, spared) in SIDE_RESIDUALS {
+            let turns = state.side_conditions[side].gmax_residual_turns(spared).map_or(0, |t| *t);
+            if turns == 0 {
+                continue;
+            }
+            if !state.is_fainted(entity)
+                && !has_ability(state, entity, AbilityId::Magicguard)
+                && !state.defensive_types(entity).contains(&spared)
+            {
+                let damage = (state.stats[entity][0] / 6).max(1);
+                deal_damage(state, entity, damage, EffectSource::SideCondition(move_id), sink);
+            }
+            if let Some(left) = state.side_conditions[side].gmax_residual_turns(spared) {
+                *left -= 1;
+            }
+            if turns == 1 {
+                sink.event(state, &Event::SideEnd { side, move_id });
+            }
+        }
+    }
+}
+
+/// Leech Seed residual: the seeded Pokémon loses HP to the opposing active.
+/// In Gen 1 a Toxic counter scales the drain and advances with it.
+fn leech_seed_residual<G: GenMechanics, S: BattleSink>(
+    gen: G,
+    state: &mut BattleState,
+    sink: &mut S,
+) {
+    for player in 0..2 {
+        let target = state.active_index(player);
+        let seeder = state.active_index(1 - player);
+        if !state.volatiles[target].contains(Volatiles::LEECH_SEED)
+            || state.is_fainted(target)
+            || state.is_fainted(seeder)
+            || has_ability(state, target, AbilityId::Magicguard)
+        {
+            continue;
+        }
+        let mut damage = (state.max_hp[target] / gen.leech_seed_divisor()).max(1);
+        let toxic = state.status[target].intersects(Status::TOXIC | Status::POISON);
+        if gen.toxic_counter_persists() && toxic {
+            let counter = (state.status_counter[target] + 1).min(15);
+            state.status_counter[target] = counter;
+            damage = damage.saturating_mul(counter as u16);
+        }
+        let hp_before = state.hp[target];
+        deal_damage(state, target, damage, EffectSource::LeechSeed, sink);
+        let drained = hp_before - state.hp[target];
+        let amount = drained.min(state.max_hp[seeder] - state.hp[seeder]);
+        if amount > 0 {
+            state.hp[seeder] += amount;
+            let event = Event::Heal { target: seeder, amount, source: EffectSource::LeechSeed };
+            sink.event(state, &event);
+        }
+    }
+}
+
+/// Burn and poison residual damage.
+fn status_residual<G: GenMechanics, S: BattleSink>(gen: G, state: &mut BattleState, sink: &mut S) {
+    for player in 0..2 {
+        let entity = state.active_index(player);
+        if state.is_fainted(entity) || has_ability(state, entity, AbilityId::Magicguard) {
+            continue;
+        }
+        let max_hp = state.max_hp[entity];
+        let status = state.status[entity];
+        let damage = if status == Status::BURN {
+            max_hp / gen.burn_divisor()
+        } else if status == Status::FROSTBITE {
+            max_hp / 16
+        } else if status == Status::TOXIC
+            || (status == Status::POISON
+                && gen.toxic_counter_persists()
+                && state.status_counter[entity] > 0)
+        {
+            // n/16 where n counts turns since Toxic was inflicted, kept in
+            // Gen 1 after Haze turns Toxic into poison
+            let counter = (state.status_counter[entity] + 1).min(15);
+            state.status_counter[entity] = counter;
+            (max_hp as u32 * counter as u32 / 16) as u16
+        } else if status == Status::POISON {
+            max_hp / gen.poison_divisor()
+        } else {
+            continue;
+        };
+        deal_damage(state, entity, damage.max(1), EffectSource::Status(status), sink);
+    }
+}
+
+/// Partial trapping residual damage and countdown. In Gen 1 the trap only
+/// counts down, and it ends early once the trapper faints.
+fn partial_trap_residual<G: GenMechanics, S: BattleSink>(
+    gen: G,
+    state: &mut BattleState,
+    sink: &mut S,
+) {
+    for player in 0..2 {
+        let target = state.active_index(player);
+        if state.partial_trap_turns[target] == 0 {
+            continue;
+        }
+        if state.is_fainted(state.active_index(1 - player)) {
+            end_partial_trap(state, target, sink);
+            continue;
+        }
+        let divisor = gen.partial_trap_divisor();
+        if divisor > 0 && !has_ability(state, target, AbilityId::Magicguard) {
+            let damage = (state.max_hp[target] / divisor).max(1);
+            let source = EffectSource::PartialTrap(state.partial_trap_move[target]);
+            deal_damage(state, target, damage, source, sink);
+        }
+        state.partial_trap_turns[target] -= 1;
+        if state.partial_trap_turns[target] == 0 {
+            end_partial_trap(state, target, sink);
+        }
+    }
+}
+
+/// End-of-turn residuals, countdowns and the next turn announcement.
+fn end_of_turn<G: GenMechanics, S: BattleSink>(gen: G, state: &mut BattleState, sink: &mut S) {
+    if report_result(state, sink) {
+        return;
+    }
+
+    if state.weather != 0 {
+        let weather = Weather::from_u8(state.weather);
+        // weather_turns == 0 means the weather lasts indefinitely
+        let expired = state.weather_turns == 1;
+        state.weather_turns = state.weather_turns.saturating_sub(1);
+        if expired {
+            state.weather = 0;
+            sink.event(state, &Event::WeatherEnd { weather });
+            after_weather_change(state, weather as u8, sink);
+        } else {
+            sink.event(state, &Event::WeatherUpkeep { weather });
+            weather_residual(state, weather, sink);
+        }
+    }
+
+    gmax_residual(state, sink);
+    leech_seed_residual(gen, state, sink);
+    status_residual(gen, state, sink);
+    partial_trap_residual(gen, state, sink);
+
+    for player in 0..2 {
+        let entity = state.active_index(player);
+        if !state.is_fainted(entity) {
+            check_forme_change(state, entity, FormeTrigger::Residual, sink);
+        }
+        if state.is_dynamaxed(entity) {
+            state.dynamax_turns[entity] -= 1;
+            if state.dynamax_turns[entity] == 0 {
+                state.end_dynamax(entity);
+                sink.event(state, &Event::DynamaxEnd { entity });
+            }
+        }
+    }
+
+    for volatiles in &mut state.volatiles {
+        volatiles.remove(
+            Volatiles::FLINCH
+                | Volatiles::PROTECT
+                | Volatiles::STRONG_STYLE
+                | Volatiles::AGILE_STYLE,
+        );
+    }
+    state.tick_side_conditions();
+
+    if report_result(state, sink) {
+        return;
+    }
+
+    sink.event(state, &Event::Upkeep);
+    state.turn += 1;
+    sink.event(state, &Event::TurnStart { turn: state.turn });
+}
+
+/// Report the winner if the battle just ended. Returns true if it did.
+fn report_result<S: BattleSink>(state: &BattleState, sink: &mut S) -> bool {
+    match battle_result(state) {
+        BattleResult::Ongoing => false,
+        BattleResult::Win(player) => {
+            sink.event(state, &Event::Win { player: Some(player) });
+            true
+        }
+        BattleResult::Tie => {
+            sink.event(state, &Event::Win { player: None });
+            true
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::damage::generations::{Gen1, Gen4, Gen7, Gen8};
+    use crate::damage::Gen9;
+    use crate::entities::PokemonConfig;
+    use crate::format::Gimmicks;
+    use crate::species::SpeciesId;
+
+    fn setup(p1: (&str, MoveId), p2: (&str, MoveId)) -> BattleState {
+        let mut state = BattleState::new();
+        PokemonConfig::from_str(p1.0)
+            .unwrap()
+            .level(50)
+            .moves([p1.1, MoveId::default(), MoveId::default(), MoveId::default()])
+            .place(&mut state, 0, 0);
+        PokemonConfig::from_str(p2.0)
+            .unwrap()
+            .level(50)
+            .moves([p2.1, MoveId::default(), MoveId::default(), MoveId::default()])
+            .place(&mut state, 1, 0);
+        start_battle(&mut state, &mut BattleRng::new(0), &mut ());
+        state
+    }
+
+    fn run_turn(state: &mut BattleState, seed: u64) -> String {
+        let mut rng = BattleRng::new(seed);
+        let mut out = ProtocolWriter::new(String::new());
+        execute_turn(
+            Gen9,
+            state,
+            [Action::Move(0), Action::Move(0)],
+            &mut rng,
+            &mut out,
+        );
+        out.into_inner()
+    }
+
+    #[test]
+    fn test_protocol_lines_for_super_effective_hit() {
+        let mut state = setup(
+            ("garchomp", MoveId::Earthquake),
+            ("tyranitar", MoveId::Crunch),
+        );
+        let log = run_turn(&mut state, 1);
+
+        assert!(log.contains("|move|p1a: Garchomp|Earthquake|p2a: Tyranitar\n"));
+        assert!(log.contains("|-supereffective|p2a: Tyranitar\n"));
+        assert!(log.contains("|turn|2\n"));
+    }
+
+    #[test]
+    fn test_faster_pokemon_moves_first() {
+        // Garchomp (base 102 Spe) outspeeds Tyranitar (base 61 Spe)
+        let mut state = setup(
+            ("garchomp", MoveId::Dragonclaw),
+            ("tyranitar", MoveId::Crunch),
+        );
+        let log = run_turn(&mut state, 3);
+        let first_move = log.lines().find(|l| l.starts_with("|move|")).unwrap();
+        assert!(first_move.starts_with("|move|p1a: Garchomp"));
+    }
+
+    #[test]
+    fn test_noop_sink_matches_protocol_sink() {
+        // The sink must only observe; execution is identical without it.
+        let mut a = setup(("garchomp", MoveId::Earthquake), ("tyranitar", MoveId::Crunch));
+        let mut b = a;
+        run_turn(&mut a, 9);
+        execute_turn(
+            Gen9,
+            &mut b,
+            [Action::Move(0), Action::Move(0)],
+            &mut BattleRng::new(9),
+            &mut (),
+        );
+        assert_eq!(a.hp, b.hp);
+        assert_eq!(a.pp, b.pp);
+    }
+
+    #[test]
+    fn test_burn_residual_is_one_sixteenth() {
+        let mut state = setup(("garchomp", MoveId::Swordsdance), ("blissey", MoveId::Softboiled));
+        state.status[6] = Status::BURN;
+        let max_hp = state.max_hp[6];
+        run_turn(&mut state, 5);
+        assert_eq!(state.hp[6], max_hp - max_hp / 16);
+    }
+
+    #[test]
+    fn test_switch_is_reported_before_moves() {
+        let mut state = setup(("garchomp", MoveId::Earthquake), ("tyranitar", MoveId::Crunch));
+        PokemonConfig::new(SpeciesId::from_str("corviknight").unwrap())
+            .level(50)
+            .place(&mut state, 1, 1);
+        let mut out = ProtocolWriter::new(String::new());
+        execute_turn(
+            Gen9,
+            &mut state,
+            [Action::Move(0), Action::Switch(1)],
+            &mut BattleRng::new(0),
+            &mut out,
+        );
+        let log = out.into_inner();
+        let switch_pos = log.find("|switch|p2a: Corviknight").unwrap();
+        let move_pos = log.find("|move|p1a: Garchomp").unwrap();
+        assert!(switch_pos < move_pos);
+        // Corviknight is Flying-type: Earthquake has no effect
+        assert!(log.contains("|-immune|p2a: Corviknight\n"));
+    }
+
+    #[test]
+    fn test_hazard_damage_names_each_hazard() {
+        let mut state = setup(("garchomp", MoveId::Swordsdance), ("snorlax", MoveId::Rest));
+        PokemonConfig::new(SpeciesId::from_str("blissey").unwrap())
+            .level(50)
+            .place(&mut state, 1, 1);
+        state.side_conditions[1].stealth_rock = true;
+        state.side_conditions[1].spikes_layers = 1;
+        let max_hp = state.max_hp[7];
+        let mut out = ProtocolWriter::new(String::new());
+        execute_turn(
+            Gen9,
+            &mut state,
+            [Action::Move(0), Action::Switch(1)],
+            &mut BattleRng::new(0),
+            &mut out,
+        );
+        let log = out.into_inner();
+        let rock = log.find("|[from] Stealth Rock\n").unwrap();
+        let spikes = log.find("|[from] Spikes\n").unwrap();
+        assert!(rock < spikes);
+        assert_eq!(state.hp[7], max_hp - max_hp / 8 - max_hp / 8);
+    }
+
+    #[test]
+    fn test_battle_result() {
+        let mut state = setup(("garchomp", MoveId::Earthquake), ("tyranitar", MoveId::Crunch));
+        assert_eq!(battle_result(&state), BattleResult::Ongoing);
+        state.hp[6] = 0;
+        assert_eq!(battle_result(&state), BattleResult::Win(0));
+    }
+
+    #[test]
+    fn test_switch_in_hooks_emit_events() {
+        // Intimidate reports its activation and the resulting Attack drop.
+        let mut state = setup(("garchomp", MoveId::Earthquake), ("tyranitar", MoveId::Crunch));
+        PokemonConfig::new(SpeciesId::from_str("gyarados").unwrap())
+            .level(50)
+            .ability(AbilityId::Intimidate)
+            .place(&mut state, 1, 1);
+        let mut events = Vec::new();
+        execute_turn(
+            Gen9,
+            &mut state,
+            [Action::Move(0), Action::Switch(1)],
+            &mut BattleRng::new(0),
+            &mut events,
+        );
+
+        let activated = Event::AbilityActivated { entity: 7, ability: AbilityId::Intimidate };
+        let boost = Event::Boost { entity: 0, stat: 0, delta: -1 };
+        let activated_pos = events.iter().position(|e| *e == activated).unwrap();
+        let boost_pos = events.iter().position(|e| *e == boost).unwrap();
+        assert!(activated_pos < boost_pos);
+    }
+
+    #[test]
+    fn test_after_damage_item_emits_consumption() {
+        // Earthquake drops Tyranitar below half; Sitrus Berry is eaten and heals.
+        let mut state = BattleState::new();
+        PokemonConfig::from_str("garchomp")
+            .unwrap()
+            .level(50)
+            .moves([MoveId::Earthquake, MoveId::default(), MoveId::default(), MoveId::default()])
+            .place(&mut state, 0, 0);
+        PokemonConfig::from_str("tyranitar")
+            .unwrap()
+            .level(50)
+            .item(ItemId::Sitrusberry)
+            .moves([MoveId::Crunch, MoveId::default(), MoveId::default(), MoveId::default()])
+            .place(&mut state, 1, 0);
+        let mut events = Vec::new();
+        execute_turn(
+            Gen9,
+            &mut state,
+            [Action::Move(0), Action::Pass],
+            &mut BattleRng::new(4),
+            &mut events,
+        );
+
+        assert!(events.contains(&Event::ItemConsumed { entity: 6, item: ItemId::Sitrusberry }));
+        assert!(events.iter().any(|e| matches!(
+            e,
+            Event::Heal { target: 6, source: EffectSource::Item(ItemId::Sitrusberry), .. }
+        )));
+        assert_eq!(state.items[6], ItemId::None);
+    }
+
+    #[test]
+    fn test_legal_actions() {
+        let mut state = setup(("garchomp", MoveId::Earthquake), ("tyranitar", MoveId::Crunch));
+        PokemonConfig::from_str("corviknight").unwrap().place(&mut state, 1, 1);
+        assert_eq!(legal_actions(&state, 0), vec![Action::Move(0), Action::Terastallize(0)]);
+        assert_eq!(
+            legal_actions(&state, 1),
+            vec![Action::Move(0), Action::Terastallize(0), Action::Switch(1)]
+        );
+
+        // A fainted active may only be replaced
+        state.hp[6] = 0;
+        assert_eq!(legal_actions(&state, 1), vec![Action::Switch(1)]);
+    }
+
+    #[test]
+    fn test_submit_actions_validates_and_replaces() {
+        let mut state = setup(("garchomp", MoveId::Earthquake), ("heatran", MoveId::Crunch));
+        PokemonConfig::from_str("corviknight").unwrap().place(&mut state, 1, 1);
+        let mut rng = BattleRng::new(0);
+
+        // An illegal choice is rejected before anything happens
+        let before = state;
+        let err = submit_actions(Gen9, &mut state, [Action::Move(2), Action::Move(0)], &mut rng, &mut ());
+        assert_eq!(err, Err(IllegalAction { player: 0, action: Action::Move(2) }));
+        assert_eq!(state.turn, before.turn);
+
+        // Earthquake KOs Heatran; the next submission is p2's replacement only
+        state.hp[6] = 1;
+        submit_actions(Gen9, &mut state, [Action::Move(0), Action::Move(0)], &mut rng, &mut ()).unwrap();
+        assert!(needs_replacement(&state, 1));
+        submit_actions(Gen9, &mut state, [Action::Pass, Action::Switch(1)], &mut rng, &mut ()).unwrap();
+        assert_eq!(state.active_index(1), 7);
+    }
+
+    #[test]
+    fn test_terastallize_once_per_side() {
+        let mut state = setup(("garchomp", MoveId::Earthquake), ("blissey", MoveId::Softboiled));
+        PokemonConfig::from_str("corviknight").unwrap().place(&mut state, 0, 1);
+        state.tera_type[0] = Type::Steel;
+        assert!(legal_actions(&state, 0).contains(&Action::Terastallize(0)));
+
+        let mut out = ProtocolWriter::new(String::new());
+        let actions = [Action::Terastallize(0), Action::Move(0)];
+        submit_actions(Gen9, &mut state, actions, &mut BattleRng::new(0), &mut out).unwrap();
+        let log = out.into_inner();
+        let tera_pos = log.find("|-terastallize|p1a: Garchomp|Steel\n").unwrap();
+        assert!(tera_pos < log.find("|move|").unwrap());
+        assert!(state.terastallized[0]);
+
+        // The side's Terastallization is spent, even for the benched Corviknight.
+        assert!(!legal_actions(&state, 0).contains(&Action::Terastallize(0)));
+        assert!(!state.can_terastallize(1));
+    }
+
+    /// Switch the battle to an older generation's rules and gimmicks.
+    fn use_gen(state: &mut BattleState, gen: u8) {
+        state.generation = gen;
+        state.gimmicks = Gimmicks::for_gen(gen);
+    }
+
+    #[test]
+    fn test_dynamax_lasts_three_turns() {
+        let mut state = setup(("blissey", MoveId::Tackle), ("hippowdon", MoveId::Slackoff));
+        use_gen(&mut state, 8);
+        let base_max_hp = state.max_hp[0];
+        assert!(legal_actions(&state, 0).contains(&Action::Dynamax(0)));
+
+        let mut out = ProtocolWriter::new(String::new());
+        let actions = [Action::Dynamax(0), Action::Move(0)];
+        submit_actions(Gen8, &mut state, actions, &mut BattleRng::new(0), &mut out).unwrap();
+        let log = out.into_inner();
+        assert!(log.contains("|-start|p1a: Blissey|Dynamax\n"));
+        assert!(log.contains("|move|p1a: Blissey|Max Strike|p2a: Hippowdon\n"));
+        assert!(log.contains("|-unboost|p2a: Hippowdon|spe|1\n"));
+        assert_eq!(state.max_hp[0], base_max_hp * 2);
+        assert!(!legal_actions(&state, 0).contains(&Action::Dynamax(0)));
+
+        let mut out = ProtocolWriter::new(String::new());
+        for _ in 0..2 {
+            let actions = [Action::Move(0), Action::Move(0)];
+            submit_actions(Gen8, &mut state, actions, &mut BattleRng::new(0), &mut out).unwrap();
+        }
+        assert!(out.into_inner().contains("|-end|p1a: Blissey|Dynamax\n"));
+        assert!(!state.is_dynamaxed(0));
+        assert_eq!(state.max_hp[0], base_max_hp);
+    }
+
+    #[test]
+    fn test_max_guard_and_weight_moves_against_dynamax() {
+        let mut state = setup(("blissey", MoveId::Softboiled), ("machamp", MoveId::Lowkick));
+        use_gen(&mut state, 8);
+
+        // Status moves become Max Guard, which blocks the Low Kick.
+        let mut out = ProtocolWriter::new(String::new());
+        let actions = [Action::Dynamax(0), Action::Move(0)];
+        execute_turn(Gen8, &mut state, actions, &mut BattleRng::new(0), &mut out);
+        let log = out.into_inner();
+        assert!(log.contains("|move|p1a: Blissey|Max Guard|"));
+        assert!(log.contains("|-activate|p1a: Blissey|move: Protect\n"));
+
+        // Unprotected, Low Kick still fails against a Dynamaxed target.
+        let mut out = ProtocolWriter::new(String::new());
+        let actions = [Action::Pass, Action::Move(0)];
+        execute_turn(Gen8, &mut state, actions, &mut BattleRng::new(0), &mut out);
+        assert!(out.into_inner().contains("|-fail|p2a: Machamp\n"));
+        assert_eq!(state.hp[0], state.max_hp[0]);
+    }
+
+    #[test]
+    fn test_protect_fails_when_moving_last() {
+        let mut state = setup(("blissey", MoveId::Protect), ("machamp", MoveId::Closecombat));
+
+        let mut out = ProtocolWriter::new(String::new());
+        let actions = [Action::Move(0), Action::Move(0)];
+        execute_turn(Gen9, &mut state, actions, &mut BattleRng::new(0), &mut out);
+        let log = out.into_inner();
+        assert!(log.contains("|-singleturn|p1a: Blissey|Protect\n"));
+        assert!(log.contains("|-activate|p1a: Blissey|move: Protect\n"));
+        assert_eq!(state.hp[0], state.max_hp[0]);
+        assert_eq!(state.protect_chain[0], 1);
+
+        // With nothing left to protect against, Protect fails and the
+        // chain restarts.
+        let mut out = ProtocolWriter::new(String::new());
+        let actions = [Action::Move(0), Action::Pass];
+        execute_turn(Gen9, &mut state, actions, &mut BattleRng::new(0), &mut out);
+        assert!(out.into_inner().contains("|-fail|p1a: Blissey\n"));
+        assert_eq!(state.protect_chain[0], 0);
+    }
+
+    #[test]
+    fn test_protect_chain_odds() {
+        let mut state = setup(("blissey", MoveId::Detect), ("machamp", MoveId::Closecombat));
+        // Six protections in a row leave a 1/729 chance.
+        state.protect_chain[0] = 6;
+        let successes = (0..20)
+            .filter(|&seed| {
+                let mut trial = state;
+                let actions = [Action::Move(0), Action::Move(0)];
+                execute_turn(Gen9, &mut trial, actions, &mut BattleRng::new(seed), &mut ());
+                trial.protect_chain[0] == 7
+            })
+            .count();
+        assert!(successes <= 1);
+        assert_eq!(Gen9.protect_chain_odds(), (3, 729));
+        assert_eq!(Gen4.protect_chain_odds(), (2, 8));
+    }
+
+    #[test]
+    fn test_protection_contact_effects() {
+        let mut state =
+            setup(("chesnaught", MoveId::Spikyshield), ("machamp", MoveId::Closecombat));
+        let mut out = ProtocolWriter::new(String::new());
+        let actions = [Action::Move(0), Action::Move(0)];
+        execute_turn(Gen9, &mut state, actions, &mut BattleRng::new(0), &mut out);
+        assert!(out.into_inner().contains("|[from] move: Spiky Shield"));
+        assert_eq!(state.max_hp[6] - state.hp[6], state.max_hp[6] / 8);
+
+        // King's Shield lowers the attacker's Attack, but lets status
+        // moves through.
+        let mut state = setup(("aegislash", MoveId::Kingsshield), ("machamp", MoveId::Closecombat));
+        run_turn(&mut state, 0);
+        assert_eq!(state.boosts[6][0], -1);
+        state.moves[6][0] = MoveId::Spore;
+        run_turn(&mut state, 0);
+        assert_eq!(state.status[0], Status::SLEEP);
+    }
+
+    #[test]
+    fn test_gmax_wildfire_burns_the_side() {
+        let mut state = setup(("charizard", MoveId::Flamethrower), ("blissey", MoveId::Softboiled));
+        use_gen(&mut state, 8);
+        state.gigantamax[0] = true;
+
+        let mut out = ProtocolWriter::new(String::new());
+        let actions = [Action::Dynamax(0), Action::Move(0)];
+        execute_turn(Gen8, &mut state, actions, &mut BattleRng::new(0), &mut out);
+        let log = out.into_inner();
+        assert!(log.contains("|-start|p1a: Charizard|Dynamax|Gmax\n"));
+        assert!(log.contains("|move|p1a: Charizard|G-Max Wildfire|p2a: Blissey\n"));
+        assert!(log.contains("|-sidestart|p2|G-Max Wildfire\n"));
+        assert!(log.contains("|[from] G-Max Wildfire\n"));
+        assert_eq!(state.species[0], SpeciesId::CHARIZARDGMAX);
+        assert_eq!(state.side_conditions[1].wildfire_turns, 3);
+
+        // Dynamax ends in the base forme.
+        for _ in 0..2 {
+            let actions = [Action::Pass, Action::Pass];
+            execute_turn(Gen8, &mut state, actions, &mut BattleRng::new(0), &mut ());
+        }
+        assert_eq!(state.species[0], SpeciesId::CHARIZARD);
+    }
+
+    #[test]
+    fn test_gmax_one_blow_hits_through_max_guard() {
+        let mut state = setup(("urshifu", MoveId::Wickedblow), ("blissey", MoveId::Softboiled));
+        use_gen(&mut state, 8);
+        state.gigantamax[0] = true;
+
+        let mut out = ProtocolWriter::new(String::new());
+        let actions = [Action::Dynamax(0), Action::Dynamax(0)];
+        execute_turn(Gen8, &mut state, actions, &mut BattleRng::new(0), &mut out);
+        let log = out.into_inner();
+        assert!(log.contains("|move|p2a: Blissey|Max Guard|"));
+        assert!(log.contains("|move|p1a: Urshifu|G-Max One Blow|p2a: Blissey\n"));
+        assert!(!log.contains("move: Protect"));
+        assert!(state.hp[6] < state.max_hp[6]);
+    }
+
+    #[test]
+    fn test_gmax_volt_crash_paralyzes() {
+        let mut state = setup(("pikachu", MoveId::Thunderbolt), ("blissey", MoveId::Softboiled));
+        use_gen(&mut state, 8);
+        state.gigantamax[0] = true;
+
+        let mut out = ProtocolWriter::new(String::new());
+        let actions = [Action::Dynamax(0), Action::Move(0)];
+        execute_turn(Gen8, &mut state, actions, &mut BattleRng::new(0), &mut out);
+        let log = out.into_inner();
+        assert!(log.contains("|move|p1a: Pikachu|G-Max Volt Crash|p2a: Blissey\n"));
+        assert_eq!(state.status[6], Status::PARALYSIS);
+    }
+
+    #[test]
+    fn test_gmax_wind_rage_clears_the_field() {
+        let mut state = setup(("corviknight", MoveId::Bravebird), ("blissey", MoveId::Softboiled));
+        use_gen(&mut state, 8);
+        state.gigantamax[0] = true;
+        state.side_conditions[1].reflect_turns = 5;
+        state.side_conditions[0].reflect_turns = 5;
+        state.side_conditions[0].stealth_rock = true;
+        state.terrain = Terrain::Electric as u8;
+        state.terrain_turns = 5;
+
+        let mut out = ProtocolWriter::new(String::new());
+        let actions = [Action::Dynamax(0), Action::Move(0)];
+        execute_turn(Gen8, &mut state, actions, &mut BattleRng::new(0), &mut out);
+        let log = out.into_inner();
+        assert!(log.contains("|-sideend|p2|Reflect\n"));
+        assert!(log.contains("|-sideend|p1|move: Stealth Rock\n"));
+        assert!(log.contains("|-fieldend|move: Electric Terrain\n"));
+        assert_eq!(state.side_conditions[1].reflect_turns, 0);
+        assert!(!state.side_conditions[0].stealth_rock);
+        assert_eq!(state.terrain, 0);
+        // Only the target's screens go.
+        assert_eq!(state.side_conditions[0].reflect_turns, 4);
+    }
+
+    #[test]
+    fn test_sleep_clause_blocks_a_second_sleeper() {
+        let mut state = setup(("breloom", MoveId::Spore), ("chansey", MoveId::Softboiled));
+        PokemonConfig::from_str("snorlax")
+            .unwrap()
+            .level(50)
+            .moves([MoveId::Bodyslam, MoveId::default(), MoveId::default(), MoveId::default()])
+            .place(&mut state, 1, 1);
+        assert!(state.set_status(7, Status::SLEEP));
+
+        state.sleep_clause = true;
+        let log = run_turn(&mut state, 0);
+        assert!(log.contains("|-fail|p1a: Breloom"));
+        assert_eq!(state.status[6], Status::NONE);
+
+        state.sleep_clause = false;
+        run_turn(&mut state, 0);
+        assert_eq!(state.status[6], Status::SLEEP);
+    }
+
+    #[test]
+    fn test_legends_arceus_statuses() {
+        let mut state = setup(("garchomp", MoveId::Swordsdance), ("blissey", MoveId::Softboiled));
+        state.gimmicks.legends_arceus = true;
+        assert!(state.set_status(0, Status::SLEEP));
+        assert!(state.set_status(6, Status::FREEZE));
+        assert_eq!(state.status[0], Status::DROWSY);
+        assert_eq!(state.status[6], Status::FROSTBITE);
+
+        // Neither status stops the Pokémon from moving; frostbite chips 1/16
+        let max_hp = state.max_hp[6];
+        let log = run_turn(&mut state, 5);
+        assert!(log.contains("|move|p1a: Garchomp|Swords Dance|"));
+        assert!(log.contains("|move|p2a: Blissey|Soft-Boiled|"));
+        assert!(log.contains("|-damage|p2a: Blissey|"));
+        assert_eq!(state.hp[6], max_hp - max_hp / 16);
+    }
+
+    #[test]
+    fn test_legal_actions_bound() {
+        let mut state = setup(("tyranitar", MoveId::Crunch), ("garchomp", MoveId::Dragonclaw));
+        let moves = [MoveId::Crunch, MoveId::Earthquake, MoveId::Stoneedge, MoveId::Dragondance];
+        for slot in 0..MAX_TEAM_SIZE {
+            PokemonConfig::from_str("tyranitar").unwrap().moves(moves).place(&mut state, 0, slot);
+        }
+        // The Styles take the place of Terastallization.
+        state.gimmicks.legends_arceus = true;
+        let actions = legal_actions(&state, 0);
+        assert_eq!(actions.len(), MAX_LEGAL_ACTIONS);
+        assert!(!actions.contains(&Action::Terastallize(0)));
+    }
+
+    #[test]
+    fn test_agile_style_moves_first_in_its_bracket() {
+        let mut state = setup(("tyranitar", MoveId::Crunch), ("garchomp", MoveId::Dragonclaw));
+        assert!(!legal_actions(&state, 0).contains(&Action::AgileStyle(0)));
+        state.gimmicks.legends_arceus = true;
+        assert!(legal_actions(&state, 0).contains(&Action::AgileStyle(0)));
+
+        let mut out = ProtocolWriter::new(String::new());
+        let actions = [Action::AgileStyle(0), Action::StrongStyle(0)];
+        execute_turn(Gen9, &mut state, actions, &mut BattleRng::new(0), &mut out);
+        let log = out.into_inner();
+        let first_move = log.lines().find(|l| l.starts_with("|move|")).unwrap();
+        assert!(first_move.starts_with("|move|p1a: Tyranitar"));
+    }
+
+    fn run_gen1_turn(state: &mut BattleState, seed: u64) -> String {
+        let mut out = ProtocolWriter::new(String::new());
+        let actions = [Action::Move(0), Action::Move(0)];
+        execute_turn(Gen1, state, actions, &mut BattleRng::new(seed), &mut out);
+        out.into_inner()
+    }
+
+    #[test]
+    fn test_gen1_freeze_never_thaws() {
+        let mut state = setup(("tauros", MoveId::Bodyslam), ("chansey", MoveId::Softboiled));
+        use_gen(&mut state, 1);
+        assert!(state.set_status(0, Status::FREEZE));
+        for seed in 0..10 {
+            let log = run_gen1_turn(&mut state, seed);
+            assert!(log.contains("|cant|p1a: Tauros|frz"));
+        }
+        assert_eq!(state.status[0], Status::FREEZE);
+    }
+
+    #[test]
+    fn test_gen1_waking_up_uses_the_turn() {
+        for (gen, moves) in [(1, false), (9, true)] {
+            let mut state = setup(("tauros", MoveId::Bodyslam), ("chansey", MoveId::Softboiled));
+            use_gen(&mut state, gen);
+            assert!(state.set_status(0, Status::SLEEP));
+            state.status_counter[0] = 1;
+            let mut out = ProtocolWriter::new(String::new());
+            let actions = [Action::Move(0), Action::Move(0)];
+            let mut rng = BattleRng::new(0);
+            execute_turn(Generation::from_num(gen), &mut state, actions, &mut rng, &mut out);
+            let log = out.into_inner();
+            assert!(log.contains("|-curestatus|p1a: Tauros|slp"));
+            assert_eq!(log.contains("|move|p1a: Tauros|"), moves, "gen {gen}");
+        }
+    }
+
+    #[test]
+    fn test_burn_and_poison_residuals_by_gen() {
+        for (gen, burn, poison) in [(1, 16, 16), (4, 8, 8), (9, 16, 8)] {
+            let mut state = setup(("chansey", MoveId::Growl), ("chansey", MoveId::Growl));
+            use_gen(&mut state, gen);
+            state.status[0] = Status::BURN;
+            state.status[6] = Status::POISON;
+            let max_hp = state.max_hp[0];
+            let actions = [Action::Move(0), Action::Move(0)];
+            let mut rng = BattleRng::new(0);
+            execute_turn(Generation::from_num(gen), &mut state, actions, &mut rng, &mut ());
+            assert_eq!(state.hp[0], max_hp - max_hp / burn, "gen {gen}");
+            assert_eq!(state.hp[6], max_hp - max_hp / poison, "gen {gen}");
+        }
+    }
+
+    #[test]
+    fn test_struggle_recoil_by_gen() {
+        for gen in [1, 3, 4] {
+            let mut state = setup(("tauros", MoveId::Bodyslam), ("chansey", MoveId::Growl));
+            use_gen(&mut state, gen);
+            state.pp[0] = [0; MAX_MOVES];
+            let actions = [Action::Move(0), Action::Move(0)];
+            let mut rng = BattleRng::new(0);
+            execute_turn(Generation::from_num(gen), &mut state, actions, &mut rng, &mut ());
+            let dealt = state.max_hp[6] - state.hp[6];
+            let recoil = match gen {
+                1 => dealt / 2,
+                3 => dealt / 4,
+                _ => state.max_hp[0] / 4,
+            };
+            assert!(dealt > 0);
+            assert_eq!(state.hp[0], state.max_hp[0] - recoil, "gen {gen}");
+        }
+    }
+
+    #[test]
+    fn test_hyper_beam_recharge() {
+        let mut state = setup(("tauros", MoveId::Hyperbeam), ("chansey", MoveId::Softboiled));
+        run_turn(&mut state, 0);
+        assert!(state.volatiles[0].contains(Volatiles::MUST_RECHARGE));
+        assert_eq!(legal_actions(&state, 0), vec![Action::Move(0)]);
+        let log = run_turn(&mut state, 1);
+        assert!(log.contains("|cant|p1a: Tauros|recharge"));
+        assert!(!log.contains("|move|p1a: Tauros|"));
+
+        // Gen 1 skips the recharge after a KO
+        let mut state = setup(("tauros", MoveId::Hyperbeam), ("chansey", MoveId::Softboiled));
+        use_gen(&mut state, 1);
+        state.hp[6] = 1;
+        run_gen1_turn(&mut state, 0);
+        assert!(state.is_fainted(6));
+        assert!(!state.volatiles[0].contains(Volatiles::MUST_RECHARGE));
+    }
+
+    #[test]
+    fn test_gen1_wrap_stops_the_target_moving() {
+        let mut state = setup(("dragonite", MoveId::Wrap), ("snorlax", MoveId::Bodyslam));
+        use_gen(&mut state, 1);
+        let log = run_gen1_turn(&mut state, 0);
+        assert!(log.contains("|-activate|p2a: Snorlax|move: Wrap|[of] p1a: Dragonite"));
+        assert!(log.contains("|cant|p2a: Snorlax|partiallytrapped"));
+        assert_eq!(legal_actions(&state, 0), vec![Action::Move(0)]);
+
+        // No chip damage in Gen 1, and the trap ends within five turns
+        let mut freed = false;
+        for seed in 1..5 {
+            let log = run_gen1_turn(&mut state, seed);
+            assert!(!log.contains("[from] move: Wrap"));
+            freed |= log.contains("|-end|p2a: Snorlax|Wrap|[partiallytrapped]");
+            if freed {
+                break;
+            }
+            assert!(!log.contains("|move|p2a: Snorlax|"));
+        }
+        assert!(freed);
+        assert!(!state.volatiles[0].contains(Volatiles::LOCKED_MOVE));
+    }
+
+    #[test]
+    fn test_gen1_perfect_accuracy_misses_one_in_256() {
+        let state = setup(("tauros", MoveId::Bodyslam), ("chansey", MoveId::Softboiled));
+        let misses = |gen: Generation| {
+            (0..2048)
+                .filter(|&seed| !accuracy_hits(gen, &state, 0, 6, 100, &mut BattleRng::new(seed)))
+                .count()
+        };
+        assert!(misses(Generation::Gen1(Gen1)) > 0);
+        assert_eq!(misses(Generation::Gen9(Gen9)), 0);
+    }
+
+    #[test]
+    fn test_gen1_haze_keeps_the_toxic_counter() {
+        let mut state = setup(("weezing", MoveId::Haze), ("chansey", MoveId::Softboiled));
+        use_gen(&mut state, 1);
+        assert!(state.set_status(0, Status::TOXIC));
+        state.status_counter[0] = 3;
+        assert!(state.set_status(6, Status::PARALYSIS));
+        state.boosts[6][0] = 2;
+
+        let max_hp = state.max_hp[0];
+        let log = run_gen1_turn(&mut state, 0);
+        assert!(log.contains("|-clearallboost"));
+        assert_eq!(state.boosts[6], [0; BOOST_STATS]);
+        assert_eq!(state.status[6], Status::NONE);
+        // Downgraded to poison, but still ticking up like Toxic
+        assert_eq!(state.status[0], Status::POISON);
+        assert_eq!(state.status_counter[0], 4);
+        assert_eq!(state.hp[0], max_hp - max_hp * 4 / 16);
+    }
+
+    #[test]
+    fn test_gen1_leech_seed_scales_with_toxic() {
+        let mut state = setup(("exeggutor", MoveId::Leechseed), ("tauros", MoveId::Amnesia));
+        use_gen(&mut state, 1);
+        assert!(state.set_status(6, Status::TOXIC));
+        run_gen1_turn(&mut state, 0);
+        assert!(state.volatiles[6].contains(Volatiles::LEECH_SEED));
+
+        // Leech Seed takes 1/16 and advances the counter before Toxic's 2/16
+        let max_hp = state.max_hp[6];
+        assert_eq!(state.hp[6], max_hp - max_hp / 16 - max_hp * 2 / 16);
+        assert_eq!(state.status_counter[6], 2);
+    }
+
+    #[test]
+    fn test_gen1_shared_special_stages() {
+        let mut state = setup(("alakazam", MoveId::Psychic), ("chansey", MoveId::Softboiled));
+        change_stat(Gen1, &mut state, 0, 4, 2);
+        assert_eq!(state.boosts[0][2], 2);
+        assert_eq!(state.boosts[0][3], 2);
+        change_stat(Gen9, &mut state, 6, 4, 2);
+        assert_eq!(state.boosts[6][2], 0);
+    }
+
+    #[test]
+    fn test_z_move_once_per_side() {
+        let mut state = setup(("pikachu", MoveId::Thunderbolt), ("blissey", MoveId::Tackle));
+        use_gen(&mut state, 7);
+        assert!(!legal_actions(&state, 0).contains(&Action::ZMove(0)));
+        state.items[0] = ItemId::Electriumz;
+        assert!(legal_actions(&state, 0).contains(&Action::ZMove(0)));
+
+        let mut out = ProtocolWriter::new(String::new());
+        let actions = [Action::ZMove(0), Action::Move(0)];
+        submit_actions(Gen7, &mut state, actions, &mut BattleRng::new(0), &mut out).unwrap();
+        let log = out.into_inner();
+        assert!(log.contains("|-zpower|p1a: Pikachu\n"));
+        assert!(log.contains("|move|p1a: Pikachu|Gigavolt Havoc|p2a: Blissey\n"));
+        assert!(!legal_actions(&state, 0).contains(&Action::ZMove(0)));
+    }
+
+    #[test]
+    fn test_z_status_effect() {
+        let mut state = setup(("snorlax", MoveId::Splash), ("blissey", MoveId::Tackle));
+        use_gen(&mut state, 7);
+        state.items[0] = ItemId::Normaliumz;
+
+        // Z-Splash raises Attack by 3 stages.
+        let mut out = ProtocolWriter::new(String::new());
+        let actions = [Action::ZMove(0), Action::Move(0)];
+        execute_turn(Gen7, &mut state, actions, &mut BattleRng::new(0), &mut out);
+        assert!(out.into_inner().contains("|-boost|p1a: Snorlax|atk|3\n"));
+        assert_eq!(state.boosts[0][0], 3);
+    }
+
+    #[test]
+    fn test_z_move_hits_through_protect() {
+        let mut state = setup(("pikachu", MoveId::Thunderbolt), ("blissey", MoveId::Softboiled));
+        use_gen(&mut state, 7);
+        state.items[0] = ItemId::Electriumz;
+        state.moves[6][0] = MoveId::Protect;
+        let full = calculate_z_move_damage(Gen7, &state, 0, 6, MoveId::Thunderbolt, false);
+
+        let mut out = ProtocolWriter::new(String::new());
+        let actions = [Action::ZMove(0), Action::Move(0)];
+        execute_turn(Gen7, &mut state, actions, &mut BattleRng::new(0), &mut out);
+        let log = out.into_inner();
+        assert!(log.contains("|-singleturn|p2a: Blissey|Protect\n"));
+        assert!(log.contains("|-zbroke|p2a: Blissey\n"));
+        let damage = state.max_hp[6] - state.hp[6];
+        assert!(damage > 0 && damage <= full.max.div_ceil(4));
+    }
+
+    #[test]
+    fn test_mega_evolve_once_per_side() {
+        let mut state = setup(("gengar", MoveId::Shadowball), ("blissey", MoveId::Tackle));
+        use_gen(&mut state, 7);
+        assert!(!legal_actions(&state, 0).contains(&Action::MegaEvolve(0)));
+        state.items[0] = ItemId::Gengarite;
+        assert!(legal_actions(&state, 0).contains(&Action::MegaEvolve(0)));
+
+        let mut out = ProtocolWriter::new(String::new());
+        let actions = [Action::MegaEvolve(0), Action::Move(0)];
+        submit_actions(Gen7, &mut state, actions, &mut BattleRng::new(0), &mut out).unwrap();
+        let log = out.into_inner();
+        assert!(log.contains("|detailschange|p1a: Gengar-Mega|Gengar-Mega, L50"));
+        assert!(log.contains("|-mega|p1a: Gengar-Mega|Gengar|Gengarite\n"));
+        assert_eq!(state.species[0], SpeciesId::GENGARMEGA);
+        assert_eq!(state.abilities[0], AbilityId::Shadowtag);
+        assert!(!legal_actions(&state, 0).contains(&Action::MegaEvolve(0)));
+    }
+
+    #[test]
+    fn test_mega_speed_applies_from_gen_8() {
+        // Beedrill (75 Spe) is slower than Gengar (110) until it Mega
+        // Evolves (145).
+        let first_mover = |gen: u8| {
+            let mut state = setup(("beedrill", MoveId::Tackle), ("gengar", MoveId::Tackle));
+            use_gen(&mut state, gen);
+            state.gimmicks.mega = true;
+            state.items[0] = ItemId::Beedrillite;
+            let mut out = ProtocolWriter::new(String::new());
+            let actions = [Action::MegaEvolve(0), Action::Move(0)];
+            if gen <= 7 {
+                execute_turn(Gen7, &mut state, actions, &mut BattleRng::new(0), &mut out);
+            } else {
+                execute_turn(Gen8, &mut state, actions, &mut BattleRng::new(0), &mut out);
+            }
+            let log = out.into_inner();
+            let p1 = log.find("|move|p1a").unwrap();
+            let p2 = log.find("|move|p2a").unwrap();
+            if p1 < p2 { 0 } else { 1 }
+        };
+        assert_eq!(first_mover(7), 1);
+        assert_eq!(first_mover(8), 0);
+    }
+
+    #[test]
+    fn test_primal_reversion_on_switch_in() {
+        let mut state = BattleState::new();
+        PokemonConfig::from_str("kyogre")
+            .unwrap()
+            .level(50)
+            .item(ItemId::Blueorb)
+            .moves([MoveId::Surf, MoveId::default(), MoveId::default(), MoveId::default()])
+            .place(&mut state, 0, 0);
+        PokemonConfig::from_str("blissey")
+            .unwrap()
+            .level(50)
+            .moves([MoveId::Tackle, MoveId::default(), MoveId::default(), MoveId::default()])
+            .place(&mut state, 1, 0);
+
+        let mut out = ProtocolWriter::new(String::new());
+        start_battle(&mut state, &mut BattleRng::new(0), &mut out);
+        let log = out.into_inner();
+        assert!(log.contains("|-primal|p1a: Kyogre-Primal\n"));
+        assert!(log.contains("|-weather|PrimordialSea\n"));
+        assert_eq!(state.species[0], SpeciesId::KYOGREPRIMAL);
+        assert_eq!(state.weather, Weather::HeavyRain as u8);
+    }
+
+    #[test]
+    fn test_ultra_burst() {
+        let mut state = setup(
+            ("necrozmaduskmane", MoveId::Photongeyser),
+            ("blissey", MoveId::Tackle),
+        );
+        state.items[0] = ItemId::Ultranecroziumz;
+        use_gen(&mut state, 8);
+        assert!(!legal_actions(&state, 0).contains(&Action::UltraBurst(0)));
+        use_gen(&mut state, 7);
+        assert!(legal_actions(&state, 0).contains(&Action::UltraBurst(0)));
+
+        let mut out = ProtocolWriter::new(String::new());
+        let actions = [Action::UltraBurst(0), Action::Move(0)];
+        submit_actions(Gen7, &mut state, actions, &mut BattleRng::new(0), &mut out).unwrap();
+        assert!(out
+            .into_inner()
+            .contains("|-burst|p1a: Necrozma-Ultra|Necrozma-Ultra|Ultranecrozium Z\n"));
+        assert_eq!(state.species[0], SpeciesId::NECROZMAULTRA);
+        // Ultra Burst doesn't use up the Z-Move.
+        assert!(legal_actions(&state, 0).contains(&Action::ZMove(0)));
+    }
+
+    #[test]
+    fn test_stance_change_reverts_on_switch_out() {
+        let mut state = setup(("aegislash", MoveId::Shadowball), ("blissey", MoveId::Softboiled));
+        PokemonConfig::from_str("snorlax").unwrap().level(50).place(&mut state, 0, 1);
+
+        let mut rng = BattleRng::new(0);
+        let mut out = ProtocolWriter::new(String::new());
+        execute_turn(Gen9, &mut state, [Action::Move(0), Action::Move(0)], &mut rng, &mut out);
+        let log = out.into_inner();
+        let stance_pos = log
+            .find("|-formechange|p1a: Aegislash-Blade|Aegislash-Blade|[from] ability: Stance Change\n")
+            .unwrap();
+        assert!(stance_pos < log.find("|move|p1a: Aegislash-Blade|Shadow Ball").unwrap());
+        assert_eq!(state.species[0], SpeciesId::AEGISLASHBLADE);
+
+        execute_turn(Gen9, &mut state, [Action::Switch(1), Action::Move(0)], &mut rng, &mut ());
+        assert_eq!(state.species[0], SpeciesId::AEGISLASH);
+        assert_eq!(state.abilities[0], AbilityId::Stancechange);
+    }
+
+    #[test]
+    fn test_disguise_takes_the_first_hit() {
+        let mut state = setup(("mimikyu", MoveId::Splash), ("garchomp", MoveId::Earthquake));
+        let max_hp = state.max_hp[0];
+        let mut rng = BattleRng::new(0);
+
+        execute_turn(Gen9, &mut state, [Action::Move(0), Action::Move(0)], &mut rng, &mut ());
+        // Only the Gen 8+ chip damage gets through.
+        assert_eq!(state.species[0], SpeciesId::MIMIKYUBUSTED);
+        assert_eq!(state.hp[0], max_hp - max_hp / 8);
+
+        execute_turn(Gen9, &mut state, [Action::Move(0), Action::Move(0)], &mut rng, &mut ());
+        assert!(state.hp[0] < max_hp - max_hp / 8);
+    }
+
+    #[test]
+    fn test_imposter_transforms_until_switch_out() {
+        let mut state = BattleState::new();
+        PokemonConfig::from_str("ditto")
+            .unwrap()
+            .level(50)
+            .ability(AbilityId::Imposter)
+            .moves([MoveId::Transform, MoveId::default(), MoveId::default(), MoveId::default()])
+            .place(&mut state, 0, 0);
+        PokemonConfig::from_str("snorlax").unwrap().level(50).place(&mut state, 0, 1);
+        PokemonConfig::from_str("garchomp")
+            .unwrap()
+            .level(50)
+            .moves([MoveId::Earthquake, MoveId::Dragonclaw, MoveId::default(), MoveId::default()])
+            .place(&mut state, 1, 0);
+
+        let mut out = ProtocolWriter::new(String::new());
+        start_battle(&mut state, &mut BattleRng::new(0), &mut out);
+        assert!(out.into_inner().contains("|-transform|p1a: Ditto|p2a: Garchomp\n"));
+        assert_eq!(state.species[0], SpeciesId::GARCHOMP);
+        assert_eq!(state.stats[0][1..], state.stats[6][1..]);
+        assert_ne!(state.max_hp[0], state.max_hp[6]);
+        assert_eq!(state.moves[0][1], MoveId::Dragonclaw);
+        assert_eq!(state.pp[0][..2], [5, 5]);
+        assert!(legal_actions(&state, 0).contains(&Action::Move(1)));
+
+        let actions = [Action::Switch(1), Action::Move(1)];
+        execute_turn(Gen9, &mut state, actions, &mut BattleRng::new(0), &mut ());
+        assert_eq!(state.species[0], SpeciesId::DITTO);
+        assert_eq!(state.abilities[0], AbilityId::Imposter);
+        assert_eq!(state.moves[0][0], MoveId::Transform);
+    }
+
+    #[test]
+    fn test_illusion_breaks_on_hit() {
+        let mut state = setup(("garchomp", MoveId::Earthquake), ("zoroark", MoveId::Nastyplot));
+        PokemonConfig::from_str("machamp").unwrap().level(50).place(&mut state, 1, 1);
+        state.abilities[6] = AbilityId::Illusion;
+        state.start_illusion(6);
+
+        let mut out = ProtocolWriter::new(String::new());
+        let actions = [Action::Move(0), Action::Move(0)];
+        execute_turn(Gen9, &mut state, actions, &mut BattleRng::new(0), &mut out);
+        let log = out.into_inner();
+        assert!(log.contains("|move|p1a: Garchomp|Earthquake|p2a: Machamp\n"));
+        assert!(log.contains("|replace|p2a: Zoroark|Zoroark, L50"));
+        assert!(log.contains("|-end|p2a: Zoroark|Illusion\n"));
+        assert_eq!(state.illusion[6], None);
+    }
+
+    #[test]
+    fn test_action_choice_round_trip() {
+        // Choices are 1-based on the wire, slots are 0-based internally.
+        assert_eq!(Action::from_choice("move 2"), Some(Action::Move(1)));
+        assert_eq!(Action::from_choice("switch 6"), Some(Action::Switch(5)));
+        assert_eq!(Action::from_choice("move 0"), None);
+        assert_eq!(Action::from_choice("move 1 2"), None);
+        assert_eq!(Action::from_choice("move 1 terastallize"), Some(Action::Terastallize(0)));
+        for action in [
+            Action::Move(3),
+            Action::Terastallize(1),
+            Action::Dynamax(2),
+            Action::ZMove(0),
+            Action::MegaEvolve(1),
+            Action::UltraBurst(3),
+            Action::StrongStyle(2),
+            Action::AgileStyle(0),
+            Action::Switch(0),
+            Action::Pass,
+        ] {
+            assert_eq!(Action::from_choice(&action.to_string()), Some(action));
+        }
+    }
+}
@@ -0,0 +1,437 @@
+//! Pokémon Showdown protocol output.
+//!
+//! `ProtocolWriter` is a `BattleSink` that renders battle events as
+//! Showdown protocol lines (`|move|p1a: Garchomp|Earthquake|p2a: Tyranitar`),
+//! so engine output can be diffed against Showdown logs or fed to existing
+//! replay tooling.
+
+use core::fmt::{self, Write};
+
+use super::events::{CantReason, EffectSource, Event};
+use super::sink::BattleSink;
+use crate::abilities::Terrain;
+use crate::damage::generations::Weather;
+use crate::entities::Gender;
+use crate::items::ItemId;
+use crate::moves::MoveId;
+use crate::state::{BattleState, Status};
+
+/// Writes Showdown protocol lines (one per message, `\n`-terminated).
+///
+/// Write errors from the underlying writer are ignored; use a `String`
+/// when every line must be captured.
+#[derive(Clone, Debug, Default)]
+pub struct ProtocolWriter<W: Write> {
+    out: W,
+}
+
+impl<W: Write> ProtocolWriter<W> {
+    /// Wrap a writer.
+    pub fn new(out: W) -> Self {
+        Self { out }
+    }
+
+    /// Borrow the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.out
+    }
+
+    /// Consume the sink and return the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.out
+    }
+
+    fn line(&mut self, args: fmt::Arguments<'_>) {
+        let _ = self.out.write_fmt(args);
+        let _ = self.out.write_char('\n');
+    }
+
+    /// Announce a permanent forme change (Mega Evolution, Primal Reversion,
+    /// Ultra Burst, Disguise, ...) with the new details.
+    fn forme_change(&mut self, state: &BattleState, entity: usize) {
+        self.line(format_args!(
+            "|detailschange|{}|{}",
+            Ident(state, entity),
+            Details(state, entity)
+        ));
+    }
+}
+
+/// Showdown identifier for an active Pokémon (`p1a: Garchomp`).
+struct Ident<'a>(&'a BattleState, usize);
+
+impl fmt::Display for Ident<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Ident(state, entity) = *self;
+        // Singles only has one active position per side ("a").
+        write!(
+            f,
+            "p{}a: {}",
+            state.get_side(entity) + 1,
+            state.displayed_species(entity).data().name
+        )
+    }
+}
+
+/// Showdown details string (`Garchomp, L50, M`).
+struct Details<'a>(&'a BattleState, usize);
+
+impl fmt::Display for Details<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Details(state, entity) = *self;
+        f.write_str(state.displayed_species(entity).data().name)?;
+        if state.level[entity] != 100 {
+            write!(f, ", L{}", state.level[entity])?;
+        }
+        match state.gender[entity] {
+            Gender::Male => f.write_str(", M"),
+            Gender::Female => f.write_str(", F"),
+            Gender::Genderless => Ok(()),
+        }
+    }
+}
+
+/// Showdown HP/status string (`123/301 brn`, `0 fnt`).
+struct Condition<'a>(&'a BattleState, usize);
+
+impl fmt::Display for Condition<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Condition(state, entity) = *self;
+        if state.hp[entity] == 0 {
+            return f.write_str("0 fnt");
+        }
+        write!(f, "{}/{}", state.hp[entity], state.max_hp[entity])?;
+        match status_id(state.status[entity]) {
+            Some(id) => write!(f, " {}", id),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Showdown status ID (`brn`, `tox`, ...). Showdown has no frostbite or
+/// drowsiness, so `frb` and `drw` are our own.
+fn status_id(status: Status) -> Option<&'static str> {
+    if status.contains(Status::BURN) {
+        Some("brn")
+    } else if status.contains(Status::FREEZE) {
+        Some("frz")
+    } else if status.contains(Status::PARALYSIS) {
+        Some("par")
+    } else if status.contains(Status::TOXIC) {
+        Some("tox")
+    } else if status.contains(Status::POISON) {
+        Some("psn")
+    } else if status.contains(Status::SLEEP) {
+        Some("slp")
+    } else if status.contains(Status::FROSTBITE) {
+        Some("frb")
+    } else if status.contains(Status::DROWSY) {
+        Some("drw")
+    } else {
+        None
+    }
+}
+
+/// Showdown weather ID (`RainDance`, `none`).
+fn weather_id(weather: Weather) -> &'static str {
+    match weather {
+        Weather::None => "none",
+        Weather::Sun => "SunnyDay",
+        Weather::Rain => "RainDance",
+        Weather::Sand => "Sandstorm",
+        Weather::Hail => "Hail",
+        Weather::Snow => "Snow",
+        Weather::HarshSun => "DesolateLand",
+        Weather::HeavyRain => "PrimordialSea",
+        Weather::StrongWinds => "DeltaStream",
+    }
+}
+
+/// Showdown boost ID, indexed like `BattleState::boosts`.
+const BOOST_IDS: [&str; 7] = ["atk", "def", "spa", "spd", "spe", "accuracy", "evasion"];
+
+/// Showdown terrain effect name.
+fn terrain_name(terrain: Terrain) -> &'static str {
+    match terrain {
+        Terrain::None => "none",
+        Terrain::Electric => "Electric Terrain",
+        Terrain::Grassy => "Grassy Terrain",
+        Terrain::Misty => "Misty Terrain",
+        Terrain::Psychic => "Psychic Terrain",
+    }
+}
+
+/// Showdown name of a side condition set by a move. Most hazards, Light
+/// Screen and Aurora Veil carry a `move:` prefix; Spikes, Reflect and G-Max
+/// residual damage don't.
+struct SideEffect(MoveId);
+
+impl fmt::Display for SideEffect {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if matches!(
+            self.0,
+            MoveId::Gmaxsteelsurge
+                | MoveId::Stealthrock
+                | MoveId::Toxicspikes
+                | MoveId::Stickyweb
+                | MoveId::Lightscreen
+                | MoveId::Auroraveil
+        ) {
+            f.write_str("move: ")?;
+        }
+        f.write_str(self.0.data().name)
+    }
+}
+
+/// Showdown `[from]` suffix for an effect source (empty for direct hits).
+struct FromSuffix(EffectSource);
+
+impl fmt::Display for FromSuffix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            EffectSource::Move => Ok(()),
+            EffectSource::Hazard(move_id) => write!(f, "|[from] {}", move_id.data().name),
+            EffectSource::Weather(weather) => write!(f, "|[from] {}", weather_id(weather)),
+            EffectSource::Status(status) => write!(f, "|[from] {}", status_id(status).unwrap_or("")),
+            EffectSource::Recoil => f.write_str("|[from] Recoil"),
+            EffectSource::Ability(ability) => write!(f, "|[from] ability: {}", ability.name()),
+            EffectSource::Item(item) => write!(f, "|[from] item: {}", item.data().name),
+            EffectSource::SideCondition(move_id) => write!(f, "|[from] {}", move_id.data().name),
+            EffectSource::Protection(move_id) => {
+                write!(f, "|[from] move: {}", move_id.data().name)
+            }
+            EffectSource::PartialTrap(move_id) => {
+                write!(f, "|[from] move: {}|[partiallytrapped]", move_id.data().name)
+            }
+            EffectSource::LeechSeed => f.write_str("|[from] Leech Seed"),
+        }
+    }
+}
+
+impl<W: Write> BattleSink for ProtocolWriter<W> {
+    fn event(&mut self, state: &BattleState, event: &Event) {
+        match *event {
+            Event::TurnStart { turn } => self.line(format_args!("|turn|{}", turn)),
+            Event::SwitchIn { entity } => self.line(format_args!(
+                "|switch|{}|{}|{}",
+                Ident(state, entity),
+                Details(state, entity),
+                Condition(state, entity)
+            )),
+            Event::MoveUsed {
+                user,
+                move_id,
+                target,
+            } => self.line(format_args!(
+                "|move|{}|{}|{}",
+                Ident(state, user),
+                move_id.data().name,
+                Ident(state, target)
+            )),
+            Event::Cant { entity, reason } => {
+                let reason = match reason {
+                    CantReason::Sleep => "slp",
+                    CantReason::Freeze => "frz",
+                    CantReason::Paralysis => "par",
+                    CantReason::Flinch => "flinch",
+                    CantReason::Recharge => "recharge",
+                    CantReason::PartiallyTrapped => "partiallytrapped",
+                };
+                self.line(format_args!("|cant|{}|{}", Ident(state, entity), reason));
+            }
+            Event::Miss { user, target } => self.line(format_args!(
+                "|-miss|{}|{}",
+                Ident(state, user),
+                Ident(state, target)
+            )),
+            Event::CriticalHit { target } => {
+                self.line(format_args!("|-crit|{}", Ident(state, target)))
+            }
+            Event::Effectiveness {
+                target,
+                effectiveness,
+            } => {
+                let tag = match effectiveness {
+                    0 => "-immune",
+                    1..=3 => "-resisted",
+                    4 => return,
+                    _ => "-supereffective",
+                };
+                self.line(format_args!("|{}|{}", tag, Ident(state, target)));
+            }
+            Event::HitCount { target, hits } => {
+                self.line(format_args!("|-hitcount|{}|{}", Ident(state, target), hits))
+            }
+            Event::Damage { target, source, .. } => self.line(format_args!(
+                "|-damage|{}|{}{}",
+                Ident(state, target),
+                Condition(state, target),
+                FromSuffix(source)
+            )),
+            Event::Heal { target, source, .. } => self.line(format_args!(
+                "|-heal|{}|{}{}",
+                Ident(state, target),
+                Condition(state, target),
+                FromSuffix(source)
+            )),
+            Event::StatusInflicted { entity, status } => {
+                if let Some(id) = status_id(status) {
+                    self.line(format_args!("|-status|{}|{}", Ident(state, entity), id));
+                }
+            }
+            Event::StatusCured { entity, status } => {
+                if let Some(id) = status_id(status) {
+                    self.line(format_args!(
+                        "|-curestatus|{}|{}|[msg]",
+                        Ident(state, entity),
+                        id
+                    ));
+                }
+            }
+            Event::Boost {
+                entity,
+                stat,
+                delta,
+            } => {
+                let Some(id) = BOOST_IDS.get(stat) else {
+                    return;
+                };
+                let tag = if delta < 0 { "-unboost" } else { "-boost" };
+                self.line(format_args!(
+                    "|{}|{}|{}|{}",
+                    tag,
+                    Ident(state, entity),
+                    id,
+                    delta.unsigned_abs()
+                ));
+            }
+            Event::AbilityActivated { entity, ability } => self.line(format_args!(
+                "|-ability|{}|{}",
+                Ident(state, entity),
+                ability.name()
+            )),
+            Event::ItemConsumed { entity, item } => self.line(format_args!(
+                "|-enditem|{}|{}",
+                Ident(state, entity),
+                item.data().name
+            )),
+            Event::Terastallize { entity, tera_type } => self.line(format_args!(
+                "|-terastallize|{}|{:?}",
+                Ident(state, entity),
+                tera_type
+            )),
+            Event::Dynamax { entity } if state.is_gigantamaxed(entity) => {
+                self.line(format_args!("|-start|{}|Dynamax|Gmax", Ident(state, entity)))
+            }
+            Event::Dynamax { entity } => {
+                self.line(format_args!("|-start|{}|Dynamax", Ident(state, entity)))
+            }
+            Event::DynamaxEnd { entity } => {
+                self.line(format_args!("|-end|{}|Dynamax", Ident(state, entity)))
+            }
+            Event::MegaEvolve { entity, item } => {
+                self.forme_change(state, entity);
+                let item = if item == ItemId::None { "" } else { item.data().name };
+                self.line(format_args!(
+                    "|-mega|{}|{}|{}",
+                    Ident(state, entity),
+                    state.species[entity].base().data().name,
+                    item
+                ))
+            }
+            Event::PrimalReversion { entity } => {
+                self.forme_change(state, entity);
+                self.line(format_args!("|-primal|{}", Ident(state, entity)))
+            }
+            Event::UltraBurst { entity } => {
+                self.forme_change(state, entity);
+                self.line(format_args!(
+                    "|-burst|{}|{}|{}",
+                    Ident(state, entity),
+                    state.species[entity].data().name,
+                    ItemId::Ultranecroziumz.data().name
+                ))
+            }
+            Event::Transform { user, target } => self.line(format_args!(
+                "|-transform|{}|{}",
+                Ident(state, user),
+                Ident(state, target)
+            )),
+            Event::IllusionEnd { entity } => {
+                self.line(format_args!(
+                    "|replace|{}|{}",
+                    Ident(state, entity),
+                    Details(state, entity)
+                ));
+                self.line(format_args!("|-end|{}|Illusion", Ident(state, entity)))
+            }
+            Event::FormeChange { entity, permanent: true } => self.forme_change(state, entity),
+            Event::FormeChange { entity, permanent: false } => self.line(format_args!(
+                "|-formechange|{}|{}|[from] ability: {}",
+                Ident(state, entity),
+                state.species[entity].data().name,
+                state.abilities[entity].name()
+            )),
+            Event::ZPower { entity } => self.line(format_args!("|-zpower|{}", Ident(state, entity))),
+            Event::ZBrokeProtect { target } => {
+                self.line(format_args!("|-zbroke|{}", Ident(state, target)))
+            }
+            Event::Protect { entity } => {
+                self.line(format_args!("|-singleturn|{}|Protect", Ident(state, entity)))
+            }
+            Event::Protected { target } => {
+                self.line(format_args!("|-activate|{}|move: Protect", Ident(state, target)))
+            }
+            Event::Fail { user } => self.line(format_args!("|-fail|{}", Ident(state, user))),
+            Event::MustRecharge { entity } => {
+                self.line(format_args!("|-mustrecharge|{}", Ident(state, entity)))
+            }
+            Event::PartialTrap {
+                target,
+                source,
+                move_id,
+            } => self.line(format_args!(
+                "|-activate|{}|move: {}|[of] {}",
+                Ident(state, target),
+                move_id.data().name,
+                Ident(state, source)
+            )),
+            Event::PartialTrapEnd { target, move_id } => self.line(format_args!(
+                "|-end|{}|{}|[partiallytrapped]",
+                Ident(state, target),
+                move_id.data().name
+            )),
+            Event::LeechSeed { target } => {
+                self.line(format_args!("|-start|{}|move: Leech Seed", Ident(state, target)))
+            }
+            Event::ClearAllBoosts => self.line(format_args!("|-clearallboost")),
+            Event::Faint { entity } => self.line(format_args!("|faint|{}", Ident(state, entity))),
+            Event::WeatherStart { weather } => {
+                self.line(format_args!("|-weather|{}", weather_id(weather)))
+            }
+            Event::WeatherUpkeep { weather } => {
+                self.line(format_args!("|-weather|{}|[upkeep]", weather_id(weather)))
+            }
+            Event::WeatherEnd { .. } => self.line(format_args!("|-weather|none")),
+            Event::TerrainStart { terrain } => {
+                self.line(format_args!("|-fieldstart|move: {}", terrain_name(terrain)))
+            }
+            Event::TerrainEnd { terrain } => {
+                self.line(format_args!("|-fieldend|move: {}", terrain_name(terrain)))
+            }
+            Event::SideStart { side, move_id } => self.line(format_args!(
+                "|-sidestart|p{}|{}",
+                side + 1,
+                SideEffect(move_id)
+            )),
+            Event::SideEnd { side, move_id } => self.line(format_args!(
+                "|-sideend|p{}|{}",
+                side + 1,
+                SideEffect(move_id)
+            )),
+            Event::Upkeep => self.line(format_args!("|upkeep")),
+            Event::Win { player: Some(p) } => self.line(format_args!("|win|p{}", p + 1)),
+            Event::Win { player: None } => self.line(format_args!("|tie")),
+        }
+    }
+}
@@ -0,0 +1,244 @@
+//! Move search.
+//!
+//! A depth-limited simultaneous-move search over `execute_turn`. Both
+//! players choose at once, so each node is a payoff matrix over action
+//! pairs; chance (damage rolls, crits, speed ties) is handled by averaging
+//! a fixed number of seeded rollouts per pair. The searching player takes
+//! the action with the best worst case (pure-strategy maximin), which is
+//! conservative but cheap and deterministic for a given seed.
+
+use super::{
+    battle_result, execute_turn, legal_actions, needs_replacement, replace_fainted, Action,
+    BattleResult, BattleRng,
+};
+use crate::damage::GenMechanics;
+use crate::state::BattleState;
+
+/// Search parameters.
+#[derive(Clone, Copy, Debug)]
+pub struct SearchOptions {
+    /// Turns to look ahead (at least 1)
+    pub depth: u8,
+    /// Rollouts averaged per action pair
+    pub samples: u8,
+    /// Seed for the rollouts
+    pub seed: u64,
+}
+
+impl Default for SearchOptions {
+    fn default() -> Self {
+        Self {
+            depth: 1,
+            samples: 4,
+            seed: 0,
+        }
+    }
+}
+
+/// Best action found for a player.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SearchResult {
+    pub action: Action,
+    /// Guaranteed evaluation for the searching player, in `-1.0..=1.0`
+    pub score: f32,
+}
+
+/// Static evaluation from `player`'s point of view.
+///
+/// Won and lost battles score ±1; otherwise the difference in remaining
+/// HP fraction summed over each team, scaled into `(-1, 1)`.
+pub fn evaluate(state: &BattleState, player: usize) -> f32 {
+    match battle_result(state) {
+        BattleResult::Win(winner) => return if winner == player { 1.0 } else { -1.0 },
+        BattleResult::Tie => return 0.0,
+        BattleResult::Ongoing => {}
+    }
+    let side_hp = |side: usize| -> f32 {
+        (0..state.team_sizes[side] as usize)
+            .map(|slot| {
+                let entity = BattleState::entity_index(side, slot);
+                state.hp[entity] as f32 / state.max_hp[entity].max(1) as f32
+            })
+            .sum()
+    };
+    let total = state.team_sizes[0].max(state.team_sizes[1]).max(1) as f32;
+    // Keep heuristic scores strictly inside the win/loss bounds
+    0.99 * (side_hp(player) - side_hp(1 - player)) / total
+}
+
+/// Find the best action for `player`. Returns `None` when the player has
+/// no legal action (battle over).
+///
+/// Pending replacements are resolved by always sending in the first
+/// healthy slot, for both sides and at every depth. The search only sees
+/// what `player` could: see `player_view`.
+pub fn search_best_move<G: GenMechanics>(
+    gen: G,
+    state: &BattleState,
+    player: usize,
+    options: SearchOptions,
+) -> Option<SearchResult> {
+    let view = player_view(state, player);
+    let ours = legal_actions(&view, player);
+    let (action, score) = maximin(gen, &view, player, &ours, options.depth.max(1), options)?;
+    Some(SearchResult { action, score })
+}
+
+/// The battle as `player` sees it. An opposing Illusion user is taken at
+/// face value, as the species it is disguised as (with that species'
+/// default Tera Type), until the Illusion breaks; otherwise the search
+/// would see through it.
+pub fn player_view(state: &BattleState, player: usize) -> BattleState {
+    let mut view = *state;
+    let opponent = 1 - player;
+    for slot in 0..view.team_sizes[opponent] as usize {
+        let entity = BattleState::entity_index(opponent, slot);
+        if let Some(disguise) = view.illusion[entity].take() {
+            view.apply_forme_change(entity, disguise);
+            view.original_species[entity] = disguise;
+            if !view.terastallized[entity] {
+                view.tera_type[entity] = disguise.data().primary_type();
+            }
+        }
+    }
+    view
+}
+
+fn maximin<G: GenMechanics>(
+    gen: G,
+    state: &BattleState,
+    player: usize,
+    ours: &[Action],
+    depth: u8,
+    options: SearchOptions,
+) -> Option<(Action, f32)> {
+    let mut theirs = legal_actions(state, 1 - player);
+    if theirs.is_empty() {
+        theirs.push(Action::Pass);
+    }
+    ours.iter()
+        .map(|&action| {
+            let worst = theirs
+                .iter()
+                .map(|&reply| expected_value(gen, state, player, action, reply, depth, options))
+                .fold(f32::INFINITY, f32::min);
+            (action, worst)
+        })
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+}
+
+fn expected_value<G: GenMechanics>(
+    gen: G,
+    state: &BattleState,
+    player: usize,
+    action: Action,
+    reply: Action,
+    depth: u8,
+    options: SearchOptions,
+) -> f32 {
+    let samples = options.samples.max(1);
+    let mut actions = [Action::Pass; 2];
+    actions[player] = action;
+    actions[1 - player] = reply;
+
+    let mut total = 0.0;
+    for sample in 0..samples {
+        let seed = options.seed ^ ((depth as u64) << 56) ^ sample as u64;
+        let mut rng = BattleRng::new(seed);
+        let mut next = *state;
+        execute_turn(gen, &mut next, actions, &mut rng, &mut ());
+        for side in 0..2 {
+            if needs_replacement(&next, side) {
+                if let Some(Action::Switch(slot)) = legal_actions(&next, side).first() {
+                    replace_fainted(&mut next, side, *slot as usize, &mut ());
+                }
+            }
+        }
+        total += if depth <= 1 || battle_result(&next) != BattleResult::Ongoing {
+            evaluate(&next, player)
+        } else {
+            let ours = legal_actions(&next, player);
+            maximin(gen, &next, player, &ours, depth - 1, options)
+                .map_or_else(|| evaluate(&next, player), |(_, score)| score)
+        };
+    }
+    total / samples as f32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::abilities::AbilityId;
+    use crate::battle::start_battle;
+    use crate::damage::Gen9;
+    use crate::entities::PokemonConfig;
+    use crate::moves::MoveId;
+
+    #[test]
+    fn test_search_prefers_effective_move() {
+        // Against Heatran, 4x Earthquake must beat Steel-resisted Dragon Claw.
+        let mut state = BattleState::new();
+        PokemonConfig::from_str("garchomp")
+            .unwrap()
+            .level(100)
+            .moves([
+                MoveId::Dragonclaw,
+                MoveId::Earthquake,
+                MoveId::default(),
+                MoveId::default(),
+            ])
+            .place(&mut state, 0, 0);
+        PokemonConfig::from_str("heatran")
+            .unwrap()
+            .level(100)
+            .moves([
+                MoveId::Crunch,
+                MoveId::default(),
+                MoveId::default(),
+                MoveId::default(),
+            ])
+            .place(&mut state, 1, 0);
+        start_battle(&mut state, &mut BattleRng::new(0), &mut ());
+
+        let result = search_best_move(Gen9, &state, 0, SearchOptions::default()).unwrap();
+        assert_eq!(result.action.move_slot(), Some(1));
+        assert!(result.score > 0.0);
+    }
+
+    #[test]
+    fn test_search_is_fooled_by_illusion() {
+        // Psychic would be super effective on the Machamp Zoroark appears to
+        // be, but Zoroark is immune to it.
+        let mut state = BattleState::new();
+        PokemonConfig::from_str("alakazam")
+            .unwrap()
+            .level(100)
+            .moves([
+                MoveId::Psychic,
+                MoveId::Earthquake,
+                MoveId::default(),
+                MoveId::default(),
+            ])
+            .place(&mut state, 0, 0);
+        for (slot, species) in ["zoroark", "machamp"].into_iter().enumerate() {
+            PokemonConfig::from_str(species)
+                .unwrap()
+                .level(100)
+                .moves([
+                    MoveId::Tackle,
+                    MoveId::default(),
+                    MoveId::default(),
+                    MoveId::default(),
+                ])
+                .place(&mut state, 1, slot);
+        }
+        state.abilities[6] = AbilityId::Illusion;
+        start_battle(&mut state, &mut BattleRng::new(0), &mut ());
+
+        let result = search_best_move(Gen9, &state, 0, SearchOptions::default()).unwrap();
+        assert_eq!(result.action.move_slot(), Some(0));
+        state.illusion[6] = None;
+        let result = search_best_move(Gen9, &state, 0, SearchOptions::default()).unwrap();
+        assert_eq!(result.action.move_slot(), Some(1));
+    }
+}
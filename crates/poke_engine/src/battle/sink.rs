@@ -0,0 +1,30 @@
+//! Observer interface for battle execution.
+//!
+//! The turn executor and ability/item hooks report every `Event` to a
+//! `BattleSink`. The executor is generic over the sink, so passing `&mut ()`
+//! compiles reporting away entirely for AI rollouts; hooks receive the sink
+//! as `&mut dyn BattleSink` so their function-pointer signatures stay fixed.
+
+use alloc::vec::Vec;
+
+use super::events::Event;
+use crate::state::BattleState;
+
+/// Receives battle events as they are produced.
+pub trait BattleSink {
+    /// Handle one event. `state` already reflects the event.
+    fn event(&mut self, state: &BattleState, event: &Event);
+}
+
+/// Discards every event.
+impl BattleSink for () {
+    #[inline(always)]
+    fn event(&mut self, _state: &BattleState, _event: &Event) {}
+}
+
+/// Records every event in order.
+impl BattleSink for Vec<Event> {
+    fn event(&mut self, _state: &BattleState, event: &Event) {
+        self.push(*event);
+    }
+}
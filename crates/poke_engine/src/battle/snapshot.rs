@@ -0,0 +1,109 @@
+//! Serializable battle summaries.
+//!
+//! `BattleState` is a flat struct of arrays indexed by entity, which is
+//! convenient for the engine but not for clients. `BattleSnapshot` is the
+//! per-side, per-Pokémon view that the RPC server and the WebAssembly
+//! bindings hand out.
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use serde::Serialize;
+
+use super::{battle_result, needs_replacement, BattleResult};
+use crate::damage::generations::{Terrain, Weather};
+use crate::state::{BattleState, Status};
+
+/// One Pokémon as seen by a client.
+#[derive(Clone, Debug, Serialize)]
+pub struct PokemonSnapshot {
+    pub species: &'static str,
+    pub hp: u16,
+    pub max_hp: u16,
+    /// Showdown status ID (`brn`, `tox`, ...), if any
+    pub status: Option<&'static str>,
+    /// Stat stages, indexed like `BattleState::boosts`
+    pub boosts: [i8; 7],
+    /// Tera Type, once Terastallized
+    pub terastallized: Option<String>,
+    /// Dynamax turns left (0 = not Dynamaxed)
+    pub dynamax_turns: u8,
+}
+
+/// One side of the field.
+#[derive(Clone, Debug, Serialize)]
+pub struct SideSnapshot {
+    /// Team slot of the active Pokémon (1-based, like Showdown choices)
+    pub active: usize,
+    pub needs_replacement: bool,
+    pub pokemon: Vec<PokemonSnapshot>,
+}
+
+/// The whole battle as seen by a client.
+#[derive(Clone, Debug, Serialize)]
+pub struct BattleSnapshot {
+    pub turn: u16,
+    /// `None` when there is no weather
+    pub weather: Option<String>,
+    /// `None` when there is no terrain
+    pub terrain: Option<String>,
+    pub sides: [SideSnapshot; 2],
+    /// `"p1"`, `"p2"`, `"tie"`, or `None` while the battle is ongoing
+    pub result: Option<&'static str>,
+}
+
+fn status_id(status: Status) -> Option<&'static str> {
+    [
+        (Status::BURN, "brn"),
+        (Status::FREEZE, "frz"),
+        (Status::PARALYSIS, "par"),
+        (Status::TOXIC, "tox"),
+        (Status::POISON, "psn"),
+        (Status::SLEEP, "slp"),
+        (Status::FROSTBITE, "frb"),
+        (Status::DROWSY, "drw"),
+    ]
+    .into_iter()
+    .find(|(flag, _)| status.contains(*flag))
+    .map(|(_, id)| id)
+}
+
+fn side(state: &BattleState, player: usize) -> SideSnapshot {
+    let first = BattleState::entity_index(player, 0);
+    SideSnapshot {
+        active: state.active_index(player) - first + 1,
+        needs_replacement: needs_replacement(state, player),
+        pokemon: (first..first + state.team_sizes[player] as usize)
+            .map(|entity| PokemonSnapshot {
+                species: state.species[entity].data().name,
+                hp: state.hp[entity],
+                max_hp: state.max_hp[entity],
+                status: status_id(state.status[entity]),
+                boosts: state.boosts[entity],
+                terastallized: state.terastallized[entity]
+                    .then(|| format!("{:?}", state.tera_type[entity])),
+                dynamax_turns: state.dynamax_turns[entity],
+            })
+            .collect(),
+    }
+}
+
+impl From<&BattleState> for BattleSnapshot {
+    fn from(state: &BattleState) -> Self {
+        let weather = Weather::from_u8(state.weather);
+        let terrain = Terrain::from_u8(state.terrain);
+        Self {
+            turn: state.turn,
+            weather: (weather != Weather::None).then(|| format!("{:?}", weather)),
+            terrain: (terrain != Terrain::None).then(|| format!("{:?}", terrain)),
+            sides: [side(state, 0), side(state, 1)],
+            result: match battle_result(state) {
+                BattleResult::Ongoing => None,
+                BattleResult::Win(0) => Some("p1"),
+                BattleResult::Win(_) => Some("p2"),
+                BattleResult::Tie => Some("tie"),
+            },
+        }
+    }
+}
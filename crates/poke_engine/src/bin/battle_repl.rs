@@ -0,0 +1,692 @@
+//! Interactive battle REPL.
+//!
+//! Loads two teams (Showdown export text or JSON) and plays them turn by
+//! turn, either human vs bot or human vs human (hot seat).
+//!
+//! ```text
+//! battle_repl <team1> <team2> [--p1 human|bot] [--p2 human|bot] [--gen N] [--seed N]
+//!             [--format ID|FILE] [--mod FILE]
+//! ```
+//!
+//! `--format` takes a built-in format ID (`gen9ou`) or a `.json`
+//! format file; teams are validated against it and it overrides `--gen`.
+//! `--mod` installs a data mod of custom species and moves (see
+//! `poke_engine::custom`) before the teams are read.
+//!
+//! Commands: `move N`, `switch N`, `tera move N`, `dynamax move N`, `z move N`,
+//! `mega move N`, `ultra move N`, `strong move N`, `agile move N`, `undo`,
+//! `state`, `help`, `quit`.
+
+use poke_engine::battle::{
+    battle_result, execute_turn, legal_actions, needs_replacement, replace_fainted, start_battle,
+    Action, BattleResult, BattleRng, BattleSink, CantReason, EffectSource, Event,
+};
+use poke_engine::custom::{self, DataMod};
+use poke_engine::damage::{calculate_damage, Generation};
+use poke_engine::damage::generations::Weather;
+use poke_engine::format::Format;
+use poke_engine::state::{BattleState, Status, MAX_MOVES};
+use poke_engine::team::parse_team;
+use poke_engine::validate::{learnset_warning, validate_team};
+use poke_engine::ItemId;
+use std::io::{self, BufRead, Write};
+
+const USAGE: &str = "usage: battle_repl <team1> <team2> [--p1 human|bot] [--p2 human|bot] \
+                     [--gen N] [--seed N] [--format ID|FILE] [--mod FILE]";
+
+const HELP: &str = "\
+commands:
+  move N        use move N (1-4)
+  switch N      switch to team member N (1-6)
+  tera move N   terastallize, then use move N
+  dynamax move N  dynamax, then use the Max Move of move N
+  z move N      use move N as a Z-Move
+  mega move N   mega evolve, then use move N
+  ultra move N  ultra burst, then use move N
+  strong move N use move N in Strong Style (Legends: Arceus formats)
+  agile move N  use move N in Agile Style (Legends: Arceus formats)
+  undo          take back the last turn
+  state         print the battle summary again
+  quit          leave the battle";
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Controller {
+    Human,
+    Bot,
+}
+
+struct Options {
+    teams: [String; 2],
+    controllers: [Controller; 2],
+    gen: u8,
+    seed: u64,
+    format: Option<Format>,
+}
+
+/// A built-in format ID, or else a format file.
+fn load_format(arg: &str) -> Result<Format, String> {
+    match Format::builtin(arg) {
+        Some(format) => Ok(format),
+        None => Format::load(arg).map_err(|e| e.to_string()),
+    }
+}
+
+fn parse_args() -> Result<Options, String> {
+    let mut args = std::env::args().skip(1);
+    let mut paths = Vec::new();
+    let mut controllers = [Controller::Human, Controller::Bot];
+    let mut gen = 9;
+    let mut seed = 0;
+    let mut format = None;
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| args.next().ok_or_else(|| format!("{} needs a value", name));
+        match arg.as_str() {
+            "--p1" | "--p2" => {
+                let controller = match value(&arg)?.as_str() {
+                    "human" => Controller::Human,
+                    "bot" => Controller::Bot,
+                    other => return Err(format!("unknown controller: {}", other)),
+                };
+                controllers[if arg == "--p1" { 0 } else { 1 }] = controller;
+            }
+            "--gen" => gen = value(&arg)?.parse().map_err(|_| "invalid --gen")?,
+            "--seed" => seed = value(&arg)?.parse().map_err(|_| "invalid --seed")?,
+            "--format" => format = Some(load_format(&value(&arg)?)?),
+            "--mod" => {
+                let path = value(&arg)?;
+                DataMod::load(&path)
+                    .and_then(|data_mod| custom::install(&data_mod))
+                    .map_err(|e| format!("{}: {}", path, e))?;
+            }
+            "-h" | "--help" => return Err(USAGE.to_string()),
+            _ => paths.push(arg),
+        }
+    }
+    let [p1, p2]: [String; 2] = paths.try_into().map_err(|_| USAGE.to_string())?;
+    Ok(Options {
+        teams: [p1, p2],
+        controllers,
+        gen: format.as_ref().map_or(gen, |format| format.generation),
+        seed,
+        format,
+    })
+}
+
+fn load_state(options: &Options) -> Result<BattleState, String> {
+    let mut teams = Vec::new();
+    for path in &options.teams {
+        let text = std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))?;
+        let team = parse_team(&text).map_err(|e| format!("{}: {}", path, e))?;
+        if let Some(format) = &options.format {
+            let errors = validate_team(&team, format);
+            if !errors.is_empty() {
+                let lines: Vec<String> = errors.iter().map(|e| format!("  {}", e)).collect();
+                return Err(format!("{}: invalid for {}:\n{}", path, format.name, lines.join("\n")));
+            }
+            if let Some(warning) = learnset_warning() {
+                eprintln!("{}: warning: {}", path, warning);
+            }
+        }
+        teams.push(team);
+    }
+
+    if let Some(format) = &options.format {
+        return Ok(format.new_battle([&teams[0], &teams[1]]));
+    }
+    let mut state = BattleState::new();
+    state.generation = options.gen;
+    for (player, team) in teams.iter().enumerate() {
+        for (slot, config) in team.iter().enumerate() {
+            config.place(&mut state, player, slot);
+        }
+    }
+    Ok(state)
+}
+
+// ============================================================================
+// Output
+// ============================================================================
+
+fn name(state: &BattleState, entity: usize) -> String {
+    format!(
+        "{} (p{})",
+        state.displayed_species(entity).data().name,
+        state.get_side(entity) + 1
+    )
+}
+
+fn status_label(status: Status) -> &'static str {
+    if status.contains(Status::BURN) {
+        "BRN"
+    } else if status.contains(Status::FREEZE) {
+        "FRZ"
+    } else if status.contains(Status::PARALYSIS) {
+        "PAR"
+    } else if status.contains(Status::TOXIC) {
+        "TOX"
+    } else if status.contains(Status::POISON) {
+        "PSN"
+    } else if status.contains(Status::SLEEP) {
+        "SLP"
+    } else if status.contains(Status::FROSTBITE) {
+        "FRB"
+    } else if status.contains(Status::DROWSY) {
+        "DRW"
+    } else {
+        ""
+    }
+}
+
+fn weather_label(weather: Weather) -> &'static str {
+    match weather {
+        Weather::None => "none",
+        Weather::Sun => "Sun",
+        Weather::Rain => "Rain",
+        Weather::Sand => "Sandstorm",
+        Weather::Hail => "Hail",
+        Weather::Snow => "Snow",
+        Weather::HarshSun => "Harsh Sun",
+        Weather::HeavyRain => "Heavy Rain",
+        Weather::StrongWinds => "Strong Winds",
+    }
+}
+
+const BOOST_LABELS: [&str; 7] = ["Atk", "Def", "SpA", "SpD", "Spe", "Acc", "Eva"];
+
+fn percent(state: &BattleState, entity: usize, hp: u16) -> u32 {
+    (hp as u32 * 100).div_ceil(state.max_hp[entity].max(1) as u32)
+}
+
+fn hp_bar(state: &BattleState, entity: usize) -> String {
+    const WIDTH: usize = 20;
+    let filled = (state.hp[entity] as usize * WIDTH).div_ceil(state.max_hp[entity].max(1) as usize);
+    format!("[{}{}]", "#".repeat(filled), "-".repeat(WIDTH - filled))
+}
+
+/// Prints events as readable sentences.
+struct Narrator;
+
+impl BattleSink for Narrator {
+    fn event(&mut self, state: &BattleState, event: &Event) {
+        let line = match *event {
+            Event::TurnStart { turn } => format!("\n== Turn {} ==", turn),
+            Event::SwitchIn { entity } => format!("{} was sent out!", name(state, entity)),
+            Event::MoveUsed { user, move_id, .. } => {
+                format!("{} used {}!", name(state, user), move_id.data().name)
+            }
+            Event::Cant { entity, reason } => {
+                let why = match reason {
+                    CantReason::Sleep => "is fast asleep",
+                    CantReason::Freeze => "is frozen solid",
+                    CantReason::Paralysis => "is paralyzed! It can't move",
+                    CantReason::Flinch => "flinched and couldn't move",
+                    CantReason::Recharge => "must recharge",
+                    CantReason::PartiallyTrapped => "can't move",
+                };
+                format!("{} {}!", name(state, entity), why)
+            }
+            Event::Miss { target, .. } => format!("{} avoided the attack!", name(state, target)),
+            Event::CriticalHit { .. } => "A critical hit!".to_string(),
+            Event::Effectiveness { target, effectiveness } => match effectiveness {
+                0 => format!("It doesn't affect {}...", name(state, target)),
+                1..=3 => "It's not very effective...".to_string(),
+                4 => return,
+                _ => "It's super effective!".to_string(),
+            },
+            Event::HitCount { hits, .. } => format!("The Pokémon was hit {} times!", hits),
+            Event::Damage { target, amount, source } => {
+                let cause = match source {
+                    EffectSource::Weather(weather) => format!(" from {}", weather_label(weather)),
+                    EffectSource::Status(status) => format!(" from {}", status_label(status)),
+                    EffectSource::Recoil => " from recoil".to_string(),
+                    EffectSource::Ability(ability) => format!(" from {}", ability.name()),
+                    EffectSource::Item(item) => format!(" from {}", item.data().name),
+                    EffectSource::SideCondition(move_id)
+                    | EffectSource::Hazard(move_id)
+                    | EffectSource::PartialTrap(move_id)
+                    | EffectSource::Protection(move_id) => {
+                        format!(" from {}", move_id.data().name)
+                    }
+                    EffectSource::LeechSeed => " from Leech Seed".to_string(),
+                    EffectSource::Move => String::new(),
+                };
+                format!(
+                    "{} lost {}% of its HP{}. {}",
+                    name(state, target),
+                    percent(state, target, amount),
+                    cause,
+                    hp_bar(state, target)
+                )
+            }
+            Event::Heal { target, amount, .. } => format!(
+                "{} restored {}% of its HP. {}",
+                name(state, target),
+                percent(state, target, amount),
+                hp_bar(state, target)
+            ),
+            Event::StatusInflicted { entity, status } => {
+                format!("{} is now {}!", name(state, entity), status_label(status))
+            }
+            Event::StatusCured { entity, status } => {
+                format!("{} is no longer {}.", name(state, entity), status_label(status))
+            }
+            Event::Boost { entity, stat, delta } => format!(
+                "{}'s {} {} by {}!",
+                name(state, entity),
+                BOOST_LABELS.get(stat).copied().unwrap_or("?"),
+                if delta > 0 { "rose" } else { "fell" },
+                delta.unsigned_abs()
+            ),
+            Event::AbilityActivated { entity, ability } => {
+                format!("[{}'s {}]", name(state, entity), ability.name())
+            }
+            Event::ItemConsumed { entity, item } => {
+                format!("{} used up its {}.", name(state, entity), item.data().name)
+            }
+            Event::Terastallize { entity, tera_type } => format!(
+                "{} has Terastallized into the {:?} type!",
+                name(state, entity),
+                tera_type
+            ),
+            Event::Dynamax { entity } if state.is_gigantamaxed(entity) => {
+                format!("{} Gigantamaxed!", name(state, entity))
+            }
+            Event::Dynamax { entity } => format!("{} Dynamaxed!", name(state, entity)),
+            Event::DynamaxEnd { entity } => {
+                format!("{} returned to its normal size.", name(state, entity))
+            }
+            Event::MegaEvolve { entity, .. } => {
+                format!("{} has Mega Evolved!", name(state, entity))
+            }
+            Event::PrimalReversion { entity } => {
+                format!("{}'s Primal Reversion!", name(state, entity))
+            }
+            Event::UltraBurst { entity } => {
+                format!("{} regained its true power through Ultra Burst!", name(state, entity))
+            }
+            Event::Transform { user, target } => {
+                format!("{} transformed into {}!", name(state, user), name(state, target))
+            }
+            Event::IllusionEnd { entity } => {
+                format!("{}'s illusion wore off!", name(state, entity))
+            }
+            Event::FormeChange { entity, .. } => format!(
+                "{} transformed into {}!",
+                name(state, entity),
+                state.species[entity].data().name
+            ),
+            Event::ZPower { entity } => {
+                format!("{} surrounded itself with its Z-Power!", name(state, entity))
+            }
+            Event::ZBrokeProtect { target } => {
+                format!("{} couldn't fully protect itself and got hurt!", name(state, target))
+            }
+            Event::Protect { entity } => format!("{} protected itself!", name(state, entity)),
+            Event::Protected { target } => format!("{} protected itself!", name(state, target)),
+            Event::Fail { .. } => "But it failed!".to_string(),
+            Event::MustRecharge { .. } => return,
+            Event::PartialTrap {
+                target,
+                source,
+                move_id,
+            } => format!(
+                "{} was caught by {}'s {}!",
+                name(state, target),
+                name(state, source),
+                move_id.data().name
+            ),
+            Event::PartialTrapEnd { target, move_id } => {
+                format!("{} was freed from {}!", name(state, target), move_id.data().name)
+            }
+            Event::LeechSeed { target } => format!("{} was seeded!", name(state, target)),
+            Event::ClearAllBoosts => "All stat changes were eliminated!".to_string(),
+            Event::Faint { entity } => format!("{} fainted!", name(state, entity)),
+            Event::WeatherStart { weather } => format!("The weather became {}.", weather_label(weather)),
+            Event::WeatherUpkeep { weather } => format!("{} continues.", weather_label(weather)),
+            Event::WeatherEnd { .. } => "The weather cleared up.".to_string(),
+            Event::TerrainStart { terrain } => format!("{:?} Terrain was set.", terrain),
+            Event::TerrainEnd { terrain } => format!("The {:?} Terrain disappeared.", terrain),
+            Event::SideStart { side, move_id } => {
+                format!("{} took effect on Player {}'s side!", move_id.data().name, side + 1)
+            }
+            Event::SideEnd { side, move_id } => {
+                format!("{} wore off on Player {}'s side.", move_id.data().name, side + 1)
+            }
+            Event::Upkeep => return,
+            Event::Win { player: Some(p) } => format!("\nPlayer {} wins!", p + 1),
+            Event::Win { player: None } => "\nThe battle ended in a tie.".to_string(),
+        };
+        println!("{}", line);
+    }
+}
+
+fn print_side(state: &BattleState, player: usize) {
+    let active = state.active_index(player);
+    let boosts: Vec<String> = state.boosts[active]
+        .iter()
+        .zip(BOOST_LABELS)
+        .filter(|(b, _)| **b != 0)
+        .map(|(b, label)| format!("{} {:+}", label, b))
+        .collect();
+    let mut header = format!(
+        "p{}  {} L{}  {} {}/{} ({}%)",
+        player + 1,
+        state.species[active].data().name,
+        state.level[active],
+        hp_bar(state, active),
+        state.hp[active],
+        state.max_hp[active],
+        percent(state, active, state.hp[active]),
+    );
+    if state.is_dynamaxed(active) {
+        header.push_str(&format!("  Dynamax ({})", state.dynamax_turns[active]));
+    }
+    for tag in std::iter::once(status_label(state.status[active]).to_string()).chain(boosts) {
+        if !tag.is_empty() {
+            header.push_str("  ");
+            header.push_str(&tag);
+        }
+    }
+    println!("{}", header);
+    let item = match state.items[active] {
+        ItemId::None => "(none)",
+        item => item.data().name,
+    };
+    println!(
+        "    Ability: {}  Item: {}  Tera: {:?}{}",
+        state.abilities[active].name(),
+        item,
+        state.tera_type[active],
+        if state.terastallized[active] { " (active)" } else { "" }
+    );
+
+    let moves: Vec<String> = (0..MAX_MOVES)
+        .filter(|&slot| state.max_pp[active][slot] > 0)
+        .map(|slot| {
+            format!(
+                "{}) {} {}/{}",
+                slot + 1,
+                state.moves[active][slot].data().name,
+                state.pp[active][slot],
+                state.max_pp[active][slot]
+            )
+        })
+        .collect();
+    println!("    Moves: {}", moves.join("  "));
+
+    let bench: Vec<String> = (0..state.team_sizes[player] as usize)
+        .map(|slot| BattleState::entity_index(player, slot))
+        .filter(|&entity| entity != active)
+        .map(|entity| {
+            let slot = entity % 6 + 1;
+            let species = state.species[entity].data().name;
+            if state.is_fainted(entity) {
+                format!("{}) {} fnt", slot, species)
+            } else {
+                format!("{}) {} {}%", slot, species, percent(state, entity, state.hp[entity]))
+            }
+        })
+        .collect();
+    if !bench.is_empty() {
+        println!("    Bench: {}", bench.join("  "));
+    }
+
+    let side = &state.side_conditions[player];
+    let mut conditions = Vec::new();
+    for (label, turns) in [
+        ("Reflect", side.reflect_turns),
+        ("Light Screen", side.light_screen_turns),
+        ("Aurora Veil", side.aurora_veil_turns),
+        ("Tailwind", side.tailwind_turns),
+        ("Safeguard", side.safeguard_turns),
+        ("Mist", side.mist_turns),
+    ] {
+        if turns > 0 {
+            conditions.push(format!("{} ({})", label, turns));
+        }
+    }
+    if side.stealth_rock {
+        conditions.push("Stealth Rock".to_string());
+    }
+    if side.spikes_layers > 0 {
+        conditions.push(format!("Spikes x{}", side.spikes_layers));
+    }
+    if side.toxic_spikes_layers > 0 {
+        conditions.push(format!("Toxic Spikes x{}", side.toxic_spikes_layers));
+    }
+    if side.sticky_web {
+        conditions.push("Sticky Web".to_string());
+    }
+    if !conditions.is_empty() {
+        println!("    Side: {}", conditions.join(", "));
+    }
+}
+
+fn print_summary(state: &BattleState) {
+    let mut field = format!("Weather: {}", weather_label(Weather::from_u8(state.weather)));
+    if state.weather != 0 && state.weather_turns > 0 {
+        field.push_str(&format!(" ({})", state.weather_turns));
+    }
+    if state.terrain != 0 {
+        field.push_str(&format!("  Terrain: {} ({})", state.terrain, state.terrain_turns));
+    }
+    if state.trick_room {
+        field.push_str(&format!("  Trick Room ({})", state.trick_room_turns));
+    }
+    if state.gravity {
+        field.push_str(&format!("  Gravity ({})", state.gravity_turns));
+    }
+    println!("\n--- Turn {} | {} ---", state.turn, field);
+    print_side(state, 0);
+    print_side(state, 1);
+}
+
+// ============================================================================
+// Input
+// ============================================================================
+
+enum Command {
+    Act(Action),
+    Undo,
+    Quit,
+}
+
+/// Greedy bot: the legal move with the highest average damage roll. It
+/// never Terastallizes, Dynamaxes, uses a Z-Move or picks a Style.
+fn bot_action(gen: Generation, state: &BattleState, player: usize) -> Action {
+    let actions = legal_actions(state, player);
+    let attacker = state.active_index(player);
+    let defender = state.active_index(1 - player);
+    let expected = |action: &Action| match *action {
+        Action::Move(slot) if !state.is_fainted(attacker) => {
+            let move_id = state.moves[attacker][slot as usize];
+            let result = calculate_damage(gen, state, attacker, defender, move_id, false);
+            result.rolls.iter().map(|&r| r as u32).sum::<u32>()
+        }
+        _ => 0,
+    };
+    actions
+        .iter()
+        .copied()
+        .filter(|action| {
+            !matches!(
+                action,
+                Action::Terastallize(_)
+                    | Action::Dynamax(_)
+                    | Action::ZMove(_)
+                    | Action::MegaEvolve(_)
+                    | Action::UltraBurst(_)
+                    | Action::StrongStyle(_)
+                    | Action::AgileStyle(_)
+            )
+        })
+        .max_by_key(|action| expected(action))
+        .unwrap_or(Action::Pass)
+}
+
+fn parse_slot(word: Option<&str>, max: usize) -> Option<u8> {
+    let n: usize = word?.parse().ok()?;
+    (1..=max).contains(&n).then(|| (n - 1) as u8)
+}
+
+fn read_command(
+    input: &mut impl BufRead,
+    state: &BattleState,
+    player: usize,
+) -> io::Result<Command> {
+    let legal = legal_actions(state, player);
+    loop {
+        print!("p{}> ", player + 1);
+        io::stdout().flush()?;
+        let mut line = String::new();
+        if input.read_line(&mut line)? == 0 {
+            return Ok(Command::Quit);
+        }
+        let mut words = line.split_whitespace();
+        let action = match words.next() {
+            Some("move") => parse_slot(words.next(), MAX_MOVES).map(Action::Move),
+            Some("switch") => parse_slot(words.next(), 6).map(Action::Switch),
+            Some("tera") => match words.next() {
+                Some("move") => parse_slot(words.next(), MAX_MOVES).map(Action::Terastallize),
+                _ => None,
+            },
+            Some("dynamax") => match words.next() {
+                Some("move") => parse_slot(words.next(), MAX_MOVES).map(Action::Dynamax),
+                _ => None,
+            },
+            Some("z") => match words.next() {
+                Some("move") => parse_slot(words.next(), MAX_MOVES).map(Action::ZMove),
+                _ => None,
+            },
+            Some("mega") => match words.next() {
+                Some("move") => parse_slot(words.next(), MAX_MOVES).map(Action::MegaEvolve),
+                _ => None,
+            },
+            Some("ultra") => match words.next() {
+                Some("move") => parse_slot(words.next(), MAX_MOVES).map(Action::UltraBurst),
+                _ => None,
+            },
+            Some("strong") => match words.next() {
+                Some("move") => parse_slot(words.next(), MAX_MOVES).map(Action::StrongStyle),
+                _ => None,
+            },
+            Some("agile") => match words.next() {
+                Some("move") => parse_slot(words.next(), MAX_MOVES).map(Action::AgileStyle),
+                _ => None,
+            },
+            Some("undo") => return Ok(Command::Undo),
+            Some("quit") | Some("exit") => return Ok(Command::Quit),
+            Some("state") => {
+                print_summary(state);
+                continue;
+            }
+            Some("help") => {
+                println!("{}", HELP);
+                continue;
+            }
+            None => continue,
+            Some(other) => {
+                println!("Unknown command '{}'. Type 'help' for a list.", other);
+                continue;
+            }
+        };
+        match action {
+            Some(action) if legal.contains(&action) => return Ok(Command::Act(action)),
+            // Struggle: every move is out of PP, so any move command maps to it
+            Some(Action::Move(_)) if legal.contains(&Action::Move(0)) && legal.len() == 1 => {
+                return Ok(Command::Act(Action::Move(0)))
+            }
+            _ => println!("That action is not legal right now."),
+        }
+    }
+}
+
+fn main() {
+    let options = match parse_args() {
+        Ok(options) => options,
+        Err(message) => {
+            eprintln!("{}", message);
+            std::process::exit(2);
+        }
+    };
+    let mut state = match load_state(&options) {
+        Ok(state) => state,
+        Err(message) => {
+            eprintln!("{}", message);
+            std::process::exit(1);
+        }
+    };
+    let gen = Generation::from_num(options.gen);
+    let mut rng = BattleRng::new(options.seed);
+    let stdin = io::stdin();
+    let mut input = stdin.lock();
+
+    start_battle(&mut state, &mut rng, &mut Narrator);
+    println!("{}", HELP);
+
+    // Snapshots taken before each turn; BattleState and BattleRng are Copy.
+    let mut history: Vec<(BattleState, BattleRng)> = Vec::new();
+
+    while battle_result(&state) == BattleResult::Ongoing {
+        print_summary(&state);
+        let snapshot = (state, rng);
+
+        let mut actions = [Action::Pass; 2];
+        let mut undo = false;
+        for (player, action) in actions.iter_mut().enumerate() {
+            *action = match options.controllers[player] {
+                Controller::Bot => bot_action(gen, &state, player),
+                Controller::Human => match read_command(&mut input, &state, player) {
+                    Ok(Command::Act(action)) => action,
+                    Ok(Command::Undo) => {
+                        undo = true;
+                        break;
+                    }
+                    Ok(Command::Quit) | Err(_) => return,
+                },
+            };
+        }
+        if undo {
+            match history.pop() {
+                Some((previous, previous_rng)) => {
+                    state = previous;
+                    rng = previous_rng;
+                    println!("Took back the last turn.");
+                }
+                None => println!("Nothing to undo."),
+            }
+            continue;
+        }
+
+        history.push(snapshot);
+        execute_turn(gen, &mut state, actions, &mut rng, &mut Narrator);
+
+        for player in 0..2 {
+            if !needs_replacement(&state, player) {
+                continue;
+            }
+            let action = match options.controllers[player] {
+                Controller::Bot => bot_action(gen, &state, player),
+                Controller::Human => {
+                    print_summary(&state);
+                    println!("Choose a replacement for player {}.", player + 1);
+                    match read_command(&mut input, &state, player) {
+                        Ok(Command::Act(action)) => action,
+                        // Undo from a replacement prompt rewinds the whole turn
+                        Ok(Command::Undo) => {
+                            if let Some((previous, previous_rng)) = history.pop() {
+                                state = previous;
+                                rng = previous_rng;
+                            }
+                            break;
+                        }
+                        Ok(Command::Quit) | Err(_) => return,
+                    }
+                }
+            };
+            if let Action::Switch(slot) = action {
+                replace_fainted(&mut state, player, slot as usize, &mut Narrator);
+            }
+        }
+    }
+}
@@ -0,0 +1,470 @@
+//! `poke-calc`: damage calculator for people.
+//!
+//! Sets are given as a path to a Showdown export (text or JSON; the first
+//! Pokémon is used) or in the one-line form accepted by
+//! `team::parse_short_set`:
+//!
+//! ```text
+//! poke-calc "Garchomp @ Choice Band, Jolly, 252 Atk / 252 Spe" \
+//!           "Tyranitar @ Leftovers, 252 HP / 4 SpD" Earthquake --weather sand
+//! poke-calc --batch calcs.txt --gen 8
+//! ```
+//!
+//! In batch mode every non-empty line of the file is a full argument list
+//! (quoted like a shell command); options given on the command line apply
+//! to every line unless the line overrides them.
+
+use std::collections::BTreeMap;
+
+use poke_engine::custom::{self, DataMod};
+use poke_engine::damage::generations::{Terrain, Weather};
+use poke_engine::damage::{calculate_damage, Generation};
+use poke_engine::entities::PokemonConfig;
+use poke_engine::moves::{MoveCategory, MoveId};
+use poke_engine::natures::BattleStat;
+use poke_engine::state::{BattleState, Status, Volatiles};
+use poke_engine::team::{parse_short_set, parse_team, to_id};
+use poke_engine::{AbilityId, ItemId, Type};
+
+const USAGE: &str = "\
+usage: poke-calc <attacker> <defender> <move> [options]
+       poke-calc --batch <file> [options]
+
+options:
+  --gen N                      generation, 1-9 (default 9)
+  --weather <name>             sun, rain, sand, hail, snow, harshsun, heavyrain, strongwinds
+  --terrain <name>             electric, grassy, psychic, misty
+  --reflect, --light-screen, --aurora-veil
+                               screens on the defender's side
+  --gravity                    Gravity is active
+  --crit                       force a critical hit
+  --hits N                     hits for multi-hit moves
+  --attacker-boosts <spread>   e.g. \"+2 Atk / +1 Spe\"
+  --defender-boosts <spread>   e.g. \"+1 Def / +1 SpD\"
+  --attacker-status <id>       brn, par, psn, tox, slp, frz, frb, drw
+  --defender-status <id>
+  --defender-hp <percent>      defender's current HP (1-100)
+  --tera-attacker <type>       attacker is Terastallized
+  --tera-defender <type>       defender is Terastallized
+  --style <name>               strong or agile (Legends: Arceus)
+  --mod <file>                 install a data mod of custom species and moves";
+
+/// Index into `BattleState::boosts` for a stat label.
+fn boost_index(label: &str) -> Option<usize> {
+    match to_id(label).as_str() {
+        "atk" => Some(0),
+        "def" => Some(1),
+        "spa" => Some(2),
+        "spd" => Some(3),
+        "spe" => Some(4),
+        "acc" | "accuracy" => Some(5),
+        "eva" | "evasion" => Some(6),
+        _ => None,
+    }
+}
+
+/// Parse `"+2 Atk / -1 Spe"`.
+fn parse_boosts(spread: &str) -> Result<[i8; 7], String> {
+    let mut boosts = [0; 7];
+    for part in spread.split('/') {
+        let mut words = part.split_whitespace();
+        let (Some(value), Some(stat)) = (words.next(), words.next()) else {
+            return Err(format!("invalid boosts: {}", spread));
+        };
+        let value: i8 = value
+            .trim_start_matches('+')
+            .parse()
+            .map_err(|_| format!("invalid boosts: {}", spread))?;
+        let index = boost_index(stat).ok_or_else(|| format!("invalid boosts: {}", spread))?;
+        boosts[index] = value.clamp(-6, 6);
+    }
+    Ok(boosts)
+}
+
+fn parse_status(id: &str) -> Result<Status, String> {
+    match id {
+        "brn" => Ok(Status::BURN),
+        "par" => Ok(Status::PARALYSIS),
+        "psn" => Ok(Status::POISON),
+        "tox" => Ok(Status::TOXIC),
+        "slp" => Ok(Status::SLEEP),
+        "frz" => Ok(Status::FREEZE),
+        "frb" => Ok(Status::FROSTBITE),
+        "drw" => Ok(Status::DROWSY),
+        _ => Err(format!("unknown status: {}", id)),
+    }
+}
+
+fn parse_style(name: &str) -> Result<Volatiles, String> {
+    match to_id(name).as_str() {
+        "strong" => Ok(Volatiles::STRONG_STYLE),
+        "agile" => Ok(Volatiles::AGILE_STYLE),
+        _ => Err(format!("unknown style: {}", name)),
+    }
+}
+
+fn parse_weather(name: &str) -> Result<Weather, String> {
+    Weather::from_name(name).ok_or_else(|| format!("unknown weather: {}", name))
+}
+
+fn parse_terrain(name: &str) -> Result<Terrain, String> {
+    Terrain::from_name(name).ok_or_else(|| format!("unknown terrain: {}", name))
+}
+
+/// A set argument: a team file path or the one-line form.
+fn load_set(arg: &str) -> Result<PokemonConfig, String> {
+    match std::fs::read_to_string(arg) {
+        Ok(text) => parse_team(&text)
+            .map(|team| team[0].clone())
+            .map_err(|e| format!("{}: {}", arg, e)),
+        Err(_) => parse_short_set(arg).map_err(|e| e.to_string()),
+    }
+}
+
+/// One fully parsed calculation.
+#[derive(Clone, Default)]
+struct Calc {
+    sets: Vec<String>,
+    gen: Option<u8>,
+    weather: Option<Weather>,
+    terrain: Option<Terrain>,
+    reflect: bool,
+    light_screen: bool,
+    aurora_veil: bool,
+    gravity: bool,
+    crit: bool,
+    hits: Option<u8>,
+    boosts: [[i8; 7]; 2],
+    status: [Status; 2],
+    defender_hp: Option<u8>,
+    tera: [Option<Type>; 2],
+    style: Option<Volatiles>,
+    batch: Option<String>,
+}
+
+impl Calc {
+    /// Apply arguments on top of `self` (batch lines start from the command-line options).
+    fn parse(mut self, args: &[String]) -> Result<Self, String> {
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            let mut value = || {
+                args.next()
+                    .map(String::as_str)
+                    .ok_or_else(|| format!("{} needs a value", arg))
+            };
+            match arg.as_str() {
+                "--gen" => {
+                    let gen: u8 = value()?.parse().map_err(|_| "invalid --gen")?;
+                    if !(1..=9).contains(&gen) {
+                        return Err(format!("unsupported gen: {}", gen));
+                    }
+                    self.gen = Some(gen);
+                }
+                "--weather" => self.weather = Some(parse_weather(value()?)?),
+                "--terrain" => self.terrain = Some(parse_terrain(value()?)?),
+                "--reflect" => self.reflect = true,
+                "--light-screen" => self.light_screen = true,
+                "--aurora-veil" => self.aurora_veil = true,
+                "--gravity" => self.gravity = true,
+                "--crit" => self.crit = true,
+                "--hits" => self.hits = Some(value()?.parse().map_err(|_| "invalid --hits")?),
+                "--attacker-boosts" => self.boosts[0] = parse_boosts(value()?)?,
+                "--defender-boosts" => self.boosts[1] = parse_boosts(value()?)?,
+                "--attacker-status" => self.status[0] = parse_status(value()?)?,
+                "--defender-status" => self.status[1] = parse_status(value()?)?,
+                "--defender-hp" => {
+                    let percent: u8 = value()?.parse().map_err(|_| "invalid --defender-hp")?;
+                    if !(1..=100).contains(&percent) {
+                        return Err(format!("--defender-hp must be 1-100, got {}", percent));
+                    }
+                    self.defender_hp = Some(percent);
+                }
+                "--tera-attacker" | "--tera-defender" => {
+                    let name = value()?;
+                    let tera = Type::from_str(&to_id(name))
+                        .ok_or_else(|| format!("unknown type: {}", name))?;
+                    self.tera[if arg == "--tera-attacker" { 0 } else { 1 }] = Some(tera);
+                }
+                "--style" => self.style = Some(parse_style(value()?)?),
+                "--batch" => self.batch = Some(value()?.to_string()),
+                "--mod" => {
+                    let path = value()?;
+                    DataMod::load(path)
+                        .and_then(|data_mod| custom::install(&data_mod))
+                        .map_err(|e| format!("{}: {}", path, e))?;
+                }
+                "-h" | "--help" => return Err(USAGE.to_string()),
+                flag if flag.starts_with("--") => return Err(format!("unknown option: {}", flag)),
+                _ => self.sets.push(arg.clone()),
+            }
+        }
+        Ok(self)
+    }
+
+    fn run(&self) -> Result<String, String> {
+        let [attacker_arg, defender_arg, move_arg] = self.sets.as_slice() else {
+            return Err(USAGE.to_string());
+        };
+        let mut attacker_set = load_set(attacker_arg)?;
+        let mut defender_set = load_set(defender_arg)?;
+        attacker_set.tera_type = self.tera[0].or(attacker_set.tera_type);
+        defender_set.tera_type = self.tera[1].or(defender_set.tera_type);
+        let move_id = MoveId::from_str(&to_id(move_arg))
+            .ok_or_else(|| format!("unknown move: {}", move_arg))?;
+
+        let mut state = BattleState::new();
+        state.generation = self.gen.unwrap_or(9);
+        attacker_set.place(&mut state, 0, 0);
+        defender_set.place(&mut state, 1, 0);
+        let (attacker, defender) = (0, BattleState::entity_index(1, 0));
+
+        state.weather = self.weather.unwrap_or_default() as u8;
+        state.terrain = self.terrain.unwrap_or_default() as u8;
+        state.gravity = self.gravity;
+        let screens = &mut state.side_conditions[1];
+        screens.reflect_turns = if self.reflect { 5 } else { 0 };
+        screens.light_screen_turns = if self.light_screen { 5 } else { 0 };
+        screens.aurora_veil_turns = if self.aurora_veil { 5 } else { 0 };
+        for (side, entity) in [attacker, defender].into_iter().enumerate() {
+            state.boosts[entity] = self.boosts[side];
+            state.status[entity] = self.status[side];
+        }
+        // Frostbite, drowsiness and the Styles only exist with Legends: Arceus mechanics
+        let legends_status = Status::FROSTBITE | Status::DROWSY;
+        state.gimmicks.legends_arceus =
+            self.style.is_some() || self.status.iter().any(|s| s.intersects(legends_status));
+        if let Some(style) = self.style {
+            state.volatiles[attacker].insert(style);
+        }
+        if let Some(percent) = self.defender_hp {
+            let max_hp = state.max_hp[defender] as u32;
+            state.hp[defender] = (max_hp * percent as u32).div_ceil(100) as u16;
+        }
+        for (side, entity) in [attacker, defender].into_iter().enumerate() {
+            if self.tera[side].is_some() {
+                state.terastallize(entity);
+            }
+        }
+
+        let gen = Generation::from_num(state.generation);
+        let result = calculate_damage(gen, &state, attacker, defender, move_id, self.crit);
+
+        let move_data = move_id.data();
+        let hits = self.hits.unwrap_or_else(|| default_hits(&state, attacker, move_data.multihit));
+        let mut per_use = uniform(&result.rolls);
+        for _ in 1..hits {
+            per_use = convolve(&per_use, &uniform(&result.rolls), u16::MAX);
+        }
+        for extra in result.multi_hit_rolls.iter().flatten() {
+            per_use = convolve(&per_use, &uniform(extra), u16::MAX);
+        }
+        let min = *per_use.keys().next().unwrap_or(&0);
+        let max = *per_use.keys().next_back().unwrap_or(&0);
+
+        let max_hp = state.max_hp[defender].max(1) as f64;
+        let mut out = format!(
+            "{} {} vs. {}: {}-{} ({:.1} - {:.1}%)",
+            describe_attacker(&attacker_set, self.tera[0], move_data.category),
+            move_data.name,
+            describe_defender(&defender_set, self.tera[1], move_data.category),
+            min,
+            max,
+            min as f64 * 100.0 / max_hp,
+            max as f64 * 100.0 / max_hp,
+        );
+        if let Some(ko) = ko_chance(&per_use, state.hp[defender]) {
+            out.push_str(" -- ");
+            out.push_str(&ko);
+        }
+        let rolls: Vec<String> = result.rolls.iter().map(u16::to_string).collect();
+        out.push_str(&format!("\nPossible damage amounts: ({})", rolls.join(", ")));
+        if hits > 1 {
+            out.push_str(&format!(" x{} hits", hits));
+        }
+        Ok(out)
+    }
+}
+
+/// Hits assumed for a multi-hit move when `--hits` is not given.
+fn default_hits(state: &BattleState, attacker: usize, (min, max): (u8, u8)) -> u8 {
+    if state.abilities[attacker] == AbilityId::Skilllink {
+        max.max(1)
+    } else if min == max {
+        min.max(1)
+    } else {
+        // Showdown's calculator assumes three hits for 2-5 hit moves
+        3.clamp(min, max)
+    }
+}
+
+/// Showdown's description prefix: `252+ Atk Choice Band Tera Dragon Garchomp`.
+fn describe_attacker(set: &PokemonConfig, tera: Option<Type>, category: MoveCategory) -> String {
+    let (index, stat, label) = match category {
+        MoveCategory::Special => (3, BattleStat::SpA, "SpA"),
+        _ => (1, BattleStat::Atk, "Atk"),
+    };
+    let mut out = format!("{}{} {}", set.evs[index], nature_sign(set, stat), label);
+    push_item_and_species(&mut out, set, tera);
+    out
+}
+
+/// Showdown's description suffix: `252 HP / 4 SpD Tyranitar`.
+fn describe_defender(set: &PokemonConfig, tera: Option<Type>, category: MoveCategory) -> String {
+    let (index, stat, label) = match category {
+        MoveCategory::Special => (4, BattleStat::SpD, "SpD"),
+        _ => (2, BattleStat::Def, "Def"),
+    };
+    let mut out = format!(
+        "{} HP / {}{} {}",
+        set.evs[0],
+        set.evs[index],
+        nature_sign(set, stat),
+        label
+    );
+    push_item_and_species(&mut out, set, tera);
+    out
+}
+
+fn nature_sign(set: &PokemonConfig, stat: BattleStat) -> &'static str {
+    match set.nature.stat_modifier(stat) {
+        11 => "+",
+        9 => "-",
+        _ => "",
+    }
+}
+
+fn push_item_and_species(out: &mut String, set: &PokemonConfig, tera: Option<Type>) {
+    if set.item != ItemId::None {
+        out.push(' ');
+        out.push_str(set.item.data().name);
+    }
+    if let Some(tera) = tera {
+        out.push_str(&format!(" Tera {:?}", tera));
+    }
+    out.push(' ');
+    out.push_str(set.species.data().name);
+}
+
+/// Damage distribution: total damage -> probability.
+type Distribution = BTreeMap<u16, f64>;
+
+fn uniform(rolls: &[u16; 16]) -> Distribution {
+    let mut dist = Distribution::new();
+    for &roll in rolls {
+        *dist.entry(roll).or_default() += 1.0 / 16.0;
+    }
+    dist
+}
+
+/// Sum of two independent distributions, capping totals at `cap`.
+fn convolve(a: &Distribution, b: &Distribution, cap: u16) -> Distribution {
+    let mut out = Distribution::new();
+    for (&x, &px) in a {
+        for (&y, &py) in b {
+            *out.entry(x.saturating_add(y).min(cap)).or_default() += px * py;
+        }
+    }
+    out
+}
+
+/// `"guaranteed OHKO"` / `"37.5% chance to 2HKO"` for the first N (up to 9)
+/// with a non-zero KO chance. Ignores residual damage and recovery.
+fn ko_chance(per_use: &Distribution, hp: u16) -> Option<String> {
+    if hp == 0 || per_use.keys().all(|&d| d == 0) {
+        return None;
+    }
+    let mut total = per_use.clone();
+    for n in 1..=9 {
+        let chance: f64 = total.range(hp..).map(|(_, p)| p).sum();
+        if chance > 0.0 {
+            let label = if n == 1 { "OHKO".to_string() } else { format!("{}HKO", n) };
+            return Some(if chance >= 1.0 - 1e-9 {
+                format!("guaranteed {}", label)
+            } else if chance < 0.0005 {
+                format!("<0.1% chance to {}", label)
+            } else {
+                format!("{:.1}% chance to {}", chance * 100.0, label)
+            });
+        }
+        // Totals at or past `hp` all count as a KO, so capping keeps the map small
+        total = convolve(&total, per_use, hp);
+    }
+    None
+}
+
+/// Split a batch line into arguments, honoring double and single quotes.
+fn split_args(line: &str) -> Result<Vec<String>, String> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut quote = None;
+    let mut in_arg = false;
+    for c in line.chars() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), c) => current.push(c),
+            (None, '"' | '\'') => {
+                quote = Some(c);
+                in_arg = true;
+            }
+            (None, c) if c.is_whitespace() => {
+                if in_arg {
+                    args.push(std::mem::take(&mut current));
+                    in_arg = false;
+                }
+            }
+            (None, c) => {
+                current.push(c);
+                in_arg = true;
+            }
+        }
+    }
+    if quote.is_some() {
+        return Err(format!("unterminated quote: {}", line));
+    }
+    if in_arg {
+        args.push(current);
+    }
+    Ok(args)
+}
+
+fn run_batch(defaults: &Calc, path: &str) -> Result<bool, String> {
+    let text = std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))?;
+    let mut ok = true;
+    for (number, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let calc = Calc {
+            sets: Vec::new(),
+            ..defaults.clone()
+        };
+        match split_args(line).and_then(|args| calc.parse(&args)).and_then(|c| c.run()) {
+            Ok(out) => println!("{}\n", out),
+            Err(e) => {
+                eprintln!("{}:{}: {}", path, number + 1, e);
+                ok = false;
+            }
+        }
+    }
+    Ok(ok)
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let result = Calc::default().parse(&args).and_then(|calc| match &calc.batch {
+        Some(path) => run_batch(&calc, path),
+        None => calc.run().map(|out| {
+            println!("{}", out);
+            true
+        }),
+    });
+    match result {
+        Ok(true) => {}
+        Ok(false) => std::process::exit(1),
+        Err(message) => {
+            eprintln!("{}", message);
+            std::process::exit(2);
+        }
+    }
+}
@@ -0,0 +1,387 @@
+//! JSON-RPC 2.0 server over stdio.
+//!
+//! Reads one request per line on stdin and writes one response per line on
+//! stdout. Battles created with `new_battle` stay in memory under an
+//! integer handle until `close_battle`, so clients can step a battle or
+//! query it repeatedly without respawning the process.
+//!
+//! Sets are either Showdown export text (the one-line form is accepted for
+//! single sets) or objects in Showdown's `PokemonSet` JSON shape; teams are
+//! export text or an array of sets. Choices use Showdown's syntax
+//! (`"move 1"`, `"switch 3"`, `"pass"`), 1-based. A `format` is a built-in
+//! format ID (`"gen9ou"`) or a `Format` object; it overrides `gen`.
+//!
+//! | method             | params                                                        |
+//! |--------------------|---------------------------------------------------------------|
+//! | `calc_damage`      | `attacker`, `defender`, `move`, `gen?`, `crit?`, `field?`      |
+//! | `new_battle`       | `p1`, `p2` (teams), `gen?` or `format?`, `seed?`               |
+//! | `apply_actions`    | `battle`, `p1`, `p2` (choices)                                 |
+//! | `legal_actions`    | `battle`, `player` (1 or 2)                                    |
+//! | `search_best_move` | `battle`, `player`, `depth?`, `samples?`, `seed?`              |
+//! | `validate_team`    | `team`, `format?`                                              |
+//! | `close_battle`     | `battle`                                                       |
+
+use std::collections::HashMap;
+use std::io::{self, BufRead, Write};
+
+use poke_engine::battle::{
+    legal_actions, search_best_move, start_battle, submit_actions, Action, BattleRng,
+    BattleSnapshot, ProtocolWriter, SearchOptions,
+};
+use poke_engine::damage::generations::{Terrain, Weather};
+use poke_engine::damage::{calculate_damage, Generation};
+use poke_engine::entities::PokemonConfig;
+use poke_engine::format::Format;
+use poke_engine::moves::MoveId;
+use poke_engine::state::BattleState;
+use poke_engine::team::{parse_short_set, parse_showdown_set, parse_team, to_id, PokemonSetJson};
+use poke_engine::validate::{learnset_warning, validate_team};
+use serde_json::{json, Value};
+
+// JSON-RPC error codes
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+/// Application error: unknown battle handle, illegal choice, ...
+const BATTLE_ERROR: i64 = -32000;
+
+struct RpcError {
+    code: i64,
+    message: String,
+}
+
+impl RpcError {
+    fn params(message: impl Into<String>) -> Self {
+        Self {
+            code: INVALID_PARAMS,
+            message: message.into(),
+        }
+    }
+
+    fn battle(message: impl Into<String>) -> Self {
+        Self {
+            code: BATTLE_ERROR,
+            message: message.into(),
+        }
+    }
+}
+
+type RpcResult = Result<Value, RpcError>;
+
+struct Battle {
+    gen: Generation,
+    state: BattleState,
+    rng: BattleRng,
+}
+
+#[derive(Default)]
+struct Server {
+    battles: HashMap<u64, Battle>,
+    next_handle: u64,
+}
+
+// ============================================================================
+// Parameter helpers
+// ============================================================================
+
+fn param<'a>(params: &'a Value, name: &str) -> Result<&'a Value, RpcError> {
+    params
+        .get(name)
+        .ok_or_else(|| RpcError::params(format!("missing parameter: {}", name)))
+}
+
+fn param_str<'a>(params: &'a Value, name: &str) -> Result<&'a str, RpcError> {
+    param(params, name)?
+        .as_str()
+        .ok_or_else(|| RpcError::params(format!("{} must be a string", name)))
+}
+
+fn param_u64(params: &Value, name: &str) -> Result<Option<u64>, RpcError> {
+    match params.get(name) {
+        None | Some(Value::Null) => Ok(None),
+        Some(value) => value
+            .as_u64()
+            .map(Some)
+            .ok_or_else(|| RpcError::params(format!("{} must be a non-negative integer", name))),
+    }
+}
+
+fn param_gen(params: &Value) -> Result<Generation, RpcError> {
+    let gen = param_u64(params, "gen")?.unwrap_or(9);
+    if !(1..=9).contains(&gen) {
+        return Err(RpcError::params(format!("unsupported gen: {}", gen)));
+    }
+    Ok(Generation::from_num(gen as u8))
+}
+
+/// Format parameter: a built-in format ID (`"gen9ou"`) or a `Format` object.
+fn param_format(params: &Value) -> Result<Option<Format>, RpcError> {
+    match params.get("format") {
+        None | Some(Value::Null) => Ok(None),
+        Some(Value::String(id)) => Format::builtin(id)
+            .map(Some)
+            .ok_or_else(|| RpcError::params(format!("unknown format: {}", id))),
+        Some(value) => serde_json::from_value(value.clone())
+            .map(Some)
+            .map_err(|e| RpcError::params(format!("invalid format: {}", e))),
+    }
+}
+
+/// Player parameter: 1 or 2 on the wire, 0 or 1 internally.
+fn param_player(params: &Value) -> Result<usize, RpcError> {
+    match param_u64(params, "player")? {
+        Some(p @ 1..=2) => Ok(p as usize - 1),
+        _ => Err(RpcError::params("player must be 1 or 2")),
+    }
+}
+
+fn parse_set(value: &Value) -> Result<PokemonConfig, RpcError> {
+    let config = match value {
+        Value::String(text) if text.contains('\n') => parse_showdown_set(text),
+        Value::String(text) => parse_short_set(text),
+        Value::Object(_) => serde_json::from_value::<PokemonSetJson>(value.clone())
+            .map_err(|e| RpcError::params(e.to_string()))?
+            .to_config(),
+        _ => return Err(RpcError::params("a set must be a string or an object")),
+    };
+    config.map_err(|e| RpcError::params(e.to_string()))
+}
+
+fn parse_team_value(value: &Value) -> Result<Vec<PokemonConfig>, RpcError> {
+    let team = match value {
+        Value::String(text) => parse_team(text),
+        Value::Array(_) => parse_team(&value.to_string()),
+        _ => return Err(RpcError::params("a team must be a string or an array")),
+    };
+    team.map_err(|e| RpcError::params(e.to_string()))
+}
+
+// ============================================================================
+// Responses
+// ============================================================================
+
+fn state_json(state: &BattleState) -> Value {
+    json!(BattleSnapshot::from(state))
+}
+
+fn log_lines(log: ProtocolWriter<String>) -> Value {
+    json!(log.into_inner().lines().collect::<Vec<_>>())
+}
+
+// ============================================================================
+// Methods
+// ============================================================================
+
+impl Server {
+    fn battle(&mut self, params: &Value) -> Result<&mut Battle, RpcError> {
+        let handle = param_u64(params, "battle")?
+            .ok_or_else(|| RpcError::params("missing parameter: battle"))?;
+        self.battles
+            .get_mut(&handle)
+            .ok_or_else(|| RpcError::battle(format!("unknown battle: {}", handle)))
+    }
+
+    fn dispatch(&mut self, method: &str, params: &Value) -> RpcResult {
+        match method {
+            "calc_damage" => calc_damage(params),
+            "new_battle" => self.new_battle(params),
+            "apply_actions" => self.apply_actions(params),
+            "legal_actions" => {
+                let player = param_player(params)?;
+                let battle = self.battle(params)?;
+                let actions: Vec<String> = legal_actions(&battle.state, player)
+                    .into_iter()
+                    .map(|action| action.to_string())
+                    .collect();
+                Ok(json!(actions))
+            }
+            "search_best_move" => {
+                let player = param_player(params)?;
+                let options = SearchOptions {
+                    depth: param_u64(params, "depth")?.unwrap_or(1).clamp(1, 3) as u8,
+                    samples: param_u64(params, "samples")?.unwrap_or(4).clamp(1, 64) as u8,
+                    seed: param_u64(params, "seed")?.unwrap_or(0),
+                };
+                let battle = self.battle(params)?;
+                match search_best_move(battle.gen, &battle.state, player, options) {
+                    Some(result) => Ok(json!({
+                        "choice": result.action.to_string(),
+                        "score": result.score,
+                    })),
+                    None => Err(RpcError::battle("no legal action")),
+                }
+            }
+            "validate_team" => {
+                let format = param_format(params)?.unwrap_or_default();
+                let errors = match parse_team_value(param(params, "team")?) {
+                    Ok(team) => validate_team(&team, &format)
+                        .iter()
+                        .map(ToString::to_string)
+                        .collect(),
+                    Err(e) => vec![e.message],
+                };
+                let warnings: Vec<&str> = learnset_warning().into_iter().collect();
+                Ok(json!({ "valid": errors.is_empty(), "errors": errors, "warnings": warnings }))
+            }
+            "close_battle" => {
+                let handle = param_u64(params, "battle")?.unwrap_or_default();
+                Ok(json!(self.battles.remove(&handle).is_some()))
+            }
+            _ => Err(RpcError {
+                code: METHOD_NOT_FOUND,
+                message: format!("unknown method: {}", method),
+            }),
+        }
+    }
+
+    fn new_battle(&mut self, params: &Value) -> RpcResult {
+        let p1 = parse_team_value(param(params, "p1")?)?;
+        let p2 = parse_team_value(param(params, "p2")?)?;
+        let (gen, mut state) = match param_format(params)? {
+            Some(format) => (
+                Generation::from_num(format.generation),
+                format.new_battle([&p1, &p2]),
+            ),
+            None => {
+                let gen = param_gen(params)?;
+                let mut state = BattleState::new();
+                state.generation = gen.num();
+                for (player, team) in [&p1, &p2].into_iter().enumerate() {
+                    for (slot, config) in team.iter().enumerate() {
+                        config.place(&mut state, player, slot);
+                    }
+                }
+                (gen, state)
+            }
+        };
+        let mut rng = BattleRng::new(param_u64(params, "seed")?.unwrap_or(0));
+        let mut log = ProtocolWriter::new(String::new());
+        start_battle(&mut state, &mut rng, &mut log);
+
+        let handle = self.next_handle;
+        self.next_handle += 1;
+        self.battles.insert(handle, Battle { gen, state, rng });
+        Ok(json!({ "battle": handle, "state": state_json(&state), "log": log_lines(log) }))
+    }
+
+    /// Run a turn, or the pending replacements (see `submit_actions`).
+    fn apply_actions(&mut self, params: &Value) -> RpcResult {
+        let mut actions = [Action::Pass; 2];
+        for (action, key) in actions.iter_mut().zip(["p1", "p2"]) {
+            let choice = param_str(params, key)?;
+            *action = Action::from_choice(choice)
+                .ok_or_else(|| RpcError::params(format!("invalid choice: {}", choice)))?;
+        }
+        let battle = self.battle(params)?;
+        let mut log = ProtocolWriter::new(String::new());
+        submit_actions(
+            battle.gen,
+            &mut battle.state,
+            actions,
+            &mut battle.rng,
+            &mut log,
+        )
+        .map_err(|e| RpcError::battle(e.to_string()))?;
+        Ok(json!({ "state": state_json(&battle.state), "log": log_lines(log) }))
+    }
+}
+
+fn calc_damage(params: &Value) -> RpcResult {
+    let gen = param_gen(params)?;
+    let attacker_set = parse_set(param(params, "attacker")?)?;
+    let defender_set = parse_set(param(params, "defender")?)?;
+    let move_name = param_str(params, "move")?;
+    let move_id = MoveId::from_str(&to_id(move_name))
+        .ok_or_else(|| RpcError::params(format!("unknown move: {}", move_name)))?;
+
+    let mut state = BattleState::new();
+    state.generation = gen.num();
+    attacker_set.place(&mut state, 0, 0);
+    defender_set.place(&mut state, 1, 0);
+    let defender = BattleState::entity_index(1, 0);
+
+    if let Some(field) = params.get("field") {
+        let name = |key: &str| field.get(key).and_then(Value::as_str);
+        let flag = |key: &str| field.get(key).and_then(Value::as_bool).unwrap_or(false);
+        if let Some(weather) = name("weather") {
+            state.weather = Weather::from_name(weather)
+                .ok_or_else(|| RpcError::params(format!("unknown weather: {}", weather)))?
+                as u8;
+        }
+        if let Some(terrain) = name("terrain") {
+            state.terrain = Terrain::from_name(terrain)
+                .ok_or_else(|| RpcError::params(format!("unknown terrain: {}", terrain)))?
+                as u8;
+        }
+        state.gravity = flag("gravity");
+        let screens = &mut state.side_conditions[1];
+        screens.reflect_turns = if flag("reflect") { 5 } else { 0 };
+        screens.light_screen_turns = if flag("light_screen") { 5 } else { 0 };
+        screens.aurora_veil_turns = if flag("aurora_veil") { 5 } else { 0 };
+    }
+
+    let crit = params.get("crit").and_then(Value::as_bool).unwrap_or(false);
+    let result = calculate_damage(gen, &state, 0, defender, move_id, crit);
+    Ok(json!({
+        "rolls": result.rolls,
+        "min": result.min,
+        "max": result.max,
+        "effectiveness": result.effectiveness,
+        "multi_hit_rolls": result.multi_hit_rolls,
+        "defender_hp": state.max_hp[defender],
+    }))
+}
+
+// ============================================================================
+// Transport
+// ============================================================================
+
+fn error_response(id: Value, code: i64, message: String) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "error": { "code": code, "message": message } })
+}
+
+fn handle_line(server: &mut Server, line: &str) -> Option<Value> {
+    let request: Value = match serde_json::from_str(line) {
+        Ok(request) => request,
+        Err(e) => return Some(error_response(Value::Null, PARSE_ERROR, e.to_string())),
+    };
+    let id = request.get("id").cloned();
+    let Some(method) = request.get("method").and_then(Value::as_str) else {
+        return Some(error_response(
+            id.unwrap_or(Value::Null),
+            INVALID_REQUEST,
+            "missing method".to_string(),
+        ));
+    };
+    let params = request.get("params").cloned().unwrap_or(Value::Null);
+    let result = server.dispatch(method, &params);
+
+    // Requests without an id are notifications and get no response
+    let id = id?;
+    Some(match result {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err(e) => error_response(id, e.code, e.message),
+    })
+}
+
+fn main() {
+    let mut server = Server::default();
+    let stdin = io::stdin();
+    let stdout = io::stdout();
+    let mut out = stdout.lock();
+    for line in stdin.lock().lines() {
+        let Ok(line) = line else { break };
+        if line.trim().is_empty() {
+            continue;
+        }
+        if let Some(response) = handle_line(&mut server, &line) {
+            if writeln!(out, "{}", response)
+                .and_then(|_| out.flush())
+                .is_err()
+            {
+                break;
+            }
+        }
+    }
+}
@@ -0,0 +1,540 @@
+//! Runtime data mods: custom species and moves (fakemon, CAP, balance
+//! patches) without rebuilding the generated tables.
+//!
+//! A `DataMod` uses Showdown's pokedex and moves shapes. Keys that name a
+//! built-in species or move override it: with `"inherit": true` only the
+//! given fields change, otherwise the entry replaces it outright (as in
+//! Showdown's mods). Other keys are new entries and get IDs from
+//! `SpeciesId::COUNT` / `MoveId::COUNT` up, in key order; moves are limited
+//! to the `MoveId::CUSTOM_SLOTS` reserved IDs.
+//!
+//! `install` swaps the active mod atomically, so a mod can be reloaded
+//! while the process runs. `data()` hands out `'static` references that
+//! running battles may still hold, so replaced data is leaked rather than
+//! freed. Custom entries have no hooks (they use the generic damage path)
+//! and custom species have no learnsets.
+
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+use core::ptr;
+use core::sync::atomic::{AtomicPtr, Ordering};
+use serde::Deserialize;
+
+use crate::abilities::AbilityId;
+use crate::dex::Nonstandard;
+use crate::moves::{Move, MoveCategory, MoveFlags, MoveId, MoveTarget, MOVES};
+use crate::species::{GenderRatio, Species, SpeciesId, FLAG_EVOLVED, FLAG_NFE, SPECIES};
+use crate::team::to_id;
+use crate::terrains::TerrainId;
+use crate::types::Type;
+
+/// Custom species and moves, keyed by Showdown ID.
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct DataMod {
+    #[serde(default)]
+    pub species: BTreeMap<String, SpeciesEntry>,
+    #[serde(default)]
+    pub moves: BTreeMap<String, MoveEntry>,
+}
+
+/// Base stats as in Showdown's pokedex
+#[derive(Clone, Copy, Debug, Deserialize)]
+pub struct BaseStats {
+    pub hp: u8,
+    pub atk: u8,
+    pub def: u8,
+    pub spa: u8,
+    pub spd: u8,
+    pub spe: u8,
+}
+
+/// A pokedex entry. Fields left out keep the inherited value (with
+/// `inherit`) or their default.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SpeciesEntry {
+    #[serde(default)]
+    pub inherit: bool,
+    pub name: Option<String>,
+    pub types: Option<Vec<String>>,
+    pub base_stats: Option<BaseStats>,
+    /// Slots "0", "1" and "H" to ability names
+    pub abilities: Option<BTreeMap<String, String>>,
+    pub weightkg: Option<f32>,
+    pub base_species: Option<String>,
+    pub evos: Option<Vec<String>>,
+    pub prevo: Option<String>,
+    /// "M", "F" or "N" for single-gender and genderless species
+    pub gender: Option<String>,
+    pub gen: Option<u8>,
+}
+
+/// A move entry. Fields left out keep the inherited value (with `inherit`)
+/// or their default.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MoveEntry {
+    #[serde(default)]
+    pub inherit: bool,
+    pub name: Option<String>,
+    #[serde(rename = "type")]
+    pub move_type: Option<String>,
+    pub category: Option<String>,
+    pub base_power: Option<u16>,
+    pub accuracy: Option<Accuracy>,
+    pub pp: Option<u8>,
+    pub priority: Option<i8>,
+    pub target: Option<String>,
+    /// Showdown flag names ("contact", "protect", ...) to 1
+    pub flags: Option<BTreeMap<String, u8>>,
+    pub gen: Option<u8>,
+}
+
+/// Accuracy: a percentage, or `true` for moves that never miss
+#[derive(Clone, Copy, Debug, Deserialize)]
+#[serde(untagged)]
+pub enum Accuracy {
+    AlwaysHits(bool),
+    Percent(u8),
+}
+
+/// Why a data mod can't be loaded or installed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ModError {
+    Io(String),
+    Json(String),
+    /// An entry names an unknown type, ability, species, etc.
+    Invalid {
+        entry: String,
+        reason: String,
+    },
+    /// More new moves than `MoveId::CUSTOM_SLOTS`
+    TooManyMoves(usize),
+}
+
+impl fmt::Display for ModError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ModError::Io(s) => write!(f, "cannot read data mod: {}", s),
+            ModError::Json(s) => write!(f, "invalid JSON: {}", s),
+            ModError::Invalid { entry, reason } => write!(f, "{}: {}", entry, reason),
+            ModError::TooManyMoves(count) => {
+                write!(f, "{} new moves (at most {})", count, MoveId::CUSTOM_SLOTS)
+            }
+        }
+    }
+}
+
+impl core::error::Error for ModError {}
+
+#[cfg(feature = "std")]
+impl DataMod {
+    pub fn from_json(text: &str) -> Result<Self, ModError> {
+        serde_json::from_str(text).map_err(|e| ModError::Json(e.to_string()))
+    }
+
+    pub fn load(path: impl AsRef<std::path::Path>) -> Result<Self, ModError> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| ModError::Io(format!("{}: {}", path.display(), e)))?;
+        Self::from_json(&text)
+    }
+}
+
+/// A resolved mod, leaked once installed.
+struct Installed {
+    species: Vec<Species>,
+    species_keys: BTreeMap<String, SpeciesId>,
+    species_overrides: BTreeMap<u16, Species>,
+    moves: Vec<Move>,
+    move_keys: BTreeMap<String, MoveId>,
+    move_overrides: BTreeMap<u16, Move>,
+}
+
+static INSTALLED: AtomicPtr<Installed> = AtomicPtr::new(ptr::null_mut());
+
+#[inline]
+fn installed() -> Option<&'static Installed> {
+    // SAFETY: only ever set to a leaked `Box<Installed>`, never freed
+    unsafe { INSTALLED.load(Ordering::Acquire).as_ref() }
+}
+
+/// Install `data_mod`, replacing any installed mod. On error the previous
+/// mod stays active.
+pub fn install(data_mod: &DataMod) -> Result<(), ModError> {
+    let resolved = Box::leak(Box::new(resolve(data_mod)?));
+    INSTALLED.store(resolved, Ordering::Release);
+    Ok(())
+}
+
+/// Remove the installed mod; built-in data applies again.
+pub fn uninstall() {
+    INSTALLED.store(ptr::null_mut(), Ordering::Release);
+}
+
+/// Custom data for `id`, if the installed mod adds or overrides it.
+#[inline]
+pub(crate) fn species(id: SpeciesId) -> Option<&'static Species> {
+    let installed = installed()?;
+    match (id.0 as usize).checked_sub(SpeciesId::COUNT) {
+        Some(index) => installed.species.get(index),
+        None => installed.species_overrides.get(&id.0),
+    }
+}
+
+pub(crate) fn species_id(key: &str) -> Option<SpeciesId> {
+    installed()?.species_keys.get(key).copied()
+}
+
+/// Custom data for `id`, if the installed mod adds or overrides it.
+#[inline]
+pub(crate) fn move_data(id: MoveId) -> Option<&'static Move> {
+    let installed = installed()?;
+    match (id as usize).checked_sub(MoveId::COUNT) {
+        Some(index) => installed.moves.get(index),
+        None => installed.move_overrides.get(&(id as u16)),
+    }
+}
+
+pub(crate) fn move_id(key: &str) -> Option<MoveId> {
+    installed()?.move_keys.get(key).copied()
+}
+
+pub(crate) fn move_from_u16(id: u16) -> Option<MoveId> {
+    let index = (id as usize).checked_sub(MoveId::COUNT)?;
+    (index < installed()?.moves.len()).then(|| MoveId::CUSTOM[index])
+}
+
+impl SpeciesId {
+    /// Whether this ID belongs to a species added by a data mod.
+    pub fn is_custom(self) -> bool {
+        self.0 as usize >= SpeciesId::COUNT
+    }
+}
+
+impl MoveId {
+    /// Whether this ID belongs to a move added by a data mod.
+    pub fn is_custom(self) -> bool {
+        self as usize >= MoveId::COUNT
+    }
+}
+
+fn leak(s: &str) -> &'static str {
+    Box::leak(s.to_string().into_boxed_str())
+}
+
+fn invalid(entry: &str, reason: String) -> ModError {
+    ModError::Invalid {
+        entry: entry.to_string(),
+        reason,
+    }
+}
+
+fn builtin_species(key: &str) -> Option<SpeciesId> {
+    SpeciesId::from_str(key).filter(|id| !id.is_custom())
+}
+
+fn resolve(data_mod: &DataMod) -> Result<Installed, ModError> {
+    // Assign IDs first so entries can refer to each other (formes, evos)
+    let mut species_keys = BTreeMap::new();
+    let mut next = SpeciesId::COUNT as u16;
+    for key in data_mod.species.keys() {
+        let id = to_id(key);
+        if builtin_species(&id).is_none() {
+            if species_keys.insert(id, SpeciesId(next)).is_some() {
+                return Err(invalid(key, "duplicate species".to_string()));
+            }
+            next += 1;
+        }
+    }
+    let mut move_keys = BTreeMap::new();
+    for key in data_mod.moves.keys() {
+        let id = to_id(key);
+        if MoveId::from_str(&id).filter(|id| !id.is_custom()).is_none() {
+            let index = move_keys.len();
+            let slot = *MoveId::CUSTOM
+                .get(index)
+                .ok_or(ModError::TooManyMoves(index + 1))?;
+            if move_keys.insert(id, slot).is_some() {
+                return Err(invalid(key, "duplicate move".to_string()));
+            }
+        }
+    }
+
+    let lookup_species = |entry: &str, name: &str| {
+        let key = to_id(name);
+        builtin_species(&key)
+            .or_else(|| species_keys.get(&key).copied())
+            .ok_or_else(|| invalid(entry, format!("unknown species {:?}", name)))
+    };
+
+    let mut installed = Installed {
+        species: Vec::new(),
+        species_keys: BTreeMap::new(),
+        species_overrides: BTreeMap::new(),
+        moves: Vec::new(),
+        move_keys: BTreeMap::new(),
+        move_overrides: BTreeMap::new(),
+    };
+    for (key, entry) in &data_mod.species {
+        let builtin = builtin_species(&to_id(key));
+        let parent = builtin
+            .filter(|_| entry.inherit)
+            .map(|id| SPECIES[id.0 as usize]);
+        let species = species_from_entry(key, entry, parent, &lookup_species)?;
+        match builtin {
+            Some(id) => {
+                installed.species_overrides.insert(id.0, species);
+            }
+            None => installed.species.push(species),
+        }
+    }
+    for (key, entry) in &data_mod.moves {
+        let builtin = MoveId::from_str(&to_id(key)).filter(|id| !id.is_custom());
+        let parent = builtin
+            .filter(|_| entry.inherit)
+            .map(|id| MOVES[id as usize]);
+        let data = move_from_entry(key, entry, parent)?;
+        match builtin {
+            Some(id) => {
+                installed.move_overrides.insert(id as u16, data);
+            }
+            None => installed.moves.push(data),
+        }
+    }
+    installed.species_keys = species_keys;
+    installed.move_keys = move_keys;
+    Ok(installed)
+}
+
+fn parse_type(entry: &str, name: &str) -> Result<Type, ModError> {
+    Type::from_str(name).ok_or_else(|| invalid(entry, format!("unknown type {:?}", name)))
+}
+
+fn species_from_entry(
+    key: &str,
+    entry: &SpeciesEntry,
+    parent: Option<Species>,
+    lookup_species: &dyn Fn(&str, &str) -> Result<SpeciesId, ModError>,
+) -> Result<Species, ModError> {
+    let missing = |field: &str| invalid(key, format!("missing {}", field));
+    let mut species = match parent {
+        Some(parent) => parent,
+        None => Species {
+            name: leak(entry.name.as_deref().ok_or_else(|| missing("name"))?),
+            base_stats: [0; 6],
+            type1: 0,
+            type2: 0,
+            weight: 0,
+            ability0: 0,
+            ability1: 0,
+            hidden_ability: 0,
+            base_species: 0,
+            flags: 0,
+            gender_ratio: GenderRatio::OneToOne,
+            mega_forme: 0,
+            mega_forme_y: 0,
+            primal_forme: 0,
+            gen: crate::dex::CURRENT_GEN,
+            nonstandard: Some(Nonstandard::Custom),
+        },
+    };
+    if let Some(name) = &entry.name {
+        species.name = leak(name);
+    }
+    match (&entry.types, parent) {
+        (Some(types), _) => {
+            let first = types.first().ok_or_else(|| missing("types"))?;
+            species.type1 = parse_type(key, first)? as u8;
+            species.type2 = match types.get(1) {
+                Some(second) => parse_type(key, second)? as u8 + 1,
+                None => 0,
+            };
+        }
+        (None, None) => return Err(missing("types")),
+        (None, Some(_)) => {}
+    }
+    match (entry.base_stats, parent) {
+        (Some(s), _) => species.base_stats = [s.hp, s.atk, s.def, s.spa, s.spd, s.spe],
+        (None, None) => return Err(missing("baseStats")),
+        (None, Some(_)) => {}
+    }
+    if let Some(abilities) = &entry.abilities {
+        let ability = |slot: &str| -> Result<u16, ModError> {
+            match abilities.get(slot) {
+                Some(name) => AbilityId::from_str(&to_id(name))
+                    .map(|id| id as u16)
+                    .ok_or_else(|| invalid(key, format!("unknown ability {:?}", name))),
+                None => Ok(0),
+            }
+        };
+        species.ability0 = ability("0")?;
+        species.ability1 = ability("1")?;
+        species.hidden_ability = ability("H")?;
+    }
+    if let Some(kg) = entry.weightkg {
+        species.weight = (kg * 10.0 + 0.5) as u16;
+    }
+    if let Some(base) = &entry.base_species {
+        species.base_species = lookup_species(key, base)?.0 + 1;
+    }
+    if let Some(evos) = &entry.evos {
+        species.flags &= !FLAG_NFE;
+        if !evos.is_empty() {
+            species.flags |= FLAG_NFE;
+        }
+    }
+    if let Some(prevo) = &entry.prevo {
+        lookup_species(key, prevo)?;
+        species.flags |= FLAG_EVOLVED;
+    }
+    if let Some(gender) = &entry.gender {
+        species.gender_ratio = match gender.as_str() {
+            "M" => GenderRatio::AlwaysMale,
+            "F" => GenderRatio::AlwaysFemale,
+            "N" => GenderRatio::Genderless,
+            other => return Err(invalid(key, format!("unknown gender {:?}", other))),
+        };
+    }
+    if let Some(gen) = entry.gen {
+        species.gen = gen;
+    }
+    Ok(species)
+}
+
+fn move_from_entry(key: &str, entry: &MoveEntry, parent: Option<Move>) -> Result<Move, ModError> {
+    let missing = |field: &str| invalid(key, format!("missing {}", field));
+    let mut data = match parent {
+        Some(parent) => parent,
+        None => Move {
+            name: leak(entry.name.as_deref().ok_or_else(|| missing("name"))?),
+            primary_type: Type::Normal,
+            category: MoveCategory::Status,
+            power: 0,
+            accuracy: 0,
+            pp: 0,
+            priority: 0,
+            flags: MoveFlags::empty(),
+            terrain: TerrainId::None,
+            target: MoveTarget::from_str("normal").expect("normal target"),
+            multihit: (0, 0),
+            z_power: None,
+            gen: crate::dex::CURRENT_GEN,
+            nonstandard: Some(Nonstandard::Custom),
+        },
+    };
+    if let Some(name) = &entry.name {
+        data.name = leak(name);
+    }
+    match (&entry.move_type, parent) {
+        (Some(name), _) => data.primary_type = parse_type(key, name)?,
+        (None, None) => return Err(missing("type")),
+        (None, Some(_)) => {}
+    }
+    match (entry.category.as_deref(), parent) {
+        (Some("Physical"), _) => data.category = MoveCategory::Physical,
+        (Some("Special"), _) => data.category = MoveCategory::Special,
+        (Some("Status"), _) => data.category = MoveCategory::Status,
+        (Some(other), _) => return Err(invalid(key, format!("unknown category {:?}", other))),
+        (None, None) => return Err(missing("category")),
+        (None, Some(_)) => {}
+    }
+    if let Some(power) = entry.base_power {
+        data.power = power;
+    }
+    match entry.accuracy {
+        Some(Accuracy::Percent(percent)) => data.accuracy = percent,
+        Some(Accuracy::AlwaysHits(_)) => data.accuracy = 0,
+        None => {}
+    }
+    if let Some(pp) = entry.pp {
+        data.pp = pp;
+    }
+    if let Some(priority) = entry.priority {
+        data.priority = priority;
+    }
+    if let Some(target) = &entry.target {
+        data.target = MoveTarget::from_str(target)
+            .ok_or_else(|| invalid(key, format!("unknown target {:?}", target)))?;
+    }
+    if let Some(flags) = &entry.flags {
+        data.flags = MoveFlags::empty();
+        for flag in flags.keys() {
+            data.flags |= MoveFlags::from_name(&flag.to_ascii_uppercase())
+                .ok_or_else(|| invalid(key, format!("unknown flag {:?}", flag)))?;
+        }
+    }
+    if let Some(gen) = entry.gen {
+        data.gen = gen;
+    }
+    Ok(data)
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    // Installation is process-wide, so one test covers the whole lifecycle,
+    // overriding only entries no other test uses.
+    #[test]
+    fn test_install_custom_and_override() {
+        let data_mod = DataMod::from_json(
+            r#"{
+                "species": {
+                    "fakemon": {
+                        "name": "Fakemon",
+                        "types": ["Fire", "Fairy"],
+                        "baseStats": {"hp": 90, "atk": 60, "def": 80, "spa": 120, "spd": 90, "spe": 100},
+                        "abilities": {"0": "Blaze", "H": "Pixilate"},
+                        "weightkg": 42.5
+                    },
+                    "spinda": {"inherit": true, "baseStats": {"hp": 60, "atk": 60, "def": 60, "spa": 60, "spd": 60, "spe": 120}}
+                },
+                "moves": {
+                    "fakeblast": {
+                        "name": "Fake Blast", "type": "Fairy", "category": "Special",
+                        "basePower": 95, "accuracy": true, "pp": 10, "flags": {"protect": 1}
+                    },
+                    "barrage": {"inherit": true, "basePower": 25}
+                }
+            }"#,
+        )
+        .unwrap();
+        install(&data_mod).unwrap();
+
+        let fakemon = SpeciesId::from_str("fakemon").unwrap();
+        assert!(fakemon.is_custom());
+        let data = fakemon.data();
+        assert_eq!(data.name, "Fakemon");
+        assert_eq!(data.secondary_type(), Some(Type::Fairy));
+        assert_eq!(data.weight, 425);
+        assert_eq!(data.hidden_ability, AbilityId::Pixilate as u16);
+        assert_eq!(SpeciesId::SPINDA.data().base_stats[5], 120);
+        assert_eq!(SpeciesId::SPINDA.data().primary_type(), Type::Normal);
+
+        let fake_blast = MoveId::from_str("fakeblast").unwrap();
+        assert_eq!(fake_blast, MoveId::CUSTOM[0]);
+        assert_eq!(MoveId::from_u16(fake_blast as u16), Some(fake_blast));
+        assert_eq!(fake_blast.data().power, 95);
+        assert!(fake_blast.data().flags.contains(MoveFlags::PROTECT));
+        assert_eq!(MoveId::Barrage.data().power, 25);
+        assert_eq!(MoveId::Barrage.data().name, "Barrage");
+
+        // A failed install keeps the previous mod.
+        let bad = DataMod::from_json(
+            r#"{"moves": {"oops": {"name": "Oops", "type": "Sound", "category": "Special"}}}"#,
+        )
+        .unwrap();
+        assert!(matches!(install(&bad), Err(ModError::Invalid { .. })));
+        assert_eq!(MoveId::Barrage.data().power, 25);
+
+        uninstall();
+        assert_eq!(SpeciesId::from_str("fakemon"), None);
+        assert_eq!(MoveId::from_u16(fake_blast as u16), None);
+        assert_eq!(MoveId::Barrage.data().power, 15);
+    }
+}
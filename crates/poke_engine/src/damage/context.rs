@@ -86,6 +86,9 @@ pub struct DamageContext<'a, G: GenMechanics> {
     
     /// Whether this is a Tera STAB (Gen 9)
     pub is_tera_stab: bool,
+
+    /// Whether this move type still gets the one-time Tera Stellar boost
+    pub is_stellar_boost: bool,
     
     // ========================================================================
     // Attacker/Defender cached info
@@ -108,7 +111,7 @@ impl<'a, G: GenMechanics> DamageContext<'a, G> {
         move_id: MoveId,
         is_crit: bool,
     ) -> Self {
-        let move_data = move_id.data();
+        let move_data = move_id.data_in(gen.generation());
         let mut attacker_types = state.types[attacker];
 
         // Handle Forecast (Castform) type change for STAB
@@ -163,8 +166,15 @@ impl<'a, G: GenMechanics> DamageContext<'a, G> {
             _ => {}
         }
         
-        // Check STAB
-        let has_stab = move_type == attacker_types[0] || move_type == attacker_types[1];
+        // Check STAB. A Terastallized attacker keeps STAB on its original
+        // types and gains it on its Tera Type; a move matching both is Tera STAB.
+        // Tera Stellar instead boosts each move type once.
+        let original_stab = move_type == attacker_types[0] || move_type == attacker_types[1];
+        let tera_stab = state.terastallized[attacker]
+            && state.tera_type[attacker] != Type::Stellar
+            && move_type == state.tera_type[attacker];
+        let is_stellar_boost = state.has_stellar_boost(attacker, move_type);
+        let has_stab = original_stab || tera_stab || is_stellar_boost;
         
         // Check Adaptability
         let has_adaptability = attacker_ability == AbilityId::Adaptability;
@@ -203,7 +213,15 @@ impl<'a, G: GenMechanics> DamageContext<'a, G> {
         // Check for ability-granted immunity (Levitate, Flash Fire, etc.)
         let defender_ability = state.abilities[defender];
         if effectiveness > 0 {
-            effectiveness = Self::check_ability_immunity(state, attacker, defender, defender_ability, move_type, effectiveness);
+            effectiveness = Self::check_ability_immunity(
+                state,
+                attacker,
+                defender,
+                defender_ability,
+                move_data,
+                move_type,
+                effectiveness,
+            );
         }
         
         // Determine category (respect Physical/Special split)
@@ -237,7 +255,8 @@ impl<'a, G: GenMechanics> DamageContext<'a, G> {
             effectiveness,
             has_stab,
             has_adaptability,
-            is_tera_stab: false, // TODO: Set when Tera is implemented
+            is_tera_stab: original_stab && (tera_stab || is_stellar_boost),
+            is_stellar_boost,
             attacker_ability,
             defender_ability: state.abilities[defender],
         }
@@ -295,14 +314,17 @@ impl<'a, G: GenMechanics> DamageContext<'a, G> {
         attacker: usize,
         defender: usize,
         ability: AbilityId,
+        move_data: &Move,
         move_type: Type,
         effectiveness: u8,
     ) -> u8 {
         use crate::abilities::ABILITY_REGISTRY;
         
-        // Mold Breaker check
+        // Mold Breaker and ability-ignoring moves check
         let attacker_ability = state.abilities[attacker];
-        if matches!(attacker_ability, AbilityId::Moldbreaker | AbilityId::Teravolt | AbilityId::Turboblaze) {
+        if matches!(attacker_ability, AbilityId::Moldbreaker | AbilityId::Teravolt | AbilityId::Turboblaze)
+            || move_data.flags.contains(crate::moves::MoveFlags::IGNORES_ABILITY)
+        {
             return effectiveness;
         }
         
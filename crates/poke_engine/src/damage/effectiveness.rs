@@ -14,6 +14,8 @@ use crate::types::Type;
 /// - Ring Target (negates all type immunities)
 /// - Iron Ball / Gravity grounding for Ground vs Flying
 /// - Scrappy / Mind's Eye (Normal/Fighting vs Ghost)
+/// - Terastallization (the defender is purely its Tera Type)
+/// - Stellar-type moves (2x against Terastallized targets, neutral otherwise)
 ///
 /// Returns effectiveness on 4-scale (0=immune, 1=0.25x, 2=0.5x, 4=1x, 8=2x, 16=4x).
 ///
@@ -35,8 +37,11 @@ pub fn calculate_effectiveness<F>(
 where
     F: Fn(Type, Type, Option<Type>) -> u8,
 {
-    let def_type1 = state.types[defender][0];
-    let def_type2 = state.types[defender][1];
+    if move_type == Type::Stellar {
+        return if state.terastallized[defender] { 8 } else { 4 };
+    }
+
+    let [def_type1, def_type2] = state.defensive_types(defender);
 
     // Helper to get effectiveness against a single type
     // respecting immunity overrides (Ring Target, Iron Ball/Gravity)
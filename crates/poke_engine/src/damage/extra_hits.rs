@@ -0,0 +1,73 @@
+//! Fixed-capacity list of extra hits.
+
+use core::ops::Deref;
+
+use serde::{Serialize, Serializer};
+
+/// Most extra hits a single move can gain (a 10-hit move is 1 + 9).
+pub const MAX_EXTRA_HITS: usize = 9;
+
+/// Up to `MAX_EXTRA_HITS` values kept inline, so damage results and
+/// multi-hit hooks never allocate.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ExtraHits<T> {
+    len: u8,
+    items: [T; MAX_EXTRA_HITS],
+}
+
+impl<T: Copy + Default> ExtraHits<T> {
+    pub fn new() -> Self {
+        Self {
+            len: 0,
+            items: [T::default(); MAX_EXTRA_HITS],
+        }
+    }
+
+    /// Append a hit. Hits past the capacity are dropped.
+    pub fn push(&mut self, item: T) {
+        if let Some(slot) = self.items.get_mut(self.len as usize) {
+            *slot = item;
+            self.len += 1;
+        }
+    }
+}
+
+impl<T: Copy + Default> Default for ExtraHits<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Copy + Default> FromIterator<T> for ExtraHits<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut hits = Self::new();
+        for item in iter {
+            hits.push(item);
+        }
+        hits
+    }
+}
+
+impl<T> Deref for ExtraHits<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        &self.items[..self.len as usize]
+    }
+}
+
+impl<'a, T> IntoIterator for &'a ExtraHits<T> {
+    type Item = &'a T;
+    type IntoIter = core::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Serialized as a plain sequence of the stored hits.
+impl<T: Serialize> Serialize for ExtraHits<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter())
+    }
+}
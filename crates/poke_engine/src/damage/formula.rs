@@ -7,6 +7,7 @@ use super::generations::GenMechanics;
 use super::modifiers;
 use super::DamageContext;
 use super::DamageResult;
+use super::ExtraHits;
 use super::Modifier;
 
 /// 16-bit overflow wrapping (simulates hardware behavior).
@@ -296,11 +297,11 @@ pub fn calculate_standard<G: GenMechanics>(mut ctx: DamageContext<G>) -> DamageR
     {
         if let Some(hook) = hooks.on_modify_multi_hit {
             if let Some(modifiers) = hook(ctx.state, ctx.attacker, ctx.defender, ctx.move_id) {
-                let mut hits = Vec::new();
+                let mut hits = ExtraHits::new();
                 // The hook returns modifiers for *additional* hits.
                 // For accurate rounding we scale the pre-random base_damage and then run the final
                 // pipeline (random roll, STAB, effectiveness, burn, screens) on the scaled base.
-                for modifier in modifiers {
+                for &modifier in &modifiers {
                     let scaled_base = apply_modifier(base_damage, modifier);
                     hits.push(modifiers::compute_final_damage(&mut ctx, scaled_base));
                 }
@@ -11,8 +11,16 @@ use crate::types::Type;
 /// Key differences:
 /// - Special stat is shared (SpA = SpD)
 /// - Critical hits double the level in damage formula
+/// - Critical hit chance comes from base Speed; Focus Energy lowers it
+/// - Burn and poison deal 1/16 max HP; Struggle recoils 1/2 the damage dealt
 /// - Type effectiveness bugs (Psychic immune to Ghost)
 /// - No items, abilities, or split
+/// - Freeze never thaws on its own, and waking up uses the turn
+/// - Hyper Beam skips its recharge after a KO
+/// - Partial trapping stops the target from moving instead of chipping it
+/// - 100% accurate moves still miss 1/256 of the time
+/// - Toxic's counter survives Haze and also scales Leech Seed
+/// - Stats above 255 overflow in the damage formula
 #[derive(Clone, Copy, Debug, Default)]
 pub struct Gen1;
 
@@ -29,7 +37,66 @@ impl GenMechanics for Gen1 {
         false
     }
 
+    fn freeze_thaw_chance(&self) -> (u32, u32) {
+        (0, 1)
+    }
+    fn sleep_turns(&self) -> (u8, u8) {
+        (1, 7)
+    }
+    fn moves_on_waking(&self) -> bool {
+        false
+    }
+    fn recharges_after_ko(&self) -> bool {
+        false
+    }
+    fn partial_trap_divisor(&self) -> u16 {
+        0
+    }
+    fn partial_trap_prevents_moves(&self) -> bool {
+        true
+    }
+    fn has_one_in_256_miss(&self) -> bool {
+        true
+    }
+    fn shares_special_stat(&self) -> bool {
+        true
+    }
+    fn toxic_counter_persists(&self) -> bool {
+        true
+    }
+    fn haze_cures_status(&self) -> bool {
+        true
+    }
+    fn leech_seed_divisor(&self) -> u16 {
+        16
+    }
+
+    fn burn_divisor(&self) -> u16 {
+        16
+    }
+    fn poison_divisor(&self) -> u16 {
+        16
+    }
+    fn struggle_recoil(&self, damage_dealt: u16, _max_hp: u16) -> u16 {
+        damage_dealt / 2
+    }
+
+    // Crits scale with base Speed: base Speed / 2 out of 256, 8x for
+    // high-crit moves (capped at 255/256). Focus Energy's bug quarters the
+    // chance instead of raising it. Nothing else raises the stage here.
+    fn crit_chance(&self, stage: u8, base_speed: u8) -> (u32, u32) {
+        let focus_energy = stage >= 2;
+        let high_crit = stage % 2 == 1;
+        let threshold = if focus_energy { base_speed as u32 / 4 } else { base_speed as u32 };
+        let threshold = if high_crit { (threshold * 4).min(255) } else { threshold / 2 };
+        (threshold, 256)
+    }
+
     fn type_effectiveness(&self, atk_type: Type, def_type1: Type, def_type2: Option<Type>) -> u8 {
+        if crate::types::GEN_TYPE_CHARTS_LOADED {
+            return crate::types::type_effectiveness_in(1, atk_type, def_type1, def_type2);
+        }
+
         // Gen 1 specific type chart quirks
         // Let's reuse standard but override specific cases
         let mut m = crate::types::type_effectiveness(atk_type, def_type1, def_type2);
@@ -132,7 +199,8 @@ impl GenMechanics for Gen1 {
                 ), // Use SpA boost for Special Defense too
                 _ => 0,
             };
-            (atk, def)
+            // Boosted stats cap at 999
+            (atk.min(999), def.min(999))
         };
 
         // Burn Mod (Gen 1): Halves Attack if burned and physical move.
@@ -144,6 +212,14 @@ impl GenMechanics for Gen1 {
             atk_stat /= 2;
         }
 
+        // Stats only fit a byte in the formula: above 255, both are
+        // quartered and wrap around (so a huge Defense can become tiny)
+        let mut def_stat = def_stat;
+        if atk_stat > 255 || def_stat > 255 {
+            atk_stat = (atk_stat / 4 % 256).max(1);
+            def_stat = (def_stat / 4 % 256).max(1);
+        }
+
         // 3. Level
         let level = ctx.state.level[ctx.attacker] as u32;
         let effective_level = if ctx.is_crit { level * 2 } else { level };
@@ -272,10 +348,79 @@ mod tests {
         assert!(result.min < result.max);
     }
 
+    #[test]
+    fn test_gen1_stats_above_255_are_quartered() {
+        let mut state = BattleState::new();
+        PokemonConfig::from_str("tauros").unwrap().level(50).spawn(&mut state, 0, 0);
+        PokemonConfig::from_str("snorlax").unwrap().level(50).spawn(&mut state, 1, 0);
+
+        // Only the ratio survives once a stat tops 255
+        state.stats[0][1] = 75;
+        state.stats[6][2] = 100;
+        let small = calculate_damage(Gen1, &state, 0, 6, MoveId::Bodyslam, false);
+        state.stats[0][1] = 300;
+        state.stats[6][2] = 400;
+        let large = calculate_damage(Gen1, &state, 0, 6, MoveId::Bodyslam, false);
+        assert_eq!(large.rolls, small.rolls);
+    }
+
+    #[test]
+    fn test_gen1_crit_chance_from_base_speed() {
+        // Persian (base 115 Spe): 57/256, 255/256 with Slash
+        assert_eq!(Gen1.crit_chance(0, 115), (57, 256));
+        assert_eq!(Gen1.crit_chance(1, 115), (255, 256));
+        // Focus Energy quarters it
+        assert_eq!(Gen1.crit_chance(2, 115), (14, 256));
+        assert_eq!(Gen1.crit_chance(3, 115), (112, 256));
+    }
+
     #[test]
     fn test_gen1_psychic_immune_to_ghost() {
         let gen = Gen1;
         // Ghost vs Psychic = 0x
         assert_eq!(gen.type_effectiveness(Type::Ghost, Type::Psychic, None), 0);
     }
+
+    #[test]
+    fn test_gen1_move_data() {
+        // Gen 9 and custom (0) mechanics read the current data.
+        assert!(core::ptr::eq(MoveId::Bite.data_in(9), MoveId::Bite.data()));
+        assert!(core::ptr::eq(MoveId::Bite.data_in(0), MoveId::Bite.data()));
+    }
+
+    #[test]
+    #[ignore = "needs data/mods (run scripts/sync_showdown_data.mts)"]
+    fn test_generated_gen_data() {
+        use crate::species::SpeciesId;
+        use crate::types::type_effectiveness_in;
+
+        // Bite was a Normal move before Dark existed.
+        assert_eq!(MoveId::Bite.data_in(1).primary_type, Type::Normal);
+        assert_eq!(MoveId::Bite.data_in(2).primary_type, Type::Dark);
+        // Dig had 60 power in Gens 2-3.
+        assert_eq!(MoveId::Dig.data_in(2).power, 60);
+        assert_eq!(MoveId::Dig.data_in(3).power, 60);
+        assert_eq!(MoveId::Dig.data_in(4).power, 80);
+        // Thunderbolt lost 5 power in Gen 6.
+        assert_eq!(MoveId::Thunderbolt.data_in(4).power, 95);
+        assert_eq!(MoveId::Thunderbolt.data_in(5).power, 95);
+        assert_eq!(MoveId::Thunderbolt.data_in(6).power, 90);
+        // Sucker Punch lost 10 power in Gen 7.
+        assert_eq!(MoveId::Suckerpunch.data_in(6).power, 80);
+        assert_eq!(MoveId::Suckerpunch.data_in(7).power, 70);
+        // Multi-Attack gained 30 power in Gen 8.
+        assert_eq!(MoveId::Multiattack.data_in(7).power, 90);
+        assert_eq!(MoveId::Multiattack.data_in(8).power, 120);
+        // Grassy Glide lost 15 power in Gen 9.
+        assert_eq!(MoveId::Grassyglide.data_in(8).power, 70);
+        assert_eq!(MoveId::Grassyglide.data_in(9).power, 55);
+        // Clefable was Normal-type until Fairy arrived.
+        let clefable = SpeciesId::from_str("clefable").unwrap();
+        assert_eq!(clefable.data_in(5).primary_type(), Type::Normal);
+        assert_eq!(clefable.data_in(6).primary_type(), Type::Fairy);
+        // Ghost didn't affect Psychic in Gen 1; Steel resisted Dark until Gen 6.
+        assert_eq!(type_effectiveness_in(1, Type::Ghost, Type::Psychic, None), 0);
+        assert_eq!(type_effectiveness_in(5, Type::Dark, Type::Steel, None), 2);
+        assert_eq!(type_effectiveness_in(6, Type::Dark, Type::Steel, None), 4);
+    }
 }
@@ -12,6 +12,7 @@ use crate::types::Type;
 /// - Steel and Dark types introduced
 /// - Type effectiveness: Steel resists Ghost/Dark (unlike Gen 6+)
 /// - 2.0x crit multiplier
+/// - Frozen Pokémon thaw 25/256 of the time; sleep lasts 1-6 turns
 #[derive(Clone, Copy, Debug, Default)]
 pub struct Gen2;
 
@@ -29,6 +30,13 @@ impl GenMechanics for Gen2 {
         false
     }
 
+    fn freeze_thaw_chance(&self) -> (u32, u32) {
+        (25, 256)
+    }
+    fn sleep_turns(&self) -> (u8, u8) {
+        (1, 6)
+    }
+
     // 2.0x crit
     fn crit_multiplier(&self) -> Modifier {
         Modifier::DOUBLE // 2.0x
@@ -36,6 +44,10 @@ impl GenMechanics for Gen2 {
 
     // Type chart overrides
     fn type_effectiveness(&self, atk_type: Type, def_type1: Type, def_type2: Option<Type>) -> u8 {
+        if crate::types::GEN_TYPE_CHARTS_LOADED {
+            return crate::types::type_effectiveness_in(2, atk_type, def_type1, def_type2);
+        }
+
         // Standard chart calculation
         let mut mult = crate::types::type_effectiveness(atk_type, def_type1, def_type2);
 
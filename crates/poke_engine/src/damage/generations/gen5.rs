@@ -45,6 +45,10 @@ impl GenMechanics for Gen5 {
 
     // Steel resists Ghost/Dark
     fn type_effectiveness(&self, atk_type: Type, def_type1: Type, def_type2: Option<Type>) -> u8 {
+        if crate::types::GEN_TYPE_CHARTS_LOADED {
+            return crate::types::type_effectiveness_in(5, atk_type, def_type1, def_type2);
+        }
+
         let mut mult = crate::types::type_effectiveness(atk_type, def_type1, def_type2);
 
         let is_steel = def_type1 == Type::Steel || def_type2 == Some(Type::Steel);
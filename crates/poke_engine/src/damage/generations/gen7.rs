@@ -20,6 +20,21 @@ impl GenMechanics for Gen7 {
         true
     }
 
+    fn z_move_power(&self, base_power: u16) -> u16 {
+        match base_power {
+            0..=59 => 100,
+            60..=69 => 120,
+            70..=79 => 140,
+            80..=89 => 160,
+            90..=99 => 175,
+            100..=109 => 180,
+            110..=119 => 185,
+            120..=129 => 190,
+            130..=139 => 195,
+            _ => 200,
+        }
+    }
+
     // Mega Evolution exists
     fn has_mega_evolution(&self) -> bool {
         true
@@ -30,6 +30,25 @@ impl GenMechanics for Gen8 {
         2.0
     }
 
+    // Fighting and Poison Max Moves (Max Knuckle, Max Ooze) use a weaker
+    // table because their secondary effect boosts the user's side.
+    fn max_move_power(&self, base_power: u16, move_type: Type) -> u16 {
+        let tier = match base_power {
+            0..=44 => 0,
+            45..=54 => 1,
+            55..=64 => 2,
+            65..=74 => 3,
+            75..=109 => 4,
+            110..=149 => 5,
+            _ => 6,
+        };
+        if matches!(move_type, Type::Fighting | Type::Poison) {
+            [70, 75, 80, 85, 90, 95, 100][tier]
+        } else {
+            [90, 100, 110, 120, 130, 140, 150][tier]
+        }
+    }
+
     // STAB without Tera
     fn stab_multiplier(&self, has_adaptability: bool, _is_tera_stab: bool) -> Modifier {
         if has_adaptability {
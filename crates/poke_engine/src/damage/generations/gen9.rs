@@ -48,6 +48,9 @@ mod tests {
 
         // Tera STAB
         assert_eq!(gen.stab_multiplier(false, true), Modifier::DOUBLE); // 2.0x
+
+        // Adaptability on top of Tera STAB
+        assert_eq!(gen.stab_multiplier(true, true), Modifier::new(9216)); // 2.25x
     }
 
     #[test]
@@ -36,6 +36,9 @@ use crate::types::Type;
 /// Fixed-point scale for modifiers (4096 = 1.0x)
 pub const MOD_SCALE: u16 = 4096;
 
+/// Crit chance denominators shared by Gens 2-5 (1/16, 1/8, 1/4, 1/3, 1/2).
+pub(crate) const CRIT_DENOMINATORS_GEN2_5: [u32; 5] = [16, 8, 4, 3, 2];
+
 /// Weather conditions
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
 #[repr(u8)]
@@ -67,6 +70,27 @@ impl Weather {
             _ => Weather::None,
         }
     }
+
+    /// Parse a weather name ("rain", "RainDance", "Heavy Rain", ...).
+    pub fn from_name(name: &str) -> Option<Self> {
+        let id: alloc::string::String = name
+            .chars()
+            .filter(char::is_ascii_alphanumeric)
+            .map(|c| c.to_ascii_lowercase())
+            .collect();
+        match id.as_str() {
+            "none" => Some(Weather::None),
+            "sun" | "sunnyday" => Some(Weather::Sun),
+            "rain" | "raindance" => Some(Weather::Rain),
+            "sand" | "sandstorm" => Some(Weather::Sand),
+            "hail" => Some(Weather::Hail),
+            "snow" => Some(Weather::Snow),
+            "harshsun" | "desolateland" => Some(Weather::HarshSun),
+            "heavyrain" | "primordialsea" => Some(Weather::HeavyRain),
+            "strongwinds" | "deltastream" => Some(Weather::StrongWinds),
+            _ => None,
+        }
+    }
 }
 
 /// Terrain types
@@ -92,6 +116,23 @@ impl Terrain {
             _ => Terrain::None,
         }
     }
+
+    /// Parse a terrain name ("electric", "Electric Terrain", ...).
+    pub fn from_name(name: &str) -> Option<Self> {
+        let id: alloc::string::String = name
+            .chars()
+            .filter(char::is_ascii_alphanumeric)
+            .map(|c| c.to_ascii_lowercase())
+            .collect();
+        match id.trim_end_matches("terrain") {
+            "none" => Some(Terrain::None),
+            "electric" => Some(Terrain::Electric),
+            "grassy" => Some(Terrain::Grassy),
+            "psychic" => Some(Terrain::Psychic),
+            "misty" => Some(Terrain::Misty),
+            _ => None,
+        }
+    }
 }
 
 /// Generation-specific mechanics trait.
@@ -129,6 +170,20 @@ pub trait GenMechanics: Copy + Clone + Send + Sync + 'static {
         Modifier::ONE_POINT_FIVE // 1.5x for Gen 6+
     }
 
+    /// Critical hit chance as `(numerator, denominator)` at a given crit
+    /// stage (Focus Energy +2, a high-crit move +1, items and abilities +1)
+    /// for an attacker with `base_speed`.
+    /// Gen 7+: 1/24, 1/8, 1/2, 1/1; Gen 6: 1/16, 1/8, 1/2, 1/1;
+    /// Gen 2-5: 1/16, 1/8, 1/4, 1/3, 1/2; Gen 1: base Speed / 512
+    fn crit_chance(&self, stage: u8, _base_speed: u8) -> (u32, u32) {
+        let denominators: &[u32] = match Self::GEN {
+            2..=5 => &CRIT_DENOMINATORS_GEN2_5,
+            6 => &[16, 8, 2, 1],
+            _ => &[24, 8, 2, 1],
+        };
+        (1, denominators[(stage as usize).min(denominators.len() - 1)])
+    }
+
     /// STAB (Same Type Attack Bonus) multiplier in 4096-scale.
     ///
     /// # Arguments
@@ -136,7 +191,8 @@ pub trait GenMechanics: Copy + Clone + Send + Sync + 'static {
     /// * `is_tera_stab` - Whether this is a Tera-boosted STAB (Gen 9 only)
     fn stab_multiplier(&self, has_adaptability: bool, is_tera_stab: bool) -> Modifier {
         match (has_adaptability, is_tera_stab) {
-            (true, _) => Modifier::DOUBLE,              // 2.0x with Adaptability
+            (true, true) => Modifier::new(9216),        // 2.25x with Adaptability and Tera STAB
+            (true, false) => Modifier::DOUBLE,          // 2.0x with Adaptability
             (false, true) => Modifier::DOUBLE,          // 2.0x with Tera STAB
             (false, false) => Modifier::ONE_POINT_FIVE, // 1.5x normal STAB
         }
@@ -273,11 +329,157 @@ pub trait GenMechanics: Copy + Clone + Send + Sync + 'static {
         1.0
     }
 
-    /// Max Move power based on base move power.
-    fn max_move_power(&self, base_power: u16) -> u16 {
+    /// Max Move power based on the base move's power and type.
+    fn max_move_power(&self, base_power: u16, _move_type: Type) -> u16 {
+        base_power
+    }
+
+    /// Z-Move power based on the base move's power, for moves without an
+    /// explicit Z-Power.
+    /// Gen 7: 100-200.
+    fn z_move_power(&self, base_power: u16) -> u16 {
         base_power
     }
 
+    // ========================================================================
+    // Battle Simulation
+    // ========================================================================
+
+    /// Chance a frozen Pokémon thaws before it moves, as `(numerator,
+    /// denominator)`.
+    /// Gen 3+: 1/5, Gen 2: 25/256, Gen 1: never (only Fire moves and Haze)
+    fn freeze_thaw_chance(&self) -> (u32, u32) {
+        (1, 5)
+    }
+
+    /// Range of turns a Pokémon stays asleep (inclusive).
+    /// Gen 5+: 1-3, Gen 3-4: 2-5, Gen 2: 1-6, Gen 1: 1-7
+    fn sleep_turns(&self) -> (u8, u8) {
+        if matches!(Self::GEN, 3 | 4) {
+            (2, 5)
+        } else {
+            (1, 3)
+        }
+    }
+
+    /// Whether a Pokémon can move on the turn it wakes up.
+    /// Gen 2+: true, Gen 1: false
+    fn moves_on_waking(&self) -> bool {
+        true
+    }
+
+    /// Whether a recharge move (Hyper Beam) needs a recharge turn after
+    /// knocking out its target.
+    /// Gen 2+: true, Gen 1: false
+    fn recharges_after_ko(&self) -> bool {
+        true
+    }
+
+    /// Range of turns partial trapping (Wrap, Fire Spin, ...) lasts.
+    /// Gen 5+: 4-5, Gen 1-4: 2-5
+    fn partial_trap_turns(&self) -> (u8, u8) {
+        if matches!(Self::GEN, 1..=4) {
+            (2, 5)
+        } else {
+            (4, 5)
+        }
+    }
+
+    /// Partial trapping residual damage as a fraction of max HP, `1 /
+    /// divisor`; 0 means no residual damage.
+    /// Gen 6+: 1/8, Gen 2-5: 1/16, Gen 1: none
+    fn partial_trap_divisor(&self) -> u16 {
+        if matches!(Self::GEN, 2..=5) {
+            16
+        } else {
+            8
+        }
+    }
+
+    /// Whether a partially trapped Pokémon is unable to move, with the user
+    /// locked into the trapping move until it ends.
+    /// Gen 1: true
+    fn partial_trap_prevents_moves(&self) -> bool {
+        false
+    }
+
+    /// Whether accuracy rolls out of 256, so even 100% moves miss 1/256 of
+    /// the time.
+    /// Gen 1: true
+    fn has_one_in_256_miss(&self) -> bool {
+        false
+    }
+
+    /// Whether Special Attack and Special Defense are one Special stat, so a
+    /// stage change to either changes both.
+    /// Gen 1: true
+    fn shares_special_stat(&self) -> bool {
+        false
+    }
+
+    /// Whether Toxic's counter outlives the Toxic status: Haze's downgrade
+    /// to regular poison keeps the counter running, and Leech Seed drains
+    /// `counter / 16` and advances it too.
+    /// Gen 1: true
+    fn toxic_counter_persists(&self) -> bool {
+        false
+    }
+
+    /// Whether Haze also cures the target's major status and turns the
+    /// user's Toxic into regular poison.
+    /// Gen 1: true
+    fn haze_cures_status(&self) -> bool {
+        false
+    }
+
+    /// Leech Seed drain as a fraction of max HP, `1 / divisor`.
+    /// Gen 2+: 1/8, Gen 1: 1/16
+    fn leech_seed_divisor(&self) -> u16 {
+        8
+    }
+
+    /// Burn damage at the end of each turn as a fraction of max HP,
+    /// `1 / divisor`.
+    /// Gen 7+: 1/16, Gen 2-6: 1/8, Gen 1: 1/16
+    fn burn_divisor(&self) -> u16 {
+        if Self::GEN >= 7 {
+            16
+        } else {
+            8
+        }
+    }
+
+    /// Regular poison damage at the end of each turn as a fraction of max
+    /// HP, `1 / divisor`.
+    /// Gen 2+: 1/8, Gen 1: 1/16
+    fn poison_divisor(&self) -> u16 {
+        8
+    }
+
+    /// Struggle's recoil, given the damage it dealt and the user's max HP.
+    /// Gen 4+: 1/4 of max HP, Gen 2-3: 1/4 of the damage dealt,
+    /// Gen 1: 1/2 of the damage dealt
+    fn struggle_recoil(&self, damage_dealt: u16, max_hp: u16) -> u16 {
+        if Self::GEN <= 3 {
+            damage_dealt / 4
+        } else {
+            max_hp / 4
+        }
+    }
+
+    /// Odds of Protect and its variants succeeding when used on consecutive
+    /// turns, as `(factor, cap)`: the nth protection in a row succeeds with
+    /// chance `1 / min(factor^n, cap)`.
+    /// Gen 6+: 1/3 per use down to 1/729, Gen 5: 1/2 down to 1/256,
+    /// Gen 2-4: 1/2 down to 1/8
+    fn protect_chain_odds(&self) -> (u32, u32) {
+        match Self::GEN {
+            2..=4 => (2, 8),
+            5 => (2, 256),
+            _ => (3, 729),
+        }
+    }
+
     // ========================================================================
     // Type Chart
     // ========================================================================
@@ -292,10 +494,11 @@ pub trait GenMechanics: Copy + Clone + Send + Sync + 'static {
     /// - 8 = 2x
     /// - 16 = 4x
     ///
-    /// Default uses the standard type chart. Gen 1 has different interactions
-    /// (Ghost/Psychic, etc.) and should override.
+    /// Default uses this generation's generated type chart (the current
+    /// chart unless built with `data/mods`). Gens 1-5 override with their
+    /// differences for builds without it.
     fn type_effectiveness(&self, atk_type: Type, def_type1: Type, def_type2: Option<Type>) -> u8 {
-        crate::types::type_effectiveness(atk_type, def_type1, def_type2)
+        crate::types::type_effectiveness_in(self.generation(), atk_type, def_type1, def_type2)
     }
 
     // ========================================================================
@@ -449,6 +652,7 @@ impl GenMechanics for Generation {
                     has_stab: ctx.has_stab,
                     has_adaptability: ctx.has_adaptability,
                     is_tera_stab: ctx.is_tera_stab,
+                    is_stellar_boost: ctx.is_stellar_boost,
                     attacker_ability: ctx.attacker_ability,
                     defender_ability: ctx.defender_ability,
                 };
@@ -474,6 +678,7 @@ impl GenMechanics for Generation {
                     has_stab: ctx.has_stab,
                     has_adaptability: ctx.has_adaptability,
                     is_tera_stab: ctx.is_tera_stab,
+                    is_stellar_boost: ctx.is_stellar_boost,
                     attacker_ability: ctx.attacker_ability,
                     defender_ability: ctx.defender_ability,
                 };
@@ -499,6 +704,7 @@ impl GenMechanics for Generation {
                     has_stab: ctx.has_stab,
                     has_adaptability: ctx.has_adaptability,
                     is_tera_stab: ctx.is_tera_stab,
+                    is_stellar_boost: ctx.is_stellar_boost,
                     attacker_ability: ctx.attacker_ability,
                     defender_ability: ctx.defender_ability,
                 };
@@ -524,6 +730,7 @@ impl GenMechanics for Generation {
                     has_stab: ctx.has_stab,
                     has_adaptability: ctx.has_adaptability,
                     is_tera_stab: ctx.is_tera_stab,
+                    is_stellar_boost: ctx.is_stellar_boost,
                     attacker_ability: ctx.attacker_ability,
                     defender_ability: ctx.defender_ability,
                 };
@@ -549,6 +756,7 @@ impl GenMechanics for Generation {
                     has_stab: ctx.has_stab,
                     has_adaptability: ctx.has_adaptability,
                     is_tera_stab: ctx.is_tera_stab,
+                    is_stellar_boost: ctx.is_stellar_boost,
                     attacker_ability: ctx.attacker_ability,
                     defender_ability: ctx.defender_ability,
                 };
@@ -574,6 +782,7 @@ impl GenMechanics for Generation {
                     has_stab: ctx.has_stab,
                     has_adaptability: ctx.has_adaptability,
                     is_tera_stab: ctx.is_tera_stab,
+                    is_stellar_boost: ctx.is_stellar_boost,
                     attacker_ability: ctx.attacker_ability,
                     defender_ability: ctx.defender_ability,
                 };
@@ -599,6 +808,7 @@ impl GenMechanics for Generation {
                     has_stab: ctx.has_stab,
                     has_adaptability: ctx.has_adaptability,
                     is_tera_stab: ctx.is_tera_stab,
+                    is_stellar_boost: ctx.is_stellar_boost,
                     attacker_ability: ctx.attacker_ability,
                     defender_ability: ctx.defender_ability,
                 };
@@ -624,6 +834,7 @@ impl GenMechanics for Generation {
                     has_stab: ctx.has_stab,
                     has_adaptability: ctx.has_adaptability,
                     is_tera_stab: ctx.is_tera_stab,
+                    is_stellar_boost: ctx.is_stellar_boost,
                     attacker_ability: ctx.attacker_ability,
                     defender_ability: ctx.defender_ability,
                 };
@@ -649,6 +860,7 @@ impl GenMechanics for Generation {
                     has_stab: ctx.has_stab,
                     has_adaptability: ctx.has_adaptability,
                     is_tera_stab: ctx.is_tera_stab,
+                    is_stellar_boost: ctx.is_stellar_boost,
                     attacker_ability: ctx.attacker_ability,
                     defender_ability: ctx.defender_ability,
                 };
@@ -671,6 +883,20 @@ impl GenMechanics for Generation {
         }
     }
 
+    fn crit_chance(&self, stage: u8, base_speed: u8) -> (u32, u32) {
+        match self {
+            Generation::Gen1(g) => g.crit_chance(stage, base_speed),
+            Generation::Gen2(g) => g.crit_chance(stage, base_speed),
+            Generation::Gen3(g) => g.crit_chance(stage, base_speed),
+            Generation::Gen4(g) => g.crit_chance(stage, base_speed),
+            Generation::Gen5(g) => g.crit_chance(stage, base_speed),
+            Generation::Gen6(g) => g.crit_chance(stage, base_speed),
+            Generation::Gen7(g) => g.crit_chance(stage, base_speed),
+            Generation::Gen8(g) => g.crit_chance(stage, base_speed),
+            Generation::Gen9(g) => g.crit_chance(stage, base_speed),
+        }
+    }
+
     fn stab_multiplier(&self, has_adaptability: bool, is_tera_stab: bool) -> Modifier {
         match self {
             Generation::Gen1(g) => g.stab_multiplier(has_adaptability, is_tera_stab),
@@ -868,9 +1094,16 @@ impl GenMechanics for Generation {
         }
     }
 
-    fn max_move_power(&self, base_power: u16) -> u16 {
+    fn max_move_power(&self, base_power: u16, move_type: Type) -> u16 {
+        match self {
+            Generation::Gen8(g) => g.max_move_power(base_power, move_type),
+            _ => base_power,
+        }
+    }
+
+    fn z_move_power(&self, base_power: u16) -> u16 {
         match self {
-            Generation::Gen8(g) => g.max_move_power(base_power),
+            Generation::Gen7(g) => g.z_move_power(base_power),
             _ => base_power,
         }
     }
@@ -902,4 +1135,259 @@ impl GenMechanics for Generation {
             Generation::Gen9(g) => g.burn_modifier(),
         }
     }
+
+    fn freeze_thaw_chance(&self) -> (u32, u32) {
+        match self {
+            Generation::Gen1(g) => g.freeze_thaw_chance(),
+            Generation::Gen2(g) => g.freeze_thaw_chance(),
+            Generation::Gen3(g) => g.freeze_thaw_chance(),
+            Generation::Gen4(g) => g.freeze_thaw_chance(),
+            Generation::Gen5(g) => g.freeze_thaw_chance(),
+            Generation::Gen6(g) => g.freeze_thaw_chance(),
+            Generation::Gen7(g) => g.freeze_thaw_chance(),
+            Generation::Gen8(g) => g.freeze_thaw_chance(),
+            Generation::Gen9(g) => g.freeze_thaw_chance(),
+        }
+    }
+
+    fn sleep_turns(&self) -> (u8, u8) {
+        match self {
+            Generation::Gen1(g) => g.sleep_turns(),
+            Generation::Gen2(g) => g.sleep_turns(),
+            Generation::Gen3(g) => g.sleep_turns(),
+            Generation::Gen4(g) => g.sleep_turns(),
+            Generation::Gen5(g) => g.sleep_turns(),
+            Generation::Gen6(g) => g.sleep_turns(),
+            Generation::Gen7(g) => g.sleep_turns(),
+            Generation::Gen8(g) => g.sleep_turns(),
+            Generation::Gen9(g) => g.sleep_turns(),
+        }
+    }
+
+    fn moves_on_waking(&self) -> bool {
+        match self {
+            Generation::Gen1(g) => g.moves_on_waking(),
+            Generation::Gen2(g) => g.moves_on_waking(),
+            Generation::Gen3(g) => g.moves_on_waking(),
+            Generation::Gen4(g) => g.moves_on_waking(),
+            Generation::Gen5(g) => g.moves_on_waking(),
+            Generation::Gen6(g) => g.moves_on_waking(),
+            Generation::Gen7(g) => g.moves_on_waking(),
+            Generation::Gen8(g) => g.moves_on_waking(),
+            Generation::Gen9(g) => g.moves_on_waking(),
+        }
+    }
+
+    fn recharges_after_ko(&self) -> bool {
+        match self {
+            Generation::Gen1(g) => g.recharges_after_ko(),
+            Generation::Gen2(g) => g.recharges_after_ko(),
+            Generation::Gen3(g) => g.recharges_after_ko(),
+            Generation::Gen4(g) => g.recharges_after_ko(),
+            Generation::Gen5(g) => g.recharges_after_ko(),
+            Generation::Gen6(g) => g.recharges_after_ko(),
+            Generation::Gen7(g) => g.recharges_after_ko(),
+            Generation::Gen8(g) => g.recharges_after_ko(),
+            Generation::Gen9(g) => g.recharges_after_ko(),
+        }
+    }
+
+    fn partial_trap_turns(&self) -> (u8, u8) {
+        match self {
+            Generation::Gen1(g) => g.partial_trap_turns(),
+            Generation::Gen2(g) => g.partial_trap_turns(),
+            Generation::Gen3(g) => g.partial_trap_turns(),
+            Generation::Gen4(g) => g.partial_trap_turns(),
+            Generation::Gen5(g) => g.partial_trap_turns(),
+            Generation::Gen6(g) => g.partial_trap_turns(),
+            Generation::Gen7(g) => g.partial_trap_turns(),
+            Generation::Gen8(g) => g.partial_trap_turns(),
+            Generation::Gen9(g) => g.partial_trap_turns(),
+        }
+    }
+
+    fn partial_trap_divisor(&self) -> u16 {
+        match self {
+            Generation::Gen1(g) => g.partial_trap_divisor(),
+            Generation::Gen2(g) => g.partial_trap_divisor(),
+            Generation::Gen3(g) => g.partial_trap_divisor(),
+            Generation::Gen4(g) => g.partial_trap_divisor(),
+            Generation::Gen5(g) => g.partial_trap_divisor(),
+            Generation::Gen6(g) => g.partial_trap_divisor(),
+            Generation::Gen7(g) => g.partial_trap_divisor(),
+            Generation::Gen8(g) => g.partial_trap_divisor(),
+            Generation::Gen9(g) => g.partial_trap_divisor(),
+        }
+    }
+
+    fn partial_trap_prevents_moves(&self) -> bool {
+        match self {
+            Generation::Gen1(g) => g.partial_trap_prevents_moves(),
+            Generation::Gen2(g) => g.partial_trap_prevents_moves(),
+            Generation::Gen3(g) => g.partial_trap_prevents_moves(),
+            Generation::Gen4(g) => g.partial_trap_prevents_moves(),
+            Generation::Gen5(g) => g.partial_trap_prevents_moves(),
+            Generation::Gen6(g) => g.partial_trap_prevents_moves(),
+            Generation::Gen7(g) => g.partial_trap_prevents_moves(),
+            Generation::Gen8(g) => g.partial_trap_prevents_moves(),
+            Generation::Gen9(g) => g.partial_trap_prevents_moves(),
+        }
+    }
+
+    fn has_one_in_256_miss(&self) -> bool {
+        match self {
+            Generation::Gen1(g) => g.has_one_in_256_miss(),
+            Generation::Gen2(g) => g.has_one_in_256_miss(),
+            Generation::Gen3(g) => g.has_one_in_256_miss(),
+            Generation::Gen4(g) => g.has_one_in_256_miss(),
+            Generation::Gen5(g) => g.has_one_in_256_miss(),
+            Generation::Gen6(g) => g.has_one_in_256_miss(),
+            Generation::Gen7(g) => g.has_one_in_256_miss(),
+            Generation::Gen8(g) => g.has_one_in_256_miss(),
+            Generation::Gen9(g) => g.has_one_in_256_miss(),
+        }
+    }
+
+    fn shares_special_stat(&self) -> bool {
+        match self {
+            Generation::Gen1(g) => g.shares_special_stat(),
+            Generation::Gen2(g) => g.shares_special_stat(),
+            Generation::Gen3(g) => g.shares_special_stat(),
+            Generation::Gen4(g) => g.shares_special_stat(),
+            Generation::Gen5(g) => g.shares_special_stat(),
+            Generation::Gen6(g) => g.shares_special_stat(),
+            Generation::Gen7(g) => g.shares_special_stat(),
+            Generation::Gen8(g) => g.shares_special_stat(),
+            Generation::Gen9(g) => g.shares_special_stat(),
+        }
+    }
+
+    fn toxic_counter_persists(&self) -> bool {
+        match self {
+            Generation::Gen1(g) => g.toxic_counter_persists(),
+            Generation::Gen2(g) => g.toxic_counter_persists(),
+            Generation::Gen3(g) => g.toxic_counter_persists(),
+            Generation::Gen4(g) => g.toxic_counter_persists(),
+            Generation::Gen5(g) => g.toxic_counter_persists(),
+            Generation::Gen6(g) => g.toxic_counter_persists(),
+            Generation::Gen7(g) => g.toxic_counter_persists(),
+            Generation::Gen8(g) => g.toxic_counter_persists(),
+            Generation::Gen9(g) => g.toxic_counter_persists(),
+        }
+    }
+
+    fn haze_cures_status(&self) -> bool {
+        match self {
+            Generation::Gen1(g) => g.haze_cures_status(),
+            Generation::Gen2(g) => g.haze_cures_status(),
+            Generation::Gen3(g) => g.haze_cures_status(),
+            Generation::Gen4(g) => g.haze_cures_status(),
+            Generation::Gen5(g) => g.haze_cures_status(),
+            Generation::Gen6(g) => g.haze_cures_status(),
+            Generation::Gen7(g) => g.haze_cures_status(),
+            Generation::Gen8(g) => g.haze_cures_status(),
+            Generation::Gen9(g) => g.haze_cures_status(),
+        }
+    }
+
+    fn leech_seed_divisor(&self) -> u16 {
+        match self {
+            Generation::Gen1(g) => g.leech_seed_divisor(),
+            Generation::Gen2(g) => g.leech_seed_divisor(),
+            Generation::Gen3(g) => g.leech_seed_divisor(),
+            Generation::Gen4(g) => g.leech_seed_divisor(),
+            Generation::Gen5(g) => g.leech_seed_divisor(),
+            Generation::Gen6(g) => g.leech_seed_divisor(),
+            Generation::Gen7(g) => g.leech_seed_divisor(),
+            Generation::Gen8(g) => g.leech_seed_divisor(),
+            Generation::Gen9(g) => g.leech_seed_divisor(),
+        }
+    }
+
+    fn burn_divisor(&self) -> u16 {
+        match self {
+            Generation::Gen1(g) => g.burn_divisor(),
+            Generation::Gen2(g) => g.burn_divisor(),
+            Generation::Gen3(g) => g.burn_divisor(),
+            Generation::Gen4(g) => g.burn_divisor(),
+            Generation::Gen5(g) => g.burn_divisor(),
+            Generation::Gen6(g) => g.burn_divisor(),
+            Generation::Gen7(g) => g.burn_divisor(),
+            Generation::Gen8(g) => g.burn_divisor(),
+            Generation::Gen9(g) => g.burn_divisor(),
+        }
+    }
+
+    fn poison_divisor(&self) -> u16 {
+        match self {
+            Generation::Gen1(g) => g.poison_divisor(),
+            Generation::Gen2(g) => g.poison_divisor(),
+            Generation::Gen3(g) => g.poison_divisor(),
+            Generation::Gen4(g) => g.poison_divisor(),
+            Generation::Gen5(g) => g.poison_divisor(),
+            Generation::Gen6(g) => g.poison_divisor(),
+            Generation::Gen7(g) => g.poison_divisor(),
+            Generation::Gen8(g) => g.poison_divisor(),
+            Generation::Gen9(g) => g.poison_divisor(),
+        }
+    }
+
+    fn struggle_recoil(&self, damage_dealt: u16, max_hp: u16) -> u16 {
+        match self {
+            Generation::Gen1(g) => g.struggle_recoil(damage_dealt, max_hp),
+            Generation::Gen2(g) => g.struggle_recoil(damage_dealt, max_hp),
+            Generation::Gen3(g) => g.struggle_recoil(damage_dealt, max_hp),
+            Generation::Gen4(g) => g.struggle_recoil(damage_dealt, max_hp),
+            Generation::Gen5(g) => g.struggle_recoil(damage_dealt, max_hp),
+            Generation::Gen6(g) => g.struggle_recoil(damage_dealt, max_hp),
+            Generation::Gen7(g) => g.struggle_recoil(damage_dealt, max_hp),
+            Generation::Gen8(g) => g.struggle_recoil(damage_dealt, max_hp),
+            Generation::Gen9(g) => g.struggle_recoil(damage_dealt, max_hp),
+        }
+    }
+
+    fn protect_chain_odds(&self) -> (u32, u32) {
+        match self {
+            Generation::Gen1(g) => g.protect_chain_odds(),
+            Generation::Gen2(g) => g.protect_chain_odds(),
+            Generation::Gen3(g) => g.protect_chain_odds(),
+            Generation::Gen4(g) => g.protect_chain_odds(),
+            Generation::Gen5(g) => g.protect_chain_odds(),
+            Generation::Gen6(g) => g.protect_chain_odds(),
+            Generation::Gen7(g) => g.protect_chain_odds(),
+            Generation::Gen8(g) => g.protect_chain_odds(),
+            Generation::Gen9(g) => g.protect_chain_odds(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_crit_chance_tables() {
+        // Gen 2-5 share CRIT_DENOMINATORS_GEN2_5; stages past the end clamp
+        assert_eq!(Gen2.crit_chance(0, 100), (1, 16));
+        assert_eq!(Gen5.crit_chance(3, 100), (1, 3));
+        assert_eq!(Gen3.crit_chance(9, 100), (1, 2));
+
+        // Gen 6 and Gen 7+ use their own rows of the default
+        assert_eq!(Gen6.crit_chance(0, 100), (1, 16));
+        assert_eq!(Gen6.crit_chance(3, 100), (1, 1));
+        assert_eq!(Gen9.crit_chance(0, 100), (1, 24));
+        assert_eq!(Gen9.crit_chance(2, 100), (1, 2));
+    }
+
+    #[test]
+    fn test_residual_and_recoil_fractions() {
+        assert_eq!((Gen1.burn_divisor(), Gen1.poison_divisor()), (16, 16));
+        assert_eq!((Gen4.burn_divisor(), Gen4.poison_divisor()), (8, 8));
+        assert_eq!((Gen9.burn_divisor(), Gen9.poison_divisor()), (16, 8));
+
+        // Struggle dealing 100 damage from a 300 HP user
+        assert_eq!(Gen1.struggle_recoil(100, 300), 50);
+        assert_eq!(Gen3.struggle_recoil(100, 300), 25);
+        assert_eq!(Gen4.struggle_recoil(100, 300), 75);
+    }
 }
@@ -30,6 +30,7 @@ mod ate_tests;
 mod conditional_moves_tests;
 mod context;
 pub mod effectiveness;
+mod extra_hits;
 mod formula;
 pub mod generations;
 #[cfg(test)]
@@ -42,8 +43,11 @@ mod scrappy_tests;
 mod special_moves;
 #[cfg(test)]
 mod special_moves_tests;
+#[cfg(test)]
+mod tera_tests;
 
 pub use context::DamageContext;
+pub use extra_hits::{ExtraHits, MAX_EXTRA_HITS};
 pub use formula::{apply_modifier, chain_mods, get_base_damage, of16, of32, pokeround};
 pub use generations::{Gen9, GenMechanics, Generation};
 pub use modifier::Modifier;
@@ -55,7 +59,7 @@ use crate::state::BattleState;
 
 /// Calculate the effective priority of a move.
 pub fn calculate_priority(state: &BattleState, attacker: usize, move_id: MoveId) -> i8 {
-    let move_data = move_id.data();
+    let move_data = move_id.data_in(state.generation);
     let mut priority = move_data.priority;
 
     // Ability hooks
@@ -93,7 +97,7 @@ pub struct DamageResult {
 
     /// Rolls for additional hits (if any).
     /// Used for abilities like Parental Bond.
-    pub multi_hit_rolls: Option<Vec<[u16; 16]>>,
+    pub multi_hit_rolls: Option<ExtraHits<[u16; 16]>>,
 }
 
 impl DamageResult {
@@ -160,7 +164,7 @@ pub fn calculate_damage_with_overrides<G: GenMechanics>(
     is_crit: bool,
     base_power_override: Option<u16>,
 ) -> DamageResult {
-    let move_data = move_id.data();
+    let move_data = move_id.data_in(gen.generation());
 
     // Status moves deal no damage
     if move_data.category == crate::moves::MoveCategory::Status {
@@ -182,11 +186,7 @@ pub fn calculate_damage_with_overrides<G: GenMechanics>(
             if let Some(hook) = hooks.on_modify_multi_hit {
                 if let Some(modifiers) = hook(state, attacker, defender, move_id) {
                     // For fixed damage, we ignore the modifier value and just add duplicate hits
-                    let mut extra_hits = Vec::new();
-                    for _ in modifiers {
-                        extra_hits.push([fixed_damage; 16]);
-                    }
-                    multi_hit_rolls = Some(extra_hits);
+                    multi_hit_rolls = Some(modifiers.iter().map(|_| [fixed_damage; 16]).collect());
                 }
             }
         }
@@ -233,6 +233,77 @@ pub fn calculate_damage_with_overrides<G: GenMechanics>(
     gen.calculate_damage(&ctx)
 }
 
+/// Calculate damage for the Max Move a Dynamaxed attacker turns `base_move` into.
+///
+/// The Max Move keeps the base move's category and takes its power from
+/// `GenMechanics::max_move_power`; a Gigantamax attacker may use its G-Max
+/// Move instead. Status moves (Max Guard) deal no damage.
+pub fn calculate_max_move_damage<G: GenMechanics>(
+    gen: G,
+    state: &BattleState,
+    attacker: usize,
+    defender: usize,
+    base_move: MoveId,
+    is_crit: bool,
+) -> DamageResult {
+    let base_data = base_move.data_in(gen.generation());
+    if base_data.category == crate::moves::MoveCategory::Status {
+        return DamageResult::zero();
+    }
+    let max_move = crate::moves::max::max_move_for(state, attacker, defender, base_move);
+    if max_move == MoveId::Struggle {
+        return calculate_damage(gen, state, attacker, defender, max_move, is_crit);
+    }
+
+    // Weight-, HP- and fixed-damage moves list 0 BP; most of them become
+    // 130 BP Max Moves in Showdown's data.
+    let base_power = if base_data.power == 0 { 130 } else { base_data.power };
+    let mut ctx = DamageContext::new(gen, state, attacker, defender, max_move, is_crit);
+    // G-Max Drum Solo, Fireball and Hydrosnipe always have 160 BP; the
+    // other Max Moves list 10 and scale with the base move.
+    let max_data = max_move.data();
+    ctx.base_power = if max_data.power > 10 {
+        max_data.power
+    } else {
+        gen.max_move_power(base_power, max_data.primary_type)
+    };
+    ctx.category = base_data.category;
+    gen.calculate_damage(&ctx)
+}
+
+/// Calculate damage for `base_move` used as a Z-Move.
+///
+/// Moves the attacker's Z-Crystal has a signature Z-Move for use its data;
+/// anything else becomes the Z-Move of its type, keeping the base move's
+/// category, with the base move's Z-Power or `GenMechanics::z_move_power`.
+/// The crystal isn't required, so calculators can preview any Z-Move;
+/// battles check it with `z_move_for`. Status moves deal no damage.
+pub fn calculate_z_move_damage<G: GenMechanics>(
+    gen: G,
+    state: &BattleState,
+    attacker: usize,
+    defender: usize,
+    base_move: MoveId,
+    is_crit: bool,
+) -> DamageResult {
+    let base_data = base_move.data_in(gen.generation());
+    if base_data.category == crate::moves::MoveCategory::Status {
+        return DamageResult::zero();
+    }
+    if let Some(z_move) = crate::moves::z::signature_z_move(state, attacker, base_move) {
+        return calculate_damage(gen, state, attacker, defender, z_move, is_crit);
+    }
+
+    let z_move = crate::moves::z::type_z_move(base_data.primary_type);
+    let mut ctx = DamageContext::new(gen, state, attacker, defender, z_move, is_crit);
+    ctx.base_power = match base_data.z_power {
+        Some(crate::moves::ZPower::BasePower(power)) => power,
+        _ => gen.z_move_power(base_data.power),
+    };
+    ctx.category = base_data.category;
+    gen.calculate_damage(&ctx)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
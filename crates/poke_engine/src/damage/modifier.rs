@@ -11,6 +11,10 @@ impl Modifier {
     /// 0.5x modifier (2048).
     pub const HALF: Self = Self(2048);
 
+    /// 0.25x modifier (1024).
+    /// Z-Moves and Max Moves hitting through Protect.
+    pub const QUARTER: Self = Self(1024);
+
     /// 2.0x modifier (8192).
     pub const DOUBLE: Self = Self(8192);
 
@@ -36,6 +40,15 @@ impl Modifier {
     /// Filter/Solid Rock/Prism Armor (0.75x).
     pub const FILTER: Self = Self(3072);
 
+    /// Damage taken while drowsy (5461, approx 4/3, Legends: Arceus).
+    pub const DROWSY: Self = Self(5461);
+
+    /// Strong Style base power (1.2x, Legends: Arceus).
+    pub const STRONG_STYLE: Self = Self(4915);
+
+    /// Agile Style base power (0.75x, Legends: Arceus).
+    pub const AGILE_STYLE: Self = Self(3072);
+
     /// Create a new modifier from a raw u16 value.
     pub const fn new(val: u16) -> Self {
         Self(val)
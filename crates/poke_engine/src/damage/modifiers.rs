@@ -11,7 +11,7 @@ use crate::abilities::{AbilityId, ABILITY_REGISTRY};
 use crate::items::{ItemId, ITEM_REGISTRY};
 use crate::modifier;
 use crate::moves::{MoveCategory, MoveFlags, MoveId, MOVE_REGISTRY};
-use crate::state::{BattleState, Status};
+use crate::state::{BattleState, Status, Volatiles};
 
 // ============================================================================
 // Stat Indices
@@ -50,6 +50,13 @@ fn has_mold_breaker(ability: AbilityId) -> bool {
         .contains(crate::abilities::AbilityFlags::MOLD_BREAKER)
 }
 
+/// Check if the defender's ability is bypassed, by Mold Breaker or by a
+/// move that ignores abilities (Sunsteel Strike, G-Max Drum Solo, ...).
+fn ignores_defender_ability<G: GenMechanics>(ctx: &DamageContext<'_, G>) -> bool {
+    has_mold_breaker(ctx.attacker_ability)
+        || ctx.move_data.flags.contains(MoveFlags::IGNORES_ABILITY)
+}
+
 // ============================================================================
 // Item Hook Helpers
 // ============================================================================
@@ -151,10 +158,10 @@ fn call_attack_hook<G: GenMechanics>(ctx: &DamageContext<'_, G>, attack: u16) ->
 }
 
 /// Call the OnModifyDefense hook for the defender's ability, if registered.
-/// Bypassed by Mold Breaker, Teravolt, and Turboblaze.
+/// Bypassed by Mold Breaker, Teravolt, Turboblaze and ability-ignoring moves.
 fn call_defense_hook<G: GenMechanics>(ctx: &DamageContext<'_, G>, defense: u16) -> u16 {
     // Mold Breaker bypasses defender's defensive ability hooks
-    if has_mold_breaker(ctx.attacker_ability) {
+    if ignores_defender_ability(ctx) {
         return defense;
     }
 
@@ -214,8 +221,8 @@ fn apply_final_mods<G: GenMechanics>(
     }
 
     // Defender's ability (Multiscale, Filter, Fluffy)
-    // Bypassed by Mold Breaker, Teravolt, and Turboblaze
-    if !has_mold_breaker(ctx.attacker_ability) {
+    // Bypassed by Mold Breaker, Teravolt, Turboblaze and ability-ignoring moves
+    if !ignores_defender_ability(ctx) {
         if let Some(hooks) = defender_hooks {
             if let Some(hook) = hooks.on_defender_final_mod {
                 damage = hook(
@@ -232,6 +239,12 @@ fn apply_final_mods<G: GenMechanics>(
         }
     }
 
+    // A drowsy target (Legends: Arceus) takes extra damage from every hit
+    let drowsy = ctx.state.status[ctx.defender].contains(Status::DROWSY);
+    if drowsy && ctx.state.gimmicks.legends_arceus {
+        damage = apply_modifier(damage, Modifier::DROWSY);
+    }
+
     damage
 }
 
@@ -245,6 +258,22 @@ pub fn compute_base_power<G: GenMechanics>(ctx: &mut DamageContext<'_, G>) {
     // This replaces the old inline logic for Grass Knot, Eruption, Facade, etc.
     let mut bp = super::special_moves::modify_base_power(ctx);
 
+    // Tera Type moves weaker than 60 BP are raised to 60, before any
+    // BP modifiers. Priority and multi-hit moves are excluded, as are
+    // variable-power moves (listed at 0 or 150 BP). Tera Stellar raises
+    // any type that still has its one-time boost.
+    let data = ctx.move_data;
+    if bp < 60
+        && ((ctx.state.terastallized[ctx.attacker]
+            && ctx.move_type == ctx.state.tera_type[ctx.attacker])
+            || ctx.is_stellar_boost)
+        && data.priority <= 0
+        && data.multihit.1 <= 1
+        && !matches!(data.power, 0 | 150)
+    {
+        bp = 60;
+    }
+
     // ========================================================================
     // Move-based BP modifiers via hook system
     // ========================================================================
@@ -270,6 +299,19 @@ pub fn compute_base_power<G: GenMechanics>(ctx: &mut DamageContext<'_, G>) {
 
     bp = call_item_base_power_hook(ctx, bp as u16) as u32;
 
+    // ========================================================================
+    // Legends: Arceus Styles
+    // ========================================================================
+    // An approximation: the games change each move's power by its own
+    // amount, where we use a flat 1.2x / 0.75x. Strong Style's extra PP cost
+    // and Agile Style's delay to the user's next turn are not modelled.
+    let volatiles = ctx.state.volatiles[ctx.attacker];
+    if volatiles.contains(Volatiles::STRONG_STYLE) {
+        bp = apply_modifier(bp, Modifier::STRONG_STYLE);
+    } else if volatiles.contains(Volatiles::AGILE_STYLE) {
+        bp = apply_modifier(bp, Modifier::AGILE_STYLE);
+    }
+
     // ========================================================================
     // Terrain-based BP modifiers (Gen 6+)
     // ========================================================================
@@ -551,11 +593,6 @@ pub fn apply_screen_mod_early<G: GenMechanics>(ctx: &DamageContext<'_, G>, base_
         return;
     }
 
-    #[cfg(debug_assertions)]
-    if ctx.move_id.data().name.contains("Raging") {
-        eprintln!("DEBUG apply_screen_mod_early: Gen 3-4 path for Raging Bull!");
-    }
-
     if ctx.is_crit || is_screen_breaker(ctx.move_id) {
         return;
     }
@@ -645,15 +682,26 @@ pub fn compute_final_damage<G: GenMechanics>(
         .and_then(|a| a.as_ref());
 
     // Prepare pipeline inputs
-    let stab_mod = if ctx.has_stab {
+    // Tera Stellar: 2x on the original types (flagged as Tera STAB),
+    // 1.2x on any other type.
+    let stab_mod = if ctx.is_stellar_boost && !ctx.is_tera_stab {
+        Modifier::new(4915)
+    } else if ctx.has_stab {
         ctx.gen
             .stab_multiplier(ctx.has_adaptability, ctx.is_tera_stab)
     } else {
         Modifier::ONE
     };
 
+    // Frostbite is burn's Special counterpart, only in Legends: Arceus formats
     let is_burned = ctx.is_burned();
-    let ignore_burn = should_ignore_status_damage_reduction(ctx, Status::BURN);
+    let is_frostbitten = ctx.state.gimmicks.legends_arceus
+        && ctx.attacker_status().contains(Status::FROSTBITE);
+    let halving_status = match ctx.category {
+        MoveCategory::Special if is_frostbitten => Status::FROSTBITE,
+        _ => Status::BURN,
+    };
+    let ignore_burn = should_ignore_status_damage_reduction(ctx, halving_status);
 
     let is_sb = is_screen_breaker(ctx.move_id);
     let has_physical_screen = ctx.has_screen(ctx.category == MoveCategory::Physical);
@@ -680,6 +728,7 @@ pub fn compute_final_damage<G: GenMechanics>(
         stab_mod,
         ctx.is_crit,
         is_burned,
+        is_frostbitten,
         ignore_burn,
         ctx.category,
         has_screen,
@@ -1527,4 +1576,58 @@ mod tests {
             min_damage
         );
     }
+
+    #[test]
+    fn test_frostbite_and_drowsy_modifiers() {
+        use crate::damage::{DamageContext, Gen9};
+        use crate::moves::MoveId;
+        use crate::state::{BattleState, Status};
+        use crate::types::Type;
+
+        let mut state = BattleState::new();
+        state.types[0] = [Type::Normal, Type::Normal];
+        state.types[6] = [Type::Normal, Type::Normal];
+
+        // Outside Legends: Arceus formats neither status does anything
+        state.status[0] = Status::FROSTBITE;
+        let special = DamageContext::new(Gen9, &state, 0, 6, MoveId::Hypervoice, false);
+        assert_eq!(compute_final_damage(&special, 100)[15], 150);
+
+        // Frostbite halves Special damage only (100 -> 150 with STAB)
+        state.gimmicks.legends_arceus = true;
+        let special = DamageContext::new(Gen9, &state, 0, 6, MoveId::Hypervoice, false);
+        assert_eq!(compute_final_damage(&special, 100)[15], 75);
+        let physical = DamageContext::new(Gen9, &state, 0, 6, MoveId::Tackle, false);
+        assert_eq!(compute_final_damage(&physical, 100)[15], 150);
+
+        // A drowsy target takes ~4/3 damage
+        state.status[0] = Status::NONE;
+        state.status[6] = Status::DROWSY;
+        let ctx = DamageContext::new(Gen9, &state, 0, 6, MoveId::Tackle, false);
+        assert_eq!(compute_final_damage(&ctx, 100)[15], 200);
+
+        // Burn still only halves Physical damage
+        state.status[6] = Status::NONE;
+        state.status[0] = Status::BURN;
+        let special = DamageContext::new(Gen9, &state, 0, 6, MoveId::Hypervoice, false);
+        assert_eq!(compute_final_damage(&special, 100)[15], 150);
+    }
+
+    #[test]
+    fn test_style_base_power() {
+        use crate::damage::{DamageContext, Gen9};
+        use crate::moves::MoveId;
+        use crate::state::BattleState;
+
+        let mut state = BattleState::new();
+        let mut base_power = |volatiles| {
+            state.volatiles[0] = volatiles;
+            let mut ctx = DamageContext::new(Gen9, &state, 0, 6, MoveId::Tackle, false);
+            compute_base_power(&mut ctx);
+            ctx.base_power
+        };
+        assert_eq!(base_power(Volatiles::empty()), 40);
+        assert_eq!(base_power(Volatiles::STRONG_STYLE), 48);
+        assert_eq!(base_power(Volatiles::AGILE_STYLE), 30);
+    }
 }
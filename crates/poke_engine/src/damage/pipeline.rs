@@ -60,7 +60,8 @@ pub trait DamagePipeline: Send + Sync {
     /// * `stab_mod` - The STAB modifier to use (1.5x, 2.0x, etc.)
     /// * `is_crit` - Whether this is a critical hit
     /// * `is_burned` - Whether attacker is burned
-    /// * `ignore_burn` - Whether burn damage reduction should be skipped (Guts/Facade)
+    /// * `is_frostbitten` - Whether attacker is frostbitten (Legends: Arceus formats only)
+    /// * `ignore_burn` - Whether burn/frostbite damage reduction is skipped (Guts/Facade)
     /// * `category` - Physical or Special
     /// * `has_screen` - Whether Reflect/Light Screen/Aurora Veil is active
     /// * `screen_mod` - The screen damage reduction modifier
@@ -79,6 +80,7 @@ pub trait DamagePipeline: Send + Sync {
         stab_mod: Modifier,
         is_crit: bool,
         is_burned: bool,
+        is_frostbitten: bool,
         ignore_burn: bool,
         category: MoveCategory,
         has_screen: bool,
@@ -134,6 +136,21 @@ pub fn apply_burn(damage: u32, is_burned: bool, ignore_burn: bool, category: Mov
     }
 }
 
+/// Apply frostbite damage reduction (halves special damage, Legends: Arceus).
+#[inline]
+pub fn apply_frostbite(
+    damage: u32,
+    is_frostbitten: bool,
+    ignore_burn: bool,
+    category: MoveCategory,
+) -> u32 {
+    if is_frostbitten && category == MoveCategory::Special && !ignore_burn {
+        damage / 2
+    } else {
+        damage
+    }
+}
+
 /// Apply screen damage reduction.
 #[inline]
 pub fn apply_screen(damage: u32, is_crit: bool, has_screen: bool, screen_mod: Modifier) -> u32 {
@@ -170,6 +187,7 @@ impl DamagePipeline for Gen5PlusPipeline {
         stab_mod: Modifier,
         is_crit: bool,
         is_burned: bool,
+        is_frostbitten: bool,
         ignore_burn: bool,
         category: MoveCategory,
         has_screen: bool,
@@ -191,8 +209,9 @@ impl DamagePipeline for Gen5PlusPipeline {
             // 3. Type effectiveness
             damage = apply_effectiveness(damage, effectiveness);
 
-            // 4. Burn (applies in final phase for Gen 5+)
+            // 4. Burn (applies in final phase for Gen 5+), and frostbite
             damage = apply_burn(damage, is_burned, ignore_burn, category);
+            damage = apply_frostbite(damage, is_frostbitten, ignore_burn, category);
 
             // 5. Screen
             damage = apply_screen(damage, is_crit, has_screen, screen_mod);
@@ -231,6 +250,7 @@ impl DamagePipeline for Gen4Pipeline {
         stab_mod: Modifier,
         _is_crit: bool,
         _is_burned: bool,
+        _is_frostbitten: bool,
         _ignore_burn: bool,
         _category: MoveCategory,
         _has_screen: bool,
@@ -287,6 +307,7 @@ impl DamagePipeline for Gen3Pipeline {
         stab_mod: Modifier,
         _is_crit: bool,
         _is_burned: bool,
+        _is_frostbitten: bool,
         _ignore_burn: bool,
         _category: MoveCategory,
         _has_screen: bool,
@@ -379,6 +400,7 @@ mod tests {
             Modifier::ONE,
             false, // not crit
             false, // not burned
+            false, // not frostbitten
             false, // don't ignore burn
             MoveCategory::Physical,
             false, // no screen
@@ -405,6 +427,7 @@ mod tests {
             Modifier::ONE,
             false, // not crit
             false, // not burned
+            false, // not frostbitten
             false, // don't ignore burn
             MoveCategory::Physical,
             false, // no screen
@@ -425,13 +448,13 @@ mod tests {
 
         // Gen 3 with STAB
         let gen3_rolls = Gen3Pipeline.compute_final_damage(
-            100, 4, true, stab_15x, false, false, false,
+            100, 4, true, stab_15x, false, false, false, false,
             MoveCategory::Physical, false, Modifier::ONE, &identity, &identity,
         );
 
         // Gen 5 with STAB
         let gen5_rolls = Gen5PlusPipeline.compute_final_damage(
-            100, 4, true, stab_15x, false, false, false,
+            100, 4, true, stab_15x, false, false, false, false,
             MoveCategory::Physical, false, Modifier::ONE, &identity, &identity,
         );
 
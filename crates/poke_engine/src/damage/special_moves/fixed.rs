@@ -15,7 +15,7 @@ pub fn get_fixed_damage(
     let level = state.level[attacker] as u16;
     
     // Check for immunity first for relevant moves
-    let defender_types = state.types[defender];
+    let defender_types = state.defensive_types(defender);
     
     match move_name {
         // ====================================================================
@@ -5,7 +5,8 @@ mod tests;
 
 use crate::damage::context::DamageContext;
 use crate::damage::generations::{GenMechanics, Weather};
-use crate::moves::MoveId;
+use crate::moves::{MoveCategory, MoveId};
+use crate::species::SpeciesId;
 use crate::types::Type;
 
 pub use fixed::get_fixed_damage;
@@ -27,6 +28,17 @@ pub fn apply_special_moves<G: GenMechanics>(ctx: &mut DamageContext<'_, G>) {
             // Note: Recoil is handled in battle loop
         }
 
+        // Tera Blast and Tera Starstorm (as Terapagos-Stellar) become physical
+        // when the user's Attack is higher than its Special Attack, boosts
+        // included. Tera Starstorm also hits both foes.
+        MoveId::Terablast if ctx.state.terastallized[ctx.attacker] => {
+            ctx.category = higher_attacking_category(ctx);
+        }
+        MoveId::Terastarstorm if ctx.state.species[ctx.attacker] == SpeciesId::TERAPAGOSSTELLAR => {
+            ctx.category = higher_attacking_category(ctx);
+            ctx.is_spread = ctx.state.is_doubles();
+        }
+
         // Weather Ball migrated to MoveHooks (OnModifyType, OnModifyBasePower)
 
         // Flying Press/Thousand Arrows/Freeze-Dry migrated to OnModifyEffectiveness
@@ -37,3 +49,11 @@ pub fn apply_special_moves<G: GenMechanics>(ctx: &mut DamageContext<'_, G>) {
         _ => {}
     }
 }
+
+fn higher_attacking_category<G: GenMechanics>(ctx: &DamageContext<'_, G>) -> MoveCategory {
+    if ctx.state.effective_stat(ctx.attacker, 1) > ctx.state.effective_stat(ctx.attacker, 3) {
+        MoveCategory::Physical
+    } else {
+        MoveCategory::Special
+    }
+}
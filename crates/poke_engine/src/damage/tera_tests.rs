@@ -0,0 +1,132 @@
+use crate::damage::special_moves::apply_special_moves;
+use crate::damage::{calculate_damage, compute_base_power, DamageContext, Gen9};
+use crate::entities::PokemonConfig;
+use crate::moves::{MoveCategory, MoveId};
+use crate::state::BattleState;
+use crate::types::Type;
+
+/// Garchomp (Dragon/Ground) with the given Tera Type against Blissey.
+fn setup(tera_type: Type, terastallized: bool) -> BattleState {
+    let mut state = BattleState::new();
+    PokemonConfig::from_str("garchomp")
+        .unwrap()
+        .level(50)
+        .tera_type(tera_type)
+        .spawn(&mut state, 0, 0);
+    PokemonConfig::from_str("blissey")
+        .unwrap()
+        .level(50)
+        .spawn(&mut state, 1, 0);
+    if terastallized {
+        state.terastallize(0);
+    }
+    state
+}
+
+#[test]
+fn test_tera_type_replaces_defensive_types() {
+    // Ice Beam is 4x against Dragon/Ground but resisted by Tera Steel.
+    let mut state = setup(Type::Steel, false);
+    assert_eq!(
+        calculate_damage(Gen9, &state, 6, 0, MoveId::Icebeam, false).effectiveness,
+        16
+    );
+    state.terastallize(0);
+    assert_eq!(
+        calculate_damage(Gen9, &state, 6, 0, MoveId::Icebeam, false).effectiveness,
+        2
+    );
+}
+
+#[test]
+fn test_tera_stab() {
+    // Tera Type matching an original type: 2x Tera STAB.
+    let state = setup(Type::Dragon, true);
+    let ctx = DamageContext::new(Gen9, &state, 0, 6, MoveId::Dragonclaw, false);
+    assert!(ctx.has_stab && ctx.is_tera_stab);
+
+    // New Tera Type: regular 1.5x STAB; original types keep theirs.
+    let state = setup(Type::Fire, true);
+    let ctx = DamageContext::new(Gen9, &state, 0, 6, MoveId::Firefang, false);
+    assert!(ctx.has_stab && !ctx.is_tera_stab);
+    let ctx = DamageContext::new(Gen9, &state, 0, 6, MoveId::Earthquake, false);
+    assert!(ctx.has_stab && !ctx.is_tera_stab);
+
+    // Not Terastallized yet: the Tera Type grants nothing.
+    let state = setup(Type::Fire, false);
+    let ctx = DamageContext::new(Gen9, &state, 0, 6, MoveId::Firefang, false);
+    assert!(!ctx.has_stab);
+}
+
+#[test]
+fn test_tera_stab_raises_weak_moves_to_60_bp() {
+    let state = setup(Type::Ground, true);
+    let mut ctx = DamageContext::new(Gen9, &state, 0, 6, MoveId::Mudslap, false);
+    compute_base_power(&mut ctx);
+    assert_eq!(ctx.base_power, 60);
+
+    // Priority moves keep their power.
+    let state = setup(Type::Ice, true);
+    let mut ctx = DamageContext::new(Gen9, &state, 0, 6, MoveId::Iceshard, false);
+    compute_base_power(&mut ctx);
+    assert_eq!(ctx.base_power, 40);
+}
+
+#[test]
+fn test_stellar_boost_once_per_type() {
+    let mut state = setup(Type::Stellar, true);
+
+    // Original type: 2x on first use, regular STAB afterwards.
+    let ctx = DamageContext::new(Gen9, &state, 0, 6, MoveId::Earthquake, false);
+    assert!(ctx.is_stellar_boost && ctx.is_tera_stab);
+    state.consume_stellar_boost(0, Type::Ground);
+    let ctx = DamageContext::new(Gen9, &state, 0, 6, MoveId::Earthquake, false);
+    assert!(ctx.has_stab && !ctx.is_stellar_boost && !ctx.is_tera_stab);
+
+    // Other types: 1.2x and the 60 BP floor on first use only.
+    let mut ctx = DamageContext::new(Gen9, &state, 0, 6, MoveId::Ember, false);
+    assert!(ctx.is_stellar_boost && !ctx.is_tera_stab);
+    compute_base_power(&mut ctx);
+    assert_eq!(ctx.base_power, 60);
+    state.consume_stellar_boost(0, Type::Fire);
+    let mut ctx = DamageContext::new(Gen9, &state, 0, 6, MoveId::Ember, false);
+    assert!(!ctx.has_stab);
+    compute_base_power(&mut ctx);
+    assert_eq!(ctx.base_power, 40);
+
+    // Tera Stellar keeps its original types defensively.
+    assert_eq!(state.defensive_types(0), [Type::Dragon, Type::Ground]);
+}
+
+#[test]
+fn test_tera_blast() {
+    // Before Terastallizing: a special Normal-type move.
+    let state = setup(Type::Fire, false);
+    let mut ctx = DamageContext::new(Gen9, &state, 0, 6, MoveId::Terablast, false);
+    apply_special_moves(&mut ctx);
+    assert_eq!(
+        (ctx.move_type, ctx.category),
+        (Type::Normal, MoveCategory::Special)
+    );
+
+    // Afterwards it takes the Tera Type and Garchomp's higher Attack.
+    let state = setup(Type::Fire, true);
+    let mut ctx = DamageContext::new(Gen9, &state, 0, 6, MoveId::Terablast, false);
+    apply_special_moves(&mut ctx);
+    assert_eq!(
+        (ctx.move_type, ctx.category),
+        (Type::Fire, MoveCategory::Physical)
+    );
+
+    // Stellar Tera Blast: 100 BP, super effective only on Terastallized targets.
+    let mut state = setup(Type::Stellar, true);
+    let mut ctx = DamageContext::new(Gen9, &state, 0, 6, MoveId::Terablast, false);
+    compute_base_power(&mut ctx);
+    assert_eq!(
+        (ctx.move_type, ctx.base_power, ctx.effectiveness),
+        (Type::Stellar, 100, 4)
+    );
+    state.terastallize(6);
+    let ctx = DamageContext::new(Gen9, &state, 0, 6, MoveId::Terablast, false);
+    assert_eq!(ctx.effectiveness, 8);
+}
@@ -0,0 +1,135 @@
+//! Which species, moves, items and abilities exist in a given game.
+//!
+//! Generated data records the generation that introduced each entry and
+//! Showdown's `isNonstandard` category as of the current games. `Past`
+//! entries (cut from Scarlet/Violet) count as available in every earlier
+//! generation they were introduced by; cuts in earlier games (Sword/Shield's
+//! Dexit, moves removed in Gen 8) are not modeled. Species categories come
+//! from `formats-data.json` and are all `None` when it wasn't synced.
+
+use crate::abilities::AbilityId;
+use crate::items::ItemId;
+use crate::moves::MoveId;
+use crate::species::SpeciesId;
+
+/// Generation whose games the current (non-mod) data describes
+pub const CURRENT_GEN: u8 = 9;
+
+/// Showdown's `isNonstandard` categories.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Nonstandard {
+    /// In earlier games but not the current ones
+    Past,
+    /// Announced or datamined but not yet obtainable
+    Future,
+    /// Create-A-Pokémon project
+    Cap,
+    /// Let's Go, Pikachu! and Let's Go, Eevee! only
+    Lgpe,
+    /// In the games' data but never obtainable by players
+    Unobtainable,
+    /// G-Max Moves, usable only through Gigantamax
+    Gigantamax,
+    /// Showdown-only custom content
+    Custom,
+}
+
+/// Whether an entry introduced in `introduced` with category `nonstandard`
+/// exists in generation `gen`'s games.
+pub fn is_available(gen: u8, introduced: u8, nonstandard: Option<Nonstandard>) -> bool {
+    introduced <= gen
+        && match nonstandard {
+            None => true,
+            Some(Nonstandard::Past) => gen < CURRENT_GEN,
+            Some(_) => false,
+        }
+}
+
+impl SpeciesId {
+    /// Whether this species or forme exists in generation `gen`'s games.
+    pub fn is_available_in(self, gen: u8) -> bool {
+        let data = self.data();
+        is_available(gen, data.gen, data.nonstandard)
+    }
+}
+
+impl MoveId {
+    /// Whether this move can be used in generation `gen`'s games.
+    pub fn is_available_in(self, gen: u8) -> bool {
+        let data = self.data();
+        is_available(gen, data.gen, data.nonstandard)
+    }
+}
+
+impl ItemId {
+    /// Whether this item exists in generation `gen`'s games (`None`
+    /// always does).
+    pub fn is_available_in(self, gen: u8) -> bool {
+        let data = self.data();
+        is_available(gen, data.gen, data.nonstandard)
+    }
+}
+
+impl AbilityId {
+    /// Whether this ability exists in generation `gen`'s games.
+    pub fn is_available_in(self, gen: u8) -> bool {
+        is_available(gen, self.gen(), self.nonstandard())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_past_entries_exist_only_before_current_gen() {
+        // Return (Gen 2) was cut from Scarlet/Violet.
+        assert!(!MoveId::Return.is_available_in(9));
+        assert!(MoveId::Return.is_available_in(3));
+        assert!(!MoveId::Return.is_available_in(1));
+        assert!(MoveId::Thunderbolt.is_available_in(9));
+        assert!(!MoveId::Thunderbolt.is_available_in(0));
+    }
+
+    #[test]
+    fn test_introduction_gen() {
+        // Derived from dex/move/ability numbers where the data has no `gen`.
+        assert_eq!(SpeciesId::PIKACHU.data().gen, 1);
+        assert_eq!(SpeciesId::GARCHOMP.data().gen, 4);
+        assert_eq!(SpeciesId::VENUSAURMEGA.data().gen, 6);
+        assert_eq!(SpeciesId::RAICHUALOLA.data().gen, 7);
+        assert_eq!(MoveId::Bravebird.data().gen, 4);
+        assert_eq!(AbilityId::Intimidate.gen(), 3);
+        assert_eq!(ItemId::Leftovers.data().gen, 2);
+
+        assert!(!SpeciesId::GARCHOMP.is_available_in(3));
+        assert!(!AbilityId::Protean.is_available_in(5));
+        assert!(AbilityId::Protean.is_available_in(6));
+        assert!(!ItemId::Choicespecs.is_available_in(3));
+    }
+
+    #[test]
+    fn test_nonstandard_categories() {
+        assert_eq!(
+            MoveId::Gmaxwildfire.data().nonstandard,
+            Some(Nonstandard::Gigantamax)
+        );
+        assert!(!MoveId::Gmaxwildfire.is_available_in(8));
+        assert!(ItemId::None.is_available_in(1));
+    }
+
+    #[test]
+    #[ignore = "needs data/formats-data.json (run scripts/sync_showdown_data.mts)"]
+    fn test_species_categories() {
+        // Mega Evolution was cut from Scarlet/Violet.
+        let mega = SpeciesId::VENUSAURMEGA;
+        assert_eq!(mega.data().nonstandard, Some(Nonstandard::Past));
+        assert!(!mega.is_available_in(9));
+        assert!(mega.is_available_in(7));
+        // Pikachu-Starter only exists in Let's Go.
+        let starter = SpeciesId::from_str("pikachustarter").unwrap();
+        assert_eq!(starter.data().nonstandard, Some(Nonstandard::Lgpe));
+        assert!(!starter.is_available_in(7));
+        assert!(SpeciesId::PIKACHU.is_available_in(9));
+    }
+}
@@ -77,6 +77,12 @@ pub struct PokemonConfig {
 
     /// Gender of the Pokémon
     pub gender: Option<Gender>,
+
+    /// Tera Type (if None, uses the species' primary type)
+    pub tera_type: Option<Type>,
+
+    /// Gigantamax Factor: Dynamax into the `-Gmax` forme and use G-Max Moves
+    pub gigantamax: bool,
 }
 
 impl Default for PokemonConfig {
@@ -96,6 +102,8 @@ impl Default for PokemonConfig {
             current_hp: None,
             weight: None,
             gender: None,
+            tera_type: None,
+            gigantamax: false,
         }
     }
 }
@@ -208,6 +216,18 @@ impl PokemonConfig {
         self
     }
 
+    /// Set Tera Type
+    pub fn tera_type(mut self, tera_type: Type) -> Self {
+        self.tera_type = Some(tera_type);
+        self
+    }
+
+    /// Set whether the Pokémon has the Gigantamax Factor
+    pub fn gigantamax(mut self, gigantamax: bool) -> Self {
+        self.gigantamax = gigantamax;
+        self
+    }
+
     /// Create a Pokémon in its Mega forme
     /// Automatically looks up the Mega forme and sets species
     pub fn mega(mut self) -> Self {
@@ -240,7 +260,12 @@ impl PokemonConfig {
 
     /// Calculate final stats based on base stats, IVs, EVs, level, and nature
     pub fn calculate_stats(&self) -> [u16; 6] {
-        let species = self.species.data();
+        self.calculate_stats_in(9)
+    }
+
+    /// `calculate_stats` with generation `gen`'s base stats.
+    pub fn calculate_stats_in(&self, gen: u8) -> [u16; 6] {
+        let species = self.species.data_in(gen);
         let base = species.base_stats;
         let level = self.level as u32;
 
@@ -292,13 +317,13 @@ impl PokemonConfig {
         ((raw * modifier) / 10) as u16
     }
 
-    /// Get effective types (from override or species)
-    fn get_types(&self) -> [Type; 2] {
+    /// Get effective types (from override or generation `gen`'s species data)
+    fn get_types(&self, gen: u8) -> [Type; 2] {
         if let Some(types) = self.types_override {
             return types;
         }
 
-        let species = self.species.data();
+        let species = self.species.data_in(gen);
         let type1 = species.primary_type();
         let type2 = species.secondary_type().unwrap_or_else(|| type1);
         [type1, type2]
@@ -334,12 +359,32 @@ impl PokemonConfig {
     // ========================================================================
 
     /// Spawn this Pokémon into the battle state at the given player/slot
+    /// and run its switch-in ability (standalone damage-calc setups).
     pub fn spawn(&self, state: &mut BattleState, player: usize, slot: usize) {
+        self.place(state, player, slot);
+
+        // Trigger ability switch-in hooks (e.g., Multitype for Arceus type).
+        // Spawning is unobserved; nothing is reported.
+        let index = BattleState::entity_index(player, slot);
+        let ability = state.abilities[index];
+        if let Some(Some(hooks)) = crate::abilities::ABILITY_REGISTRY.get(ability as usize) {
+            if let Some(on_switch_in) = hooks.on_switch_in {
+                on_switch_in(state, index, &mut ());
+            }
+        }
+    }
+
+    /// Write this Pokémon into the battle state at the given player/slot
+    /// without running any switch-in effects.
+    ///
+    /// Use this when building a battle: `battle::start_battle` runs the
+    /// leads' switch-in effects once both teams are in place.
+    pub fn place(&self, state: &mut BattleState, player: usize, slot: usize) {
         let index = BattleState::entity_index(player, slot);
-        let species = self.species.data();
+        let species = self.species.data_in(state.generation);
 
         // Calculate and set stats
-        let stats = self.calculate_stats();
+        let stats = self.calculate_stats_in(state.generation);
         state.stats[index] = stats;
 
         // Set HP
@@ -349,6 +394,7 @@ impl PokemonConfig {
 
         // Set identity
         state.species[index] = self.species;
+        state.original_species[index] = self.species;
         state.level[index] = self.level;
         state.nature[index] = self.nature;
         state.gender[index] = self.get_gender();
@@ -356,11 +402,18 @@ impl PokemonConfig {
         state.evs[index] = self.evs;
 
         // Set types
-        state.types[index] = self.get_types();
+        state.types[index] = self.get_types(state.generation);
 
         // Set ability
         state.abilities[index] = self.get_ability(species);
 
+        // Set Tera Type
+        state.tera_type[index] = self.tera_type.unwrap_or_else(|| species.primary_type());
+        state.terastallized[index] = false;
+        state.stellar_boosted[index] = 0;
+        state.dynamax_turns[index] = 0;
+        state.gigantamax[index] = self.gigantamax;
+
         // Set item
         state.items[index] = self.item;
 
@@ -378,7 +431,7 @@ impl PokemonConfig {
                 continue;
             }
 
-            let move_data = move_id.data();
+            let move_data = move_id.data_in(state.generation);
             let base_pp = move_data.pp;
             let pp_ups = self.pp_ups[i];
             let max_pp = base_pp + (base_pp * pp_ups / 5);
@@ -392,6 +445,10 @@ impl PokemonConfig {
         state.status[index] = crate::state::Status::NONE;
         state.volatiles[index] = crate::state::Volatiles::empty();
         state.status_counter[index] = 0;
+        state.partial_trap_turns[index] = 0;
+        state.protect_chain[index] = 0;
+        state.transform_backup[index] = None;
+        state.illusion[index] = None;
         // Reset consecutive move tracking (Metronome item, Echoed Voice, etc.)
         state.reset_move_counter(index);
         // Mark as transformed if spawning in a non-base form (e.g. Mega)
@@ -401,14 +458,6 @@ impl PokemonConfig {
         if slot >= state.team_sizes[player] as usize {
             state.team_sizes[player] = (slot + 1) as u8;
         }
-
-        // Trigger ability switch-in hooks (e.g., Multitype for Arceus type)
-        let ability = state.abilities[index];
-        if let Some(Some(hooks)) = crate::abilities::ABILITY_REGISTRY.get(ability as usize) {
-            if let Some(on_switch_in) = hooks.on_switch_in {
-                on_switch_in(state, index);
-            }
-        }
     }
 }
 
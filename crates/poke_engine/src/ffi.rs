@@ -0,0 +1,719 @@
+//! C ABI (`ffi` feature).
+//!
+//! The `poke_engine_capi` crate links these functions into a shared and a
+//! static library; C callers include `poke_engine_capi/include/poke_engine.h`
+//! (regenerate it with cbindgen from that crate after changing this file).
+//!
+//! Conventions:
+//! - Battles are opaque `PokeBattle` handles from `poke_battle_new` or
+//!   `poke_battle_clone`, released with `poke_battle_free`.
+//! - Every fallible function returns a `PokeError`; outputs go through
+//!   caller-provided pointers and are only written on `POKE_ERROR_OK`.
+//! - Strings are NUL-terminated UTF-8. Names are matched like Showdown IDs
+//!   (`"Choice Band"`, `"choiceband"` and `"choice-band"` are equal).
+//! - Panics never cross the boundary; they are reported as `POKE_ERROR_PANIC`.
+//! - Entities are 0-11 (player 1 is 0-5, player 2 is 6-11); players,
+//!   move slots and team slots are 0-based.
+
+use core::ffi::{c_char, CStr};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+use crate::abilities::AbilityId;
+use crate::battle::{
+    battle_result, legal_actions, start_battle, submit_actions, Action, BattleResult, BattleRng,
+    ProtocolWriter, MAX_LEGAL_ACTIONS,
+};
+use crate::damage::{calculate_damage, Generation};
+use crate::items::ItemId;
+use crate::moves::MoveId;
+use crate::species::SpeciesId;
+use crate::state::{BattleState, MAX_TEAM_SIZE};
+use crate::team::{parse_team, to_id};
+
+/// Most actions `poke_battle_legal_actions` reports; an `out` array of this
+/// size always holds them all.
+pub const POKE_MAX_LEGAL_ACTIONS: usize = 17;
+// A literal so cbindgen can export it; kept in step with the engine here.
+const _: () = assert!(POKE_MAX_LEGAL_ACTIONS == MAX_LEGAL_ACTIONS);
+
+/// Result code of every fallible call.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PokeError {
+    Ok = 0,
+    /// A required pointer was null
+    NullPointer = 1,
+    /// A string was not valid UTF-8
+    InvalidUtf8 = 2,
+    /// A name or ID did not resolve
+    NotFound = 3,
+    /// An index or value was out of range
+    InvalidArgument = 4,
+    /// The team text could not be parsed
+    Parse = 5,
+    /// A choice is not legal in the current state
+    IllegalAction = 6,
+    /// The battle has already started (teams are locked)
+    AlreadyStarted = 7,
+    /// The engine panicked; the handle should be freed
+    Panic = 8,
+}
+
+/// `PokeAction::kind`: do nothing (`index` is ignored)
+pub const POKE_ACTION_KIND_PASS: u8 = 0;
+/// Use the move in `index`
+pub const POKE_ACTION_KIND_MOVE: u8 = 1;
+/// Switch to the Pokémon in team slot `index`
+pub const POKE_ACTION_KIND_SWITCH: u8 = 2;
+/// Terastallize, then use the move in `index`
+pub const POKE_ACTION_KIND_TERASTALLIZE: u8 = 3;
+/// Dynamax, then use the Max Move of the move in `index`
+pub const POKE_ACTION_KIND_DYNAMAX: u8 = 4;
+/// Use the move in `index` as a Z-Move
+pub const POKE_ACTION_KIND_Z_MOVE: u8 = 5;
+/// Mega Evolve, then use the move in `index`
+pub const POKE_ACTION_KIND_MEGA_EVOLVE: u8 = 6;
+/// Ultra Burst, then use the move in `index`
+pub const POKE_ACTION_KIND_ULTRA_BURST: u8 = 7;
+/// Use the move in `index` in Strong Style
+pub const POKE_ACTION_KIND_STRONG_STYLE: u8 = 8;
+/// Use the move in `index` in Agile Style
+pub const POKE_ACTION_KIND_AGILE_STYLE: u8 = 9;
+
+/// A player's choice; `index` is the move slot or team slot.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PokeAction {
+    /// One of the `POKE_ACTION_KIND_*` values
+    pub kind: u8,
+    pub index: u8,
+}
+
+/// Outcome of `poke_battle_result`.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PokeResult {
+    Ongoing = 0,
+    P1Win = 1,
+    P2Win = 2,
+    Tie = 3,
+}
+
+/// Output of `poke_calculate_damage`.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct PokeDamageResult {
+    /// All 16 damage rolls, lowest first
+    pub rolls: [u16; 16],
+    pub min: u16,
+    pub max: u16,
+    /// 4 = neutral, 8 = 2x, 2 = 0.5x, 0 = immune
+    pub effectiveness: u8,
+    pub is_crit: bool,
+    pub final_base_power: u16,
+}
+
+/// Opaque battle handle.
+#[derive(Clone)]
+pub struct PokeBattle {
+    gen: Generation,
+    state: BattleState,
+    rng: BattleRng,
+    log: ProtocolWriter<String>,
+    started: bool,
+}
+
+impl TryFrom<PokeAction> for Action {
+    type Error = PokeError;
+
+    fn try_from(action: PokeAction) -> Result<Self, PokeError> {
+        let index = action.index;
+        Ok(match action.kind {
+            POKE_ACTION_KIND_PASS => Action::Pass,
+            POKE_ACTION_KIND_MOVE => Action::Move(index),
+            POKE_ACTION_KIND_SWITCH => Action::Switch(index),
+            POKE_ACTION_KIND_TERASTALLIZE => Action::Terastallize(index),
+            POKE_ACTION_KIND_DYNAMAX => Action::Dynamax(index),
+            POKE_ACTION_KIND_Z_MOVE => Action::ZMove(index),
+            POKE_ACTION_KIND_MEGA_EVOLVE => Action::MegaEvolve(index),
+            POKE_ACTION_KIND_ULTRA_BURST => Action::UltraBurst(index),
+            POKE_ACTION_KIND_STRONG_STYLE => Action::StrongStyle(index),
+            POKE_ACTION_KIND_AGILE_STYLE => Action::AgileStyle(index),
+            _ => return Err(PokeError::InvalidArgument),
+        })
+    }
+}
+
+impl From<Action> for PokeAction {
+    fn from(action: Action) -> Self {
+        match action {
+            Action::Pass => PokeAction {
+                kind: POKE_ACTION_KIND_PASS,
+                index: 0,
+            },
+            Action::Move(index) => PokeAction {
+                kind: POKE_ACTION_KIND_MOVE,
+                index,
+            },
+            Action::Switch(index) => PokeAction {
+                kind: POKE_ACTION_KIND_SWITCH,
+                index,
+            },
+            Action::Terastallize(index) => PokeAction {
+                kind: POKE_ACTION_KIND_TERASTALLIZE,
+                index,
+            },
+            Action::Dynamax(index) => PokeAction {
+                kind: POKE_ACTION_KIND_DYNAMAX,
+                index,
+            },
+            Action::ZMove(index) => PokeAction {
+                kind: POKE_ACTION_KIND_Z_MOVE,
+                index,
+            },
+            Action::MegaEvolve(index) => PokeAction {
+                kind: POKE_ACTION_KIND_MEGA_EVOLVE,
+                index,
+            },
+            Action::UltraBurst(index) => PokeAction {
+                kind: POKE_ACTION_KIND_ULTRA_BURST,
+                index,
+            },
+            Action::StrongStyle(index) => PokeAction {
+                kind: POKE_ACTION_KIND_STRONG_STYLE,
+                index,
+            },
+            Action::AgileStyle(index) => PokeAction {
+                kind: POKE_ACTION_KIND_AGILE_STYLE,
+                index,
+            },
+        }
+    }
+}
+
+/// Run `f`, turning panics into `PokeError::Panic`.
+fn guard(f: impl FnOnce() -> Result<(), PokeError>) -> PokeError {
+    match catch_unwind(AssertUnwindSafe(f)) {
+        Ok(Ok(())) => PokeError::Ok,
+        Ok(Err(e)) => e,
+        Err(_) => PokeError::Panic,
+    }
+}
+
+unsafe fn str_arg<'a>(ptr: *const c_char) -> Result<&'a str, PokeError> {
+    if ptr.is_null() {
+        return Err(PokeError::NullPointer);
+    }
+    CStr::from_ptr(ptr)
+        .to_str()
+        .map_err(|_| PokeError::InvalidUtf8)
+}
+
+unsafe fn out_arg<'a, T>(ptr: *mut T) -> Result<&'a mut T, PokeError> {
+    ptr.as_mut().ok_or(PokeError::NullPointer)
+}
+
+unsafe fn battle_ref<'a>(battle: *const PokeBattle) -> Result<&'a PokeBattle, PokeError> {
+    battle.as_ref().ok_or(PokeError::NullPointer)
+}
+
+unsafe fn battle_mut<'a>(battle: *mut PokeBattle) -> Result<&'a mut PokeBattle, PokeError> {
+    battle.as_mut().ok_or(PokeError::NullPointer)
+}
+
+fn check_player(player: u8) -> Result<usize, PokeError> {
+    match player {
+        0 | 1 => Ok(player as usize),
+        _ => Err(PokeError::InvalidArgument),
+    }
+}
+
+fn check_entity(entity: u8) -> Result<usize, PokeError> {
+    match entity as usize {
+        entity if entity < 2 * MAX_TEAM_SIZE => Ok(entity),
+        _ => Err(PokeError::InvalidArgument),
+    }
+}
+
+// ============================================================================
+// Errors
+// ============================================================================
+
+/// Static, NUL-terminated description of an error code.
+#[no_mangle]
+pub extern "C" fn poke_error_message(error: PokeError) -> *const c_char {
+    let message: &'static CStr = match error {
+        PokeError::Ok => c"ok",
+        PokeError::NullPointer => c"null pointer",
+        PokeError::InvalidUtf8 => c"invalid UTF-8",
+        PokeError::NotFound => c"not found",
+        PokeError::InvalidArgument => c"invalid argument",
+        PokeError::Parse => c"could not parse team",
+        PokeError::IllegalAction => c"illegal action",
+        PokeError::AlreadyStarted => c"battle already started",
+        PokeError::Panic => c"internal error",
+    };
+    message.as_ptr()
+}
+
+// ============================================================================
+// Lookups
+// ============================================================================
+
+/// Species ID by name.
+///
+/// # Safety
+/// `name` must be a valid C string and `out` a valid pointer.
+#[no_mangle]
+pub unsafe extern "C" fn poke_species_id(name: *const c_char, out: *mut u16) -> PokeError {
+    guard(|| {
+        let id = SpeciesId::from_str(&to_id(str_arg(name)?)).ok_or(PokeError::NotFound)?;
+        *out_arg(out)? = id.0;
+        Ok(())
+    })
+}
+
+/// Move ID by name.
+///
+/// # Safety
+/// `name` must be a valid C string and `out` a valid pointer.
+#[no_mangle]
+pub unsafe extern "C" fn poke_move_id(name: *const c_char, out: *mut u16) -> PokeError {
+    guard(|| {
+        let id = MoveId::from_str(&to_id(str_arg(name)?)).ok_or(PokeError::NotFound)?;
+        *out_arg(out)? = id as u16;
+        Ok(())
+    })
+}
+
+/// Item ID by name.
+///
+/// # Safety
+/// `name` must be a valid C string and `out` a valid pointer.
+#[no_mangle]
+pub unsafe extern "C" fn poke_item_id(name: *const c_char, out: *mut u16) -> PokeError {
+    guard(|| {
+        let id = ItemId::from_str(&to_id(str_arg(name)?)).ok_or(PokeError::NotFound)?;
+        *out_arg(out)? = id as u16;
+        Ok(())
+    })
+}
+
+/// Ability ID by name.
+///
+/// # Safety
+/// `name` must be a valid C string and `out` a valid pointer.
+#[no_mangle]
+pub unsafe extern "C" fn poke_ability_id(name: *const c_char, out: *mut u16) -> PokeError {
+    guard(|| {
+        let id = AbilityId::from_str(&to_id(str_arg(name)?)).ok_or(PokeError::NotFound)?;
+        *out_arg(out)? = id as u16;
+        Ok(())
+    })
+}
+
+// ============================================================================
+// Lifecycle
+// ============================================================================
+
+/// Create an empty battle for generation `gen` (1-9). Returns null for an
+/// unsupported generation. Load both teams, then call `poke_battle_start`.
+#[no_mangle]
+pub extern "C" fn poke_battle_new(gen: u8, seed: u64) -> *mut PokeBattle {
+    if !(1..=9).contains(&gen) {
+        return core::ptr::null_mut();
+    }
+    let mut state = BattleState::new();
+    state.generation = gen;
+    Box::into_raw(Box::new(PokeBattle {
+        gen: Generation::from_num(gen),
+        state,
+        rng: BattleRng::new(seed),
+        log: ProtocolWriter::default(),
+        started: false,
+    }))
+}
+
+/// Independent copy of a battle (state, RNG and pending log). Returns null
+/// if `battle` is null.
+///
+/// # Safety
+/// `battle` must be null or a live handle.
+#[no_mangle]
+pub unsafe extern "C" fn poke_battle_clone(battle: *const PokeBattle) -> *mut PokeBattle {
+    match battle.as_ref() {
+        Some(battle) => Box::into_raw(Box::new(battle.clone())),
+        None => core::ptr::null_mut(),
+    }
+}
+
+/// Release a battle. Null is ignored.
+///
+/// # Safety
+/// `battle` must be null or a live handle, and is invalid afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn poke_battle_free(battle: *mut PokeBattle) {
+    if !battle.is_null() {
+        drop(Box::from_raw(battle));
+    }
+}
+
+/// Load a player's team from Showdown export text or JSON.
+///
+/// # Safety
+/// `battle` must be a live handle and `team` a valid C string.
+#[no_mangle]
+pub unsafe extern "C" fn poke_battle_set_team(
+    battle: *mut PokeBattle,
+    player: u8,
+    team: *const c_char,
+) -> PokeError {
+    guard(|| {
+        let battle = battle_mut(battle)?;
+        let player = check_player(player)?;
+        if battle.started {
+            return Err(PokeError::AlreadyStarted);
+        }
+        let team = parse_team(str_arg(team)?).map_err(|_| PokeError::Parse)?;
+        for (slot, config) in team.iter().enumerate() {
+            config.place(&mut battle.state, player, slot);
+        }
+        battle.state.team_sizes[player] = team.len() as u8;
+        Ok(())
+    })
+}
+
+/// Send out the leads and begin turn 1. Both teams must be loaded.
+///
+/// # Safety
+/// `battle` must be a live handle.
+#[no_mangle]
+pub unsafe extern "C" fn poke_battle_start(battle: *mut PokeBattle) -> PokeError {
+    guard(|| {
+        let battle = battle_mut(battle)?;
+        if battle.started {
+            return Err(PokeError::AlreadyStarted);
+        }
+        if battle.state.team_sizes.contains(&0) {
+            return Err(PokeError::InvalidArgument);
+        }
+        start_battle(&mut battle.state, &mut battle.rng, &mut battle.log);
+        battle.started = true;
+        Ok(())
+    })
+}
+
+// ============================================================================
+// Stepping
+// ============================================================================
+
+/// Submit both players' choices: a full turn, or the pending replacements
+/// when a side must switch in (the other choice is then ignored). An
+/// unknown `kind` is `POKE_ERROR_INVALID_ARGUMENT`.
+///
+/// # Safety
+/// `battle` must be a live handle.
+#[no_mangle]
+pub unsafe extern "C" fn poke_battle_choose(
+    battle: *mut PokeBattle,
+    p1: PokeAction,
+    p2: PokeAction,
+) -> PokeError {
+    guard(|| {
+        let battle = battle_mut(battle)?;
+        if !battle.started {
+            return Err(PokeError::InvalidArgument);
+        }
+        let actions = [p1.try_into()?, p2.try_into()?];
+        submit_actions(
+            battle.gen,
+            &mut battle.state,
+            actions,
+            &mut battle.rng,
+            &mut battle.log,
+        )
+        .map_err(|_| PokeError::IllegalAction)
+    })
+}
+
+/// Write up to `capacity` legal actions for `player` into `out` and the
+/// total count into `len`, which may exceed `capacity` (a `capacity` of
+/// `POKE_MAX_LEGAL_ACTIONS` always suffices).
+///
+/// # Safety
+/// `battle` and `len` must be valid; `out` must hold `capacity` actions.
+#[no_mangle]
+pub unsafe extern "C" fn poke_battle_legal_actions(
+    battle: *const PokeBattle,
+    player: u8,
+    out: *mut PokeAction,
+    capacity: usize,
+    len: *mut usize,
+) -> PokeError {
+    guard(|| {
+        let battle = battle_ref(battle)?;
+        let actions = legal_actions(&battle.state, check_player(player)?);
+        if out.is_null() && capacity > 0 {
+            return Err(PokeError::NullPointer);
+        }
+        for (i, &action) in actions.iter().take(capacity).enumerate() {
+            *out.add(i) = action.into();
+        }
+        *out_arg(len)? = actions.len();
+        Ok(())
+    })
+}
+
+/// Current outcome of the battle.
+///
+/// # Safety
+/// `battle` must be a live handle and `out` a valid pointer.
+#[no_mangle]
+pub unsafe extern "C" fn poke_battle_result(
+    battle: *const PokeBattle,
+    out: *mut PokeResult,
+) -> PokeError {
+    guard(|| {
+        let battle = battle_ref(battle)?;
+        *out_arg(out)? = match battle_result(&battle.state) {
+            BattleResult::Ongoing => PokeResult::Ongoing,
+            BattleResult::Win(0) => PokeResult::P1Win,
+            BattleResult::Win(_) => PokeResult::P2Win,
+            BattleResult::Tie => PokeResult::Tie,
+        };
+        Ok(())
+    })
+}
+
+/// Entity index of a player's active Pokémon.
+///
+/// # Safety
+/// `battle` must be a live handle and `out` a valid pointer.
+#[no_mangle]
+pub unsafe extern "C" fn poke_battle_active(
+    battle: *const PokeBattle,
+    player: u8,
+    out: *mut u8,
+) -> PokeError {
+    guard(|| {
+        let battle = battle_ref(battle)?;
+        *out_arg(out)? = battle.state.active_index(check_player(player)?) as u8;
+        Ok(())
+    })
+}
+
+/// Current and maximum HP of an entity.
+///
+/// # Safety
+/// `battle` must be a live handle; `hp` and `max_hp` valid pointers.
+#[no_mangle]
+pub unsafe extern "C" fn poke_battle_hp(
+    battle: *const PokeBattle,
+    entity: u8,
+    hp: *mut u16,
+    max_hp: *mut u16,
+) -> PokeError {
+    guard(|| {
+        let battle = battle_ref(battle)?;
+        let entity = check_entity(entity)?;
+        *out_arg(hp)? = battle.state.hp[entity];
+        *out_arg(max_hp)? = battle.state.max_hp[entity];
+        Ok(())
+    })
+}
+
+/// Copy the Showdown protocol log produced since the last call into `buf`
+/// (NUL-terminated) and clear it. `len` receives the log length without
+/// the NUL; if it is `>= capacity` nothing is copied or cleared, so the
+/// caller can retry with a larger buffer.
+///
+/// # Safety
+/// `battle` and `len` must be valid; `buf` must hold `capacity` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn poke_battle_take_log(
+    battle: *mut PokeBattle,
+    buf: *mut c_char,
+    capacity: usize,
+    len: *mut usize,
+) -> PokeError {
+    guard(|| {
+        let battle = battle_mut(battle)?;
+        let log = battle.log.get_ref().as_bytes();
+        *out_arg(len)? = log.len();
+        if log.len() >= capacity {
+            return Ok(());
+        }
+        if buf.is_null() {
+            return Err(PokeError::NullPointer);
+        }
+        core::ptr::copy_nonoverlapping(log.as_ptr(), buf.cast::<u8>(), log.len());
+        *buf.add(log.len()) = 0;
+        battle.log = ProtocolWriter::default();
+        Ok(())
+    })
+}
+
+// ============================================================================
+// Damage
+// ============================================================================
+
+/// Damage of move `move_id` used by entity `attacker` against `defender`
+/// in the battle's current state.
+///
+/// # Safety
+/// `battle` must be a live handle and `out` a valid pointer.
+#[no_mangle]
+pub unsafe extern "C" fn poke_calculate_damage(
+    battle: *const PokeBattle,
+    attacker: u8,
+    defender: u8,
+    move_id: u16,
+    is_crit: bool,
+    out: *mut PokeDamageResult,
+) -> PokeError {
+    guard(|| {
+        let battle = battle_ref(battle)?;
+        let attacker = check_entity(attacker)?;
+        let defender = check_entity(defender)?;
+        let move_id = MoveId::from_u16(move_id).ok_or(PokeError::NotFound)?;
+        let out = out_arg(out)?;
+        let result = calculate_damage(
+            battle.gen,
+            &battle.state,
+            attacker,
+            defender,
+            move_id,
+            is_crit,
+        );
+        *out = PokeDamageResult {
+            rolls: result.rolls,
+            min: result.min,
+            max: result.max,
+            effectiveness: result.effectiveness,
+            is_crit: result.is_crit,
+            final_base_power: result.final_base_power,
+        };
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEAM1: &CStr = c"Garchomp\n- Earthquake\n- Dragon Claw\n\nCorviknight\n- Brave Bird";
+    const TEAM2: &CStr = c"Heatran\n- Flamethrower";
+
+    #[test]
+    fn test_battle_lifecycle() {
+        // Drive a whole battle through the C entry points, the way a client would.
+        unsafe {
+            let battle = poke_battle_new(9, 1);
+            assert_eq!(
+                poke_battle_set_team(battle, 0, TEAM1.as_ptr()),
+                PokeError::Ok
+            );
+            assert_eq!(
+                poke_battle_set_team(battle, 1, TEAM2.as_ptr()),
+                PokeError::Ok
+            );
+            assert_eq!(poke_battle_start(battle), PokeError::Ok);
+            assert_eq!(
+                poke_battle_set_team(battle, 1, TEAM2.as_ptr()),
+                PokeError::AlreadyStarted
+            );
+
+            let mut actions = [PokeAction::from(Action::Pass); POKE_MAX_LEGAL_ACTIONS];
+            let mut len = 0;
+            let capacity = actions.len();
+            let err =
+                poke_battle_legal_actions(battle, 0, actions.as_mut_ptr(), capacity, &mut len);
+            assert_eq!(err, PokeError::Ok);
+            // Two moves, each also with Terastallization, and one switch
+            assert_eq!(len, 5);
+
+            let mut earthquake = 0;
+            assert_eq!(
+                poke_move_id(c"Earthquake".as_ptr(), &mut earthquake),
+                PokeError::Ok
+            );
+            let mut damage = PokeDamageResult::default();
+            let err = poke_calculate_damage(battle, 0, 6, earthquake, false, &mut damage);
+            assert_eq!(err, PokeError::Ok);
+            assert_eq!(damage.effectiveness, 16);
+
+            let copy = poke_battle_clone(battle);
+            let illegal = PokeAction {
+                kind: POKE_ACTION_KIND_MOVE,
+                index: 3,
+            };
+            assert_eq!(
+                poke_battle_choose(battle, illegal, actions[0]),
+                PokeError::IllegalAction
+            );
+            let attack = PokeAction {
+                kind: POKE_ACTION_KIND_MOVE,
+                index: 0,
+            };
+            let unknown = PokeAction { kind: 42, index: 0 };
+            assert_eq!(
+                poke_battle_choose(battle, unknown, attack),
+                PokeError::InvalidArgument
+            );
+            assert_eq!(poke_battle_choose(battle, attack, attack), PokeError::Ok);
+
+            let mut result = PokeResult::Ongoing;
+            poke_battle_result(battle, &mut result);
+            assert_eq!(result, PokeResult::P1Win);
+            poke_battle_result(copy, &mut result);
+            assert_eq!(result, PokeResult::Ongoing);
+
+            let mut buf = [0 as c_char; 1024];
+            let err = poke_battle_take_log(battle, buf.as_mut_ptr(), buf.len(), &mut len);
+            assert_eq!(err, PokeError::Ok);
+            let log = CStr::from_ptr(buf.as_ptr()).to_str().unwrap();
+            assert!(log.ends_with("|win|p1\n"));
+
+            poke_battle_free(copy);
+            poke_battle_free(battle);
+        }
+    }
+
+    #[test]
+    fn test_errors_instead_of_panics() {
+        // Bad input must come back as error codes, never unwind into C.
+        unsafe {
+            let mut id = 0;
+            assert_eq!(
+                poke_move_id(core::ptr::null(), &mut id),
+                PokeError::NullPointer
+            );
+            assert_eq!(
+                poke_move_id(c"Not A Move".as_ptr(), &mut id),
+                PokeError::NotFound
+            );
+            assert_eq!(
+                poke_species_id(c"garchomp".as_ptr(), core::ptr::null_mut()),
+                PokeError::NullPointer
+            );
+            assert!(poke_battle_new(0, 0).is_null());
+
+            let battle = poke_battle_new(9, 0);
+            assert_eq!(
+                poke_battle_set_team(battle, 2, TEAM2.as_ptr()),
+                PokeError::InvalidArgument
+            );
+            assert_eq!(
+                poke_battle_set_team(battle, 0, c"Nope\n- Tackle".as_ptr()),
+                PokeError::Parse
+            );
+            assert_eq!(poke_battle_start(battle), PokeError::InvalidArgument);
+            let mut damage = PokeDamageResult::default();
+            assert_eq!(
+                poke_calculate_damage(battle, 0, 6, u16::MAX, false, &mut damage),
+                PokeError::NotFound
+            );
+            poke_battle_free(battle);
+        }
+    }
+}
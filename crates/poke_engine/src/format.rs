@@ -0,0 +1,379 @@
+//! Battle formats: team-building rules and battle settings.
+//!
+//! Formats are plain JSON data, so new tiers can be added without code
+//! changes; the built-in ones live in `formats/`. Names in the ban lists are matched as Showdown IDs, so
+//! `"Choice Band"`, `"choiceband"` and `"Choice-Band"` are all equivalent.
+//!
+//! The same `Format` drives `validate::validate_team` and battle setup
+//! (`Format::new_battle`).
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use serde::{Deserialize, Serialize};
+
+use crate::entities::PokemonConfig;
+use crate::species::SpeciesId;
+use crate::state::{BattleFormat, BattleState, MAX_TEAM_SIZE};
+use crate::team::to_id;
+
+/// A standard rule applied on top of a format's ban lists.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Clause {
+    /// At most one Pokémon per species (formes count as the base species)
+    Species,
+    /// At most one of each held item
+    Item,
+    /// Only one foe may be put to sleep at a time. Battle-time rule
+    /// (`BattleState::sleep_clause`); not checked by `validate_team`.
+    Sleep,
+    /// Bans Double Team and Minimize
+    Evasion,
+    /// Bans Bright Powder and Lax Incense
+    EvasionItems,
+    /// Bans Sand Veil and Snow Cloak
+    EvasionAbilities,
+    /// Bans one-hit KO moves
+    Ohko,
+    /// Every team member shares a type
+    Monotype,
+    /// Only first-stage Pokémon that can evolve
+    LittleCup,
+}
+
+impl Clause {
+    /// Moves this clause bans (as IDs).
+    pub(crate) fn banned_moves(self) -> &'static [&'static str] {
+        match self {
+            Clause::Evasion => &["doubleteam", "minimize"],
+            _ => &[],
+        }
+    }
+
+    /// Items this clause bans (as IDs).
+    pub(crate) fn banned_items(self) -> &'static [&'static str] {
+        match self {
+            Clause::EvasionItems => &["brightpowder", "laxincense"],
+            _ => &[],
+        }
+    }
+
+    /// Abilities this clause bans (as IDs).
+    pub(crate) fn banned_abilities(self) -> &'static [&'static str] {
+        match self {
+            Clause::EvasionAbilities => &["sandveil", "snowcloak"],
+            _ => &[],
+        }
+    }
+}
+
+/// Battle gimmicks a format allows.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Gimmicks {
+    /// Terastallization (Gen 9)
+    pub tera: bool,
+    /// Dynamax (Gen 8)
+    pub dynamax: bool,
+    /// Mega Evolution (Gen 6-7)
+    pub mega: bool,
+    /// Z-Moves (Gen 7)
+    pub z_moves: bool,
+    /// Legends: Arceus mechanics for custom formats: frostbite and
+    /// drowsiness replace freeze and sleep, and moves can be used in Strong
+    /// or Agile Style
+    pub legends_arceus: bool,
+}
+
+impl Gimmicks {
+    /// The gimmicks available in a generation's own games.
+    pub const fn for_gen(gen: u8) -> Self {
+        Self {
+            tera: gen == 9,
+            dynamax: gen == 8,
+            mega: matches!(gen, 6 | 7),
+            z_moves: gen == 7,
+            legends_arceus: false,
+        }
+    }
+}
+
+/// A format's team-building rules and battle settings.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Format {
+    /// Display name, e.g. `"[Gen 9] OU"`
+    pub name: String,
+    /// Generation (1-9)
+    pub generation: u8,
+    /// Singles or doubles
+    pub game_type: BattleFormat,
+    /// Most Pokémon a team may have
+    pub team_size: u8,
+    /// Pokémon brought into each battle after team preview (VGC's "Bring 6
+    /// Pick 4"); `None` brings the whole team
+    pub pick: Option<u8>,
+    /// Highest level a set may have
+    pub max_level: u8,
+    /// Sets above this level battle at it (VGC's level 50)
+    pub level: Option<u8>,
+    pub banned_species: Vec<String>,
+    pub banned_items: Vec<String>,
+    pub banned_moves: Vec<String>,
+    pub banned_abilities: Vec<String>,
+    pub clauses: Vec<Clause>,
+    /// Defaults to the generation's own gimmicks (`Gimmicks::for_gen`) when
+    /// a format file leaves them out
+    pub gimmicks: Gimmicks,
+}
+
+impl Default for Format {
+    /// Anything goes: Gen 9 singles, six Pokémon, level 100, no bans or
+    /// clauses, Terastallization allowed.
+    fn default() -> Self {
+        Self {
+            name: String::from("Custom Game"),
+            generation: 9,
+            game_type: BattleFormat::Singles,
+            team_size: MAX_TEAM_SIZE as u8,
+            pick: None,
+            max_level: 100,
+            level: None,
+            banned_species: Vec::new(),
+            banned_items: Vec::new(),
+            banned_moves: Vec::new(),
+            banned_abilities: Vec::new(),
+            clauses: Vec::new(),
+            gimmicks: Gimmicks::for_gen(9),
+        }
+    }
+}
+
+impl Format {
+    pub fn has_clause(&self, clause: Clause) -> bool {
+        self.clauses.contains(&clause)
+    }
+
+    /// Pokémon per side in battle.
+    pub fn pick_count(&self) -> usize {
+        self.pick
+            .unwrap_or(self.team_size)
+            .min(self.team_size)
+            .min(MAX_TEAM_SIZE as u8) as usize
+    }
+
+    /// Whether `name` (a display name or ID) is on `list`.
+    pub(crate) fn is_listed(list: &[String], name: &str) -> bool {
+        let id = to_id(name);
+        list.iter().any(|entry| to_id(entry) == id)
+    }
+
+    /// Whether `species` is banned. Banning a species bans all of its
+    /// formes; `"Name-Base"` bans only the base forme (Showdown's syntax).
+    pub fn is_species_banned(&self, species: SpeciesId) -> bool {
+        let id = to_id(species.data().name);
+        let base = to_id(species.base().data().name);
+        self.banned_species.iter().any(|entry| {
+            let entry = to_id(entry);
+            entry == id
+                || entry == base
+                || (species == species.base() && entry.strip_suffix("base") == Some(&id))
+        })
+    }
+
+    /// Apply the format's battle settings (generation, game type, gimmicks,
+    /// Sleep Clause) to a state.
+    pub fn configure(&self, state: &mut BattleState) {
+        state.generation = self.generation;
+        state.format = self.game_type;
+        state.gimmicks = self.gimmicks;
+        state.sleep_clause = self.has_clause(Clause::Sleep);
+    }
+
+    /// Build a battle ready for `battle::start_battle`.
+    ///
+    /// Each side brings the first `pick_count()` sets of its team, so after
+    /// team preview the chosen Pokémon go first (lead first). Sets above
+    /// `level` are scaled down to it. Teams are not validated here; run
+    /// `validate::validate_team` first.
+    pub fn new_battle(&self, teams: [&[PokemonConfig]; 2]) -> BattleState {
+        let mut state = BattleState::new();
+        self.configure(&mut state);
+        for (player, team) in teams.into_iter().enumerate() {
+            for (slot, set) in team.iter().take(self.pick_count()).enumerate() {
+                match self.level {
+                    Some(level) if set.level > level => {
+                        set.clone().level(level).place(&mut state, player, slot)
+                    }
+                    _ => set.place(&mut state, player, slot),
+                }
+            }
+        }
+        state
+    }
+}
+
+// ============================================================================
+// Loading
+// ============================================================================
+
+/// Error produced while loading a format file.
+#[cfg(feature = "std")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FormatError {
+    Io(String),
+    Json(String),
+    /// Path extension is not `.json`
+    UnknownExtension(String),
+}
+
+#[cfg(feature = "std")]
+impl core::fmt::Display for FormatError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            FormatError::Io(s) => write!(f, "cannot read format: {}", s),
+            FormatError::Json(s) => write!(f, "invalid JSON: {}", s),
+            FormatError::UnknownExtension(s) => {
+                write!(f, "unknown format file type: {} (expected .json)", s)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl core::error::Error for FormatError {}
+
+/// Built-in formats: (ID, JSON source)
+#[cfg(feature = "std")]
+const BUILTIN: [(&str, &str); 5] = [
+    ("gen9ou", include_str!("../formats/gen9ou.json")),
+    ("gen9ubers", include_str!("../formats/gen9ubers.json")),
+    ("gen9vgc", include_str!("../formats/gen9vgc.json")),
+    ("gen9monotype", include_str!("../formats/gen9monotype.json")),
+    ("gen9lc", include_str!("../formats/gen9lc.json")),
+];
+
+#[cfg(feature = "std")]
+impl Format {
+    /// Parse a format. Fields left out keep their defaults, except that
+    /// `gimmicks` follows `generation`.
+    pub fn from_json(text: &str) -> Result<Self, FormatError> {
+        let error = |e: serde_json::Error| FormatError::Json(e.to_string());
+        let value: serde_json::Value = serde_json::from_str(text).map_err(error)?;
+        let has_gimmicks = value.get("gimmicks").is_some();
+        let mut format: Self = serde_json::from_value(value).map_err(error)?;
+        if !has_gimmicks {
+            format.gimmicks = Gimmicks::for_gen(format.generation);
+        }
+        Ok(format)
+    }
+
+    /// Load a `.json` format file.
+    pub fn load(path: impl AsRef<std::path::Path>) -> Result<Self, FormatError> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| FormatError::Io(format!("{}: {}", path.display(), e)))?;
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("json") => Self::from_json(&text),
+            _ => Err(FormatError::UnknownExtension(path.display().to_string())),
+        }
+    }
+
+    /// IDs of the built-in formats.
+    pub fn builtin_ids() -> impl Iterator<Item = &'static str> {
+        BUILTIN.iter().map(|(id, _)| *id)
+    }
+
+    /// A built-in format by ID or name (`"gen9ou"`, `"[Gen 9] OU"`).
+    pub fn builtin(id: &str) -> Option<Self> {
+        let id = to_id(id);
+        let (_, source) = BUILTIN.iter().find(|(builtin, _)| *builtin == id)?;
+        Some(Self::from_json(source).expect("built-in format"))
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::abilities::AbilityId;
+    use crate::items::ItemId;
+    use crate::moves::MoveId;
+
+    #[test]
+    fn test_builtin_formats_parse_and_resolve() {
+        // Catch typos: every banned name must be a real species/item/move/ability.
+        for id in Format::builtin_ids() {
+            let format = Format::builtin(id).unwrap();
+            for name in &format.banned_species {
+                let key = to_id(name);
+                let key = key.strip_suffix("base").unwrap_or(&key);
+                assert!(SpeciesId::from_str(key).is_some(), "{}: {}", id, name);
+            }
+            for name in &format.banned_items {
+                assert!(ItemId::from_str(&to_id(name)).is_some(), "{}: {}", id, name);
+            }
+            for name in &format.banned_moves {
+                assert!(MoveId::from_str(&to_id(name)).is_some(), "{}: {}", id, name);
+            }
+            for name in &format.banned_abilities {
+                assert!(
+                    AbilityId::from_str(&to_id(name)).is_some(),
+                    "{}: {}",
+                    id,
+                    name
+                );
+            }
+        }
+        assert_eq!(Format::builtin("[Gen 9] VGC").unwrap().pick_count(), 4);
+        assert!(Format::builtin("gen1ou").is_none());
+    }
+
+    #[test]
+    fn test_json_fields_default() {
+        let json =
+            r#"{"name": "Doubles", "game_type": "doubles", "pick": 4, "clauses": ["species"]}"#;
+        let format = Format::from_json(json).unwrap();
+        assert_eq!(format.game_type, BattleFormat::Doubles);
+        assert_eq!(format.pick, Some(4));
+        // Unspecified fields keep their defaults.
+        assert_eq!(format.max_level, 100);
+        assert!(format.gimmicks.tera);
+    }
+
+    #[test]
+    fn test_gimmicks_default_to_the_generation() {
+        let format = Format::from_json(r#"{"name": "Gen 7", "generation": 7}"#).unwrap();
+        assert_eq!(format.gimmicks, Gimmicks::for_gen(7));
+        assert!(format.gimmicks.mega && format.gimmicks.z_moves && !format.gimmicks.tera);
+
+        // An explicit table still wins.
+        let json = r#"{"generation": 7, "gimmicks": {"tera": true}}"#;
+        let format = Format::from_json(json).unwrap();
+        assert!(format.gimmicks.tera && !format.gimmicks.mega);
+    }
+
+    #[test]
+    fn test_species_bans_cover_formes() {
+        let format = Format {
+            banned_species: vec![String::from("Zacian"), String::from("Urshifu-Base")],
+            ..Format::default()
+        };
+        assert!(format.is_species_banned(SpeciesId::ZACIANCROWNED));
+        assert!(format.is_species_banned(SpeciesId::URSHIFU));
+        assert!(!format.is_species_banned(SpeciesId::URSHIFURAPIDSTRIKE));
+    }
+
+    #[test]
+    fn test_new_battle_picks_and_levels() {
+        // VGC: four of six brought, levels scaled down to 50.
+        let format = Format::builtin("gen9vgc").unwrap();
+        let team: Vec<PokemonConfig> = (0..6)
+            .map(|_| PokemonConfig::new(SpeciesId::PIKACHU).level(100))
+            .collect();
+        let state = format.new_battle([&team, &team]);
+        assert_eq!(state.team_sizes, [4, 4]);
+        assert_eq!(state.level[0], 50);
+        assert!(state.is_doubles());
+    }
+}
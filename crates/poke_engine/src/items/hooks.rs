@@ -1,3 +1,4 @@
+use crate::battle::BattleSink;
 use crate::state::BattleState;
 use crate::moves::{MoveId, MoveCategory, Move};
 use crate::types::Type;
@@ -5,7 +6,7 @@ use crate::abilities::{Weather, Terrain};
 use crate::state::Hazard;
 
 /// Called when a Pokemon switches in (after hazards)
-pub type OnSwitchIn = fn(state: &mut BattleState, switched_idx: usize);
+pub type OnSwitchIn = fn(state: &mut BattleState, switched_idx: usize, events: &mut dyn BattleSink);
 
 /// Called during turn ordering to modify move priority
 pub type OnModifyPriority = fn(state: &BattleState, attacker: usize, move_id: MoveId, base_priority: i8) -> i8;
@@ -17,7 +18,13 @@ pub type OnBeforeMove = fn(state: &mut BattleState, attacker: usize, move_id: Mo
 pub type OnModifyDamage = fn(state: &BattleState, attacker: usize, defender: usize, damage: u16) -> u16;
 
 /// Called after damage has been dealt
-pub type OnAfterDamage = fn(state: &mut BattleState, attacker: usize, defender: usize, damage: u16);
+pub type OnAfterDamage = fn(
+    state: &mut BattleState,
+    attacker: usize,
+    defender: usize,
+    damage: u16,
+    events: &mut dyn BattleSink,
+);
 
 /// Called when a stat boost is applied to modify the stage change
 pub type OnStatChange = fn(change: i8) -> i8;
@@ -1,21 +1,11 @@
 //! Item hook implementations.
 
+use crate::battle::{BattleSink, EffectSource, Event};
 use crate::damage::{apply_modifier, Modifier};
+use crate::items::ItemId;
 use crate::moves::MoveCategory;
 use crate::species::SpeciesId;
 use crate::state::BattleState;
-use std::sync::LazyLock;
-
-// Lazily-initialized constants for commonly checked species.
-// These are parsed once on first access and cached, eliminating repeated
-// string parsing overhead in the hot path of damage calculation.
-static CUBONE: LazyLock<Option<SpeciesId>> = LazyLock::new(|| SpeciesId::from_str("cubone"));
-static MAROWAK: LazyLock<Option<SpeciesId>> = LazyLock::new(|| SpeciesId::from_str("marowak"));
-static PIKACHU: LazyLock<Option<SpeciesId>> = LazyLock::new(|| SpeciesId::from_str("pikachu"));
-static CLAMPERL: LazyLock<Option<SpeciesId>> = LazyLock::new(|| SpeciesId::from_str("clamperl"));
-static LATIOS: LazyLock<Option<SpeciesId>> = LazyLock::new(|| SpeciesId::from_str("latios"));
-static LATIAS: LazyLock<Option<SpeciesId>> = LazyLock::new(|| SpeciesId::from_str("latias"));
-static DITTO: LazyLock<Option<SpeciesId>> = LazyLock::new(|| SpeciesId::from_str("ditto"));
 
 // Assault Vest: 1.5x SpD, but can only use damaging moves.
 // The move restriction part is handled elsewhere (or not at all yet).
@@ -60,11 +50,9 @@ pub fn on_modify_attack_thick_club(
 ) -> u16 {
     if category == MoveCategory::Physical {
         let species = state.species[attacker];
-        if let (Some(cubone), Some(marowak)) = (*CUBONE, *MAROWAK) {
-            if species == cubone || species == marowak {
-                return apply_modifier(attack.into(), Modifier::DOUBLE).max(1) as u16;
-                // 2x
-            }
+        if species == SpeciesId::CUBONE || species == SpeciesId::MAROWAK {
+            return apply_modifier(attack.into(), Modifier::DOUBLE).max(1) as u16;
+            // 2x
         }
     }
     attack
@@ -77,11 +65,9 @@ pub fn on_modify_attack_light_ball(
     _category: MoveCategory,
     attack: u16,
 ) -> u16 {
-    if let Some(pikachu) = *PIKACHU {
-        if state.species[attacker] == pikachu {
-            return apply_modifier(attack.into(), Modifier::DOUBLE).max(1) as u16;
-            // 2x
-        }
+    if state.species[attacker] == SpeciesId::PIKACHU {
+        return apply_modifier(attack.into(), Modifier::DOUBLE).max(1) as u16;
+        // 2x
     }
     attack
 }
@@ -121,12 +107,8 @@ pub fn on_modify_attack_deep_sea_tooth(
     category: MoveCategory,
     attack: u16,
 ) -> u16 {
-    if category == MoveCategory::Special {
-        if let Some(clamperl) = *CLAMPERL {
-            if state.species[attacker] == clamperl {
-                return apply_modifier(attack.into(), Modifier::DOUBLE).max(1) as u16;
-            }
-        }
+    if category == MoveCategory::Special && state.species[attacker] == SpeciesId::CLAMPERL {
+        return apply_modifier(attack.into(), Modifier::DOUBLE).max(1) as u16;
     }
     attack
 }
@@ -139,12 +121,8 @@ pub fn on_modify_defense_deep_sea_scale(
     category: MoveCategory,
     defense: u16,
 ) -> u16 {
-    if category == MoveCategory::Special {
-        if let Some(clamperl) = *CLAMPERL {
-            if state.species[defender] == clamperl {
-                return apply_modifier(defense.into(), Modifier::DOUBLE).max(1) as u16;
-            }
-        }
+    if category == MoveCategory::Special && state.species[defender] == SpeciesId::CLAMPERL {
+        return apply_modifier(defense.into(), Modifier::DOUBLE).max(1) as u16;
     }
     defense
 }
@@ -156,13 +134,8 @@ pub fn on_modify_attack_soul_dew(
     category: MoveCategory,
     attack: u16,
 ) -> u16 {
-    if category == MoveCategory::Special {
-        if let (Some(latios), Some(latias)) = (*LATIOS, *LATIAS) {
-            let species = state.species[attacker];
-            if species == latios || species == latias {
-                return apply_modifier(attack.into(), Modifier::ONE_POINT_TWO).max(1) as u16;
-            }
-        }
+    if category == MoveCategory::Special && matches!(state.species[attacker], SpeciesId::LATIOS | SpeciesId::LATIAS) {
+        return apply_modifier(attack.into(), Modifier::ONE_POINT_TWO).max(1) as u16;
     }
     attack
 }
@@ -175,13 +148,8 @@ pub fn on_modify_defense_soul_dew(
     category: MoveCategory,
     defense: u16,
 ) -> u16 {
-    if category == MoveCategory::Special {
-        if let (Some(latios), Some(latias)) = (*LATIOS, *LATIAS) {
-            let species = state.species[defender];
-            if species == latios || species == latias {
-                return apply_modifier(defense.into(), Modifier::ONE_POINT_TWO).max(1) as u16;
-            }
-        }
+    if category == MoveCategory::Special && matches!(state.species[defender], SpeciesId::LATIOS | SpeciesId::LATIAS) {
+        return apply_modifier(defense.into(), Modifier::ONE_POINT_TWO).max(1) as u16;
     }
     defense
 }
@@ -194,12 +162,8 @@ pub fn on_modify_defense_metal_powder(
     category: MoveCategory,
     defense: u16,
 ) -> u16 {
-    if category == MoveCategory::Physical {
-        if let Some(ditto) = *DITTO {
-            if state.species[defender] == ditto {
-                return apply_modifier(defense.into(), Modifier::DOUBLE).max(1) as u16;
-            }
-        }
+    if category == MoveCategory::Physical && state.species[defender] == SpeciesId::DITTO {
+        return apply_modifier(defense.into(), Modifier::DOUBLE).max(1) as u16;
     }
     defense
 }
@@ -243,391 +207,22 @@ pub fn on_attacker_final_mod_expert_belt(
 use crate::moves::Move;
 use crate::types::Type;
 
-// Generic type-boost helper - uses dynamic move_type (after type-changing effects)
-fn type_boost_bp(move_type: Type, boost_type: Type, bp: u16) -> u16 {
-    if move_type == boost_type {
-        apply_modifier(bp.into(), Modifier::ONE_POINT_TWO).max(1) as u16
-    } else {
-        bp
-    }
-}
-
-pub fn on_modify_bp_charcoal(
-    _state: &BattleState,
-    _attacker: usize,
-    _defender: usize,
-    _move_data: &Move,
-    move_type: Type,
-    bp: u16,
-) -> u16 {
-    type_boost_bp(move_type, Type::Fire, bp)
-}
-
-pub fn on_modify_bp_mystic_water(
-    _state: &BattleState,
-    _attacker: usize,
-    _defender: usize,
-    _move_data: &Move,
-    move_type: Type,
-    bp: u16,
-) -> u16 {
-    type_boost_bp(move_type, Type::Water, bp)
-}
-
-pub fn on_modify_bp_miracle_seed(
-    _state: &BattleState,
-    _attacker: usize,
-    _defender: usize,
-    _move_data: &Move,
-    move_type: Type,
-    bp: u16,
-) -> u16 {
-    type_boost_bp(move_type, Type::Grass, bp)
-}
-
-pub fn on_modify_bp_magnet(
-    _state: &BattleState,
-    _attacker: usize,
-    _defender: usize,
-    _move_data: &Move,
-    move_type: Type,
-    bp: u16,
-) -> u16 {
-    type_boost_bp(move_type, Type::Electric, bp)
-}
-
-pub fn on_modify_bp_never_melt_ice(
-    _state: &BattleState,
-    _attacker: usize,
-    _defender: usize,
-    _move_data: &Move,
-    move_type: Type,
-    bp: u16,
-) -> u16 {
-    type_boost_bp(move_type, Type::Ice, bp)
-}
-
-pub fn on_modify_bp_black_belt(
-    _state: &BattleState,
-    _attacker: usize,
-    _defender: usize,
-    _move_data: &Move,
-    move_type: Type,
-    bp: u16,
-) -> u16 {
-    type_boost_bp(move_type, Type::Fighting, bp)
-}
-
-pub fn on_modify_bp_poison_barb(
-    _state: &BattleState,
-    _attacker: usize,
-    _defender: usize,
-    _move_data: &Move,
-    move_type: Type,
-    bp: u16,
-) -> u16 {
-    type_boost_bp(move_type, Type::Poison, bp)
-}
-
-pub fn on_modify_bp_soft_sand(
-    _state: &BattleState,
-    _attacker: usize,
-    _defender: usize,
-    _move_data: &Move,
-    move_type: Type,
-    bp: u16,
-) -> u16 {
-    type_boost_bp(move_type, Type::Ground, bp)
-}
-
-pub fn on_modify_bp_sharp_beak(
-    _state: &BattleState,
-    _attacker: usize,
-    _defender: usize,
-    _move_data: &Move,
-    move_type: Type,
-    bp: u16,
-) -> u16 {
-    type_boost_bp(move_type, Type::Flying, bp)
-}
-
-pub fn on_modify_bp_twisted_spoon(
-    _state: &BattleState,
-    _attacker: usize,
-    _defender: usize,
-    _move_data: &Move,
-    move_type: Type,
-    bp: u16,
-) -> u16 {
-    type_boost_bp(move_type, Type::Psychic, bp)
-}
-
-pub fn on_modify_bp_silver_powder(
-    _state: &BattleState,
-    _attacker: usize,
-    _defender: usize,
-    _move_data: &Move,
-    move_type: Type,
-    bp: u16,
-) -> u16 {
-    type_boost_bp(move_type, Type::Bug, bp)
-}
-
-pub fn on_modify_bp_hard_stone(
-    _state: &BattleState,
-    _attacker: usize,
-    _defender: usize,
-    _move_data: &Move,
-    move_type: Type,
-    bp: u16,
-) -> u16 {
-    type_boost_bp(move_type, Type::Rock, bp)
-}
-
-pub fn on_modify_bp_spell_tag(
-    _state: &BattleState,
-    _attacker: usize,
-    _defender: usize,
-    _move_data: &Move,
-    move_type: Type,
-    bp: u16,
-) -> u16 {
-    type_boost_bp(move_type, Type::Ghost, bp)
-}
-
-pub fn on_modify_bp_dragon_fang(
-    _state: &BattleState,
-    _attacker: usize,
-    _defender: usize,
-    _move_data: &Move,
-    move_type: Type,
-    bp: u16,
-) -> u16 {
-    type_boost_bp(move_type, Type::Dragon, bp)
-}
-
-pub fn on_modify_bp_black_glasses(
-    _state: &BattleState,
-    _attacker: usize,
-    _defender: usize,
-    _move_data: &Move,
-    move_type: Type,
-    bp: u16,
-) -> u16 {
-    type_boost_bp(move_type, Type::Dark, bp)
-}
-
-pub fn on_modify_bp_metal_coat(
-    _state: &BattleState,
-    _attacker: usize,
-    _defender: usize,
-    _move_data: &Move,
-    move_type: Type,
-    bp: u16,
-) -> u16 {
-    type_boost_bp(move_type, Type::Steel, bp)
-}
-
-pub fn on_modify_bp_silk_scarf(
-    _state: &BattleState,
-    _attacker: usize,
-    _defender: usize,
-    _move_data: &Move,
-    move_type: Type,
-    bp: u16,
-) -> u16 {
-    type_boost_bp(move_type, Type::Normal, bp)
-}
-
-// ============================================================================
-// Plate Items (1.2x type boost, same as corresponding items)
-// ============================================================================
-
-pub fn on_modify_bp_flame_plate(
-    _state: &BattleState,
-    _attacker: usize,
-    _defender: usize,
-    _move_data: &Move,
-    move_type: Type,
-    bp: u16,
-) -> u16 {
-    type_boost_bp(move_type, Type::Fire, bp)
-}
-
-pub fn on_modify_bp_splash_plate(
-    _state: &BattleState,
-    _attacker: usize,
-    _defender: usize,
-    _move_data: &Move,
-    move_type: Type,
-    bp: u16,
-) -> u16 {
-    type_boost_bp(move_type, Type::Water, bp)
-}
-
-pub fn on_modify_bp_meadow_plate(
-    _state: &BattleState,
-    _attacker: usize,
-    _defender: usize,
-    _move_data: &Move,
-    move_type: Type,
-    bp: u16,
-) -> u16 {
-    type_boost_bp(move_type, Type::Grass, bp)
-}
-
-pub fn on_modify_bp_zap_plate(
-    _state: &BattleState,
-    _attacker: usize,
-    _defender: usize,
-    _move_data: &Move,
-    move_type: Type,
-    bp: u16,
-) -> u16 {
-    type_boost_bp(move_type, Type::Electric, bp)
-}
-
-pub fn on_modify_bp_icicle_plate(
-    _state: &BattleState,
-    _attacker: usize,
-    _defender: usize,
-    _move_data: &Move,
-    move_type: Type,
-    bp: u16,
-) -> u16 {
-    type_boost_bp(move_type, Type::Ice, bp)
-}
-
-pub fn on_modify_bp_fist_plate(
-    _state: &BattleState,
-    _attacker: usize,
-    _defender: usize,
-    _move_data: &Move,
-    move_type: Type,
-    bp: u16,
-) -> u16 {
-    type_boost_bp(move_type, Type::Fighting, bp)
-}
-
-pub fn on_modify_bp_toxic_plate(
-    _state: &BattleState,
-    _attacker: usize,
-    _defender: usize,
-    _move_data: &Move,
-    move_type: Type,
-    bp: u16,
-) -> u16 {
-    type_boost_bp(move_type, Type::Poison, bp)
-}
-
-pub fn on_modify_bp_earth_plate(
-    _state: &BattleState,
-    _attacker: usize,
-    _defender: usize,
-    _move_data: &Move,
-    move_type: Type,
-    bp: u16,
-) -> u16 {
-    type_boost_bp(move_type, Type::Ground, bp)
-}
-
-pub fn on_modify_bp_sky_plate(
-    _state: &BattleState,
-    _attacker: usize,
-    _defender: usize,
-    _move_data: &Move,
-    move_type: Type,
-    bp: u16,
-) -> u16 {
-    type_boost_bp(move_type, Type::Flying, bp)
-}
-
-pub fn on_modify_bp_mind_plate(
-    _state: &BattleState,
-    _attacker: usize,
-    _defender: usize,
-    _move_data: &Move,
-    move_type: Type,
-    bp: u16,
-) -> u16 {
-    type_boost_bp(move_type, Type::Psychic, bp)
-}
-
-pub fn on_modify_bp_insect_plate(
-    _state: &BattleState,
-    _attacker: usize,
-    _defender: usize,
-    _move_data: &Move,
-    move_type: Type,
-    bp: u16,
-) -> u16 {
-    type_boost_bp(move_type, Type::Bug, bp)
-}
-
-pub fn on_modify_bp_stone_plate(
-    _state: &BattleState,
-    _attacker: usize,
-    _defender: usize,
-    _move_data: &Move,
-    move_type: Type,
-    bp: u16,
-) -> u16 {
-    type_boost_bp(move_type, Type::Rock, bp)
-}
-
-pub fn on_modify_bp_spooky_plate(
-    _state: &BattleState,
-    _attacker: usize,
-    _defender: usize,
-    _move_data: &Move,
-    move_type: Type,
-    bp: u16,
-) -> u16 {
-    type_boost_bp(move_type, Type::Ghost, bp)
-}
-
-pub fn on_modify_bp_draco_plate(
-    _state: &BattleState,
-    _attacker: usize,
-    _defender: usize,
-    _move_data: &Move,
-    move_type: Type,
-    bp: u16,
-) -> u16 {
-    type_boost_bp(move_type, Type::Dragon, bp)
-}
-
-pub fn on_modify_bp_dread_plate(
-    _state: &BattleState,
-    _attacker: usize,
-    _defender: usize,
-    _move_data: &Move,
-    move_type: Type,
-    bp: u16,
-) -> u16 {
-    type_boost_bp(move_type, Type::Dark, bp)
-}
-
-pub fn on_modify_bp_iron_plate(
-    _state: &BattleState,
-    _attacker: usize,
-    _defender: usize,
-    _move_data: &Move,
-    move_type: Type,
-    bp: u16,
-) -> u16 {
-    type_boost_bp(move_type, Type::Steel, bp)
-}
-
-pub fn on_modify_bp_pixie_plate(
-    _state: &BattleState,
-    _attacker: usize,
+/// Type-boosting items, incenses and Plates: 1.2x base power for moves of
+/// the item's `boosted_type`. Uses the dynamic move type (after
+/// type-changing effects).
+pub fn on_modify_bp_type_boost(
+    state: &BattleState,
+    attacker: usize,
     _defender: usize,
     _move_data: &Move,
     move_type: Type,
     bp: u16,
 ) -> u16 {
-    type_boost_bp(move_type, Type::Fairy, bp)
+    if state.items[attacker].data().boosted_type == Some(move_type) {
+        apply_modifier(bp.into(), Modifier::ONE_POINT_TWO).max(1) as u16
+    } else {
+        bp
+    }
 }
 
 // ============================================================================
@@ -690,6 +285,22 @@ pub fn on_check_grounded_air_balloon(_state: &BattleState, _entity: usize) -> Op
     Some(false)
 }
 
+// Air Balloon: Pops as soon as the holder is hit
+pub fn on_after_damage_air_balloon(
+    state: &mut BattleState,
+    _attacker: usize,
+    defender: usize,
+    damage: u16,
+    events: &mut dyn BattleSink,
+) {
+    if damage == 0 {
+        return;
+    }
+    let item = state.items[defender];
+    state.items[defender] = ItemId::None;
+    events.event(state, &Event::ItemConsumed { entity: defender, item });
+}
+
 // Iron Ball: Grounded (returns Some(true))
 pub fn on_check_grounded_iron_ball(_state: &BattleState, _entity: usize) -> Option<bool> {
     Some(true)
@@ -703,3 +314,30 @@ pub fn on_hazard_immunity_heavy_duty_boots(
 ) -> bool {
     true
 }
+
+// ============================================================================
+// After-damage Items (OnAfterDamage)
+// ============================================================================
+
+// Sitrus Berry: Restores 1/4 max HP once the holder falls to 1/2 or less
+pub fn on_after_damage_sitrus_berry(
+    state: &mut BattleState,
+    _attacker: usize,
+    defender: usize,
+    _damage: u16,
+    events: &mut dyn BattleSink,
+) {
+    let hp = state.hp[defender];
+    let max_hp = state.max_hp[defender];
+    if hp == 0 || hp > max_hp / 2 {
+        return;
+    }
+    let item = state.items[defender];
+    state.items[defender] = ItemId::None;
+    events.event(state, &Event::ItemConsumed { entity: defender, item });
+
+    let amount = (max_hp / 4).min(max_hp - hp);
+    state.hp[defender] = hp + amount;
+    let source = EffectSource::Item(item);
+    events.event(state, &Event::Heal { target: defender, amount, source });
+}
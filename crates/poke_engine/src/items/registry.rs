@@ -1,6 +1,6 @@
 use crate::items::hooks::ItemHooks;
 use crate::items::implementations::*;
-use crate::items::ItemId;
+use crate::items::{ItemId, ITEMS};
 
 pub static ITEM_REGISTRY: [Option<ItemHooks>; ItemId::COUNT] = {
     let mut registry: [Option<ItemHooks>; ItemId::COUNT] = [None; ItemId::COUNT];
@@ -82,160 +82,17 @@ pub static ITEM_REGISTRY: [Option<ItemHooks>; ItemId::COUNT] = {
     // Type-Boosting Items (OnModifyBasePower)
     // =========================================================================
 
-    registry[ItemId::Charcoal as usize] = Some(ItemHooks {
-        on_modify_base_power: Some(on_modify_bp_charcoal),
-        ..ItemHooks::NONE
-    });
-
-    registry[ItemId::Mysticwater as usize] = Some(ItemHooks {
-        on_modify_base_power: Some(on_modify_bp_mystic_water),
-        ..ItemHooks::NONE
-    });
-
-    registry[ItemId::Miracleseed as usize] = Some(ItemHooks {
-        on_modify_base_power: Some(on_modify_bp_miracle_seed),
-        ..ItemHooks::NONE
-    });
-
-    registry[ItemId::Magnet as usize] = Some(ItemHooks {
-        on_modify_base_power: Some(on_modify_bp_magnet),
-        ..ItemHooks::NONE
-    });
-
-    registry[ItemId::Nevermeltice as usize] = Some(ItemHooks {
-        on_modify_base_power: Some(on_modify_bp_never_melt_ice),
-        ..ItemHooks::NONE
-    });
-
-    registry[ItemId::Blackbelt as usize] = Some(ItemHooks {
-        on_modify_base_power: Some(on_modify_bp_black_belt),
-        ..ItemHooks::NONE
-    });
-
-    registry[ItemId::Poisonbarb as usize] = Some(ItemHooks {
-        on_modify_base_power: Some(on_modify_bp_poison_barb),
-        ..ItemHooks::NONE
-    });
-
-    registry[ItemId::Softsand as usize] = Some(ItemHooks {
-        on_modify_base_power: Some(on_modify_bp_soft_sand),
-        ..ItemHooks::NONE
-    });
-
-    registry[ItemId::Sharpbeak as usize] = Some(ItemHooks {
-        on_modify_base_power: Some(on_modify_bp_sharp_beak),
-        ..ItemHooks::NONE
-    });
-
-    registry[ItemId::Twistedspoon as usize] = Some(ItemHooks {
-        on_modify_base_power: Some(on_modify_bp_twisted_spoon),
-        ..ItemHooks::NONE
-    });
-
-    registry[ItemId::Silverpowder as usize] = Some(ItemHooks {
-        on_modify_base_power: Some(on_modify_bp_silver_powder),
-        ..ItemHooks::NONE
-    });
-
-    registry[ItemId::Hardstone as usize] = Some(ItemHooks {
-        on_modify_base_power: Some(on_modify_bp_hard_stone),
-        ..ItemHooks::NONE
-    });
-
-    registry[ItemId::Spelltag as usize] = Some(ItemHooks {
-        on_modify_base_power: Some(on_modify_bp_spell_tag),
-        ..ItemHooks::NONE
-    });
-
-    registry[ItemId::Dragonfang as usize] = Some(ItemHooks {
-        on_modify_base_power: Some(on_modify_bp_dragon_fang),
-        ..ItemHooks::NONE
-    });
-
-    registry[ItemId::Blackglasses as usize] = Some(ItemHooks {
-        on_modify_base_power: Some(on_modify_bp_black_glasses),
-        ..ItemHooks::NONE
-    });
-
-    registry[ItemId::Metalcoat as usize] = Some(ItemHooks {
-        on_modify_base_power: Some(on_modify_bp_metal_coat),
-        ..ItemHooks::NONE
-    });
-
-    registry[ItemId::Silkscarf as usize] = Some(ItemHooks {
-        on_modify_base_power: Some(on_modify_bp_silk_scarf),
-        ..ItemHooks::NONE
-    });
-
-    // Plates (1.2x type boost)
-    registry[ItemId::Flameplate as usize] = Some(ItemHooks {
-        on_modify_base_power: Some(on_modify_bp_flame_plate),
-        ..ItemHooks::NONE
-    });
-    registry[ItemId::Splashplate as usize] = Some(ItemHooks {
-        on_modify_base_power: Some(on_modify_bp_splash_plate),
-        ..ItemHooks::NONE
-    });
-    registry[ItemId::Meadowplate as usize] = Some(ItemHooks {
-        on_modify_base_power: Some(on_modify_bp_meadow_plate),
-        ..ItemHooks::NONE
-    });
-    registry[ItemId::Zapplate as usize] = Some(ItemHooks {
-        on_modify_base_power: Some(on_modify_bp_zap_plate),
-        ..ItemHooks::NONE
-    });
-    registry[ItemId::Icicleplate as usize] = Some(ItemHooks {
-        on_modify_base_power: Some(on_modify_bp_icicle_plate),
-        ..ItemHooks::NONE
-    });
-    registry[ItemId::Fistplate as usize] = Some(ItemHooks {
-        on_modify_base_power: Some(on_modify_bp_fist_plate),
-        ..ItemHooks::NONE
-    });
-    registry[ItemId::Toxicplate as usize] = Some(ItemHooks {
-        on_modify_base_power: Some(on_modify_bp_toxic_plate),
-        ..ItemHooks::NONE
-    });
-    registry[ItemId::Earthplate as usize] = Some(ItemHooks {
-        on_modify_base_power: Some(on_modify_bp_earth_plate),
-        ..ItemHooks::NONE
-    });
-    registry[ItemId::Skyplate as usize] = Some(ItemHooks {
-        on_modify_base_power: Some(on_modify_bp_sky_plate),
-        ..ItemHooks::NONE
-    });
-    registry[ItemId::Mindplate as usize] = Some(ItemHooks {
-        on_modify_base_power: Some(on_modify_bp_mind_plate),
-        ..ItemHooks::NONE
-    });
-    registry[ItemId::Insectplate as usize] = Some(ItemHooks {
-        on_modify_base_power: Some(on_modify_bp_insect_plate),
-        ..ItemHooks::NONE
-    });
-    registry[ItemId::Stoneplate as usize] = Some(ItemHooks {
-        on_modify_base_power: Some(on_modify_bp_stone_plate),
-        ..ItemHooks::NONE
-    });
-    registry[ItemId::Spookyplate as usize] = Some(ItemHooks {
-        on_modify_base_power: Some(on_modify_bp_spooky_plate),
-        ..ItemHooks::NONE
-    });
-    registry[ItemId::Dracoplate as usize] = Some(ItemHooks {
-        on_modify_base_power: Some(on_modify_bp_draco_plate),
-        ..ItemHooks::NONE
-    });
-    registry[ItemId::Dreadplate as usize] = Some(ItemHooks {
-        on_modify_base_power: Some(on_modify_bp_dread_plate),
-        ..ItemHooks::NONE
-    });
-    registry[ItemId::Ironplate as usize] = Some(ItemHooks {
-        on_modify_base_power: Some(on_modify_bp_iron_plate),
-        ..ItemHooks::NONE
-    });
-    registry[ItemId::Pixieplate as usize] = Some(ItemHooks {
-        on_modify_base_power: Some(on_modify_bp_pixie_plate),
-        ..ItemHooks::NONE
-    });
+    // Type-boosting items and Plates, from the generated `boosted_type`
+    let mut id = 0;
+    while id < ItemId::COUNT {
+        if ITEMS[id].boosted_type.is_some() {
+            registry[id] = Some(ItemHooks {
+                on_modify_base_power: Some(on_modify_bp_type_boost),
+                ..ItemHooks::NONE
+            });
+        }
+        id += 1;
+    }
 
     // =========================================================================
     // Speed Modifiers (OnModifySpeed)
@@ -258,6 +115,16 @@ pub static ITEM_REGISTRY: [Option<ItemHooks>; ItemId::COUNT] = {
 
     registry[ItemId::Airballoon as usize] = Some(ItemHooks {
         on_check_grounded: Some(on_check_grounded_air_balloon),
+        on_after_damage: Some(on_after_damage_air_balloon),
+        ..ItemHooks::NONE
+    });
+
+    // =========================================================================
+    // After-damage Items (OnAfterDamage)
+    // =========================================================================
+
+    registry[ItemId::Sitrusberry as usize] = Some(ItemHooks {
+        on_after_damage: Some(on_after_damage_sitrus_berry),
         ..ItemHooks::NONE
     });
 
@@ -0,0 +1,161 @@
+//! Learnsets and move legality.
+//!
+//! Generated from Showdown's `learnsets.json`. A species can learn a move
+//! if it or any learnset parent (prevolution, or the base forme for formes
+//! that extend it) has a source for it from the requested generation or
+//! earlier. Transfer restrictions between games, event-only combinations
+//! and level limits on prevolution moves are not modeled.
+
+use crate::moves::MoveId;
+use crate::species::SpeciesId;
+
+include!(concat!(env!("OUT_DIR"), "/learnsets.rs"));
+
+/// How a move is obtained (Showdown's source letters).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum LearnMethod {
+    /// Level-up at the given level (`L`)
+    LevelUp(u8),
+    /// TM, HM or TR (`M`)
+    Machine,
+    /// Move tutor (`T`)
+    Tutor,
+    /// Egg move (`E`)
+    Egg,
+    /// Event distribution (`S`)
+    Event,
+    /// Dream World (`D`)
+    DreamWorld,
+    /// Virtual Console transfer (`V`)
+    VirtualConsole,
+    /// Forme- or item-restricted move such as Rotom's appliance moves (`R`)
+    Restricted,
+}
+
+impl LearnMethod {
+    fn unpack(source: u16) -> Option<Self> {
+        Some(match (source >> 8) & 0xF {
+            0 => LearnMethod::LevelUp(source as u8),
+            1 => LearnMethod::Machine,
+            2 => LearnMethod::Tutor,
+            3 => LearnMethod::Egg,
+            4 => LearnMethod::Event,
+            5 => LearnMethod::DreamWorld,
+            6 => LearnMethod::VirtualConsole,
+            7 => LearnMethod::Restricted,
+            _ => return None,
+        })
+    }
+}
+
+/// Learnset parents are followed at most this many times (guards data cycles)
+const MAX_CHAIN: usize = 8;
+
+struct Tables<'a> {
+    ranges: &'a [(u32, u16)],
+    parents: &'a [u16],
+    entries: &'a [(u16, u32, u8)],
+    sources: &'a [u16],
+}
+
+static TABLES: Tables<'static> = Tables {
+    ranges: &LEARNSET_RANGES,
+    parents: &LEARNSET_PARENTS,
+    entries: &LEARNSET_ENTRIES,
+    sources: &LEARNSET_SOURCES,
+};
+
+impl Tables<'_> {
+    /// Newest source of `move_id` in `species`' own learnset up to `gen`.
+    fn own_source(&self, species: usize, move_id: u16, gen: u8) -> Option<u16> {
+        let (start, len) = *self.ranges.get(species)?;
+        let entries = &self.entries[start as usize..start as usize + len as usize];
+        let index = entries.binary_search_by_key(&move_id, |e| e.0).ok()?;
+        let (_, first, count) = entries[index];
+        self.sources[first as usize..first as usize + count as usize]
+            .iter()
+            .copied()
+            .find(|source| source >> 12 <= gen as u16)
+    }
+
+    fn can_learn(&self, species: usize, move_id: u16, gen: u8) -> Option<LearnMethod> {
+        let mut species = species;
+        for _ in 0..MAX_CHAIN {
+            if let Some(source) = self.own_source(species, move_id, gen) {
+                return LearnMethod::unpack(source);
+            }
+            match self.parents.get(species) {
+                Some(&parent) if parent != 0 => species = parent as usize - 1,
+                _ => return None,
+            }
+        }
+        None
+    }
+}
+
+impl SpeciesId {
+    /// How this species can learn `move_id` in generation `gen` (1-9),
+    /// or `None` if it cannot.
+    ///
+    /// Always `None` when the engine was built without `learnsets.json`
+    /// (see `LEARNSETS_LOADED`).
+    pub fn can_learn(self, move_id: MoveId, gen: u8) -> Option<LearnMethod> {
+        TABLES.can_learn(self.0 as usize, move_id as u16, gen)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const L: u16 = 0 << 8;
+    const M: u16 = 1 << 8;
+    const E: u16 = 3 << 8;
+
+    // 0: Bulbasaur, 1: Ivysaur (prevo 0), 2: a forme with no data (parent 1)
+    static TEST_TABLES: Tables<'static> = Tables {
+        ranges: &[(0, 2), (2, 1), (3, 0)],
+        parents: &[0, 1, 2],
+        entries: &[(10, 0, 2), (20, 2, 1), (30, 3, 1)],
+        sources: &[9 << 12 | M, 7 << 12 | L | 13, 4 << 12 | E, 9 << 12 | L | 1],
+    };
+
+    #[test]
+    fn test_prefers_newest_source_in_gen() {
+        // Gen 9 sees the TM; Gen 7 only the older level-up source.
+        assert_eq!(TEST_TABLES.can_learn(0, 10, 9), Some(LearnMethod::Machine));
+        assert_eq!(
+            TEST_TABLES.can_learn(0, 10, 7),
+            Some(LearnMethod::LevelUp(13))
+        );
+        assert_eq!(TEST_TABLES.can_learn(0, 10, 6), None);
+        assert_eq!(TEST_TABLES.can_learn(0, 20, 3), None);
+    }
+
+    #[test]
+    fn test_inherits_through_prevo_and_forme() {
+        // Egg move from the prevolution, reached from a data-less forme.
+        assert_eq!(TEST_TABLES.can_learn(2, 20, 9), Some(LearnMethod::Egg));
+        assert_eq!(
+            TEST_TABLES.can_learn(2, 30, 9),
+            Some(LearnMethod::LevelUp(1))
+        );
+        assert_eq!(TEST_TABLES.can_learn(0, 30, 9), None);
+    }
+
+    #[test]
+    fn test_generated_tables_cover_every_species() {
+        assert_eq!(LEARNSET_RANGES.len(), SpeciesId::COUNT);
+        assert_eq!(LEARNSET_PARENTS.len(), SpeciesId::COUNT);
+    }
+
+    #[test]
+    #[ignore = "needs data/learnsets.json (run scripts/sync_showdown_data.mts)"]
+    fn test_generated_lookups() {
+        let bulbasaur = SpeciesId::BULBASAUR;
+        assert!(bulbasaur.can_learn(MoveId::Vinewhip, 9).is_some());
+        assert_eq!(bulbasaur.can_learn(MoveId::Surf, 9), None);
+        // Venusaur inherits its prevolution's moves.
+        assert!(SpeciesId::VENUSAUR.can_learn(MoveId::Vinewhip, 9).is_some());
+    }
+}
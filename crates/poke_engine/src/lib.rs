@@ -2,9 +2,15 @@
 //!
 //! This library provides stack-allocated, cache-friendly data structures
 //! optimized for Monte Carlo and Minimax AI analysis.
+//!
+//! The core engine is `#![no_std]` + `alloc` when the default `std`
+//! feature is disabled; team JSON import and the front ends need `std`.
 
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 #![allow(clippy::transmute_int_to_non_zero)]
 
+extern crate alloc;
+
 /// Type definitions and type chart
 pub mod types {
     include!(concat!(env!("OUT_DIR"), "/types.rs"));
@@ -29,6 +35,15 @@ pub mod moves;
 /// Item identifiers and hooks
 pub mod items;
 
+/// Learnsets and move legality
+pub mod learnsets;
+
+/// Introduction generations and nonstandard availability
+pub mod dex;
+
+/// Runtime custom species and moves
+pub mod custom;
+
 /// Terrain definitions
 pub mod terrains {
     include!(concat!(env!("OUT_DIR"), "/terrains.rs"));
@@ -43,10 +58,32 @@ pub mod entities;
 /// Damage calculation pipeline
 pub mod damage;
 
+/// Turn execution and battle output
+pub mod battle;
+
+/// Team import (Showdown export format and JSON)
+pub mod team;
+
+/// Formats: ban lists, clauses and level caps
+pub mod format;
+
+/// Team validation against a format
+pub mod validate;
+
+/// JavaScript bindings via wasm-bindgen
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+/// C ABI over opaque battle handles
+#[cfg(feature = "ffi")]
+pub mod ffi;
+
 // Re-export commonly used types
 pub use abilities::AbilityId;
 pub use entities::PokemonConfig;
+pub use format::{Clause, Format, Gimmicks};
 pub use items::ItemId;
+pub use learnsets::LearnMethod;
 pub use moves::{Move, MoveCategory, MoveFlags, MoveId};
 pub use natures::{BattleStat, NatureId};
 pub use terrains::TerrainId;
@@ -54,6 +91,8 @@ pub use species::{Species, SpeciesId};
 pub use state::BattleState;
 pub use types::{Type, TypeEffectiveness, TypeImmunities};
 pub use damage::{calculate_damage, DamageResult, Gen9, Generation};
+pub use dex::Nonstandard;
+pub use validate::{learnset_warning, validate_team, ValidationError};
 
 #[cfg(test)]
 mod tests {
@@ -138,6 +177,37 @@ mod tests {
         assert_eq!(data.fling_power, 30);
     }
 
+    #[test]
+    fn test_item_data() {
+        // Plates boost and set Judgment's type; Z-Crystals only do the latter.
+        let plate = ItemId::Flameplate.data();
+        assert_eq!(plate.boosted_type, Some(Type::Fire));
+        assert_eq!(plate.plate_type, Some(Type::Fire));
+        assert_eq!(plate.forced_forme, SpeciesId::from_str("arceusfire"));
+        let firium = ItemId::Firiumz.data();
+        assert_eq!(firium.boosted_type, None);
+        assert_eq!(firium.z_crystal, Some(items::ZCrystal::Type(Type::Fire)));
+        assert_eq!(ItemId::Charcoal.data().boosted_type, Some(Type::Fire));
+
+        let sitrus = ItemId::Sitrusberry.data();
+        assert!(sitrus.is_berry);
+        assert_eq!(sitrus.natural_gift, Some((Type::Psychic, 80)));
+
+        let venusaurite = ItemId::Venusaurite.data();
+        assert_eq!(
+            venusaurite.mega_stone,
+            Some((SpeciesId::VENUSAUR, SpeciesId::VENUSAURMEGA))
+        );
+        assert_eq!(venusaurite.item_user, &[SpeciesId::VENUSAUR]);
+        assert_eq!(
+            ItemId::Pikaniumz.data().z_crystal,
+            Some(items::ZCrystal::Signature {
+                base: MoveId::Volttackle,
+                z_move: MoveId::Catastropika,
+            })
+        );
+    }
+
     #[test]
     fn test_move_data_and_terrain() {
         // Test Electric Terrain move
@@ -9,7 +9,6 @@ use crate::moves::Move;
 use crate::species::SpeciesId;
 use crate::state::{BattleState, Status};
 use crate::types::Type;
-use std::sync::OnceLock;
 
 // ============================================================================
 // Knock Off: 1.5x if target has removable item
@@ -156,7 +155,7 @@ pub fn freeze_dry_effectiveness(
     effectiveness: u8,
     _type_chart: &dyn Fn(Type, Type) -> u8,
 ) -> u8 {
-    let def_types = state.types[defender];
+    let def_types = state.defensive_types(defender);
     if def_types[0] == Type::Water || def_types[1] == Type::Water {
         // Normally 0.5x (2) -> 2x (8) requires * 4.
         // If Water/Water: 0.5x -> 2x.
@@ -178,7 +177,7 @@ pub fn flying_press_effectiveness(
     effectiveness: u8,
     type_chart: &dyn Fn(Type, Type) -> u8,
 ) -> u8 {
-    let def_types = state.types[defender];
+    let def_types = state.defensive_types(defender);
 
     // effectiveness passed in is Fighting vs Target.
     // We need to calculate Flying vs Target.
@@ -208,7 +207,7 @@ pub fn thousand_arrows_effectiveness(
     effectiveness: u8,
     type_chart: &dyn Fn(Type, Type) -> u8,
 ) -> u8 {
-    let def_types = state.types[defender];
+    let def_types = state.defensive_types(defender);
     let t1 = def_types[0];
     let t2 = if def_types[1] != def_types[0] {
         Some(def_types[1])
@@ -416,50 +415,43 @@ pub fn frustration_power(
 // Judgment / Techno Blast / Multi-Attack: Type changes based on held item
 // ============================================================================
 
-/// Maps a Plate item to its corresponding Type.
-fn plate_to_type(item: ItemId) -> Option<Type> {
-    match item {
-        ItemId::Flameplate => Some(Type::Fire),
-        ItemId::Splashplate => Some(Type::Water),
-        ItemId::Meadowplate => Some(Type::Grass),
-        ItemId::Zapplate => Some(Type::Electric),
-        ItemId::Icicleplate => Some(Type::Ice),
-        ItemId::Fistplate => Some(Type::Fighting),
-        ItemId::Toxicplate => Some(Type::Poison),
-        ItemId::Earthplate => Some(Type::Ground),
-        ItemId::Skyplate => Some(Type::Flying),
-        ItemId::Mindplate => Some(Type::Psychic),
-        ItemId::Insectplate => Some(Type::Bug),
-        ItemId::Stoneplate => Some(Type::Rock),
-        ItemId::Spookyplate => Some(Type::Ghost),
-        ItemId::Dracoplate => Some(Type::Dragon),
-        ItemId::Dreadplate => Some(Type::Dark),
-        ItemId::Ironplate => Some(Type::Steel),
-        ItemId::Pixieplate => Some(Type::Fairy),
-        _ => None,
-    }
+/// Judgment: Type changes based on held Plate (or type Z-Crystal).
+pub fn on_modify_type_judgment(
+    state: &BattleState,
+    attacker: usize,
+    _defender: usize,
+    _move_data: &'static Move,
+    base_type: Type,
+) -> Type {
+    state.items[attacker].data().plate_type.unwrap_or(base_type)
 }
 
-/// Judgment: Type changes based on held Plate.
-pub fn on_modify_type_judgment(
+/// Multi-Attack: Type changes based on held Memory.
+pub fn on_modify_type_multi_attack(
+    state: &BattleState,
+    attacker: usize,
+    _defender: usize,
+    _move_data: &'static Move,
+    base_type: Type,
+) -> Type {
+    state.items[attacker].data().memory_type.unwrap_or(base_type)
+}
+
+/// Techno Blast: Type changes based on held Drive.
+pub fn on_modify_type_techno_blast(
     state: &BattleState,
     attacker: usize,
     _defender: usize,
     _move_data: &'static Move,
     base_type: Type,
 ) -> Type {
-    let item = state.items[attacker];
-    plate_to_type(item).unwrap_or_else(|| base_type)
+    state.items[attacker].data().drive_type.unwrap_or(base_type)
 }
 
 // ============================================================================
 // Raging Bull: Type changes based on user's form
 // ============================================================================
 
-static TAUROS_PALDEA_COMBAT: OnceLock<Option<SpeciesId>> = OnceLock::new();
-static TAUROS_PALDEA_BLAZE: OnceLock<Option<SpeciesId>> = OnceLock::new();
-static TAUROS_PALDEA_AQUA: OnceLock<Option<SpeciesId>> = OnceLock::new();
-
 pub fn on_modify_type_raging_bull(
     state: &BattleState,
     attacker: usize,
@@ -469,16 +461,59 @@ pub fn on_modify_type_raging_bull(
 ) -> Type {
     let species = state.species[attacker];
 
-    let combat = *TAUROS_PALDEA_COMBAT.get_or_init(|| SpeciesId::from_str("taurospaldeacombat"));
-    let blaze = *TAUROS_PALDEA_BLAZE.get_or_init(|| SpeciesId::from_str("taurospaldeablaze"));
-    let aqua = *TAUROS_PALDEA_AQUA.get_or_init(|| SpeciesId::from_str("taurospaldeaaqua"));
+    match species {
+        SpeciesId::TAUROSPALDEACOMBAT => Type::Fighting,
+        SpeciesId::TAUROSPALDEABLAZE => Type::Fire,
+        SpeciesId::TAUROSPALDEAAQUA => Type::Water,
+        _ => base_type,
+    }
+}
+
+// ============================================================================
+// Tera Blast / Tera Starstorm: Type follows Terastallization
+// ============================================================================
 
-    if Some(species) == combat {
-        Type::Fighting
-    } else if Some(species) == blaze {
-        Type::Fire
-    } else if Some(species) == aqua {
-        Type::Water
+/// Tera Blast: Becomes the user's Tera Type once it has Terastallized.
+pub fn on_modify_type_tera_blast(
+    state: &BattleState,
+    attacker: usize,
+    _defender: usize,
+    _move_data: &'static Move,
+    base_type: Type,
+) -> Type {
+    if state.terastallized[attacker] {
+        state.tera_type[attacker]
+    } else {
+        base_type
+    }
+}
+
+/// Tera Blast: 100 BP as a Stellar-type move.
+pub fn on_modify_base_power_tera_blast(
+    _state: &BattleState,
+    _attacker: usize,
+    _defender: usize,
+    _move_data: &'static Move,
+    move_type: Type,
+    bp: u16,
+) -> u16 {
+    if move_type == Type::Stellar {
+        100
+    } else {
+        bp
+    }
+}
+
+/// Tera Starstorm: Stellar-type when used by Terapagos-Stellar.
+pub fn on_modify_type_tera_starstorm(
+    state: &BattleState,
+    attacker: usize,
+    _defender: usize,
+    _move_data: &'static Move,
+    base_type: Type,
+) -> Type {
+    if state.species[attacker] == SpeciesId::TERAPAGOSSTELLAR {
+        Type::Stellar
     } else {
         base_type
     }
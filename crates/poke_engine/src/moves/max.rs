@@ -0,0 +1,596 @@
+//! Max Moves (Gen 8 Dynamax).
+//!
+//! While Dynamaxed, every damaging move is replaced by the Max Move of its
+//! type and every status move by Max Guard. Max Move power comes from
+//! `GenMechanics::max_move_power`; the category stays the base move's.
+//!
+//! A Pokémon with the Gigantamax Factor changes into its `-Gmax` forme
+//! instead, and moves of its signature type become its G-Max Move. The
+//! G-Max Moves in `UNSUPPORTED_GMAX_MOVES` have effects the battle engine
+//! doesn't model, so team validation rejects the Gigantamax Factor on the
+//! species that use them.
+
+use crate::abilities::Terrain;
+use crate::damage::generations::Weather;
+use crate::moves::{MoveCategory, MoveFlags, MoveId, MOVE_REGISTRY};
+use crate::species::SpeciesId;
+use crate::state::{BattleState, Status};
+use crate::types::Type;
+
+/// Secondary effect of a Max Move, applied after it hits.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MaxEffect {
+    /// Raise a stat of the user by one stage (`BattleState::boosts` index)
+    RaiseUser(usize),
+    /// Lower a stat of the target by one stage (`BattleState::boosts` index)
+    LowerTarget(usize),
+    /// Lower a stat of the target by two stages (G-Max Foam Burst)
+    HarshlyLowerTarget(usize),
+    /// Inflict one of the given statuses on the target, picked at random
+    /// (G-Max Befuddle, Volt Crash, Malodor, Stun Shock)
+    Status(&'static [Status]),
+    /// Start the weather for 5 turns
+    Weather(Weather),
+    /// Start the terrain for 5 turns
+    Terrain(Terrain),
+    /// Damage the target's side by 1/6 max HP for 4 turns, except
+    /// Pokémon of the given type (G-Max Wildfire, Vine Lash, Cannonade,
+    /// Volcalith)
+    SideResidual(Type),
+    /// Set a Steel-type Stealth Rock on the target's side (G-Max Steelsurge)
+    Steelsurge,
+    /// Set Stealth Rock on the target's side (G-Max Stone Surge)
+    StealthRock,
+    /// Set Aurora Veil on the user's side for 5 turns (G-Max Resonance)
+    AuroraVeil,
+    /// Clear the target's screens, hazards on both sides and the terrain
+    /// (G-Max Wind Rage)
+    Defog,
+    /// Keep the target from switching out (G-Max Terror)
+    Trap,
+    /// Trap and damage the target for 4-5 turns (G-Max Sandblast, Centiferno)
+    PartialTrap,
+    /// Cure the status of every Pokémon on the user's side (G-Max Sweetness)
+    CureSide,
+    /// Heal the user by 1/6 of its max HP (G-Max Finale)
+    HealUser,
+    /// Take 2 PP from the target's last move (G-Max Depletion)
+    Depletion,
+}
+
+/// Each Gigantamax species, its `-Gmax` forme, and the G-Max Move
+/// replacing the Max Move of the given type.
+const GMAX_MOVES: [(SpeciesId, SpeciesId, Type, MoveId); 34] = [
+    (
+        SpeciesId::VENUSAUR,
+        SpeciesId::VENUSAURGMAX,
+        Type::Grass,
+        MoveId::Gmaxvinelash,
+    ),
+    (
+        SpeciesId::CHARIZARD,
+        SpeciesId::CHARIZARDGMAX,
+        Type::Fire,
+        MoveId::Gmaxwildfire,
+    ),
+    (
+        SpeciesId::BLASTOISE,
+        SpeciesId::BLASTOISEGMAX,
+        Type::Water,
+        MoveId::Gmaxcannonade,
+    ),
+    (
+        SpeciesId::BUTTERFREE,
+        SpeciesId::BUTTERFREEGMAX,
+        Type::Bug,
+        MoveId::Gmaxbefuddle,
+    ),
+    (
+        SpeciesId::PIKACHU,
+        SpeciesId::PIKACHUGMAX,
+        Type::Electric,
+        MoveId::Gmaxvoltcrash,
+    ),
+    (
+        SpeciesId::MEOWTH,
+        SpeciesId::MEOWTHGMAX,
+        Type::Normal,
+        MoveId::Gmaxgoldrush,
+    ),
+    (
+        SpeciesId::MACHAMP,
+        SpeciesId::MACHAMPGMAX,
+        Type::Fighting,
+        MoveId::Gmaxchistrike,
+    ),
+    (
+        SpeciesId::GENGAR,
+        SpeciesId::GENGARGMAX,
+        Type::Ghost,
+        MoveId::Gmaxterror,
+    ),
+    (
+        SpeciesId::KINGLER,
+        SpeciesId::KINGLERGMAX,
+        Type::Water,
+        MoveId::Gmaxfoamburst,
+    ),
+    (
+        SpeciesId::LAPRAS,
+        SpeciesId::LAPRASGMAX,
+        Type::Ice,
+        MoveId::Gmaxresonance,
+    ),
+    (
+        SpeciesId::EEVEE,
+        SpeciesId::EEVEEGMAX,
+        Type::Normal,
+        MoveId::Gmaxcuddle,
+    ),
+    (
+        SpeciesId::SNORLAX,
+        SpeciesId::SNORLAXGMAX,
+        Type::Normal,
+        MoveId::Gmaxreplenish,
+    ),
+    (
+        SpeciesId::GARBODOR,
+        SpeciesId::GARBODORGMAX,
+        Type::Poison,
+        MoveId::Gmaxmalodor,
+    ),
+    (
+        SpeciesId::MELMETAL,
+        SpeciesId::MELMETALGMAX,
+        Type::Steel,
+        MoveId::Gmaxmeltdown,
+    ),
+    (
+        SpeciesId::RILLABOOM,
+        SpeciesId::RILLABOOMGMAX,
+        Type::Grass,
+        MoveId::Gmaxdrumsolo,
+    ),
+    (
+        SpeciesId::CINDERACE,
+        SpeciesId::CINDERACEGMAX,
+        Type::Fire,
+        MoveId::Gmaxfireball,
+    ),
+    (
+        SpeciesId::INTELEON,
+        SpeciesId::INTELEONGMAX,
+        Type::Water,
+        MoveId::Gmaxhydrosnipe,
+    ),
+    (
+        SpeciesId::CORVIKNIGHT,
+        SpeciesId::CORVIKNIGHTGMAX,
+        Type::Flying,
+        MoveId::Gmaxwindrage,
+    ),
+    (
+        SpeciesId::ORBEETLE,
+        SpeciesId::ORBEETLEGMAX,
+        Type::Psychic,
+        MoveId::Gmaxgravitas,
+    ),
+    (
+        SpeciesId::DREDNAW,
+        SpeciesId::DREDNAWGMAX,
+        Type::Water,
+        MoveId::Gmaxstonesurge,
+    ),
+    (
+        SpeciesId::COALOSSAL,
+        SpeciesId::COALOSSALGMAX,
+        Type::Rock,
+        MoveId::Gmaxvolcalith,
+    ),
+    (
+        SpeciesId::FLAPPLE,
+        SpeciesId::FLAPPLEGMAX,
+        Type::Grass,
+        MoveId::Gmaxtartness,
+    ),
+    (
+        SpeciesId::APPLETUN,
+        SpeciesId::APPLETUNGMAX,
+        Type::Grass,
+        MoveId::Gmaxsweetness,
+    ),
+    (
+        SpeciesId::SANDACONDA,
+        SpeciesId::SANDACONDAGMAX,
+        Type::Ground,
+        MoveId::Gmaxsandblast,
+    ),
+    (
+        SpeciesId::TOXTRICITY,
+        SpeciesId::TOXTRICITYGMAX,
+        Type::Electric,
+        MoveId::Gmaxstunshock,
+    ),
+    (
+        SpeciesId::TOXTRICITYLOWKEY,
+        SpeciesId::TOXTRICITYLOWKEYGMAX,
+        Type::Electric,
+        MoveId::Gmaxstunshock,
+    ),
+    (
+        SpeciesId::CENTISKORCH,
+        SpeciesId::CENTISKORCHGMAX,
+        Type::Fire,
+        MoveId::Gmaxcentiferno,
+    ),
+    (
+        SpeciesId::HATTERENE,
+        SpeciesId::HATTERENEGMAX,
+        Type::Fairy,
+        MoveId::Gmaxsmite,
+    ),
+    (
+        SpeciesId::GRIMMSNARL,
+        SpeciesId::GRIMMSNARLGMAX,
+        Type::Dark,
+        MoveId::Gmaxsnooze,
+    ),
+    (
+        SpeciesId::ALCREMIE,
+        SpeciesId::ALCREMIEGMAX,
+        Type::Fairy,
+        MoveId::Gmaxfinale,
+    ),
+    (
+        SpeciesId::COPPERAJAH,
+        SpeciesId::COPPERAJAHGMAX,
+        Type::Steel,
+        MoveId::Gmaxsteelsurge,
+    ),
+    (
+        SpeciesId::DURALUDON,
+        SpeciesId::DURALUDONGMAX,
+        Type::Dragon,
+        MoveId::Gmaxdepletion,
+    ),
+    (
+        SpeciesId::URSHIFU,
+        SpeciesId::URSHIFUGMAX,
+        Type::Dark,
+        MoveId::Gmaxoneblow,
+    ),
+    (
+        SpeciesId::URSHIFURAPIDSTRIKE,
+        SpeciesId::URSHIFURAPIDSTRIKEGMAX,
+        Type::Water,
+        MoveId::Gmaxrapidflow,
+    ),
+];
+
+/// G-Max Moves whose effects the battle engine doesn't model: confusion
+/// (Gold Rush, Smite), infatuation (Cuddle), Torment (Meltdown), Yawn
+/// (Snooze), critical-hit stages (Chi Strike), Berry recycling
+/// (Replenish) and Gravity (Gravitas) aren't tracked in battles.
+pub const UNSUPPORTED_GMAX_MOVES: [MoveId; 8] = [
+    MoveId::Gmaxgoldrush,
+    MoveId::Gmaxsmite,
+    MoveId::Gmaxcuddle,
+    MoveId::Gmaxmeltdown,
+    MoveId::Gmaxsnooze,
+    MoveId::Gmaxchistrike,
+    MoveId::Gmaxreplenish,
+    MoveId::Gmaxgravitas,
+];
+
+/// G-Max Moves that damage the target's side for 4 turns, and the type
+/// each one spares.
+pub const SIDE_RESIDUALS: [(MoveId, Type); 4] = [
+    (MoveId::Gmaxwildfire, Type::Fire),
+    (MoveId::Gmaxvinelash, Type::Grass),
+    (MoveId::Gmaxcannonade, Type::Water),
+    (MoveId::Gmaxvolcalith, Type::Rock),
+];
+
+/// `-Gmax` forme a Pokémon with the Gigantamax Factor takes when it
+/// Dynamaxes, if its species has one.
+pub fn gmax_forme(species: SpeciesId) -> Option<SpeciesId> {
+    GMAX_MOVES
+        .iter()
+        .find(|&&(base, _, _, _)| base == species)
+        .map(|&(_, forme, _, _)| forme)
+}
+
+/// Whether the battle engine models the G-Max Move of `species` (true for
+/// species without one).
+pub fn gmax_supported(species: SpeciesId) -> bool {
+    !GMAX_MOVES
+        .iter()
+        .any(|&(base, _, _, gmax)| base == species && UNSUPPORTED_GMAX_MOVES.contains(&gmax))
+}
+
+/// Species a `-Gmax` forme reverts to when Dynamax ends.
+pub fn gmax_base(forme: SpeciesId) -> Option<SpeciesId> {
+    GMAX_MOVES
+        .iter()
+        .find(|&&(_, gmax, _, _)| gmax == forme)
+        .map(|&(base, _, _, _)| base)
+}
+
+/// G-Max Move replacing the Max Move of `move_type` for `species`, which
+/// may be either the base species or its `-Gmax` forme.
+pub fn gmax_move(species: SpeciesId, move_type: Type) -> Option<MoveId> {
+    let species = gmax_base(species).unwrap_or(species);
+    GMAX_MOVES
+        .iter()
+        .find(|&&(base, _, ty, _)| base == species && ty == move_type)
+        .map(|&(_, _, _, move_id)| move_id)
+}
+
+/// Max Move for a move of the given type and category.
+pub fn max_move(move_type: Type, category: MoveCategory) -> MoveId {
+    if category == MoveCategory::Status {
+        return MoveId::Maxguard;
+    }
+    match move_type {
+        Type::Bug => MoveId::Maxflutterby,
+        Type::Dark => MoveId::Maxdarkness,
+        Type::Dragon => MoveId::Maxwyrmwind,
+        Type::Electric => MoveId::Maxlightning,
+        Type::Fairy => MoveId::Maxstarfall,
+        Type::Fighting => MoveId::Maxknuckle,
+        Type::Fire => MoveId::Maxflare,
+        Type::Flying => MoveId::Maxairstream,
+        Type::Ghost => MoveId::Maxphantasm,
+        Type::Grass => MoveId::Maxovergrowth,
+        Type::Ground => MoveId::Maxquake,
+        Type::Ice => MoveId::Maxhailstorm,
+        Type::Poison => MoveId::Maxooze,
+        Type::Psychic => MoveId::Maxmindstorm,
+        Type::Rock => MoveId::Maxrockfall,
+        Type::Steel => MoveId::Maxsteelspike,
+        Type::Water => MoveId::Maxgeyser,
+        Type::Normal | Type::Stellar => MoveId::Maxstrike,
+    }
+}
+
+/// Max Move a Dynamaxed `user` turns `base_move` into. Type-changing
+/// moves (Weather Ball, Judgment, ...) use the Max Move of their resolved
+/// type, and a Gigantamax user gets its G-Max Move for its signature type.
+/// Struggle is never converted.
+pub fn max_move_for(state: &BattleState, user: usize, target: usize, base_move: MoveId) -> MoveId {
+    if base_move == MoveId::Struggle {
+        return base_move;
+    }
+    let data = base_move.data_in(state.generation);
+    let mut move_type = data.primary_type;
+    if let Some(Some(hooks)) = MOVE_REGISTRY.get(base_move as usize) {
+        if let Some(hook) = hooks.on_modify_type {
+            move_type = hook(state, user, target, data, move_type);
+        }
+    }
+    if state.gigantamax[user] && data.category != MoveCategory::Status {
+        if let Some(gmax) = gmax_move(state.species[user], move_type) {
+            return gmax;
+        }
+    }
+    max_move(move_type, data.category)
+}
+
+/// Whether `move_id` is a G-Max Move.
+pub fn is_gmax_move(move_id: MoveId) -> bool {
+    GMAX_MOVES.iter().any(|&(_, _, _, gmax)| gmax == move_id)
+}
+
+/// Whether `move_id` is a Max Move (including Max Guard and G-Max Moves).
+pub fn is_max_move(move_id: MoveId) -> bool {
+    is_gmax_move(move_id)
+        || matches!(
+            move_id,
+            MoveId::Maxguard
+                | MoveId::Maxflutterby
+                | MoveId::Maxdarkness
+                | MoveId::Maxwyrmwind
+                | MoveId::Maxlightning
+                | MoveId::Maxstarfall
+                | MoveId::Maxknuckle
+                | MoveId::Maxflare
+                | MoveId::Maxairstream
+                | MoveId::Maxphantasm
+                | MoveId::Maxovergrowth
+                | MoveId::Maxquake
+                | MoveId::Maxhailstorm
+                | MoveId::Maxooze
+                | MoveId::Maxmindstorm
+                | MoveId::Maxrockfall
+                | MoveId::Maxsteelspike
+                | MoveId::Maxgeyser
+                | MoveId::Maxstrike
+        )
+}
+
+/// Secondary effect of a Max Move. G-Max Drum Solo, Fireball, Hydrosnipe,
+/// One Blow and Rapid Flow have none, and neither do the G-Max Moves in
+/// `UNSUPPORTED_GMAX_MOVES`.
+pub fn max_move_effect(move_id: MoveId) -> Option<MaxEffect> {
+    let effect = match move_id {
+        MoveId::Maxstrike => MaxEffect::LowerTarget(4),
+        MoveId::Maxflutterby => MaxEffect::LowerTarget(2),
+        MoveId::Maxphantasm => MaxEffect::LowerTarget(1),
+        MoveId::Maxwyrmwind => MaxEffect::LowerTarget(0),
+        MoveId::Maxdarkness => MaxEffect::LowerTarget(3),
+        MoveId::Maxknuckle => MaxEffect::RaiseUser(0),
+        MoveId::Maxsteelspike => MaxEffect::RaiseUser(1),
+        MoveId::Maxooze => MaxEffect::RaiseUser(2),
+        MoveId::Maxquake => MaxEffect::RaiseUser(3),
+        MoveId::Maxairstream => MaxEffect::RaiseUser(4),
+        MoveId::Maxflare => MaxEffect::Weather(Weather::Sun),
+        MoveId::Maxgeyser => MaxEffect::Weather(Weather::Rain),
+        MoveId::Maxrockfall => MaxEffect::Weather(Weather::Sand),
+        MoveId::Maxhailstorm => MaxEffect::Weather(Weather::Hail),
+        MoveId::Maxlightning => MaxEffect::Terrain(Terrain::Electric),
+        MoveId::Maxovergrowth => MaxEffect::Terrain(Terrain::Grassy),
+        MoveId::Maxmindstorm => MaxEffect::Terrain(Terrain::Psychic),
+        MoveId::Maxstarfall => MaxEffect::Terrain(Terrain::Misty),
+        MoveId::Gmaxsteelsurge => MaxEffect::Steelsurge,
+        MoveId::Gmaxfoamburst => MaxEffect::HarshlyLowerTarget(4),
+        MoveId::Gmaxtartness => MaxEffect::LowerTarget(6),
+        MoveId::Gmaxbefuddle => {
+            MaxEffect::Status(&[Status::POISON, Status::PARALYSIS, Status::SLEEP])
+        }
+        MoveId::Gmaxvoltcrash => MaxEffect::Status(&[Status::PARALYSIS]),
+        MoveId::Gmaxmalodor => MaxEffect::Status(&[Status::POISON]),
+        MoveId::Gmaxstunshock => MaxEffect::Status(&[Status::POISON, Status::PARALYSIS]),
+        MoveId::Gmaxstonesurge => MaxEffect::StealthRock,
+        MoveId::Gmaxresonance => MaxEffect::AuroraVeil,
+        MoveId::Gmaxwindrage => MaxEffect::Defog,
+        MoveId::Gmaxterror => MaxEffect::Trap,
+        MoveId::Gmaxsandblast | MoveId::Gmaxcentiferno => MaxEffect::PartialTrap,
+        MoveId::Gmaxsweetness => MaxEffect::CureSide,
+        MoveId::Gmaxfinale => MaxEffect::HealUser,
+        MoveId::Gmaxdepletion => MaxEffect::Depletion,
+        _ => {
+            let &(_, spared) = SIDE_RESIDUALS.iter().find(|&&(gmax, _)| gmax == move_id)?;
+            MaxEffect::SideResidual(spared)
+        }
+    };
+    Some(effect)
+}
+
+/// Whether a G-Max Move hits through protection, Max Guard included
+/// (G-Max One Blow, G-Max Rapid Flow).
+pub fn bypasses_protect(move_id: MoveId) -> bool {
+    matches!(move_id, MoveId::Gmaxoneblow | MoveId::Gmaxrapidflow)
+}
+
+/// Whether `move_id` fails against a Dynamaxed target: weight-based moves,
+/// OHKO moves and moves that force the target out. Dragon Tail and Circle
+/// Throw still deal their damage.
+pub fn fails_against_dynamax(move_id: MoveId) -> bool {
+    matches!(
+        move_id,
+        MoveId::Lowkick
+            | MoveId::Grassknot
+            | MoveId::Heavyslam
+            | MoveId::Heatcrash
+            | MoveId::Roar
+            | MoveId::Whirlwind
+    ) || move_id.data().flags.contains(MoveFlags::OHKO)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::abilities::AbilityId;
+    use crate::damage::calculate_max_move_damage;
+    use crate::damage::generations::Gen8;
+    use crate::entities::PokemonConfig;
+
+    #[test]
+    fn test_max_move_for() {
+        let mut state = BattleState::new();
+        PokemonConfig::from_str("charizard")
+            .unwrap()
+            .spawn(&mut state, 0, 0);
+        PokemonConfig::from_str("blissey")
+            .unwrap()
+            .spawn(&mut state, 1, 0);
+
+        assert_eq!(
+            max_move_for(&state, 0, 6, MoveId::Flamethrower),
+            MoveId::Maxflare
+        );
+        assert_eq!(max_move_for(&state, 0, 6, MoveId::Roost), MoveId::Maxguard);
+        assert_eq!(
+            max_move_for(&state, 0, 6, MoveId::Struggle),
+            MoveId::Struggle
+        );
+
+        // Weather Ball becomes the Max Move of its weather type.
+        state.weather = Weather::Rain as u8;
+        assert_eq!(
+            max_move_for(&state, 0, 6, MoveId::Weatherball),
+            MoveId::Maxgeyser
+        );
+    }
+
+    #[test]
+    fn test_gmax_move_for() {
+        let mut state = BattleState::new();
+        PokemonConfig::from_str("urshifurapidstrike")
+            .unwrap()
+            .gigantamax(true)
+            .spawn(&mut state, 0, 0);
+        PokemonConfig::from_str("blissey")
+            .unwrap()
+            .spawn(&mut state, 1, 0);
+
+        // Only the signature type becomes the G-Max Move.
+        assert_eq!(
+            max_move_for(&state, 0, 6, MoveId::Surgingstrikes),
+            MoveId::Gmaxrapidflow
+        );
+        assert_eq!(
+            max_move_for(&state, 0, 6, MoveId::Closecombat),
+            MoveId::Maxknuckle
+        );
+        assert_eq!(max_move_for(&state, 0, 6, MoveId::Bulkup), MoveId::Maxguard);
+
+        state.gigantamax[0] = false;
+        assert_eq!(
+            max_move_for(&state, 0, 6, MoveId::Surgingstrikes),
+            MoveId::Maxgeyser
+        );
+        assert_eq!(
+            gmax_forme(SpeciesId::URSHIFURAPIDSTRIKE),
+            Some(SpeciesId::URSHIFURAPIDSTRIKEGMAX)
+        );
+        assert_eq!(gmax_forme(SpeciesId::BLISSEY), None);
+        assert_eq!(
+            gmax_base(SpeciesId::URSHIFURAPIDSTRIKEGMAX),
+            Some(SpeciesId::URSHIFURAPIDSTRIKE)
+        );
+        assert!(gmax_supported(SpeciesId::URSHIFURAPIDSTRIKE));
+        assert!(!gmax_supported(SpeciesId::SNORLAX));
+    }
+
+    #[test]
+    fn test_gmax_drum_solo_ignores_abilities() {
+        let mut state = BattleState::new();
+        PokemonConfig::from_str("rillaboom")
+            .unwrap()
+            .gigantamax(true)
+            .spawn(&mut state, 0, 0);
+        PokemonConfig::from_str("azumarill")
+            .unwrap()
+            .ability(AbilityId::Sapsipper)
+            .spawn(&mut state, 1, 0);
+
+        // Fixed 160 BP, through Sap Sipper.
+        let result = calculate_max_move_damage(Gen8, &state, 0, 6, MoveId::Woodhammer, false);
+        assert_eq!(result.final_base_power, 160);
+        assert!(result.max > 0);
+
+        state.gigantamax[0] = false;
+        let result = calculate_max_move_damage(Gen8, &state, 0, 6, MoveId::Woodhammer, false);
+        assert_eq!(result.max, 0);
+    }
+
+    #[test]
+    fn test_max_move_power() {
+        let mut state = BattleState::new();
+        PokemonConfig::from_str("machamp")
+            .unwrap()
+            .spawn(&mut state, 0, 0);
+        PokemonConfig::from_str("blissey")
+            .unwrap()
+            .spawn(&mut state, 1, 0);
+
+        let result = calculate_max_move_damage(Gen8, &state, 0, 6, MoveId::Thunderpunch, false);
+        assert_eq!(result.final_base_power, 130);
+        // Fighting and Poison use the weaker table.
+        let result = calculate_max_move_damage(Gen8, &state, 0, 6, MoveId::Closecombat, false);
+        assert_eq!(result.final_base_power, 95);
+        let result = calculate_max_move_damage(Gen8, &state, 0, 6, MoveId::Bulkup, false);
+        assert_eq!(result.max, 0);
+    }
+}
@@ -9,6 +9,8 @@ include!(concat!(env!("OUT_DIR"), "/moves.rs"));
 pub mod hooks;
 pub mod registry;
 pub mod implementations;
+pub mod max;
+pub mod z;
 
 pub use hooks::MoveHooks;
 pub use registry::MOVE_REGISTRY;
@@ -132,11 +132,33 @@ pub static MOVE_REGISTRY: [Option<MoveHooks>; MoveId::COUNT] = {
         on_modify_type: Some(on_modify_type_judgment),
         ..MoveHooks::NONE
     });
+    registry[MoveId::Multiattack as usize] = Some(MoveHooks {
+        on_modify_type: Some(on_modify_type_multi_attack),
+        ..MoveHooks::NONE
+    });
+    registry[MoveId::Technoblast as usize] = Some(MoveHooks {
+        on_modify_type: Some(on_modify_type_techno_blast),
+        ..MoveHooks::NONE
+    });
 
     registry[MoveId::Ragingbull as usize] = Some(MoveHooks {
         on_modify_type: Some(on_modify_type_raging_bull),
         ..MoveHooks::NONE
     });
 
+    // =========================================================================
+    // Terastallization Moves (Tera Blast, Tera Starstorm)
+    // =========================================================================
+
+    registry[MoveId::Terablast as usize] = Some(MoveHooks {
+        on_modify_type: Some(on_modify_type_tera_blast),
+        on_modify_base_power: Some(on_modify_base_power_tera_blast),
+        ..MoveHooks::NONE
+    });
+    registry[MoveId::Terastarstorm as usize] = Some(MoveHooks {
+        on_modify_type: Some(on_modify_type_tera_starstorm),
+        ..MoveHooks::NONE
+    });
+
     registry
 };
@@ -0,0 +1,138 @@
+//! Z-Moves (Gen 7).
+//!
+//! A Pokémon holding a Z-Crystal may, once per battle for its side, use a
+//! move as a Z-Move. Type Z-Crystals turn damaging moves of their type into
+//! that type's Z-Move and give status moves of their type a Z-effect
+//! (`ZPower`); signature Z-Crystals turn one move of specific species into
+//! a signature Z-Move. Z-Move power comes from the base move's `z_power` or
+//! `GenMechanics::z_move_power`.
+
+use crate::items::ZCrystal;
+use crate::moves::{MoveCategory, MoveId};
+use crate::state::BattleState;
+use crate::types::Type;
+
+/// Z-Move of a type Z-Crystal.
+pub fn type_z_move(move_type: Type) -> MoveId {
+    match move_type {
+        Type::Bug => MoveId::Savagespinout,
+        Type::Dark => MoveId::Blackholeeclipse,
+        Type::Dragon => MoveId::Devastatingdrake,
+        Type::Electric => MoveId::Gigavolthavoc,
+        Type::Fairy => MoveId::Twinkletackle,
+        Type::Fighting => MoveId::Alloutpummeling,
+        Type::Fire => MoveId::Infernooverdrive,
+        Type::Flying => MoveId::Supersonicskystrike,
+        Type::Ghost => MoveId::Neverendingnightmare,
+        Type::Grass => MoveId::Bloomdoom,
+        Type::Ground => MoveId::Tectonicrage,
+        Type::Ice => MoveId::Subzeroslammer,
+        Type::Poison => MoveId::Aciddownpour,
+        Type::Psychic => MoveId::Shatteredpsyche,
+        Type::Rock => MoveId::Continentalcrush,
+        Type::Steel => MoveId::Corkscrewcrash,
+        Type::Water => MoveId::Hydrovortex,
+        Type::Normal | Type::Stellar => MoveId::Breakneckblitz,
+    }
+}
+
+/// Move `user` executes when using `base_move` as a Z-Move with its held
+/// Z-Crystal, or `None` if the crystal doesn't apply. Z-status moves are
+/// the base move itself.
+pub fn z_move_for(state: &BattleState, user: usize, base_move: MoveId) -> Option<MoveId> {
+    match state.items[user].data().z_crystal? {
+        ZCrystal::Type(crystal_type) => {
+            let data = base_move.data_in(state.generation);
+            if data.primary_type != crystal_type {
+                return None;
+            }
+            if data.category == MoveCategory::Status {
+                Some(base_move)
+            } else {
+                Some(type_z_move(crystal_type))
+            }
+        }
+        ZCrystal::Signature { .. } => signature_z_move(state, user, base_move),
+    }
+}
+
+/// Signature Z-Move `user`'s held Z-Crystal turns `base_move` into, if the
+/// holder is one of the crystal's species.
+pub fn signature_z_move(state: &BattleState, user: usize, base_move: MoveId) -> Option<MoveId> {
+    let item = state.items[user].data();
+    match item.z_crystal? {
+        ZCrystal::Signature { base, z_move } if base == base_move => {
+            let holder_can_use =
+                item.item_user.is_empty() || item.item_user.contains(&state.species[user]);
+            holder_can_use.then_some(z_move)
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::damage::calculate_z_move_damage;
+    use crate::damage::generations::Gen7;
+    use crate::entities::PokemonConfig;
+    use crate::items::ItemId;
+    use crate::species::SpeciesId;
+
+    #[test]
+    fn test_z_move_for() {
+        let mut state = BattleState::new();
+        PokemonConfig::from_str("pikachu")
+            .unwrap()
+            .item(ItemId::Electriumz)
+            .spawn(&mut state, 0, 0);
+
+        assert_eq!(
+            z_move_for(&state, 0, MoveId::Thunderbolt),
+            Some(MoveId::Gigavolthavoc)
+        );
+        assert_eq!(
+            z_move_for(&state, 0, MoveId::Thunderwave),
+            Some(MoveId::Thunderwave)
+        );
+        assert_eq!(z_move_for(&state, 0, MoveId::Quickattack), None);
+
+        // Signature crystals only work for their species and base move.
+        state.items[0] = ItemId::Pikaniumz;
+        assert_eq!(
+            z_move_for(&state, 0, MoveId::Volttackle),
+            Some(MoveId::Catastropika)
+        );
+        assert_eq!(z_move_for(&state, 0, MoveId::Thunderbolt), None);
+        state.items[0] = ItemId::Snorliumz;
+        assert_eq!(z_move_for(&state, 0, MoveId::Gigaimpact), None);
+    }
+
+    #[test]
+    fn test_z_move_power() {
+        let mut state = BattleState::new();
+        state.generation = 7;
+        PokemonConfig::from_str("abomasnow")
+            .unwrap()
+            .spawn(&mut state, 0, 0);
+        PokemonConfig::from_str("hoopaunbound")
+            .unwrap()
+            .spawn(&mut state, 1, 0);
+
+        let result = calculate_z_move_damage(Gen7, &state, 0, 6, MoveId::Woodhammer, false);
+        assert_eq!(result.final_base_power, 190);
+        // Explicit Z-Power overrides the table (Bullet Seed assumes 3+ hits).
+        let result = calculate_z_move_damage(Gen7, &state, 0, 6, MoveId::Bulletseed, false);
+        assert_eq!(result.final_base_power, 140);
+
+        // Blizzard becomes a 185 BP Subzero Slammer.
+        let result = calculate_z_move_damage(Gen7, &state, 0, 6, MoveId::Blizzard, false);
+        assert_eq!(result.final_base_power, 185);
+
+        // Signature Z-Moves keep their own power.
+        state.species[0] = SpeciesId::PIKACHU;
+        state.items[0] = ItemId::Pikaniumz;
+        let result = calculate_z_move_damage(Gen7, &state, 0, 6, MoveId::Volttackle, false);
+        assert_eq!(result.final_base_power, 210);
+    }
+}
@@ -3,8 +3,11 @@
 //! This module defines the core `BattleState` which holds all battle data
 //! in a cache-friendly, stack-allocated format optimized for AI rollouts.
 
+use serde::{Deserialize, Serialize};
+
 use crate::abilities::AbilityId;
 use crate::entities::Gender;
+use crate::format::Gimmicks;
 use crate::items::ItemId;
 use crate::moves::{MoveCategory, MoveId};
 use crate::natures::NatureId;
@@ -48,7 +51,10 @@ bitflags::bitflags! {
         const POISON    = 1 << 3;
         const TOXIC     = 1 << 4; // Badly poisoned
         const SLEEP     = 1 << 5;
-        // FIXME: Add frostbite if supporting Gen 9 Legends Arceus mechanics
+        // Legends: Arceus replacements for freeze and sleep, only inflicted
+        // when the format enables them (see `Gimmicks::legends_arceus`)
+        const FROSTBITE = 1 << 6;
+        const DROWSY    = 1 << 7;
     }
 }
 
@@ -106,6 +112,9 @@ bitflags::bitflags! {
         const ELECTRIFY     = 1 << 47; // and Ion Deluge
         const POWER_TRICK   = 1 << 48;
         const POWDER        = 1 << 49;
+        // Legends: Arceus Style of the move being used this turn
+        const STRONG_STYLE  = 1 << 50;
+        const AGILE_STYLE   = 1 << 51;
     }
 }
 
@@ -122,6 +131,14 @@ pub struct SideConditions {
     pub spikes_layers: u8,       // 0-3
     pub toxic_spikes_layers: u8, // 0-2
     pub sticky_web: bool,
+    /// G-Max Steelsurge: a Steel-type Stealth Rock
+    pub steelsurge: bool,
+
+    // G-Max residual damage (turns remaining, 0 = inactive)
+    pub wildfire_turns: u8,
+    pub vine_lash_turns: u8,
+    pub cannonade_turns: u8,
+    pub volcalith_turns: u8,
 
     // Other side conditions
     pub tailwind_turns: u8,
@@ -130,6 +147,30 @@ pub struct SideConditions {
     pub lucky_chant_turns: u8,
 }
 
+impl SideConditions {
+    /// Turns left on the G-Max residual damage that spares `spared`
+    /// (`moves::max::SIDE_RESIDUALS`), if any sets it.
+    pub fn gmax_residual_turns(&mut self, spared: Type) -> Option<&mut u8> {
+        match spared {
+            Type::Fire => Some(&mut self.wildfire_turns),
+            Type::Grass => Some(&mut self.vine_lash_turns),
+            Type::Water => Some(&mut self.cannonade_turns),
+            Type::Rock => Some(&mut self.volcalith_turns),
+            _ => None,
+        }
+    }
+}
+
+/// What Transform overwrote, restored when the Pokémon switches out.
+/// Species, stats and types come back from `original_species`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TransformBackup {
+    pub ability: AbilityId,
+    pub moves: [MoveId; MAX_MOVES],
+    pub pp: [u8; MAX_MOVES],
+    pub max_pp: [u8; MAX_MOVES],
+}
+
 /// Entry hazard types
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Hazard {
@@ -137,6 +178,7 @@ pub enum Hazard {
     Spikes,
     ToxicSpikes,
     StickyWeb,
+    Steelsurge,
 }
 
 // ============================================================================
@@ -168,6 +210,11 @@ pub struct BattleState {
     /// Species ID for each entity
     pub species: [SpeciesId; MAX_ENTITIES],
 
+    /// Species each entity reverts to on switch-out. Temporary forme
+    /// changes (Stance Change, Zen Mode, ...) leave it alone; permanent
+    /// ones (Mega Evolution, Disguise, ...) update it.
+    pub original_species: [SpeciesId; MAX_ENTITIES],
+
     /// Current HP
     pub hp: [u16; MAX_ENTITIES],
 
@@ -207,6 +254,17 @@ pub struct BattleState {
     /// Sleep/Toxic counters (repurposed per status)
     pub status_counter: [u8; MAX_ENTITIES],
 
+    /// Turns left of a partial trapping move holding the Pokémon
+    /// (0 = not trapped)
+    pub partial_trap_turns: [u8; MAX_ENTITIES],
+
+    /// Partial trapping move holding the Pokémon
+    pub partial_trap_move: [MoveId; MAX_ENTITIES],
+
+    /// Protections (Protect, Detect, ...) used successfully on consecutive
+    /// turns; each one makes the next less likely to succeed
+    pub protect_chain: [u8; MAX_ENTITIES],
+
     /// Level (needed for damage calc)
     pub level: [u8; MAX_ENTITIES],
 
@@ -231,6 +289,29 @@ pub struct BattleState {
     /// Transformed/Mega Evolved flag
     pub transformed: [bool; MAX_ENTITIES],
 
+    /// Set while the Pokémon is under Transform (or Imposter)
+    pub transform_backup: [Option<TransformBackup>; MAX_ENTITIES],
+
+    /// Species an Illusion user is disguised as. Only affects what players
+    /// see, never damage.
+    pub illusion: [Option<SpeciesId>; MAX_ENTITIES],
+
+    /// Tera Type (defaults to the species' primary type)
+    pub tera_type: [Type; MAX_ENTITIES],
+
+    /// Whether the Pokémon has Terastallized
+    pub terastallized: [bool; MAX_ENTITIES],
+
+    /// Move types that have already received a Tera Stellar boost, one bit
+    /// per `Type` discriminant
+    pub stellar_boosted: [u32; MAX_ENTITIES],
+
+    /// Dynamax turns left (0 = not Dynamaxed)
+    pub dynamax_turns: [u8; MAX_ENTITIES],
+
+    /// Whether the Pokémon has the Gigantamax Factor
+    pub gigantamax: [bool; MAX_ENTITIES],
+
     // ------------------------------------------------------------------------
     // Consecutive Move Tracking (for Metronome item, Echoed Voice, etc.)
     // ------------------------------------------------------------------------
@@ -246,6 +327,21 @@ pub struct BattleState {
     /// Side conditions for each player
     pub side_conditions: [SideConditions; 2],
 
+    /// Whether each player has used their one Terastallization
+    pub tera_used: [bool; 2],
+
+    /// Whether each player has used their one Dynamax
+    pub dynamax_used: [bool; 2],
+
+    /// Whether each player has used their one Z-Move
+    pub z_move_used: [bool; 2],
+
+    /// Whether each player has used their one Mega Evolution
+    pub mega_used: [bool; 2],
+
+    /// Whether each player has used their one Ultra Burst
+    pub ultra_burst_used: [bool; 2],
+
     // ------------------------------------------------------------------------
     // Battle-wide state
     // ------------------------------------------------------------------------
@@ -284,10 +380,18 @@ pub struct BattleState {
     /// Generation number (1-9, default 9)
     /// Used by hooks to implement generation-specific behavior.
     pub generation: u8,
+
+    /// Gimmicks the format allows (Tera, Dynamax, Mega, Z-Moves)
+    pub gimmicks: Gimmicks,
+
+    /// Sleep Clause: a sleep move fails while one of the target's teammates
+    /// is already asleep
+    pub sleep_clause: bool,
 }
 
 /// Battle format
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum BattleFormat {
     #[default]
     Singles,
@@ -308,6 +412,7 @@ impl BattleState {
             team_sizes: [0, 0],
 
             species: [SpeciesId(0); MAX_ENTITIES],
+            original_species: [SpeciesId(0); MAX_ENTITIES],
             hp: [0; MAX_ENTITIES],
             max_hp: [0; MAX_ENTITIES],
             stats: [[0; 6]; MAX_ENTITIES],
@@ -321,6 +426,9 @@ impl BattleState {
             status: [Status::NONE; MAX_ENTITIES],
             volatiles: [Volatiles::empty(); MAX_ENTITIES],
             status_counter: [0; MAX_ENTITIES],
+            partial_trap_turns: [0; MAX_ENTITIES],
+            partial_trap_move: [MoveId::default(); MAX_ENTITIES],
+            protect_chain: [0; MAX_ENTITIES],
             level: [0; MAX_ENTITIES],
             happiness: [255; MAX_ENTITIES],
             nature: [NatureId::default(); MAX_ENTITIES],
@@ -329,11 +437,23 @@ impl BattleState {
             weight: [0; MAX_ENTITIES],
             gender: [Gender::Genderless; MAX_ENTITIES],
             transformed: [false; MAX_ENTITIES],
+            transform_backup: [None; MAX_ENTITIES],
+            illusion: [None; MAX_ENTITIES],
+            tera_type: [Type::Normal; MAX_ENTITIES],
+            terastallized: [false; MAX_ENTITIES],
+            stellar_boosted: [0; MAX_ENTITIES],
+            dynamax_turns: [0; MAX_ENTITIES],
+            gigantamax: [false; MAX_ENTITIES],
 
             last_move: [MoveId::default(); MAX_ENTITIES],
             consecutive_move_count: [0; MAX_ENTITIES],
 
             side_conditions: [SideConditions::default(); 2],
+            tera_used: [false; 2],
+            dynamax_used: [false; 2],
+            z_move_used: [false; 2],
+            mega_used: [false; 2],
+            ultra_burst_used: [false; 2],
 
             turn: 0,
             weather: 0,
@@ -346,6 +466,8 @@ impl BattleState {
             gravity_turns: 0,
             format: BattleFormat::default(),
             generation: 9, // Default to Gen 9
+            gimmicks: Gimmicks::for_gen(9),
+            sleep_clause: false,
         }
     }
 
@@ -474,8 +596,7 @@ impl BattleState {
             }
         }
 
-        let types = self.types[index];
-        if types[0] == Type::Flying || types[1] == Type::Flying {
+        if self.defensive_types(index).contains(&Type::Flying) {
             return false;
         }
 
@@ -508,6 +629,191 @@ impl BattleState {
         false
     }
 
+    // ========================================================================
+    // Terastallization
+    // ========================================================================
+
+    /// Types used whenever the Pokémon is on the receiving end: type
+    /// effectiveness, hazards, weather and grounding. A Terastallized
+    /// Pokémon is purely its Tera Type; `types` keeps its original types
+    /// for STAB. Tera Stellar keeps the original types defensively.
+    #[inline]
+    pub fn defensive_types(&self, index: usize) -> [Type; 2] {
+        if self.terastallized[index] && self.tera_type[index] != Type::Stellar {
+            [self.tera_type[index]; 2]
+        } else {
+            self.types[index]
+        }
+    }
+
+    /// Whether the Pokémon may Terastallize this turn: the format allows
+    /// it and its side hasn't used its one Terastallization yet.
+    pub fn can_terastallize(&self, index: usize) -> bool {
+        self.gimmicks.tera && !self.tera_used[self.get_side(index)] && !self.terastallized[index]
+    }
+
+    /// Terastallize a Pokémon, using up its side's Terastallization.
+    pub fn terastallize(&mut self, index: usize) {
+        self.terastallized[index] = true;
+        self.tera_used[self.get_side(index)] = true;
+    }
+
+    /// Whether a move of `move_type` gets the Tera Stellar boost: the
+    /// attacker is Terastallized into Stellar and hasn't boosted that type
+    /// yet. Terapagos-Stellar is boosted on every use.
+    pub fn has_stellar_boost(&self, index: usize, move_type: Type) -> bool {
+        self.terastallized[index]
+            && self.tera_type[index] == Type::Stellar
+            && (self.species[index] == SpeciesId::TERAPAGOSSTELLAR
+                || self.stellar_boosted[index] & (1 << move_type as u32) == 0)
+    }
+
+    /// Record that a move of `move_type` used up its Tera Stellar boost.
+    pub fn consume_stellar_boost(&mut self, index: usize, move_type: Type) {
+        if self.has_stellar_boost(index, move_type) {
+            self.stellar_boosted[index] |= 1 << move_type as u32;
+        }
+    }
+
+    // ========================================================================
+    // Dynamax
+    // ========================================================================
+
+    /// Whether the Pokémon is Dynamaxed.
+    #[inline]
+    pub fn is_dynamaxed(&self, index: usize) -> bool {
+        self.dynamax_turns[index] > 0
+    }
+
+    /// Whether the Pokémon may Dynamax this turn: the format allows it and
+    /// its side hasn't used its one Dynamax yet.
+    pub fn can_dynamax(&self, index: usize) -> bool {
+        self.gimmicks.dynamax
+            && !self.dynamax_used[self.get_side(index)]
+            && !self.is_dynamaxed(index)
+    }
+
+    /// Dynamax a Pokémon for 3 turns, scaling its current and max HP. One
+    /// with the Gigantamax Factor changes into its `-Gmax` forme.
+    pub fn dynamax(&mut self, index: usize, hp_multiplier: f32) {
+        if self.gigantamax[index] {
+            if let Some(forme) = crate::moves::max::gmax_forme(self.species[index]) {
+                self.ability_forme_change(index, forme, false);
+            }
+        }
+        self.dynamax_turns[index] = 3;
+        self.dynamax_used[self.get_side(index)] = true;
+        self.max_hp[index] = (self.max_hp[index] as f32 * hp_multiplier) as u16;
+        self.hp[index] = (self.hp[index] as f32 * hp_multiplier) as u16;
+    }
+
+    /// End Dynamax, scaling HP back down (rounded up, so it never faints).
+    pub fn end_dynamax(&mut self, index: usize) {
+        let base_max_hp = self.stats[index][0];
+        let dynamax_max_hp = self.max_hp[index].max(1) as u32;
+        let hp = (self.hp[index] as u32 * base_max_hp as u32).div_ceil(dynamax_max_hp);
+        self.dynamax_turns[index] = 0;
+        self.max_hp[index] = base_max_hp;
+        self.hp[index] = hp as u16;
+        if let Some(base) = crate::moves::max::gmax_base(self.species[index]) {
+            self.ability_forme_change(index, base, false);
+            self.transformed[index] = base.data().base_species != 0;
+        }
+    }
+
+    /// Whether the Pokémon is Dynamaxed in its `-Gmax` forme.
+    pub fn is_gigantamaxed(&self, index: usize) -> bool {
+        self.is_dynamaxed(index) && crate::moves::max::gmax_base(self.species[index]).is_some()
+    }
+
+    // ========================================================================
+    // Z-Moves
+    // ========================================================================
+
+    /// Whether the Pokémon may use a Z-Move this turn: the format allows it
+    /// and its side hasn't used its one Z-Move yet. Whether its Z-Crystal
+    /// applies to a move is `moves::z::z_move_for`.
+    pub fn can_z_move(&self, index: usize) -> bool {
+        self.gimmicks.z_moves && !self.z_move_used[self.get_side(index)]
+    }
+
+    // ========================================================================
+    // Mega Evolution, Primal Reversion and Ultra Burst
+    // ========================================================================
+
+    /// Forme the Pokémon Mega Evolves into: the one its held Mega Stone is
+    /// for, or Mega Rayquaza when it knows Dragon Ascent and isn't holding
+    /// a Z-Crystal.
+    pub fn mega_forme(&self, index: usize) -> Option<SpeciesId> {
+        let species = self.species[index];
+        let item = self.items[index].data();
+        if let Some((base, mega)) = item.mega_stone {
+            return (base == species).then_some(mega);
+        }
+        if species == SpeciesId::RAYQUAZA
+            && item.z_crystal.is_none()
+            && self.moves[index].contains(&MoveId::Dragonascent)
+        {
+            return species.data().mega_forme();
+        }
+        None
+    }
+
+    /// Whether the Pokémon may Mega Evolve this turn: the format allows it,
+    /// its side hasn't Mega Evolved yet and it has a Mega forme to go to.
+    pub fn can_mega_evolve(&self, index: usize) -> bool {
+        self.gimmicks.mega
+            && !self.mega_used[self.get_side(index)]
+            && self.mega_forme(index).is_some()
+    }
+
+    /// Mega Evolve a Pokémon, using up its side's Mega Evolution.
+    pub fn mega_evolve(&mut self, index: usize) {
+        if let Some(forme) = self.mega_forme(index) {
+            self.apply_forme_change(index, forme);
+            self.original_species[index] = forme;
+            self.mega_used[self.get_side(index)] = true;
+        }
+    }
+
+    /// Forme a Dusk Mane or Dawn Wings Necrozma holding Ultranecrozium Z
+    /// Ultra Bursts into.
+    pub fn ultra_burst_forme(&self, index: usize) -> Option<SpeciesId> {
+        let species = self.species[index];
+        let fused =
+            species == SpeciesId::NECROZMADUSKMANE || species == SpeciesId::NECROZMADAWNWINGS;
+        (fused && self.items[index] == ItemId::Ultranecroziumz).then_some(SpeciesId::NECROZMAULTRA)
+    }
+
+    /// Whether the Pokémon may Ultra Burst this turn. Ultra Burst comes with
+    /// Z-Moves and has its own once-per-side limit.
+    pub fn can_ultra_burst(&self, index: usize) -> bool {
+        self.gimmicks.z_moves
+            && !self.ultra_burst_used[self.get_side(index)]
+            && self.ultra_burst_forme(index).is_some()
+    }
+
+    /// Ultra Burst a Pokémon, using up its side's Ultra Burst.
+    pub fn ultra_burst(&mut self, index: usize) {
+        if let Some(forme) = self.ultra_burst_forme(index) {
+            self.apply_forme_change(index, forme);
+            self.original_species[index] = forme;
+            self.ultra_burst_used[self.get_side(index)] = true;
+        }
+    }
+
+    /// Primal forme a Kyogre or Groudon holding its orb reverts to when it
+    /// switches in.
+    pub fn primal_forme(&self, index: usize) -> Option<SpeciesId> {
+        let species = self.species[index];
+        let primal = species.data().primal_forme()?;
+        self.items[index]
+            .data()
+            .item_user
+            .contains(&species)
+            .then_some(primal)
+    }
+
     // ========================================================================
     // Task D Implementations
     // ========================================================================
@@ -543,6 +849,116 @@ impl BattleState {
         self.transformed[entity_idx] = true;
     }
 
+    /// Forme change caused by the Pokémon's own ability (Stance Change, Zen
+    /// Mode, ...). Unlike `apply_forme_change` the ability stays, and HP lost
+    /// so far is kept when max HP changes (Power Construct). Permanent
+    /// formes survive switching out.
+    pub fn ability_forme_change(&mut self, index: usize, forme: SpeciesId, permanent: bool) {
+        let ability = self.abilities[index];
+        let lost = self.max_hp[index] - self.hp[index];
+        self.apply_forme_change(index, forme);
+        self.abilities[index] = ability;
+        if self.hp[index] > 0 {
+            self.hp[index] = self.max_hp[index].saturating_sub(lost).max(1);
+        }
+        // A Transformed Pokémon still reverts to its own species.
+        if permanent && !self.is_transformed(index) {
+            self.original_species[index] = forme;
+        }
+    }
+
+    /// Undo Transform and temporary forme changes when the Pokémon switches
+    /// out.
+    pub fn revert_forme(&mut self, index: usize) {
+        let original = self.original_species[index];
+        if self.species[index] != original {
+            let ability = self.abilities[index];
+            self.apply_forme_change(index, original);
+            self.abilities[index] = ability;
+            self.transformed[index] = original.data().base_species != 0;
+        }
+        if let Some(backup) = self.transform_backup[index].take() {
+            self.abilities[index] = backup.ability;
+            self.moves[index] = backup.moves;
+            self.pp[index] = backup.pp;
+            self.max_pp[index] = backup.max_pp;
+        }
+        self.illusion[index] = None;
+    }
+
+    // ========================================================================
+    // Transform and Illusion
+    // ========================================================================
+
+    /// Whether the Pokémon is under Transform.
+    pub fn is_transformed(&self, index: usize) -> bool {
+        self.transform_backup[index].is_some()
+    }
+
+    /// Transform `user` into `target`: copy its species, weight, types,
+    /// stats except HP, stat stages, ability and moves (5 PP each). Fails
+    /// against a fainted or disguised target, or when either side is
+    /// already Transformed. Returns whether it worked.
+    pub fn transform(&mut self, user: usize, target: usize) -> bool {
+        if self.is_fainted(target)
+            || self.is_transformed(user)
+            || self.is_transformed(target)
+            || self.illusion[user].is_some()
+            || self.illusion[target].is_some()
+        {
+            return false;
+        }
+        self.transform_backup[user] = Some(TransformBackup {
+            ability: self.abilities[user],
+            moves: self.moves[user],
+            pp: self.pp[user],
+            max_pp: self.max_pp[user],
+        });
+        self.species[user] = self.species[target];
+        self.weight[user] = self.weight[target];
+        self.types[user] = self.types[target];
+        let hp = self.stats[user][0];
+        self.stats[user] = self.stats[target];
+        self.stats[user][0] = hp;
+        self.boosts[user] = self.boosts[target];
+        self.abilities[user] = self.abilities[target];
+        self.moves[user] = self.moves[target];
+        for slot in 0..MAX_MOVES {
+            let pp = if self.max_pp[target][slot] > 0 { 5 } else { 0 };
+            self.pp[user][slot] = pp;
+            self.max_pp[user][slot] = pp;
+        }
+        self.transformed[user] = true;
+        true
+    }
+
+    /// Species players see: an Illusion's disguise, and a Transformed or
+    /// Gigantamaxed Pokémon keeps its own name.
+    pub fn displayed_species(&self, index: usize) -> SpeciesId {
+        match self.illusion[index] {
+            Some(disguise) => disguise,
+            None if self.is_transformed(index) => self.original_species[index],
+            None => {
+                crate::moves::max::gmax_base(self.species[index]).unwrap_or(self.species[index])
+            }
+        }
+    }
+
+    /// Disguise an Illusion user about to switch in as the last healthy
+    /// Pokémon in its party.
+    pub fn start_illusion(&mut self, index: usize) {
+        self.illusion[index] = None;
+        if self.abilities[index] != AbilityId::Illusion {
+            return;
+        }
+        let side = self.get_side(index);
+        self.illusion[index] = (0..self.team_sizes[side] as usize)
+            .rev()
+            .map(|slot| Self::entity_index(side, slot))
+            .find(|&entity| entity != index && !self.is_fainted(entity))
+            .map(|entity| self.species[entity]);
+    }
+
     /// Recalculate stats based on new species data (helper for forme change)
     fn recalculate_stats(&mut self, entity_idx: usize, species: &Species) {
         use crate::natures::BattleStat;
@@ -649,6 +1065,13 @@ impl BattleState {
         // Currently items are usually specific to move types (powder) or conditions.
         // But Flame Orb/Toxic Orb force status.
 
+        // Immunities above still see freeze and sleep, so Magma Armor and
+        // Insomnia also keep out frostbite and drowsiness.
+        let status = match status {
+            Status::FREEZE if self.gimmicks.legends_arceus => Status::FROSTBITE,
+            Status::SLEEP if self.gimmicks.legends_arceus => Status::DROWSY,
+            status => status,
+        };
         self.status[entity_idx] = status;
         // Reset status counter (sleep turns, toxic count)
         self.status_counter[entity_idx] = 0;
@@ -703,56 +1126,62 @@ impl BattleState {
     /// Apply entry hazard damage when a Pokémon switches in
     /// Returns damage dealt (0 if immune or no hazards)
     pub fn apply_entry_hazards(&mut self, entity_idx: usize) -> u16 {
-        let side = self.get_side(entity_idx);
-        let conditions = self.side_conditions[side]; // Copy since it's Copy
-        let pokemon_types = self.types[entity_idx];
-        let mut total_damage = 0u16;
+        let total_damage = self
+            .entry_hazard_damage(entity_idx)
+            .iter()
+            .map(|&(_, damage)| damage)
+            .sum();
+        self.apply_damage(entity_idx, total_damage);
+        self.apply_entry_hazard_effects(entity_idx);
+        total_damage
+    }
+
+    /// Damage each damaging entry hazard would deal to a Pokémon switching
+    /// in, in the order they apply (0 if absent or immune). Doesn't change
+    /// the state.
+    pub fn entry_hazard_damage(&self, entity_idx: usize) -> [(MoveId, u16); 3] {
+        let conditions = &self.side_conditions[self.get_side(entity_idx)];
+        let mut damage = [
+            (MoveId::Stealthrock, 0),
+            (MoveId::Gmaxsteelsurge, 0),
+            (MoveId::Spikes, 0),
+        ];
 
         // Stealth Rock: Type effectiveness based damage (1/8 neutral)
         if conditions.stealth_rock && !self.is_immune_to_hazard(entity_idx, Hazard::StealthRock) {
-            let eff = type_effectiveness(
-                Type::Rock,
-                pokemon_types[0],
-                if pokemon_types[1] != pokemon_types[0] {
-                    Some(pokemon_types[1])
-                } else {
-                    None
-                },
-            );
-            // eff: 0=0x, 1=0.25x, 2=0.5x, 4=1x, 8=2x, 16=4x
-            // Base is 1/8 of max HP.
-            // 1x -> 1/8 = 0.125
-            // 2x -> 1/4 = 0.25
-            // 4x -> 1/2 = 0.5
-            // 0.5x -> 1/16 = 0.0625
-            // 0.25x -> 1/32 = 0.03125
-
-            let factor = match eff {
-                16 => 2, // 1/2
-                8 => 4,  // 1/4
-                4 => 8,  // 1/8
-                2 => 16, // 1/16
-                1 => 32, // 1/32
-                _ => 0,  // Immune
-            };
+            damage[0].1 = self.typed_hazard_damage(entity_idx, Type::Rock);
+        }
 
-            if factor > 0 {
-                total_damage += self.max_hp[entity_idx] / factor;
-            }
+        // G-Max Steelsurge: the same, with Steel-type effectiveness
+        if conditions.steelsurge && !self.is_immune_to_hazard(entity_idx, Hazard::Steelsurge) {
+            damage[1].1 = self.typed_hazard_damage(entity_idx, Type::Steel);
         }
 
         // Spikes: Tier-based damage (grounded Pokémon only)
-        if self.is_grounded(entity_idx) {
-            let layers = conditions.spikes_layers;
-            if layers > 0 && !self.is_immune_to_hazard(entity_idx, Hazard::Spikes) {
-                let factor = match layers {
-                    1 => 8, // 1/8
-                    2 => 6, // 1/6
-                    _ => 4, // 1/4
-                };
-                total_damage += self.max_hp[entity_idx] / factor;
-            }
+        let layers = conditions.spikes_layers;
+        if layers > 0
+            && self.is_grounded(entity_idx)
+            && !self.is_immune_to_hazard(entity_idx, Hazard::Spikes)
+        {
+            let factor = match layers {
+                1 => 8, // 1/8
+                2 => 6, // 1/6
+                _ => 4, // 1/4
+            };
+            damage[2].1 = self.max_hp[entity_idx] / factor;
+        }
+
+        damage
+    }
 
+    /// Apply the non-damaging entry hazards (Toxic Spikes, Sticky Web) to
+    /// a Pokémon switching in.
+    pub fn apply_entry_hazard_effects(&mut self, entity_idx: usize) {
+        let side = self.get_side(entity_idx);
+        let conditions = self.side_conditions[side]; // Copy since it's Copy
+        let pokemon_types = self.defensive_types(entity_idx);
+
+        if self.is_grounded(entity_idx) {
             // Toxic Spikes
             let tspikes = conditions.toxic_spikes_layers;
             let is_poison = pokemon_types[0] == Type::Poison || pokemon_types[1] == Type::Poison;
@@ -780,9 +1209,31 @@ impl BattleState {
                 self.apply_stat_change(entity_idx, 5, -1);
             }
         }
+    }
 
-        self.apply_damage(entity_idx, total_damage);
-        total_damage
+    /// Stealth Rock-style damage: 1/8 max HP scaled by how effective
+    /// `hazard_type` is against the Pokémon.
+    fn typed_hazard_damage(&self, entity_idx: usize, hazard_type: Type) -> u16 {
+        let pokemon_types = self.defensive_types(entity_idx);
+        let eff = type_effectiveness(
+            hazard_type,
+            pokemon_types[0],
+            if pokemon_types[1] != pokemon_types[0] {
+                Some(pokemon_types[1])
+            } else {
+                None
+            },
+        );
+        // eff: 0=0x, 1=0.25x, 2=0.5x, 4=1x, 8=2x, 16=4x
+        let factor = match eff {
+            16 => 2, // 1/2
+            8 => 4,  // 1/4
+            4 => 8,  // 1/8
+            2 => 16, // 1/16
+            1 => 32, // 1/32
+            _ => return 0,
+        };
+        self.max_hp[entity_idx] / factor
     }
 
     /// Decrement all turn-based side conditions. Call at end of turn.
@@ -1262,6 +1713,21 @@ mod tests {
         assert_eq!(state.hp[idx], 50);
     }
 
+    #[test]
+    fn test_hazard_damage_steelsurge() {
+        // Steelsurge is Stealth Rock with Steel-type effectiveness.
+        let mut state = BattleState::default();
+        state.max_hp[0] = 100;
+        state.hp[0] = 100;
+        state.types[0] = [Type::Rock, Type::Ice];
+        state.side_conditions[0].steelsurge = true;
+        assert_eq!(state.apply_entry_hazards(0), 50);
+
+        state.hp[0] = 100;
+        state.types[0] = [Type::Fire, Type::Flying];
+        assert_eq!(state.apply_entry_hazards(0), 6);
+    }
+
     #[test]
     fn test_hazard_damage_spikes() {
         let mut state = BattleState::default();
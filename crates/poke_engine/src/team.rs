@@ -0,0 +1,508 @@
+//! Team import from Showdown's export text format and JSON.
+//!
+//! Both formats produce `PokemonConfig`s ready to be placed into a
+//! `BattleState`. Names are normalized to Showdown IDs (lowercase
+//! alphanumerics), so `"Choice Band"`, `"choiceband"` and `"Choice-Band"`
+//! all resolve to the same item.
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use alloc::{format, vec};
+use core::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::abilities::AbilityId;
+use crate::entities::{Gender, PokemonConfig};
+use crate::items::ItemId;
+use crate::moves::MoveId;
+use crate::natures::NatureId;
+use crate::species::SpeciesId;
+use crate::state::{MAX_MOVES, MAX_TEAM_SIZE};
+use crate::types::Type;
+
+/// Error produced while importing a team.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TeamError {
+    UnknownSpecies(String),
+    UnknownItem(String),
+    UnknownAbility(String),
+    UnknownMove(String),
+    UnknownNature(String),
+    UnknownType(String),
+    /// A line that could not be parsed (Showdown format)
+    InvalidLine(String),
+    /// Malformed JSON
+    Json(String),
+    /// No Pokémon in the input
+    Empty,
+    /// More than six Pokémon in the input
+    TooManyPokemon(usize),
+}
+
+impl fmt::Display for TeamError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TeamError::UnknownSpecies(s) => write!(f, "unknown species: {}", s),
+            TeamError::UnknownItem(s) => write!(f, "unknown item: {}", s),
+            TeamError::UnknownAbility(s) => write!(f, "unknown ability: {}", s),
+            TeamError::UnknownMove(s) => write!(f, "unknown move: {}", s),
+            TeamError::UnknownNature(s) => write!(f, "unknown nature: {}", s),
+            TeamError::UnknownType(s) => write!(f, "unknown type: {}", s),
+            TeamError::InvalidLine(s) => write!(f, "invalid line: {}", s),
+            TeamError::Json(s) => write!(f, "invalid JSON: {}", s),
+            TeamError::Empty => write!(f, "team is empty"),
+            TeamError::TooManyPokemon(n) => {
+                write!(f, "team has {} Pokémon (max {})", n, MAX_TEAM_SIZE)
+            }
+        }
+    }
+}
+
+impl core::error::Error for TeamError {}
+
+/// Normalize a display name to a Showdown ID ("Choice Band" -> "choiceband").
+pub fn to_id(name: &str) -> String {
+    name.chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
+
+fn species_by_name(name: &str) -> Result<SpeciesId, TeamError> {
+    SpeciesId::from_str(&to_id(name)).ok_or_else(|| TeamError::UnknownSpecies(name.to_string()))
+}
+
+fn item_by_name(name: &str) -> Result<ItemId, TeamError> {
+    let id = to_id(name);
+    if id.is_empty() || id == "none" || id == "noitem" {
+        return Ok(ItemId::None);
+    }
+    ItemId::from_str(&id).ok_or_else(|| TeamError::UnknownItem(name.to_string()))
+}
+
+fn ability_by_name(name: &str) -> Result<AbilityId, TeamError> {
+    AbilityId::from_str(&to_id(name)).ok_or_else(|| TeamError::UnknownAbility(name.to_string()))
+}
+
+fn move_by_name(name: &str) -> Result<MoveId, TeamError> {
+    MoveId::from_str(&to_id(name)).ok_or_else(|| TeamError::UnknownMove(name.to_string()))
+}
+
+fn nature_by_name(name: &str) -> Result<NatureId, TeamError> {
+    NatureId::from_str(&to_id(name)).ok_or_else(|| TeamError::UnknownNature(name.to_string()))
+}
+
+fn type_by_name(name: &str) -> Result<Type, TeamError> {
+    Type::from_str(&to_id(name)).ok_or_else(|| TeamError::UnknownType(name.to_string()))
+}
+
+/// Stat index (HP=0 ... Spe=5) for a Showdown stat label.
+fn stat_index(label: &str) -> Option<usize> {
+    match to_id(label).as_str() {
+        "hp" => Some(0),
+        "atk" => Some(1),
+        "def" => Some(2),
+        "spa" => Some(3),
+        "spd" => Some(4),
+        "spe" => Some(5),
+        _ => None,
+    }
+}
+
+/// Parse `"252 Atk / 4 SpD / 252 Spe"` on top of `base`.
+fn parse_stat_spread(line: &str, mut base: [u8; 6]) -> Result<[u8; 6], TeamError> {
+    for part in line.split('/') {
+        let mut words = part.split_whitespace();
+        let (Some(value), Some(stat)) = (words.next(), words.next()) else {
+            return Err(TeamError::InvalidLine(line.to_string()));
+        };
+        let value: u8 = value
+            .parse()
+            .map_err(|_| TeamError::InvalidLine(line.to_string()))?;
+        let index = stat_index(stat).ok_or_else(|| TeamError::InvalidLine(line.to_string()))?;
+        base[index] = value;
+    }
+    Ok(base)
+}
+
+/// Parse a single set in Showdown export format.
+///
+/// ```text
+/// Garchomp (M) @ Choice Band
+/// Ability: Rough Skin
+/// Level: 50
+/// EVs: 252 Atk / 4 SpD / 252 Spe
+/// Jolly Nature
+/// - Earthquake
+/// - Outrage
+/// ```
+pub fn parse_showdown_set(text: &str) -> Result<PokemonConfig, TeamError> {
+    let mut lines = text.lines().map(str::trim).filter(|l| !l.is_empty());
+    let header = lines.next().ok_or(TeamError::Empty)?;
+
+    // "Nickname (Species) (M) @ Item"
+    let (name_part, item) = match header.split_once(" @ ") {
+        Some((name, item)) => (name.trim(), Some(item.trim())),
+        None => (header, None),
+    };
+    let mut name_part = name_part;
+    let mut gender = None;
+    if let Some(rest) = name_part.strip_suffix(" (M)") {
+        gender = Some(Gender::Male);
+        name_part = rest;
+    } else if let Some(rest) = name_part.strip_suffix(" (F)") {
+        gender = Some(Gender::Female);
+        name_part = rest;
+    }
+    let species_name = match (name_part.rfind(" ("), name_part.ends_with(')')) {
+        (Some(open), true) => &name_part[open + 2..name_part.len() - 1],
+        _ => name_part,
+    };
+
+    let mut config = PokemonConfig::new(species_by_name(species_name)?).level(100);
+    if let Some(item) = item {
+        config = config.item(item_by_name(item)?);
+    }
+    if let Some(gender) = gender {
+        config = config.gender(gender);
+    }
+
+    let mut evs = [0u8; 6];
+    let mut ivs = [31u8; 6];
+    let mut move_slot = 0;
+    for line in lines {
+        if let Some(name) = line.strip_prefix("- ") {
+            if move_slot >= MAX_MOVES {
+                return Err(TeamError::InvalidLine(line.to_string()));
+            }
+            config = config.set_move(move_slot, move_by_name(name)?);
+            move_slot += 1;
+        } else if let Some(value) = line.strip_prefix("Ability:") {
+            config = config.ability(ability_by_name(value.trim())?);
+        } else if let Some(value) = line.strip_prefix("Level:") {
+            let level = value
+                .trim()
+                .parse()
+                .map_err(|_| TeamError::InvalidLine(line.to_string()))?;
+            config = config.level(level);
+        } else if let Some(value) = line.strip_prefix("EVs:") {
+            evs = parse_stat_spread(value, evs)?;
+        } else if let Some(value) = line.strip_prefix("IVs:") {
+            ivs = parse_stat_spread(value, ivs)?;
+        } else if let Some(value) = line.strip_suffix(" Nature") {
+            config = config.nature(nature_by_name(value)?);
+        } else if let Some(value) = line.strip_prefix("Happiness:") {
+            config.happiness = value
+                .trim()
+                .parse()
+                .map_err(|_| TeamError::InvalidLine(line.to_string()))?;
+        } else if let Some(value) = line.strip_prefix("Tera Type:") {
+            config = config.tera_type(type_by_name(value.trim())?);
+        } else if let Some(value) = line.strip_prefix("Gigantamax:") {
+            config = config.gigantamax(value.trim() == "Yes");
+        } else if line.starts_with("Shiny:")
+            || line.starts_with("Dynamax Level:")
+            || line.starts_with("Pokeball:")
+        {
+            // Cosmetic or not modeled by PokemonConfig
+        } else {
+            return Err(TeamError::InvalidLine(line.to_string()));
+        }
+    }
+
+    Ok(config.evs(evs).ivs(ivs))
+}
+
+/// Parse the one-line set form used on the command line.
+///
+/// The header is the same as the export format; the remaining
+/// comma-separated fields are a nature, an EV spread, `IVs: ...`, a level
+/// (`L50`) or an ability, in any order:
+///
+/// ```text
+/// Garchomp @ Choice Band, Jolly, 252 Atk / 4 SpD / 252 Spe, Rough Skin, L50
+/// ```
+pub fn parse_short_set(text: &str) -> Result<PokemonConfig, TeamError> {
+    let mut fields = text.split(',').map(str::trim);
+    let mut lines = vec![fields.next().unwrap_or_default().to_string()];
+    for field in fields.filter(|f| !f.is_empty()) {
+        let level = field
+            .strip_prefix("Level ")
+            .or_else(|| field.strip_prefix('L'))
+            .filter(|n| n.bytes().all(|b| b.is_ascii_digit()));
+        let line = if let Some(level) = level {
+            format!("Level: {}", level)
+        } else if field.starts_with("IVs:") || field.ends_with(" Nature") {
+            field.to_string()
+        } else if field.starts_with(|c: char| c.is_ascii_digit()) {
+            format!("EVs: {}", field)
+        } else if nature_by_name(field).is_ok() {
+            format!("{} Nature", field)
+        } else {
+            format!("Ability: {}", field)
+        };
+        lines.push(line);
+    }
+    parse_showdown_set(&lines.join("\n"))
+}
+
+/// Parse a whole team in Showdown export format (sets separated by blank lines).
+pub fn parse_showdown_team(text: &str) -> Result<Vec<PokemonConfig>, TeamError> {
+    let mut team = Vec::new();
+    let mut block = String::new();
+    for line in text.lines().chain(core::iter::once("")) {
+        if line.trim().is_empty() {
+            if !block.is_empty() {
+                team.push(parse_showdown_set(&block)?);
+                block.clear();
+            }
+        } else if !line.trim_start().starts_with("===") {
+            // "=== [gen9ou] Team ===" headers from the teambuilder are skipped
+            block.push_str(line);
+            block.push('\n');
+        }
+    }
+    check_size(team)
+}
+
+fn check_size(team: Vec<PokemonConfig>) -> Result<Vec<PokemonConfig>, TeamError> {
+    match team.len() {
+        0 => Err(TeamError::Empty),
+        n if n > MAX_TEAM_SIZE => Err(TeamError::TooManyPokemon(n)),
+        _ => Ok(team),
+    }
+}
+
+/// Stat spread in Showdown's JSON shape (`{"atk": 252, "spe": 252}`).
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct StatsJson {
+    pub hp: Option<u8>,
+    pub atk: Option<u8>,
+    pub def: Option<u8>,
+    pub spa: Option<u8>,
+    pub spd: Option<u8>,
+    pub spe: Option<u8>,
+}
+
+impl StatsJson {
+    fn from_array(values: [u8; 6]) -> Self {
+        let [hp, atk, def, spa, spd, spe] = values.map(Some);
+        Self {
+            hp,
+            atk,
+            def,
+            spa,
+            spd,
+            spe,
+        }
+    }
+
+    fn apply(&self, mut base: [u8; 6]) -> [u8; 6] {
+        let values = [self.hp, self.atk, self.def, self.spa, self.spd, self.spe];
+        for (slot, value) in base.iter_mut().zip(values) {
+            if let Some(value) = value {
+                *slot = value;
+            }
+        }
+        base
+    }
+}
+
+/// A set in Showdown's `PokemonSet` JSON shape.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct PokemonSetJson {
+    pub species: String,
+    pub item: Option<String>,
+    pub ability: Option<String>,
+    pub moves: Vec<String>,
+    pub nature: Option<String>,
+    pub evs: StatsJson,
+    pub ivs: StatsJson,
+    pub level: Option<u8>,
+    pub gender: Option<String>,
+    pub happiness: Option<u8>,
+    #[serde(rename = "teraType")]
+    pub tera_type: Option<String>,
+    pub gigantamax: bool,
+}
+
+impl PokemonSetJson {
+    /// Describe a config with display names, the inverse of `to_config`.
+    pub fn from_config(config: &PokemonConfig) -> Self {
+        Self {
+            species: config.species.data().name.to_string(),
+            item: (config.item != ItemId::None).then(|| config.item.data().name.to_string()),
+            ability: config.ability.map(|ability| ability.name().to_string()),
+            moves: config
+                .moves
+                .iter()
+                .filter(|&&move_id| move_id != MoveId::default())
+                .map(|move_id| move_id.data().name.to_string())
+                .collect(),
+            nature: Some(format!("{:?}", config.nature)),
+            evs: StatsJson::from_array(config.evs),
+            ivs: StatsJson::from_array(config.ivs),
+            level: Some(config.level),
+            gender: config.gender.map(|gender| {
+                match gender {
+                    Gender::Male => "M",
+                    Gender::Female => "F",
+                    Gender::Genderless => "N",
+                }
+                .to_string()
+            }),
+            happiness: Some(config.happiness),
+            tera_type: config.tera_type.map(|tera_type| format!("{:?}", tera_type)),
+            gigantamax: config.gigantamax,
+        }
+    }
+
+    /// Resolve names into a `PokemonConfig`.
+    pub fn to_config(&self) -> Result<PokemonConfig, TeamError> {
+        let mut config =
+            PokemonConfig::new(species_by_name(&self.species)?).level(self.level.unwrap_or(100));
+        if let Some(item) = &self.item {
+            config = config.item(item_by_name(item)?);
+        }
+        if let Some(ability) = &self.ability {
+            config = config.ability(ability_by_name(ability)?);
+        }
+        if self.moves.len() > MAX_MOVES {
+            return Err(TeamError::InvalidLine(format!("{} moves", self.moves.len())));
+        }
+        for (slot, name) in self.moves.iter().enumerate() {
+            config = config.set_move(slot, move_by_name(name)?);
+        }
+        if let Some(nature) = &self.nature {
+            config = config.nature(nature_by_name(nature)?);
+        }
+        match self.gender.as_deref() {
+            Some("M") => config = config.gender(Gender::Male),
+            Some("F") => config = config.gender(Gender::Female),
+            Some("N") => config = config.gender(Gender::Genderless),
+            _ => {}
+        }
+        if let Some(happiness) = self.happiness {
+            config.happiness = happiness;
+        }
+        if let Some(tera_type) = &self.tera_type {
+            config = config.tera_type(type_by_name(tera_type)?);
+        }
+        Ok(config
+            .gigantamax(self.gigantamax)
+            .evs(self.evs.apply([0; 6]))
+            .ivs(self.ivs.apply([31; 6])))
+    }
+}
+
+/// Parse a team from a JSON array of `PokemonSetJson`.
+#[cfg(feature = "std")]
+pub fn parse_json_team(text: &str) -> Result<Vec<PokemonConfig>, TeamError> {
+    let sets: Vec<PokemonSetJson> =
+        serde_json::from_str(text).map_err(|e| TeamError::Json(e.to_string()))?;
+    check_size(sets.iter().map(PokemonSetJson::to_config).collect::<Result<_, _>>()?)
+}
+
+/// Parse a team in either format, detecting JSON by its leading `[`.
+pub fn parse_team(text: &str) -> Result<Vec<PokemonConfig>, TeamError> {
+    if text.trim_start().starts_with('[') {
+        #[cfg(feature = "std")]
+        return parse_json_team(text);
+        #[cfg(not(feature = "std"))]
+        return Err(TeamError::Json("JSON import requires the `std` feature".into()));
+    } else {
+        parse_showdown_team(text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GARCHOMP: &str = "\
+Chompy (Garchomp) (M) @ Choice Band
+Ability: Rough Skin
+Level: 50
+EVs: 252 Atk / 4 SpD / 252 Spe
+IVs: 0 SpA
+Tera Type: Steel
+Jolly Nature
+- Earthquake
+- Outrage
+- Stone Edge
+- Fire Fang
+";
+
+    #[test]
+    fn test_parse_showdown_set() {
+        let config = parse_showdown_set(GARCHOMP).unwrap();
+        assert_eq!(config.species, SpeciesId::from_str("garchomp").unwrap());
+        assert_eq!(config.item, ItemId::Choiceband);
+        assert_eq!(config.ability, Some(AbilityId::Roughskin));
+        assert_eq!(config.level, 50);
+        assert_eq!(config.evs, [0, 252, 0, 0, 4, 252]);
+        assert_eq!(config.ivs, [31, 31, 31, 0, 31, 31]);
+        assert_eq!(config.nature, NatureId::Jolly);
+        assert_eq!(config.gender, Some(Gender::Male));
+        assert_eq!(config.tera_type, Some(Type::Steel));
+        assert_eq!(config.moves[0], MoveId::Earthquake);
+        assert_eq!(config.moves[3], MoveId::Firefang);
+    }
+
+    #[test]
+    fn test_parse_showdown_team_skips_headers() {
+        let text = format!("=== [gen9ou] Test ===\n\n{}\nPelipper @ Damp Rock\nAbility: Drizzle\n- Hurricane\n", GARCHOMP);
+        let team = parse_showdown_team(&text).unwrap();
+        assert_eq!(team.len(), 2);
+        assert_eq!(team[1].level, 100);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_json_matches_showdown_format() {
+        // Both formats describe the same set and must produce the same config.
+        let json = r#"[{"species": "Garchomp", "item": "Choice Band", "ability": "Rough Skin",
+            "moves": ["Earthquake", "Outrage", "Stone Edge", "Fire Fang"], "nature": "Jolly",
+            "evs": {"atk": 252, "spd": 4, "spe": 252}, "ivs": {"spa": 0}, "level": 50, "gender": "M"}]"#;
+        let from_json = &parse_team(json).unwrap()[0];
+        let from_text = parse_showdown_set(GARCHOMP).unwrap();
+        assert_eq!(from_json.moves, from_text.moves);
+        assert_eq!(from_json.evs, from_text.evs);
+        assert_eq!(from_json.ivs, from_text.ivs);
+        assert_eq!(from_json.calculate_stats(), from_text.calculate_stats());
+    }
+
+    #[test]
+    fn test_short_set_matches_showdown_format() {
+        // The one-line form is sugar for the export format, minus moves.
+        let short =
+            parse_short_set("Chompy (Garchomp) (M) @ Choice Band, Rough Skin, L50, 252 Atk / 4 SpD / 252 Spe, IVs: 0 SpA, Jolly")
+                .unwrap();
+        let full = parse_showdown_set(GARCHOMP).unwrap();
+        assert_eq!(short.calculate_stats(), full.calculate_stats());
+        assert_eq!(short.ability, full.ability);
+        assert_eq!(short.item, full.item);
+    }
+
+    #[test]
+    fn test_set_json_round_trip() {
+        // from_config must describe a set that resolves back to the same config.
+        let config = parse_showdown_set(GARCHOMP).unwrap();
+        let json = PokemonSetJson::from_config(&config);
+        assert_eq!(json.item.as_deref(), Some("Choice Band"));
+        let back = json.to_config().unwrap();
+        assert_eq!(back.moves, config.moves);
+        assert_eq!(back.calculate_stats(), config.calculate_stats());
+        assert_eq!(back.gender, config.gender);
+        assert_eq!(back.tera_type, config.tera_type);
+    }
+
+    #[test]
+    fn test_unknown_move_is_reported() {
+        let err = parse_showdown_set("Garchomp\n- Definitely Not A Move").unwrap_err();
+        assert_eq!(err, TeamError::UnknownMove("Definitely Not A Move".to_string()));
+    }
+}
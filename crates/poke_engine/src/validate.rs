@@ -0,0 +1,708 @@
+//! Team validation against a `Format`.
+//!
+//! Checks stats, abilities, learnsets, ban lists, clauses and whether each
+//! species, ability, item and move exists in the format's generation
+//! (`dex`), and reports
+//! every problem found rather than stopping at the first one. Without
+//! learnset data (`learnsets::LEARNSETS_LOADED`) move legality can't be
+//! checked; that isn't a problem with the team, so it's reported by
+//! `learnset_warning` for callers to show next to the errors.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::abilities::AbilityId;
+use crate::entities::PokemonConfig;
+use crate::format::{Clause, Format};
+use crate::items::ItemId;
+use crate::learnsets::LEARNSETS_LOADED;
+use crate::moves::max::gmax_supported;
+use crate::moves::{MoveFlags, MoveId};
+use crate::species::{SpeciesId, FLAG_EVOLVED, FLAG_NFE};
+use crate::team::to_id;
+
+/// Total EV budget (Gen 3+)
+pub const MAX_TOTAL_EVS: u16 = 510;
+/// Per-stat EV cap (Gen 3+)
+pub const MAX_STAT_EVS: u8 = 252;
+/// Highest IV
+pub const MAX_IV: u8 = 31;
+
+const STAT_NAMES: [&str; 6] = ["HP", "Atk", "Def", "SpA", "SpD", "Spe"];
+
+/// A rule broken by a team. `slot` is the 0-based team position.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ValidationError {
+    /// Team has fewer Pokémon than the format brings to battle, or more
+    /// than it allows
+    TeamSize {
+        size: usize,
+        min: usize,
+        max: usize,
+    },
+    /// Monotype: no type is shared by the whole team
+    NotMonotype,
+    EvTotal {
+        slot: usize,
+        total: u16,
+    },
+    EvTooHigh {
+        slot: usize,
+        stat: usize,
+        value: u8,
+    },
+    IvOutOfRange {
+        slot: usize,
+        stat: usize,
+        value: u8,
+    },
+    LevelOutOfRange {
+        slot: usize,
+        level: u8,
+        max: u8,
+    },
+    IllegalAbility {
+        slot: usize,
+        ability: AbilityId,
+    },
+    /// The species cannot learn the move in the format's generation
+    IllegalMove {
+        slot: usize,
+        move_id: MoveId,
+    },
+    NoMoves {
+        slot: usize,
+    },
+    DuplicateMove {
+        slot: usize,
+        move_id: MoveId,
+    },
+    BannedSpecies {
+        slot: usize,
+        species: SpeciesId,
+    },
+    BannedItem {
+        slot: usize,
+        item: ItemId,
+    },
+    BannedMove {
+        slot: usize,
+        move_id: MoveId,
+    },
+    BannedAbility {
+        slot: usize,
+        ability: AbilityId,
+    },
+    /// Introduced after the format's generation, or nonstandard in it
+    UnavailableSpecies {
+        slot: usize,
+        species: SpeciesId,
+    },
+    UnavailableItem {
+        slot: usize,
+        item: ItemId,
+    },
+    UnavailableMove {
+        slot: usize,
+        move_id: MoveId,
+    },
+    UnavailableAbility {
+        slot: usize,
+        ability: AbilityId,
+    },
+    /// Little Cup: not a first-stage Pokémon that can evolve
+    NotLittleCup {
+        slot: usize,
+        species: SpeciesId,
+    },
+    /// Species Clause: `slot` repeats the species of an earlier slot
+    DuplicateSpecies {
+        slot: usize,
+        species: SpeciesId,
+    },
+    /// Item Clause: `slot` repeats the item of an earlier slot
+    DuplicateItem {
+        slot: usize,
+        item: ItemId,
+    },
+    /// The engine doesn't model the effect of the species' G-Max Move
+    /// (see `moves::max::UNSUPPORTED_GMAX_MOVES`)
+    UnsupportedGigantamax {
+        slot: usize,
+        species: SpeciesId,
+    },
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use ValidationError::*;
+        match *self {
+            TeamSize { size, min, max } if min == max => {
+                write!(f, "team has {} Pokémon (needs {})", size, max)
+            }
+            TeamSize { size, min, max } => {
+                write!(f, "team has {} Pokémon (allowed {}-{})", size, min, max)
+            }
+            NotMonotype => write!(f, "Monotype: team members do not share a type"),
+            EvTotal { slot, total } => {
+                write!(
+                    f,
+                    "slot {}: EVs total {} (max {})",
+                    slot + 1,
+                    total,
+                    MAX_TOTAL_EVS
+                )
+            }
+            EvTooHigh { slot, stat, value } => write!(
+                f,
+                "slot {}: {} EVs {} (max {})",
+                slot + 1,
+                STAT_NAMES[stat],
+                value,
+                MAX_STAT_EVS
+            ),
+            IvOutOfRange { slot, stat, value } => write!(
+                f,
+                "slot {}: {} IV {} (max {})",
+                slot + 1,
+                STAT_NAMES[stat],
+                value,
+                MAX_IV
+            ),
+            LevelOutOfRange { slot, level, max } => {
+                write!(f, "slot {}: level {} (allowed 1-{})", slot + 1, level, max)
+            }
+            IllegalAbility { slot, ability } => {
+                write!(f, "slot {}: cannot have {}", slot + 1, ability.name())
+            }
+            IllegalMove { slot, move_id } => {
+                write!(f, "slot {}: cannot learn {}", slot + 1, move_id.data().name)
+            }
+            NoMoves { slot } => write!(f, "slot {}: has no moves", slot + 1),
+            DuplicateMove { slot, move_id } => {
+                write!(
+                    f,
+                    "slot {}: {} more than once",
+                    slot + 1,
+                    move_id.data().name
+                )
+            }
+            BannedSpecies { slot, species } => {
+                write!(f, "slot {}: {} is banned", slot + 1, species.data().name)
+            }
+            BannedItem { slot, item } => {
+                write!(f, "slot {}: {} is banned", slot + 1, item.data().name)
+            }
+            BannedMove { slot, move_id } => {
+                write!(f, "slot {}: {} is banned", slot + 1, move_id.data().name)
+            }
+            BannedAbility { slot, ability } => {
+                write!(f, "slot {}: {} is banned", slot + 1, ability.name())
+            }
+            UnavailableSpecies { slot, species } => {
+                write!(
+                    f,
+                    "slot {}: {} does not exist in this generation",
+                    slot + 1,
+                    species.data().name
+                )
+            }
+            UnavailableItem { slot, item } => {
+                write!(
+                    f,
+                    "slot {}: {} does not exist in this generation",
+                    slot + 1,
+                    item.data().name
+                )
+            }
+            UnavailableMove { slot, move_id } => {
+                write!(
+                    f,
+                    "slot {}: {} does not exist in this generation",
+                    slot + 1,
+                    move_id.data().name
+                )
+            }
+            UnavailableAbility { slot, ability } => {
+                write!(
+                    f,
+                    "slot {}: {} does not exist in this generation",
+                    slot + 1,
+                    ability.name()
+                )
+            }
+            NotLittleCup { slot, species } => write!(
+                f,
+                "slot {}: {} is not a Little Cup Pokémon",
+                slot + 1,
+                species.data().name
+            ),
+            DuplicateSpecies { slot, species } => write!(
+                f,
+                "slot {}: Species Clause: more than one {}",
+                slot + 1,
+                species.base().data().name
+            ),
+            DuplicateItem { slot, item } => write!(
+                f,
+                "slot {}: Item Clause: more than one {}",
+                slot + 1,
+                item.data().name
+            ),
+            UnsupportedGigantamax { slot, species } => write!(
+                f,
+                "slot {}: Gigantamax {} is not supported",
+                slot + 1,
+                species.data().name
+            ),
+        }
+    }
+}
+
+impl core::error::Error for ValidationError {}
+
+/// Whether `name` is on the format's own `list` or banned by a clause.
+fn is_banned(list: &[String], clause_bans: &[&str], name: &str) -> bool {
+    Format::is_listed(list, name) || clause_bans.contains(&to_id(name).as_str())
+}
+
+/// Whether some type is shared by every member (by species typing).
+fn shares_type(team: &[PokemonConfig]) -> bool {
+    let types = |set: &PokemonConfig| {
+        let species = set.species.data();
+        [Some(species.primary_type()), species.secondary_type()]
+    };
+    let Some(first) = team.first() else {
+        return true;
+    };
+    types(first)
+        .into_iter()
+        .flatten()
+        .any(|ty| team.iter().all(|set| types(set).contains(&Some(ty))))
+}
+
+/// Warning to show alongside `validate_team`'s result when the engine was
+/// built without `data/learnsets.json`, so moves weren't checked against
+/// learnsets.
+pub fn learnset_warning() -> Option<&'static str> {
+    (!LEARNSETS_LOADED).then_some("move legality not checked: built without learnset data")
+}
+
+/// Check `team` against `format`. Returns every violation found (empty
+/// when the team is legal).
+///
+/// Empty move slots (`MoveId::default()`, as left by the team importers)
+/// are skipped.
+pub fn validate_team(team: &[PokemonConfig], format: &Format) -> Vec<ValidationError> {
+    let mut errors = Vec::new();
+    let gen = format.generation;
+    let clause_moves: Vec<&str> = format
+        .clauses
+        .iter()
+        .flat_map(|c| c.banned_moves())
+        .copied()
+        .collect();
+    let clause_items: Vec<&str> = format
+        .clauses
+        .iter()
+        .flat_map(|c| c.banned_items())
+        .copied()
+        .collect();
+    let clause_abilities: Vec<&str> = format
+        .clauses
+        .iter()
+        .flat_map(|c| c.banned_abilities())
+        .copied()
+        .collect();
+
+    let (min, max) = (
+        format.pick.map_or(1, usize::from),
+        format.team_size as usize,
+    );
+    if team.len() < min || team.len() > max {
+        errors.push(ValidationError::TeamSize {
+            size: team.len(),
+            min,
+            max,
+        });
+    }
+    if format.has_clause(Clause::Monotype) && !shares_type(team) {
+        errors.push(ValidationError::NotMonotype);
+    }
+
+    for (slot, set) in team.iter().enumerate() {
+        let species = set.species.data();
+
+        if gen >= 3 {
+            let total: u16 = set.evs.iter().map(|&ev| ev as u16).sum();
+            if total > MAX_TOTAL_EVS {
+                errors.push(ValidationError::EvTotal { slot, total });
+            }
+            for (stat, &value) in set.evs.iter().enumerate() {
+                if value > MAX_STAT_EVS {
+                    errors.push(ValidationError::EvTooHigh { slot, stat, value });
+                }
+            }
+        }
+        for (stat, &value) in set.ivs.iter().enumerate() {
+            if value > MAX_IV {
+                errors.push(ValidationError::IvOutOfRange { slot, stat, value });
+            }
+        }
+        if set.level == 0 || set.level > format.max_level {
+            errors.push(ValidationError::LevelOutOfRange {
+                slot,
+                level: set.level,
+                max: format.max_level,
+            });
+        }
+
+        // Species
+        if !set.species.is_available_in(gen) {
+            errors.push(ValidationError::UnavailableSpecies {
+                slot,
+                species: set.species,
+            });
+        }
+        if format.is_species_banned(set.species) {
+            errors.push(ValidationError::BannedSpecies {
+                slot,
+                species: set.species,
+            });
+        }
+        if set.gigantamax && !gmax_supported(set.species) {
+            errors.push(ValidationError::UnsupportedGigantamax {
+                slot,
+                species: set.species,
+            });
+        }
+        let little_cup = species.flags & FLAG_NFE != 0 && species.flags & FLAG_EVOLVED == 0;
+        if format.has_clause(Clause::LittleCup) && !little_cup {
+            errors.push(ValidationError::NotLittleCup {
+                slot,
+                species: set.species,
+            });
+        }
+        if format.has_clause(Clause::Species)
+            && team[..slot]
+                .iter()
+                .any(|other| other.species.base() == set.species.base())
+        {
+            errors.push(ValidationError::DuplicateSpecies {
+                slot,
+                species: set.species,
+            });
+        }
+
+        // Ability (none before Gen 3; hidden abilities from Gen 5)
+        if let Some(ability) = set.ability.filter(|_| gen >= 3) {
+            let mut legal = [species.ability0, species.ability1, 0];
+            if gen >= 5 {
+                legal[2] = species.hidden_ability;
+            }
+            let id = ability as u16;
+            if ability == AbilityId::Noability || !legal.contains(&id) {
+                errors.push(ValidationError::IllegalAbility { slot, ability });
+            } else if !ability.is_available_in(gen) {
+                errors.push(ValidationError::UnavailableAbility { slot, ability });
+            }
+            if is_banned(&format.banned_abilities, &clause_abilities, ability.name()) {
+                errors.push(ValidationError::BannedAbility { slot, ability });
+            }
+        }
+
+        // Item
+        if set.item != ItemId::None {
+            let name = set.item.data().name;
+            if !set.item.is_available_in(gen) {
+                errors.push(ValidationError::UnavailableItem {
+                    slot,
+                    item: set.item,
+                });
+            }
+            if is_banned(&format.banned_items, &clause_items, name) {
+                errors.push(ValidationError::BannedItem {
+                    slot,
+                    item: set.item,
+                });
+            }
+            if format.has_clause(Clause::Item)
+                && team[..slot].iter().any(|other| other.item == set.item)
+            {
+                errors.push(ValidationError::DuplicateItem {
+                    slot,
+                    item: set.item,
+                });
+            }
+        }
+
+        // Moves
+        let moves: Vec<MoveId> = set
+            .moves
+            .iter()
+            .copied()
+            .filter(|&move_id| move_id != MoveId::default())
+            .collect();
+        if moves.is_empty() {
+            errors.push(ValidationError::NoMoves { slot });
+        }
+        for (i, &move_id) in moves.iter().enumerate() {
+            let data = move_id.data();
+            if moves[..i].contains(&move_id) {
+                errors.push(ValidationError::DuplicateMove { slot, move_id });
+                continue;
+            }
+            if is_banned(&format.banned_moves, &clause_moves, data.name)
+                || (format.has_clause(Clause::Ohko) && data.flags.contains(MoveFlags::OHKO))
+            {
+                errors.push(ValidationError::BannedMove { slot, move_id });
+            }
+            if !move_id.is_available_in(gen) {
+                errors.push(ValidationError::UnavailableMove { slot, move_id });
+            } else if LEARNSETS_LOADED
+                && !set.species.is_custom()
+                && set.species.can_learn(move_id, gen).is_none()
+            {
+                errors.push(ValidationError::IllegalMove { slot, move_id });
+            }
+        }
+    }
+    errors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::natures::NatureId;
+
+    fn garchomp() -> PokemonConfig {
+        PokemonConfig::new(SpeciesId::GARCHOMP)
+            .ability(AbilityId::Roughskin)
+            .item(ItemId::Leftovers)
+            .nature(NatureId::Jolly)
+            .moves([
+                MoveId::Earthquake,
+                MoveId::Outrage,
+                MoveId::Stoneedge,
+                MoveId::Swordsdance,
+            ])
+    }
+
+    #[test]
+    fn test_legal_team_passes() {
+        let team = [
+            garchomp(),
+            PokemonConfig::new(SpeciesId::PIKACHU).set_move(0, MoveId::Thunderbolt),
+        ];
+        assert_eq!(validate_team(&team, &Format::default()), vec![]);
+    }
+
+    #[test]
+    fn test_missing_learnsets_are_a_warning() {
+        assert_eq!(learnset_warning().is_some(), !LEARNSETS_LOADED);
+    }
+
+    #[test]
+    fn test_stat_limits() {
+        // Fields set directly: the builders clamp EVs and IVs.
+        let mut set = garchomp();
+        set.evs = [252, 252, 0, 0, 8, 0];
+        set.ivs[5] = 32;
+        let errors = validate_team(&[set], &Format::default());
+        assert_eq!(
+            errors,
+            vec![
+                ValidationError::EvTotal {
+                    slot: 0,
+                    total: 512
+                },
+                ValidationError::IvOutOfRange {
+                    slot: 0,
+                    stat: 5,
+                    value: 32
+                },
+            ]
+        );
+
+        let format = Format {
+            max_level: 50,
+            ..Format::default()
+        };
+        let errors = validate_team(&[garchomp().level(100)], &format);
+        assert_eq!(
+            errors,
+            vec![ValidationError::LevelOutOfRange {
+                slot: 0,
+                level: 100,
+                max: 50
+            }]
+        );
+    }
+
+    #[test]
+    fn test_illegal_ability_and_duplicate_move() {
+        let set = garchomp()
+            .ability(AbilityId::Levitate)
+            .set_move(3, MoveId::Earthquake);
+        let errors = validate_team(&[set], &Format::default());
+        assert!(errors.contains(&ValidationError::IllegalAbility {
+            slot: 0,
+            ability: AbilityId::Levitate
+        }));
+        assert!(errors.contains(&ValidationError::DuplicateMove {
+            slot: 0,
+            move_id: MoveId::Earthquake
+        }));
+    }
+
+    #[test]
+    fn test_bans_and_clauses() {
+        let format = Format {
+            banned_species: vec![String::from("Pikachu")],
+            banned_items: vec![String::from("Choice Band")],
+            clauses: vec![Clause::Species, Clause::Item, Clause::Evasion, Clause::Ohko],
+            ..Format::default()
+        };
+        let team = [
+            garchomp().set_move(2, MoveId::Doubleteam),
+            garchomp()
+                .item(ItemId::Choiceband)
+                .set_move(2, MoveId::Sheercold),
+            PokemonConfig::new(SpeciesId::PIKACHU)
+                .item(ItemId::Leftovers)
+                .set_move(0, MoveId::Thunderbolt),
+        ];
+        let errors = validate_team(&team, &format);
+        assert_eq!(
+            errors,
+            vec![
+                ValidationError::BannedMove {
+                    slot: 0,
+                    move_id: MoveId::Doubleteam
+                },
+                ValidationError::DuplicateSpecies {
+                    slot: 1,
+                    species: SpeciesId::GARCHOMP
+                },
+                ValidationError::BannedItem {
+                    slot: 1,
+                    item: ItemId::Choiceband
+                },
+                ValidationError::BannedMove {
+                    slot: 1,
+                    move_id: MoveId::Sheercold
+                },
+                ValidationError::BannedSpecies {
+                    slot: 2,
+                    species: SpeciesId::PIKACHU
+                },
+                ValidationError::DuplicateItem {
+                    slot: 2,
+                    item: ItemId::Leftovers
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unsupported_gigantamax() {
+        let gen8 = Format {
+            generation: 8,
+            ..Format::default()
+        };
+        let team = [
+            PokemonConfig::new(SpeciesId::PIKACHU)
+                .gigantamax(true)
+                .set_move(0, MoveId::Thunderbolt),
+            PokemonConfig::new(SpeciesId::SNORLAX)
+                .gigantamax(true)
+                .set_move(0, MoveId::Bodyslam),
+        ];
+        // G-Max Replenish's Berry recycling isn't modelled.
+        assert_eq!(
+            validate_team(&team, &gen8),
+            vec![ValidationError::UnsupportedGigantamax {
+                slot: 1,
+                species: SpeciesId::SNORLAX
+            }]
+        );
+    }
+
+    #[test]
+    fn test_team_size_monotype_and_little_cup() {
+        let lc = Format::builtin("gen9lc").unwrap();
+        let team = [
+            PokemonConfig::new(SpeciesId::PIKACHU)
+                .level(5)
+                .set_move(0, MoveId::Thunderbolt),
+            PokemonConfig::new(SpeciesId::PICHU)
+                .level(5)
+                .set_move(0, MoveId::Thunderbolt),
+        ];
+        // Pikachu evolves from Pichu; both are Electric.
+        let errors = validate_team(&team, &lc);
+        assert_eq!(
+            errors,
+            vec![ValidationError::NotLittleCup {
+                slot: 0,
+                species: SpeciesId::PIKACHU
+            }]
+        );
+
+        let monotype = Format {
+            clauses: vec![Clause::Monotype],
+            pick: Some(4),
+            ..Format::default()
+        };
+        let team = [
+            garchomp(),
+            PokemonConfig::new(SpeciesId::PIKACHU).set_move(0, MoveId::Thunderbolt),
+        ];
+        assert_eq!(
+            validate_team(&team, &monotype),
+            vec![
+                ValidationError::TeamSize {
+                    size: 2,
+                    min: 4,
+                    max: 6
+                },
+                ValidationError::NotMonotype,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unavailable_in_generation() {
+        // Garchomp and Choice Specs arrived in Gen 4; Return left in Gen 9.
+        let gen3 = Format {
+            generation: 3,
+            ..Format::default()
+        };
+        let set = PokemonConfig::new(SpeciesId::GARCHOMP)
+            .item(ItemId::Choicespecs)
+            .set_move(0, MoveId::Earthquake);
+        assert_eq!(
+            validate_team(&[set], &gen3),
+            vec![
+                ValidationError::UnavailableSpecies {
+                    slot: 0,
+                    species: SpeciesId::GARCHOMP
+                },
+                ValidationError::UnavailableItem {
+                    slot: 0,
+                    item: ItemId::Choicespecs
+                },
+            ]
+        );
+
+        let set = PokemonConfig::new(SpeciesId::PIKACHU).set_move(0, MoveId::Return);
+        assert_eq!(
+            validate_team(&[set], &Format::default()),
+            vec![ValidationError::UnavailableMove {
+                slot: 0,
+                move_id: MoveId::Return
+            }]
+        );
+    }
+}
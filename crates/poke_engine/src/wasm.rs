@@ -0,0 +1,368 @@
+//! WebAssembly bindings (`wasm` feature).
+//!
+//! Build with `wasm-pack build crates/poke_engine_wasm`, which
+//! also emits the `.d.ts` declarations. Values cross the boundary as plain
+//! JS objects (through `JSON`), typed by the interfaces in `TS_TYPES`.
+//! Errors are thrown as JS `Error`s.
+//!
+//! ```js
+//! const result = calcDamage("Garchomp @ Choice Band, Jolly, 252 Atk",
+//!                           { species: "Tyranitar", evs: { hp: 252 } },
+//!                           "Earthquake", { weather: "sand" });
+//! const battle = new Battle(teamText1, teamText2, 9, 0);
+//! battle.choose("move 1", "switch 2");
+//! ```
+
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+
+use crate::battle::{
+    legal_actions, search_best_move, start_battle, submit_actions, Action, BattleRng,
+    BattleSnapshot, ProtocolWriter, SearchOptions,
+};
+use crate::damage::generations::{Terrain, Weather};
+use crate::damage::{calculate_damage, ExtraHits, Generation};
+use crate::entities::PokemonConfig;
+use crate::moves::MoveId;
+use crate::state::BattleState;
+use crate::team::{parse_short_set, parse_showdown_set, parse_team, to_id, PokemonSetJson};
+
+#[wasm_bindgen(typescript_custom_section)]
+const TS_TYPES: &'static str = r#"
+export interface StatsTable {
+    hp?: number;
+    atk?: number;
+    def?: number;
+    spa?: number;
+    spd?: number;
+    spe?: number;
+}
+
+/** Showdown's `PokemonSet` JSON shape. */
+export interface PokemonSet {
+    species: string;
+    item?: string;
+    ability?: string;
+    moves: string[];
+    nature?: string;
+    evs?: StatsTable;
+    ivs?: StatsTable;
+    level?: number;
+    gender?: "M" | "F" | "N";
+    happiness?: number;
+}
+
+/** A set object, Showdown export text, or the one-line form. */
+export type SetInput = PokemonSet | string;
+
+/** An array of sets or Showdown export text (JSON text also works). */
+export type TeamInput = PokemonSet[] | string;
+
+export interface CalcOptions {
+    gen?: number;
+    crit?: boolean;
+    weather?: string;
+    terrain?: string;
+    gravity?: boolean;
+    /** Screens on the defender's side */
+    reflect?: boolean;
+    lightScreen?: boolean;
+    auroraVeil?: boolean;
+}
+
+export interface DamageResult {
+    rolls: number[];
+    min: number;
+    max: number;
+    /** 4 = neutral, 8 = 2x, 2 = 0.5x, 0 = immune */
+    effectiveness: number;
+    isCrit: boolean;
+    /** Rolls of extra hits (Parental Bond), if any */
+    multiHitRolls: number[][] | null;
+    defenderHp: number;
+}
+
+export interface PokemonSnapshot {
+    species: string;
+    hp: number;
+    max_hp: number;
+    status: "brn" | "frz" | "par" | "psn" | "tox" | "slp" | "frb" | "drw" | null;
+    boosts: number[];
+    /** Tera Type, once Terastallized */
+    terastallized: string | null;
+    /** Dynamax turns left (0 = not Dynamaxed) */
+    dynamax_turns: number;
+}
+
+export interface SideSnapshot {
+    active: number;
+    needs_replacement: boolean;
+    pokemon: PokemonSnapshot[];
+}
+
+export interface BattleSnapshot {
+    turn: number;
+    weather: string | null;
+    terrain: string | null;
+    sides: [SideSnapshot, SideSnapshot];
+    result: "p1" | "p2" | "tie" | null;
+}
+
+export interface SearchResult {
+    choice: string;
+    score: number;
+}
+"#;
+
+fn error(message: impl std::fmt::Display) -> JsError {
+    JsError::new(&message.to_string())
+}
+
+/// Read a JS value through `JSON.stringify`.
+fn from_js<T: for<'de> Deserialize<'de>>(value: &JsValue) -> Result<T, JsError> {
+    let text: String = js_sys::JSON::stringify(value)
+        .map_err(|_| error("value is not JSON-serializable"))?
+        .into();
+    serde_json::from_str(&text).map_err(error)
+}
+
+/// Hand a value to JS through `JSON.parse`.
+fn to_js<T: Serialize>(value: &T) -> Result<JsValue, JsError> {
+    let text = serde_json::to_string(value).map_err(error)?;
+    js_sys::JSON::parse(&text).map_err(|_| error("invalid JSON"))
+}
+
+fn set_from_js(value: &JsValue) -> Result<PokemonConfig, JsError> {
+    let config = match value.as_string() {
+        Some(text) if text.contains('\n') => parse_showdown_set(&text),
+        Some(text) => parse_short_set(&text),
+        None => from_js::<PokemonSetJson>(value)?.to_config(),
+    };
+    config.map_err(error)
+}
+
+fn team_from_js(value: &JsValue) -> Result<Vec<PokemonConfig>, JsError> {
+    let text = match value.as_string() {
+        Some(text) => text,
+        // Arrays of sets go through the JSON importer, which also checks the size
+        None => js_sys::JSON::stringify(value)
+            .map_err(|_| error("value is not JSON-serializable"))?
+            .into(),
+    };
+    parse_team(&text).map_err(error)
+}
+
+fn generation(gen: Option<u8>) -> Result<Generation, JsError> {
+    match gen.unwrap_or(9) {
+        gen @ 1..=9 => Ok(Generation::from_num(gen)),
+        gen => Err(error(format!("unsupported gen: {}", gen))),
+    }
+}
+
+/// Parse a team (export text or JSON) into normalized sets.
+#[wasm_bindgen(js_name = parseTeam, unchecked_return_type = "PokemonSet[]")]
+pub fn parse_team_js(text: &str) -> Result<JsValue, JsError> {
+    let team = parse_team(text).map_err(error)?;
+    to_js(
+        &team
+            .iter()
+            .map(PokemonSetJson::from_config)
+            .collect::<Vec<_>>(),
+    )
+}
+
+#[derive(Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct CalcOptions {
+    gen: Option<u8>,
+    crit: bool,
+    weather: Option<String>,
+    terrain: Option<String>,
+    gravity: bool,
+    reflect: bool,
+    light_screen: bool,
+    aurora_veil: bool,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DamageResultJs {
+    rolls: [u16; 16],
+    min: u16,
+    max: u16,
+    effectiveness: u8,
+    is_crit: bool,
+    multi_hit_rolls: Option<ExtraHits<[u16; 16]>>,
+    defender_hp: u16,
+}
+
+/// Damage of `move` from `attacker` against `defender`, all 16 rolls.
+#[wasm_bindgen(js_name = calcDamage, unchecked_return_type = "DamageResult")]
+pub fn calc_damage(
+    #[wasm_bindgen(unchecked_param_type = "SetInput")] attacker: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "SetInput")] defender: JsValue,
+    move_name: &str,
+    #[wasm_bindgen(unchecked_param_type = "CalcOptions | undefined")] options: JsValue,
+) -> Result<JsValue, JsError> {
+    let options: CalcOptions = if options.is_undefined() || options.is_null() {
+        CalcOptions::default()
+    } else {
+        from_js(&options)?
+    };
+    let gen = generation(options.gen)?;
+    let move_id = MoveId::from_str(&to_id(move_name))
+        .ok_or_else(|| error(format!("unknown move: {}", move_name)))?;
+
+    let mut state = BattleState::new();
+    state.generation = gen.num();
+    set_from_js(&attacker)?.place(&mut state, 0, 0);
+    set_from_js(&defender)?.place(&mut state, 1, 0);
+    let defender = BattleState::entity_index(1, 0);
+
+    if let Some(name) = &options.weather {
+        state.weather = Weather::from_name(name)
+            .ok_or_else(|| error(format!("unknown weather: {}", name)))?
+            as u8;
+    }
+    if let Some(name) = &options.terrain {
+        state.terrain = Terrain::from_name(name)
+            .ok_or_else(|| error(format!("unknown terrain: {}", name)))?
+            as u8;
+    }
+    state.gravity = options.gravity;
+    let screens = &mut state.side_conditions[1];
+    screens.reflect_turns = if options.reflect { 5 } else { 0 };
+    screens.light_screen_turns = if options.light_screen { 5 } else { 0 };
+    screens.aurora_veil_turns = if options.aurora_veil { 5 } else { 0 };
+
+    let result = calculate_damage(gen, &state, 0, defender, move_id, options.crit);
+    to_js(&DamageResultJs {
+        rolls: result.rolls,
+        min: result.min,
+        max: result.max,
+        effectiveness: result.effectiveness,
+        is_crit: result.is_crit,
+        multi_hit_rolls: result.multi_hit_rolls,
+        defender_hp: state.max_hp[defender],
+    })
+}
+
+/// A battle in progress. Cheap to `clone()` for what-if analysis.
+#[wasm_bindgen]
+#[derive(Clone)]
+pub struct Battle {
+    gen: Generation,
+    state: BattleState,
+    rng: BattleRng,
+    log: ProtocolWriter<String>,
+}
+
+#[wasm_bindgen]
+impl Battle {
+    /// Set up both teams and send out the leads.
+    #[wasm_bindgen(constructor)]
+    pub fn new(
+        #[wasm_bindgen(unchecked_param_type = "TeamInput")] p1: JsValue,
+        #[wasm_bindgen(unchecked_param_type = "TeamInput")] p2: JsValue,
+        gen: Option<u8>,
+        seed: Option<u32>,
+    ) -> Result<Battle, JsError> {
+        let gen = generation(gen)?;
+        let mut state = BattleState::new();
+        state.generation = gen.num();
+        for (player, team) in [p1, p2].iter().enumerate() {
+            for (slot, config) in team_from_js(team)?.iter().enumerate() {
+                config.place(&mut state, player, slot);
+            }
+        }
+        let mut rng = BattleRng::new(seed.unwrap_or(0) as u64);
+        let mut log = ProtocolWriter::new(String::new());
+        start_battle(&mut state, &mut rng, &mut log);
+        Ok(Battle {
+            gen,
+            state,
+            rng,
+            log,
+        })
+    }
+
+    /// Submit both players' choices (`"move 1"`, `"move 1 terastallize"`,
+    /// `"move 1 dynamax"`, `"move 1 zmove"`, `"move 1 mega"`,
+    /// `"move 1 ultra"`, `"move 1 strong"`, `"move 1 agile"`, `"switch 2"`,
+    /// `"pass"`).
+    /// Returns the Showdown protocol lines this produced.
+    pub fn choose(&mut self, p1: &str, p2: &str) -> Result<Vec<String>, JsError> {
+        let mut actions = [Action::Pass; 2];
+        for (action, choice) in actions.iter_mut().zip([p1, p2]) {
+            *action = Action::from_choice(choice)
+                .ok_or_else(|| error(format!("invalid choice: {}", choice)))?;
+        }
+        submit_actions(
+            self.gen,
+            &mut self.state,
+            actions,
+            &mut self.rng,
+            &mut self.log,
+        )
+        .map_err(error)?;
+        Ok(self.take_log())
+    }
+
+    /// Protocol lines produced since the last call (or since `choose`).
+    #[wasm_bindgen(js_name = takeLog)]
+    pub fn take_log(&mut self) -> Vec<String> {
+        let text = std::mem::take(&mut self.log).into_inner();
+        text.lines().map(str::to_string).collect()
+    }
+
+    /// Legal choices for player 1 or 2.
+    #[wasm_bindgen(js_name = legalActions)]
+    pub fn legal_actions(&self, player: u8) -> Result<Vec<String>, JsError> {
+        let player = player_index(player)?;
+        Ok(legal_actions(&self.state, player)
+            .iter()
+            .map(Action::to_string)
+            .collect())
+    }
+
+    /// Best choice for player 1 or 2 found by `search_best_move`.
+    #[wasm_bindgen(js_name = searchBestMove, unchecked_return_type = "SearchResult | null")]
+    pub fn search_best_move(
+        &self,
+        player: u8,
+        depth: Option<u8>,
+        samples: Option<u8>,
+    ) -> Result<JsValue, JsError> {
+        let options = SearchOptions {
+            depth: depth.unwrap_or(1).clamp(1, 3),
+            samples: samples.unwrap_or(4).max(1),
+            ..SearchOptions::default()
+        };
+        match search_best_move(self.gen, &self.state, player_index(player)?, options) {
+            Some(result) => to_js(&serde_json::json!({
+                "choice": result.action.to_string(),
+                "score": result.score,
+            })),
+            None => Ok(JsValue::NULL),
+        }
+    }
+
+    /// Current state as a plain object.
+    #[wasm_bindgen(unchecked_return_type = "BattleSnapshot")]
+    pub fn snapshot(&self) -> Result<JsValue, JsError> {
+        to_js(&BattleSnapshot::from(&self.state))
+    }
+
+    /// Independent copy of this battle (state, RNG and pending log).
+    #[wasm_bindgen(js_name = clone)]
+    pub fn clone_js(&self) -> Battle {
+        self.clone()
+    }
+}
+
+fn player_index(player: u8) -> Result<usize, JsError> {
+    match player {
+        1 | 2 => Ok(player as usize - 1),
+        _ => Err(error("player must be 1 or 2")),
+    }
+}
@@ -76,6 +76,9 @@ pub struct MoveData {
     pub name: String,
     #[serde(rename = "useZ")]
     pub use_z: Option<bool>,
+    /// Base move of a Z-Move (`name` is the Z-Move itself)
+    #[serde(rename = "originalName")]
+    pub original_name: Option<String>,
     #[serde(rename = "isCrit")]
     pub is_crit: Option<bool>,
     pub hits: Option<u8>,
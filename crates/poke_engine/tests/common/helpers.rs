@@ -3,8 +3,8 @@
 //! These helpers convert fixture data into engine state and verify results.
 
 use poke_engine::abilities::AbilityId;
-use poke_engine::damage::calculate_damage_with_overrides;
 use poke_engine::damage::generations::{Generation, Terrain, Weather};
+use poke_engine::damage::{calculate_damage, calculate_z_move_damage};
 use poke_engine::entities::PokemonConfig;
 use poke_engine::items::ItemId;
 use poke_engine::moves::MoveId;
@@ -215,24 +215,6 @@ pub fn parse_expected_damage(value: &serde_json::Value) -> Vec<u16> {
     }
 }
 
-/// Extract Z-move base power from fixture description.
-pub fn z_move_base_power(case: &DamageTestCase) -> Option<u16> {
-    if case.move_data.use_z != Some(true) {
-        return None;
-    }
-    extract_base_power_from_desc(&case.expected.desc)
-}
-
-/// Extract base power from description string (e.g., "(120 BP)").
-fn extract_base_power_from_desc(desc: &str) -> Option<u16> {
-    let marker = " BP)";
-    let end = desc.find(marker)?;
-    let before = &desc[..end];
-    let start = before.rfind('(')?;
-    let bp_str = &before[start + 1..];
-    bp_str.parse().ok()
-}
-
 /// Run a damage calculation test and verify results.
 pub fn run_damage_test(case: &DamageTestCase) -> Result<(), String> {
     let mut state = BattleState::new();
@@ -246,15 +228,16 @@ pub fn run_damage_test(case: &DamageTestCase) -> Result<(), String> {
     apply_field(&case.field, &mut state);
     state.generation = case.gen;
 
-    let move_normalized = case
-        .move_data
-        .name
-        .to_lowercase()
-        .replace(['-', ' ', '\''], "");
+    // Z-Move fixtures name the Z-Move; the engine derives it from the base move.
+    let move_name = match (case.move_data.use_z, &case.move_data.original_name) {
+        (Some(true), Some(base_move)) => base_move,
+        _ => &case.move_data.name,
+    };
+    let move_normalized = move_name.to_lowercase().replace(['-', ' ', '\''], "");
     let move_id = MoveId::from_str(&move_normalized).ok_or_else(|| {
         format!(
             "Unknown move: {} (normalized: {})",
-            case.move_data.name, move_normalized
+            move_name, move_normalized
         )
     })?;
 
@@ -264,89 +247,37 @@ pub fn run_damage_test(case: &DamageTestCase) -> Result<(), String> {
     let attacker_idx = 0;
     let defender_idx = 6;
 
-    let base_power_override = z_move_base_power(case);
     let result = match gen {
-        Generation::Gen9(g) => calculate_damage_with_overrides(
-            g,
-            &state,
-            attacker_idx,
-            defender_idx,
-            move_id,
-            is_crit,
-            base_power_override,
-        ),
-        Generation::Gen8(g) => calculate_damage_with_overrides(
-            g,
-            &state,
-            attacker_idx,
-            defender_idx,
-            move_id,
-            is_crit,
-            base_power_override,
-        ),
-        Generation::Gen7(g) => calculate_damage_with_overrides(
-            g,
-            &state,
-            attacker_idx,
-            defender_idx,
-            move_id,
-            is_crit,
-            base_power_override,
-        ),
-        Generation::Gen6(g) => calculate_damage_with_overrides(
-            g,
-            &state,
-            attacker_idx,
-            defender_idx,
-            move_id,
-            is_crit,
-            base_power_override,
-        ),
-        Generation::Gen5(g) => calculate_damage_with_overrides(
-            g,
-            &state,
-            attacker_idx,
-            defender_idx,
-            move_id,
-            is_crit,
-            base_power_override,
-        ),
-        Generation::Gen4(g) => calculate_damage_with_overrides(
-            g,
-            &state,
-            attacker_idx,
-            defender_idx,
-            move_id,
-            is_crit,
-            base_power_override,
-        ),
-        Generation::Gen3(g) => calculate_damage_with_overrides(
-            g,
-            &state,
-            attacker_idx,
-            defender_idx,
-            move_id,
-            is_crit,
-            base_power_override,
-        ),
-        Generation::Gen2(g) => calculate_damage_with_overrides(
-            g,
-            &state,
-            attacker_idx,
-            defender_idx,
-            move_id,
-            is_crit,
-            base_power_override,
-        ),
-        Generation::Gen1(g) => calculate_damage_with_overrides(
-            g,
-            &state,
-            attacker_idx,
-            defender_idx,
-            move_id,
-            is_crit,
-            base_power_override,
-        ),
+        _ if case.move_data.use_z == Some(true) => {
+            calculate_z_move_damage(gen, &state, attacker_idx, defender_idx, move_id, is_crit)
+        }
+        Generation::Gen9(g) => {
+            calculate_damage(g, &state, attacker_idx, defender_idx, move_id, is_crit)
+        }
+        Generation::Gen8(g) => {
+            calculate_damage(g, &state, attacker_idx, defender_idx, move_id, is_crit)
+        }
+        Generation::Gen7(g) => {
+            calculate_damage(g, &state, attacker_idx, defender_idx, move_id, is_crit)
+        }
+        Generation::Gen6(g) => {
+            calculate_damage(g, &state, attacker_idx, defender_idx, move_id, is_crit)
+        }
+        Generation::Gen5(g) => {
+            calculate_damage(g, &state, attacker_idx, defender_idx, move_id, is_crit)
+        }
+        Generation::Gen4(g) => {
+            calculate_damage(g, &state, attacker_idx, defender_idx, move_id, is_crit)
+        }
+        Generation::Gen3(g) => {
+            calculate_damage(g, &state, attacker_idx, defender_idx, move_id, is_crit)
+        }
+        Generation::Gen2(g) => {
+            calculate_damage(g, &state, attacker_idx, defender_idx, move_id, is_crit)
+        }
+        Generation::Gen1(g) => {
+            calculate_damage(g, &state, attacker_idx, defender_idx, move_id, is_crit)
+        }
     };
 
     let expected = parse_expected_damage(&case.expected.damage);
@@ -44,6 +44,25 @@ pub const SKIPPED_FIXTURES: &[&str] = &[
     "gen8-Parental-Bond--gen-8--195",
     "gen9-Parental-Bond--gen-9--198",
     "gen9-Parental-Bond--gen-9--199",
+    // =========================================================================
+    // Tera Stellar Repeat-Use Tests
+    // =========================================================================
+    // The calc's "Stellar boost already used" toggle isn't captured in the
+    // fixture inputs, so these are indistinguishable from their first-use
+    // twins. Correctness test: tests/stellar_correctness.rs
+    "gen9-should-only-be-displayed-on-defender-for-Stellar-attacks-390",
+    "gen9-should-not-be-displayed-for-non-boosted-attacks-393",
+    "gen9-should-distinguish-between-first-use-for-Tera-Blast-395",
+    "gen9-should-boost-the-Base-Power-of-moves-under-60-Base-Power-if-it-s-the-first-use-of-the-move-397",
+    "gen9-should-boost-the-Base-Power-of-moves-under-60-Base-Power-if-it-s-the-first-use-of-the-move-399",
+    // =========================================================================
+    // Z-Move Base Move Tests
+    // =========================================================================
+    // The fixture predates the scraper recording `originalName`, so it names
+    // only the Z-Move and not the move it comes from. Re-scrape with
+    // scripts/scrape_damage_tests.mts to re-enable it.
+    // Correctness test: tests/z_move_correctness.rs
+    "gen7-Z-Move-critical-hits-329",
 ];
 
 /// Check if a fixture should be skipped.
@@ -66,6 +85,21 @@ pub fn skip_reason(fixture_id: &str) -> Option<&'static str> {
     if fixture_id.contains("Parental-Bond") {
         return Some("Stateless calculator cannot handle inter-hit state changes (Boosts/HP)");
     }
+    if fixture_id == "gen7-Z-Move-critical-hits-329" {
+        return Some("Fixture doesn't record the Z-Move's base move (see z_move_correctness.rs)");
+    }
+    if matches!(
+        fixture_id,
+        "gen9-should-only-be-displayed-on-defender-for-Stellar-attacks-390"
+            | "gen9-should-not-be-displayed-for-non-boosted-attacks-393"
+            | "gen9-should-distinguish-between-first-use-for-Tera-Blast-395"
+            | "gen9-should-boost-the-Base-Power-of-moves-under-60-Base-Power-if-it-s-the-first-use-of-the-move-397"
+            | "gen9-should-boost-the-Base-Power-of-moves-under-60-Base-Power-if-it-s-the-first-use-of-the-move-399"
+    ) {
+        return Some(
+            "Fixture doesn't record whether the Tera Stellar boost was already used (see stellar_correctness.rs)",
+        );
+    }
     None
 }
 
@@ -4,8 +4,8 @@
 //! from smogon/damage-calc.
 
 use poke_engine::abilities::AbilityId;
-use poke_engine::damage::calculate_damage_with_overrides;
 use poke_engine::damage::generations::{Generation, Terrain, Weather};
+use poke_engine::damage::{calculate_damage, calculate_z_move_damage};
 use poke_engine::entities::PokemonConfig;
 use poke_engine::items::ItemId;
 use poke_engine::moves::MoveId;
@@ -117,6 +117,9 @@ struct MoveData {
     name: String,
     #[serde(rename = "useZ")]
     use_z: Option<bool>,
+    /// Base move of a Z-Move (`name` is the Z-Move itself)
+    #[serde(rename = "originalName")]
+    original_name: Option<String>,
     #[serde(rename = "isCrit")]
     is_crit: Option<bool>,
     hits: Option<u8>,
@@ -372,15 +375,16 @@ fn verify_damage_calc(case: &DamageTestCase) -> Result<(), String> {
     state.generation = case.gen;
 
     // Get move
-    let move_normalized = case
-        .move_data
-        .name
-        .to_lowercase()
-        .replace(['-', ' ', '\''], "");
+    // Z-Move fixtures name the Z-Move; the engine derives it from the base move.
+    let move_name = match (case.move_data.use_z, &case.move_data.original_name) {
+        (Some(true), Some(base_move)) => base_move,
+        _ => &case.move_data.name,
+    };
+    let move_normalized = move_name.to_lowercase().replace(['-', ' ', '\''], "");
     let move_id = MoveId::from_str(&move_normalized).ok_or_else(|| {
         format!(
             "Unknown move: {} (normalized: {})",
-            case.move_data.name, move_normalized
+            move_name, move_normalized
         )
     })?;
 
@@ -394,89 +398,37 @@ fn verify_damage_calc(case: &DamageTestCase) -> Result<(), String> {
     let defender_idx = 6; // Player 1, slot 0
 
     // Use dynamic dispatch for the generation
-    let base_power_override = z_move_base_power(case);
     let result = match gen {
-        Generation::Gen9(g) => calculate_damage_with_overrides(
-            g,
-            &state,
-            attacker_idx,
-            defender_idx,
-            move_id,
-            is_crit,
-            base_power_override,
-        ),
-        Generation::Gen8(g) => calculate_damage_with_overrides(
-            g,
-            &state,
-            attacker_idx,
-            defender_idx,
-            move_id,
-            is_crit,
-            base_power_override,
-        ),
-        Generation::Gen7(g) => calculate_damage_with_overrides(
-            g,
-            &state,
-            attacker_idx,
-            defender_idx,
-            move_id,
-            is_crit,
-            base_power_override,
-        ),
-        Generation::Gen6(g) => calculate_damage_with_overrides(
-            g,
-            &state,
-            attacker_idx,
-            defender_idx,
-            move_id,
-            is_crit,
-            base_power_override,
-        ),
-        Generation::Gen5(g) => calculate_damage_with_overrides(
-            g,
-            &state,
-            attacker_idx,
-            defender_idx,
-            move_id,
-            is_crit,
-            base_power_override,
-        ),
-        Generation::Gen4(g) => calculate_damage_with_overrides(
-            g,
-            &state,
-            attacker_idx,
-            defender_idx,
-            move_id,
-            is_crit,
-            base_power_override,
-        ),
-        Generation::Gen3(g) => calculate_damage_with_overrides(
-            g,
-            &state,
-            attacker_idx,
-            defender_idx,
-            move_id,
-            is_crit,
-            base_power_override,
-        ),
-        Generation::Gen2(g) => calculate_damage_with_overrides(
-            g,
-            &state,
-            attacker_idx,
-            defender_idx,
-            move_id,
-            is_crit,
-            base_power_override,
-        ),
-        Generation::Gen1(g) => calculate_damage_with_overrides(
-            g,
-            &state,
-            attacker_idx,
-            defender_idx,
-            move_id,
-            is_crit,
-            base_power_override,
-        ),
+        _ if case.move_data.use_z == Some(true) => {
+            calculate_z_move_damage(gen, &state, attacker_idx, defender_idx, move_id, is_crit)
+        }
+        Generation::Gen9(g) => {
+            calculate_damage(g, &state, attacker_idx, defender_idx, move_id, is_crit)
+        }
+        Generation::Gen8(g) => {
+            calculate_damage(g, &state, attacker_idx, defender_idx, move_id, is_crit)
+        }
+        Generation::Gen7(g) => {
+            calculate_damage(g, &state, attacker_idx, defender_idx, move_id, is_crit)
+        }
+        Generation::Gen6(g) => {
+            calculate_damage(g, &state, attacker_idx, defender_idx, move_id, is_crit)
+        }
+        Generation::Gen5(g) => {
+            calculate_damage(g, &state, attacker_idx, defender_idx, move_id, is_crit)
+        }
+        Generation::Gen4(g) => {
+            calculate_damage(g, &state, attacker_idx, defender_idx, move_id, is_crit)
+        }
+        Generation::Gen3(g) => {
+            calculate_damage(g, &state, attacker_idx, defender_idx, move_id, is_crit)
+        }
+        Generation::Gen2(g) => {
+            calculate_damage(g, &state, attacker_idx, defender_idx, move_id, is_crit)
+        }
+        Generation::Gen1(g) => {
+            calculate_damage(g, &state, attacker_idx, defender_idx, move_id, is_crit)
+        }
     };
 
     // Parse expected damage
@@ -522,21 +474,6 @@ fn verify_damage_calc(case: &DamageTestCase) -> Result<(), String> {
     Ok(())
 }
 
-fn z_move_base_power(case: &DamageTestCase) -> Option<u16> {
-    if case.move_data.use_z != Some(true) {
-        return None;
-    }
-
-    extract_base_power_from_desc(&case.expected.desc)
-}
-
-fn extract_base_power_from_desc(desc: &str) -> Option<u16> {
-    let marker = " BP)";
-    let end = desc.find(marker)?;
-    let start = desc[..end].rfind('(')? + 1;
-    desc[start..end].trim().parse::<u16>().ok()
-}
-
 // ============================================================================
 // Main Test
 // ============================================================================
@@ -4,13 +4,14 @@
 //! allowing filtering with `cargo test Arceus` etc.
 
 use poke_engine::abilities::AbilityId;
-use poke_engine::damage::calculate_damage_with_overrides;
 use poke_engine::damage::generations::{Generation, Terrain, Weather};
+use poke_engine::damage::{calculate_damage_with_overrides, calculate_z_move_damage};
 use poke_engine::entities::PokemonConfig;
 use poke_engine::items::ItemId;
 use poke_engine::moves::{MoveFlags, MoveId};
 use poke_engine::natures::NatureId;
 use poke_engine::state::BattleState;
+use poke_engine::types::Type;
 
 use libtest_mimic::{Arguments, Failed, Trial};
 use serde::Deserialize;
@@ -52,6 +53,25 @@ const SKIPPED_FIXTURES: &[&str] = &[
     "gen8-Parental-Bond--gen-8--195",
     "gen9-Parental-Bond--gen-9--198",
     "gen9-Parental-Bond--gen-9--199",
+    // =========================================================================
+    // Tera Stellar Repeat-Use Tests
+    // =========================================================================
+    // The calc's "Stellar boost already used" toggle isn't captured in the
+    // fixture inputs, so these are indistinguishable from their first-use
+    // twins. Correctness test: tests/stellar_correctness.rs
+    "gen9-should-only-be-displayed-on-defender-for-Stellar-attacks-390",
+    "gen9-should-not-be-displayed-for-non-boosted-attacks-393",
+    "gen9-should-distinguish-between-first-use-for-Tera-Blast-395",
+    "gen9-should-boost-the-Base-Power-of-moves-under-60-Base-Power-if-it-s-the-first-use-of-the-move-397",
+    "gen9-should-boost-the-Base-Power-of-moves-under-60-Base-Power-if-it-s-the-first-use-of-the-move-399",
+    // =========================================================================
+    // Z-Move Base Move Tests
+    // =========================================================================
+    // The fixture predates the scraper recording `originalName`, so it names
+    // only the Z-Move and not the move it comes from. Re-scrape with
+    // scripts/scrape_damage_tests.mts to re-enable it.
+    // Correctness test: tests/z_move_correctness.rs
+    "gen7-Z-Move-critical-hits-329",
 ];
 
 // ============================================================================
@@ -124,6 +144,9 @@ pub struct MoveData {
     pub name: String,
     #[serde(rename = "useZ")]
     pub use_z: Option<bool>,
+    /// Base move of a Z-Move (`name` is the Z-Move itself)
+    #[serde(rename = "originalName")]
+    pub original_name: Option<String>,
     #[serde(rename = "isCrit")]
     pub is_crit: Option<bool>,
     pub hits: Option<u8>,
@@ -238,8 +261,19 @@ fn spawn_pokemon(
         config = config.ivs(iv_array);
     }
 
+    if let Some(ref tera_str) = data.tera_type {
+        let tera_type =
+            Type::from_str(tera_str).ok_or_else(|| format!("Unknown Tera Type: {}", tera_str))?;
+        config = config.tera_type(tera_type);
+    }
+
     config.spawn(state, player, slot);
 
+    // The calc treats any Tera Type as already Terastallized
+    if data.tera_type.is_some() {
+        state.terastallize(BattleState::entity_index(player, slot));
+    }
+
     if let Some(ref boosts) = data.boosts {
         let entity_idx = BattleState::entity_index(player, slot);
         state.boosts[entity_idx][0] = boosts.atk.unwrap_or_default();
@@ -395,33 +429,8 @@ fn verify_rolls(actual: &[u16; 16], expected: &[u16], context: &str) -> Result<(
 }
 
 fn get_base_power_override(case: &DamageTestCase) -> Option<u16> {
-    // First check for explicit move overrides (e.g., basePower: 75)
-    if let Some(ref overrides) = case.move_data.overrides {
-        if let Some(bp) = overrides.base_power {
-            return Some(bp);
-        }
-    }
-    // Then check for Z-move base power from description
-    if case.move_data.use_z != Some(true) {
-        return None;
-    }
-    extract_base_power_from_desc(&case.expected.desc)
-}
-
-fn z_move_base_power(case: &DamageTestCase) -> Option<u16> {
-    if case.move_data.use_z != Some(true) {
-        return None;
-    }
-    extract_base_power_from_desc(&case.expected.desc)
-}
-
-fn extract_base_power_from_desc(desc: &str) -> Option<u16> {
-    let marker = " BP)";
-    let end = desc.find(marker)?;
-    let before = &desc[..end];
-    let start = before.rfind('(')?;
-    let bp_str = &before[start + 1..];
-    bp_str.parse().ok()
+    // Explicit move overrides (e.g., basePower: 75)
+    case.move_data.overrides.as_ref()?.base_power
 }
 
 // ============================================================================
@@ -465,15 +474,16 @@ fn run_damage_test(case: &DamageTestCase) -> Result<(), String> {
         );
     }
 
-    let move_normalized = case
-        .move_data
-        .name
-        .to_lowercase()
-        .replace(['-', ' ', '\''], "");
+    // Z-Move fixtures name the Z-Move; the engine derives it from the base move.
+    let move_name = match (case.move_data.use_z, &case.move_data.original_name) {
+        (Some(true), Some(base_move)) => base_move,
+        _ => &case.move_data.name,
+    };
+    let move_normalized = move_name.to_lowercase().replace(['-', ' ', '\''], "");
     let move_id = MoveId::from_str(&move_normalized).ok_or_else(|| {
         format!(
             "Unknown move: {} (normalized: {})",
-            case.move_data.name, move_normalized
+            move_name, move_normalized
         )
     })?;
 
@@ -489,6 +499,9 @@ fn run_damage_test(case: &DamageTestCase) -> Result<(), String> {
 
     let base_power_override = get_base_power_override(case);
     let result = match gen {
+        _ if case.move_data.use_z == Some(true) => {
+            calculate_z_move_damage(gen, &state, attacker_idx, defender_idx, move_id, is_crit)
+        }
         Generation::Gen9(g) => calculate_damage_with_overrides(
             g,
             &state,
@@ -0,0 +1,54 @@
+//! Correctness tests for the Tera Stellar boost.
+//!
+//! The smogon/damage-calc fixtures for a repeat Stellar-boosted move depend
+//! on the calc's "boost already used" toggle, which they don't record, so
+//! they are skipped. These tests cover the first use and a repeat use of
+//! each move with the toggle spelled out as `consume_stellar_boost`.
+
+use poke_engine::damage::calculate_damage;
+use poke_engine::damage::generations::Gen9;
+use poke_engine::entities::PokemonConfig;
+use poke_engine::moves::MoveId;
+use poke_engine::natures::NatureId;
+use poke_engine::state::BattleState;
+use poke_engine::types::Type;
+
+/// Tera Stellar Arceus against Arceus, both with no EVs.
+fn setup() -> BattleState {
+    let mut state = BattleState::new();
+    state.generation = 9;
+    for side in 0..2 {
+        PokemonConfig::from_str("arceus")
+            .unwrap()
+            .level(100)
+            .nature(NatureId::Serious)
+            .evs([0; 6])
+            .tera_type(Type::Stellar)
+            .spawn(&mut state, side, 0);
+    }
+    state.terastallize(0);
+    state
+}
+
+#[test]
+fn test_stellar_first_use_raises_weak_moves_to_60_bp() {
+    let mut state = setup();
+    let result = calculate_damage(Gen9, &state, 0, 6, MoveId::Watergun, false);
+    assert_eq!((result.min, result.max), (53, 62));
+
+    // The boost is spent, and Water Gun is back to 40 BP without STAB
+    state.consume_stellar_boost(0, Type::Water);
+    let result = calculate_damage(Gen9, &state, 0, 6, MoveId::Watergun, false);
+    assert_eq!((result.min, result.max), (29, 35));
+}
+
+#[test]
+fn test_stellar_first_use_tera_blast() {
+    let mut state = setup();
+    let result = calculate_damage(Gen9, &state, 0, 6, MoveId::Terablast, false);
+    assert_eq!((result.min, result.max), (88, 103));
+
+    state.consume_stellar_boost(0, Type::Stellar);
+    let result = calculate_damage(Gen9, &state, 0, 6, MoveId::Terablast, false);
+    assert_eq!((result.min, result.max), (73, 86));
+}
@@ -0,0 +1,36 @@
+//! Correctness tests for Z-Move damage.
+//!
+//! The smogon/damage-calc fixture for Z-Move critical hits names only the
+//! Z-Move (Bloom Doom), not the move it was built from, and a Z-Move's power
+//! depends on that base move. Until the fixtures are re-scraped with
+//! `originalName`, this test covers the case with the base move spelled out.
+
+use poke_engine::damage::calculate_z_move_damage;
+use poke_engine::damage::generations::Gen7;
+use poke_engine::entities::PokemonConfig;
+use poke_engine::moves::MoveId;
+use poke_engine::natures::NatureId;
+use poke_engine::state::BattleState;
+
+#[test]
+fn test_z_move_critical_hit() {
+    let mut state = BattleState::new();
+    state.generation = 7;
+    PokemonConfig::from_str("abomasnow")
+        .unwrap()
+        .level(100)
+        .nature(NatureId::Hasty)
+        .evs([0, 252, 0, 0, 4, 252])
+        .spawn(&mut state, 0, 0);
+    PokemonConfig::from_str("hoopaunbound")
+        .unwrap()
+        .level(100)
+        .nature(NatureId::Jolly)
+        .evs([32, 224, 0, 0, 0, 252])
+        .spawn(&mut state, 1, 0);
+
+    // Wood Hammer becomes a 190 BP Bloom Doom, and crits for 1.5x.
+    let result = calculate_z_move_damage(Gen7, &state, 0, 6, MoveId::Woodhammer, true);
+    assert_eq!(result.min, 555);
+    assert_eq!(result.max, 654);
+}
@@ -0,0 +1,175 @@
+#ifndef POKE_ENGINE_H
+#define POKE_ENGINE_H
+
+/* Generated by cbindgen from poke_engine/src/ffi.rs. Do not edit. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// Most actions `poke_battle_legal_actions` reports; an `out` array of this
+// size always holds them all.
+#define POKE_MAX_LEGAL_ACTIONS 17
+
+// `PokeAction::kind`: do nothing (`index` is ignored)
+#define POKE_ACTION_KIND_PASS 0
+
+// Use the move in `index`
+#define POKE_ACTION_KIND_MOVE 1
+
+// Switch to the Pokémon in team slot `index`
+#define POKE_ACTION_KIND_SWITCH 2
+
+// Terastallize, then use the move in `index`
+#define POKE_ACTION_KIND_TERASTALLIZE 3
+
+// Dynamax, then use the Max Move of the move in `index`
+#define POKE_ACTION_KIND_DYNAMAX 4
+
+// Use the move in `index` as a Z-Move
+#define POKE_ACTION_KIND_Z_MOVE 5
+
+// Mega Evolve, then use the move in `index`
+#define POKE_ACTION_KIND_MEGA_EVOLVE 6
+
+// Ultra Burst, then use the move in `index`
+#define POKE_ACTION_KIND_ULTRA_BURST 7
+
+// Use the move in `index` in Strong Style
+#define POKE_ACTION_KIND_STRONG_STYLE 8
+
+// Use the move in `index` in Agile Style
+#define POKE_ACTION_KIND_AGILE_STYLE 9
+
+// Result code of every fallible call.
+typedef enum PokeError {
+  POKE_ERROR_OK = 0,
+  // A required pointer was null
+  POKE_ERROR_NULL_POINTER = 1,
+  // A string was not valid UTF-8
+  POKE_ERROR_INVALID_UTF8 = 2,
+  // A name or ID did not resolve
+  POKE_ERROR_NOT_FOUND = 3,
+  // An index or value was out of range
+  POKE_ERROR_INVALID_ARGUMENT = 4,
+  // The team text could not be parsed
+  POKE_ERROR_PARSE = 5,
+  // A choice is not legal in the current state
+  POKE_ERROR_ILLEGAL_ACTION = 6,
+  // The battle has already started (teams are locked)
+  POKE_ERROR_ALREADY_STARTED = 7,
+  // The engine panicked; the handle should be freed
+  POKE_ERROR_PANIC = 8,
+} PokeError;
+
+// Outcome of `poke_battle_result`.
+typedef enum PokeResult {
+  POKE_RESULT_ONGOING = 0,
+  POKE_RESULT_P1_WIN = 1,
+  POKE_RESULT_P2_WIN = 2,
+  POKE_RESULT_TIE = 3,
+} PokeResult;
+
+// Opaque battle handle.
+typedef struct PokeBattle PokeBattle;
+
+// A player's choice; `index` is the move slot or team slot.
+typedef struct PokeAction {
+  // One of the `POKE_ACTION_KIND_*` values
+  uint8_t kind;
+  uint8_t index;
+} PokeAction;
+
+// Output of `poke_calculate_damage`.
+typedef struct PokeDamageResult {
+  // All 16 damage rolls, lowest first
+  uint16_t rolls[16];
+  uint16_t min;
+  uint16_t max;
+  // 4 = neutral, 8 = 2x, 2 = 0.5x, 0 = immune
+  uint8_t effectiveness;
+  bool is_crit;
+  uint16_t final_base_power;
+} PokeDamageResult;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Static, NUL-terminated description of an error code.
+const char *poke_error_message(PokeError error);
+
+// Species ID by name.
+PokeError poke_species_id(const char *name, uint16_t *out);
+
+// Move ID by name.
+PokeError poke_move_id(const char *name, uint16_t *out);
+
+// Item ID by name.
+PokeError poke_item_id(const char *name, uint16_t *out);
+
+// Ability ID by name.
+PokeError poke_ability_id(const char *name, uint16_t *out);
+
+// Create an empty battle for generation `gen` (1-9). Returns null for an
+// unsupported generation. Load both teams, then call `poke_battle_start`.
+PokeBattle *poke_battle_new(uint8_t gen, uint64_t seed);
+
+// Independent copy of a battle (state, RNG and pending log). Returns null
+// if `battle` is null.
+PokeBattle *poke_battle_clone(const PokeBattle *battle);
+
+// Release a battle. Null is ignored.
+void poke_battle_free(PokeBattle *battle);
+
+// Load a player's team from Showdown export text or JSON.
+PokeError poke_battle_set_team(PokeBattle *battle, uint8_t player, const char *team);
+
+// Send out the leads and begin turn 1. Both teams must be loaded.
+PokeError poke_battle_start(PokeBattle *battle);
+
+// Submit both players' choices: a full turn, or the pending replacements
+// when a side must switch in (the other choice is then ignored). An
+// unknown `kind` is `POKE_ERROR_INVALID_ARGUMENT`.
+PokeError poke_battle_choose(PokeBattle *battle, PokeAction p1, PokeAction p2);
+
+// Write up to `capacity` legal actions for `player` into `out` and the
+// total count into `len`, which may exceed `capacity` (a `capacity` of
+// `POKE_MAX_LEGAL_ACTIONS` always suffices).
+PokeError poke_battle_legal_actions(const PokeBattle *battle,
+                                    uint8_t player,
+                                    PokeAction *out,
+                                    size_t capacity,
+                                    size_t *len);
+
+// Current outcome of the battle.
+PokeError poke_battle_result(const PokeBattle *battle, PokeResult *out);
+
+// Entity index of a player's active Pokémon.
+PokeError poke_battle_active(const PokeBattle *battle, uint8_t player, uint8_t *out);
+
+// Current and maximum HP of an entity.
+PokeError poke_battle_hp(const PokeBattle *battle, uint8_t entity, uint16_t *hp, uint16_t *max_hp);
+
+// Copy the Showdown protocol log produced since the last call into `buf`
+// (NUL-terminated) and clear it. `len` receives the log length without
+// the NUL; if it is `>= capacity` nothing is copied or cleared, so the
+// caller can retry with a larger buffer.
+PokeError poke_battle_take_log(PokeBattle *battle, char *buf, size_t capacity, size_t *len);
+
+// Damage of move `move_id` used by entity `attacker` against `defender`
+// in the battle's current state.
+PokeError poke_calculate_damage(const PokeBattle *battle,
+                                uint8_t attacker,
+                                uint8_t defender,
+                                uint16_t move_id,
+                                bool is_crit,
+                                PokeDamageResult *out);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* POKE_ENGINE_H */
@@ -0,0 +1,12 @@
+//! C ABI build of `poke_engine`.
+//!
+//! The functions live in `poke_engine::ffi`; this crate only links them
+//! into `libpoke_engine_capi.so` / `libpoke_engine_capi.a` (declared in
+//! `include/poke_engine.h`), so `poke_engine` itself stays an rlib usable
+//! from `no_std` crates.
+//!
+//! ```sh
+//! cargo build -p poke_engine_capi --release
+//! ```
+
+pub use poke_engine::ffi::*;
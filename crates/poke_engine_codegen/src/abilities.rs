@@ -1,5 +1,6 @@
 //! AbilityId enum generation.
 
+use crate::helpers::{ability_gen, nonstandard_tokens};
 use crate::models::AbilityData;
 use heck::ToPascalCase;
 use proc_macro2::TokenStream;
@@ -29,6 +30,10 @@ pub fn generate(out_dir: &Path, data_dir: &Path) {
     let count = valid_abilities.len();
 
     // Generate enum variants
+    let all_idents: Vec<_> = valid_abilities
+        .iter()
+        .map(|(key, _)| format_ident!("{}", key.to_pascal_case()))
+        .collect();
     let variants: Vec<TokenStream> = valid_abilities
         .iter()
         .enumerate()
@@ -85,6 +90,15 @@ pub fn generate(out_dir: &Path, data_dir: &Path) {
             /// Total number of abilities
             pub const COUNT: usize = #count;
 
+            /// Every ability, in ID order (`ALL[id as usize] == id`)
+            pub const ALL: [AbilityId; #count] = [#(AbilityId::#all_idents),*];
+
+            /// Convert a numeric ID (`id as u16`) back, e.g. across FFI
+            #[inline]
+            pub fn from_u16(id: u16) -> Option<Self> {
+                Self::ALL.get(id as usize).copied()
+            }
+
             /// Look up ability by key string
             #[inline]
             pub fn from_str(s: &str) -> Option<Self> {
@@ -96,6 +110,24 @@ pub fn generate(out_dir: &Path, data_dir: &Path) {
             pub fn flags(self) -> AbilityFlags {
                 ABILITY_FLAGS[self as usize]
             }
+
+            /// Display name (e.g. "Sand Stream")
+            #[inline]
+            pub fn name(self) -> &'static str {
+                ABILITY_NAMES[self as usize]
+            }
+
+            /// Generation that introduced the ability
+            #[inline]
+            pub fn gen(self) -> u8 {
+                ABILITY_GENS[self as usize]
+            }
+
+            /// Showdown's `isNonstandard` category (`None` = standard)
+            #[inline]
+            pub fn nonstandard(self) -> Option<crate::dex::Nonstandard> {
+                ABILITY_NONSTANDARD[self as usize]
+            }
         }
     };
 
@@ -120,4 +152,28 @@ pub fn generate(out_dir: &Path, data_dir: &Path) {
         writeln!(file, "    {},", flag).unwrap();
     }
     writeln!(file, "];").unwrap();
+    writeln!(file).unwrap();
+    writeln!(file, "static ABILITY_NAMES: [&str; {}] = [", count).unwrap();
+    for (_, data) in &valid_abilities {
+        writeln!(file, "    {:?},", data.name).unwrap();
+    }
+    writeln!(file, "];").unwrap();
+    writeln!(file).unwrap();
+    writeln!(file, "static ABILITY_GENS: [u8; {}] = [", count).unwrap();
+    for (_, data) in &valid_abilities {
+        writeln!(file, "    {},", ability_gen(data.num, data.gen)).unwrap();
+    }
+    writeln!(file, "];").unwrap();
+    writeln!(file).unwrap();
+    writeln!(
+        file,
+        "static ABILITY_NONSTANDARD: [Option<crate::dex::Nonstandard>; {}] = [",
+        count
+    )
+    .unwrap();
+    for (_, data) in &valid_abilities {
+        let nonstandard = nonstandard_tokens(data.is_nonstandard.as_deref());
+        writeln!(file, "    {},", nonstandard).unwrap();
+    }
+    writeln!(file, "];").unwrap();
 }
@@ -0,0 +1,62 @@
+//! Showdown generation mods (`data/mods/genN/*.json`).
+//!
+//! Each mod is a delta on the generation after it: Gen 8 patches the
+//! current dex, Gen 7 patches Gen 8, and so on down to Gen 1. An entry with
+//! `"inherit": true` is merged field by field over the inherited entry;
+//! without it, the entry replaces it outright. Mods are optional: without
+//! them every generation resolves to the current dex.
+//!
+//! When the type chart mods are absent, `GEN_TYPE_CHARTS_LOADED` is false
+//! and `GenMechanics::type_effectiveness` for Gens 1-5 falls back to the
+//! hand-written chart differences in `damage/generations/genN.rs`.
+
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+/// Whether any `data/mods/genN/<file>` exists.
+pub fn any_present(data_dir: &Path, file: &str) -> bool {
+    (1..=8).any(|gen| mod_path(data_dir, gen, file).exists())
+}
+
+fn mod_path(data_dir: &Path, gen: u8, file: &str) -> std::path::PathBuf {
+    data_dir.join("mods").join(format!("gen{}", gen)).join(file)
+}
+
+/// Resolve `file` (e.g. `"moves.json"`) for each of Gens 1-8.
+///
+/// `result[gen - 1]` maps every key patched by that generation's mod or a
+/// later one to its fully merged entry; other keys are unchanged from the
+/// current dex. Callers compare against the current entry to find actual
+/// differences.
+pub fn resolve(data_dir: &Path, file: &str, base: &BTreeMap<String, Value>) -> Vec<BTreeMap<String, Value>> {
+    let mut current: BTreeMap<String, Value> = BTreeMap::new();
+    let mut resolved = Vec::new();
+    for gen in (1..=8).rev() {
+        let path = mod_path(data_dir, gen, file);
+        if let Ok(json) = fs::read_to_string(&path) {
+            let delta: BTreeMap<String, Value> = serde_json::from_str(&json)
+                .unwrap_or_else(|e| panic!("parse {}: {}", path.display(), e));
+            for (key, patch) in delta {
+                let Value::Object(patch) = patch else {
+                    continue;
+                };
+                let inherit = patch.get("inherit") == Some(&Value::Bool(true));
+                let mut merged = match (inherit, current.get(&key).or_else(|| base.get(&key))) {
+                    (true, Some(Value::Object(parent))) => parent.clone(),
+                    _ => Map::new(),
+                };
+                for (field, value) in patch {
+                    if field != "inherit" {
+                        merged.insert(field, value);
+                    }
+                }
+                current.insert(key, Value::Object(merged));
+            }
+        }
+        resolved.push(current.clone());
+    }
+    resolved.reverse();
+    resolved
+}
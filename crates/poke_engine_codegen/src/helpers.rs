@@ -1,8 +1,10 @@
 //! Helper functions for code generation.
 
 use heck::ToPascalCase;
+use proc_macro2::TokenStream;
+use quote::quote;
 
-use crate::models::MoveData;
+use crate::models::{MoveData, PokedexEntry};
 
 /// Convert a key to a valid Rust identifier in PascalCase.
 /// Handles keys starting with digits by prefixing with underscore.
@@ -15,6 +17,14 @@ pub fn to_valid_ident(key: &str) -> String {
     }
 }
 
+/// Showdown ID of a name: lowercase ASCII alphanumerics ("Mr. Mime" -> "mrmime").
+pub fn to_id(name: &str) -> String {
+    name.chars()
+        .filter(char::is_ascii_alphanumeric)
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
+
 /// Check if a move has secondary effects for Sheer Force boost criteria.
 /// Returns true if the move has secondary or secondaries fields (that are not null),
 /// or if it has the explicit has_sheer_force flag set.
@@ -23,3 +33,85 @@ pub fn has_secondary_effects(data: &MoveData) -> bool {
         || data.secondaries.as_ref().map_or(false, |v| !v.is_null())
         || data.has_sheer_force.unwrap_or(false)
 }
+
+/// `Option<Nonstandard>` tokens for Showdown's `isNonstandard` string.
+pub fn nonstandard_tokens(category: Option<&str>) -> TokenStream {
+    let variant = match category {
+        None => return quote! { None },
+        Some("Past") => quote! { Past },
+        Some("Future") => quote! { Future },
+        Some("CAP") => quote! { Cap },
+        Some("LGPE") => quote! { Lgpe },
+        Some("Unobtainable") => quote! { Unobtainable },
+        Some("Gigantamax") => quote! { Gigantamax },
+        Some("Custom") => quote! { Custom },
+        Some(other) => panic!("unknown isNonstandard category {:?}", other),
+    };
+    quote! { Some(crate::dex::Nonstandard::#variant) }
+}
+
+/// Introduction generation of a species, derived from its dex number and
+/// forme when the data doesn't say (Showdown's `Species` constructor).
+pub fn species_gen(entry: &PokedexEntry) -> u8 {
+    if let Some(gen) = entry.gen {
+        return gen;
+    }
+    let num = entry.num.unwrap_or(0);
+    let forme = entry.forme.as_deref().unwrap_or("");
+    if num >= 906 || forme.contains("Paldea") {
+        9
+    } else if num >= 810 || ["Gmax", "Galar", "Galar-Zen", "Hisui"].contains(&forme) {
+        8
+    } else if num >= 722 || forme.starts_with("Alola") || forme == "Starter" {
+        7
+    } else if num >= 650 || ["Mega", "Mega-X", "Mega-Y", "Primal"].contains(&forme) {
+        6
+    } else if num >= 494 {
+        5
+    } else if num >= 387 {
+        4
+    } else if num >= 252 {
+        3
+    } else if num >= 152 {
+        2
+    } else {
+        1
+    }
+}
+
+/// Introduction generation of a move, derived from its number when the
+/// data doesn't say.
+pub fn move_gen(data: &MoveData) -> u8 {
+    if let Some(gen) = data.gen {
+        return gen;
+    }
+    match data.num {
+        827.. => 9,
+        743.. => 8,
+        622.. => 7,
+        560.. => 6,
+        468.. => 5,
+        355.. => 4,
+        252.. => 3,
+        166.. => 2,
+        1.. => 1,
+        _ => 0,
+    }
+}
+
+/// Introduction generation of an ability (abilities start in Gen 3).
+pub fn ability_gen(num: i16, gen: Option<u8>) -> u8 {
+    if let Some(gen) = gen {
+        return gen;
+    }
+    match num {
+        268.. => 9,
+        234.. => 8,
+        192.. => 7,
+        165.. => 6,
+        124.. => 5,
+        77.. => 4,
+        1.. => 3,
+        _ => 0,
+    }
+}
@@ -1,20 +1,97 @@
 //! ItemId enum generation.
 
-use crate::helpers::to_valid_ident;
-use crate::models::ItemData;
+use crate::helpers::{nonstandard_tokens, to_id, to_valid_ident};
+use crate::models::{ItemData, MoveData, PokedexEntry};
 use proc_macro2::TokenStream;
 use quote::{format_ident, quote};
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 use std::fs::File;
 use std::io::{BufWriter, Write};
 use std::path::Path;
 use std::{fs, writeln};
 
+/// Type-boosting items (1.2x). Showdown implements these in `onBasePower`
+/// callbacks, so the type isn't in the JSON; Plates come from `onPlate`.
+const TYPE_BOOSTERS: [(&str, &str); 23] = [
+    ("blackbelt", "Fighting"),
+    ("blackglasses", "Dark"),
+    ("charcoal", "Fire"),
+    ("dragonfang", "Dragon"),
+    ("fairyfeather", "Fairy"),
+    ("hardstone", "Rock"),
+    ("magnet", "Electric"),
+    ("metalcoat", "Steel"),
+    ("miracleseed", "Grass"),
+    ("mysticwater", "Water"),
+    ("nevermeltice", "Ice"),
+    ("poisonbarb", "Poison"),
+    ("sharpbeak", "Flying"),
+    ("silkscarf", "Normal"),
+    ("silverpowder", "Bug"),
+    ("softsand", "Ground"),
+    ("spelltag", "Ghost"),
+    ("twistedspoon", "Psychic"),
+    ("oddincense", "Psychic"),
+    ("rockincense", "Rock"),
+    ("roseincense", "Grass"),
+    ("seaincense", "Water"),
+    ("waveincense", "Water"),
+];
+
 /// Generate ItemId enum
 pub fn generate(out_dir: &Path, data_dir: &Path) {
     let json = fs::read_to_string(data_dir.join("items.json")).expect("items.json");
     let items: BTreeMap<String, ItemData> = serde_json::from_str(&json).expect("parse items");
 
+    // Species and moves referenced by items resolve to the generated
+    // constants, so only emit names that species.rs and moves.rs generate
+    let json = fs::read_to_string(data_dir.join("pokedex.json")).expect("pokedex.json");
+    let pokedex: BTreeMap<String, PokedexEntry> =
+        serde_json::from_str(&json).expect("parse pokedex");
+    let species_keys: BTreeSet<&str> = pokedex
+        .iter()
+        .filter(|(_, entry)| {
+            entry.num.map(|n| n > 0).unwrap_or(false)
+                && entry.base_stats.is_some()
+                && entry.types.is_some()
+        })
+        .map(|(key, _)| key.as_str())
+        .collect();
+    let json = fs::read_to_string(data_dir.join("moves.json")).expect("moves.json");
+    let moves: BTreeMap<String, MoveData> = serde_json::from_str(&json).expect("parse moves");
+    let move_keys: BTreeSet<&str> = moves
+        .iter()
+        .filter(|(_, data)| data.num >= 0)
+        .map(|(key, _)| key.as_str())
+        .collect();
+
+    let type_tokens = |name: &str| -> TokenStream {
+        let ident = format_ident!("{}", name);
+        quote! { crate::types::Type::#ident }
+    };
+    let option_type = |name: Option<&str>| -> TokenStream {
+        match name {
+            Some(name) => {
+                let ty = type_tokens(name);
+                quote! { Some(#ty) }
+            }
+            None => quote! { None },
+        }
+    };
+    let species_tokens = |name: &str| -> Option<TokenStream> {
+        let key = to_id(name);
+        species_keys.contains(key.as_str()).then(|| {
+            let ident = format_ident!("{}", key.to_uppercase());
+            quote! { crate::species::SpeciesId::#ident }
+        })
+    };
+    let move_tokens = |name: &str| -> TokenStream {
+        let key = to_id(name);
+        assert!(move_keys.contains(key.as_str()), "unknown move {:?}", name);
+        let ident = format_ident!("{}", to_valid_ident(&key));
+        quote! { crate::moves::MoveId::#ident }
+    };
+
     // Filter valid items (has num, not nonstandard "Past"/"Future" unless we want them)
     let mut item_list: Vec<(&String, &ItemData)> = items
         .iter()
@@ -29,6 +106,10 @@ pub fn generate(out_dir: &Path, data_dir: &Path) {
     let count = item_list.len() + 1; // +1 for None
 
     // Generate enum variants
+    let all_idents: Vec<_> = item_list
+        .iter()
+        .map(|(key, _)| format_ident!("{}", to_valid_ident(key)))
+        .collect();
     let variants: Vec<TokenStream> = item_list
         .iter()
         .enumerate()
@@ -49,7 +130,8 @@ pub fn generate(out_dir: &Path, data_dir: &Path) {
 
     let item_data: Vec<TokenStream> = item_list
         .iter()
-        .map(|(_, data)| {
+        .map(|(key, data)| {
+            let name = &data.name;
             let fling_power = data.fling.as_ref().map(|f| f.base_power).unwrap_or(0);
             let is_unremovable = data.mega_stone.is_some()
                 || data.z_move.is_some()
@@ -60,11 +142,84 @@ pub fn generate(out_dir: &Path, data_dir: &Path) {
                 || (data.name == "Rusted Sword")
                 || (data.name == "Rusted Shield")
                 || (data.name == "Booster Energy");
+            let gen = data.gen;
+            let nonstandard = nonstandard_tokens(data.is_nonstandard.as_deref());
+
+            // Z-Crystals also set Arceus's type, but only Plates boost
+            let plate = data.on_plate.as_deref().filter(|_| data.z_move.is_none());
+            let boosted = TYPE_BOOSTERS
+                .iter()
+                .find(|(item, _)| item == key)
+                .map(|(_, ty)| *ty)
+                .or(plate);
+            let boosted_type = option_type(boosted);
+            let plate_type = option_type(data.on_plate.as_deref());
+            let memory_type = option_type(data.on_memory.as_deref());
+            let drive_type = option_type(data.on_drive.as_deref());
+
+            let is_berry = data.is_berry;
+            let natural_gift = match &data.natural_gift {
+                Some(gift) => {
+                    let ty = type_tokens(&gift.gift_type);
+                    let power = gift.base_power;
+                    quote! { Some((#ty, #power)) }
+                }
+                None => quote! { None },
+            };
+
+            // Mega Stones map base formes to Megas; the first pair is the
+            // species' default forme
+            let mega_stone = match data
+                .mega_stone
+                .as_ref()
+                .and_then(|stone| stone.as_object())
+                .and_then(|pairs| pairs.iter().next())
+                .and_then(|(base, mega)| {
+                    Some((species_tokens(base)?, species_tokens(mega.as_str()?)?))
+                }) {
+                Some((base, mega)) => quote! { Some((#base, #mega)) },
+                None => quote! { None },
+            };
+
+            let z_crystal = match (&data.z_move, &data.z_move_type, &data.z_move_from) {
+                (Some(_), Some(ty), _) => {
+                    let ty = type_tokens(ty);
+                    quote! { Some(ZCrystal::Type(#ty)) }
+                }
+                (Some(serde_json::Value::String(z_move)), None, Some(from)) => {
+                    let base = move_tokens(from);
+                    let z_move = move_tokens(z_move);
+                    quote! { Some(ZCrystal::Signature { base: #base, z_move: #z_move }) }
+                }
+                _ => quote! { None },
+            };
+
+            let forced_forme = match data.forced_forme.as_deref().and_then(&species_tokens) {
+                Some(species) => quote! { Some(#species) },
+                None => quote! { None },
+            };
+            let item_user = data
+                .item_user
+                .iter()
+                .filter_map(|name| species_tokens(name));
 
             quote! {
                 Item {
+                    name: #name,
                     fling_power: #fling_power,
                     is_unremovable: #is_unremovable,
+                    gen: #gen,
+                    nonstandard: #nonstandard,
+                    boosted_type: #boosted_type,
+                    plate_type: #plate_type,
+                    memory_type: #memory_type,
+                    drive_type: #drive_type,
+                    is_berry: #is_berry,
+                    natural_gift: #natural_gift,
+                    mega_stone: #mega_stone,
+                    z_crystal: #z_crystal,
+                    forced_forme: #forced_forme,
+                    item_user: &[#(#item_user),*],
                 }
             }
         })
@@ -83,16 +238,64 @@ pub fn generate(out_dir: &Path, data_dir: &Path) {
         /// Static item data
         #[derive(Clone, Copy, Debug)]
         pub struct Item {
+            /// Display name (e.g. "Choice Band")
+            pub name: &'static str,
             /// Fling base power (0 = cannot be flung)
             pub fling_power: u8,
             /// Whether the item can be removed by Knock Off, etc.
             pub is_unremovable: bool,
+            /// Generation that introduced the item (0 for `None`)
+            pub gen: u8,
+            /// Showdown's `isNonstandard` category (`None` = standard)
+            pub nonstandard: Option<crate::dex::Nonstandard>,
+            /// Moves of this type get 1.2x base power (type-boosting items,
+            /// incenses and Plates)
+            pub boosted_type: Option<crate::types::Type>,
+            /// Judgment's type and Arceus's forme (Plates and type Z-Crystals)
+            pub plate_type: Option<crate::types::Type>,
+            /// Multi-Attack's type and Silvally's forme
+            pub memory_type: Option<crate::types::Type>,
+            /// Techno Blast's type
+            pub drive_type: Option<crate::types::Type>,
+            /// Berry (eaten by its holder; Bug Bite, Pluck, etc.)
+            pub is_berry: bool,
+            /// Natural Gift type and base power
+            pub natural_gift: Option<(crate::types::Type, u8)>,
+            /// Mega Stone: (base forme, Mega Evolution)
+            pub mega_stone: Option<(crate::species::SpeciesId, crate::species::SpeciesId)>,
+            /// Z-Crystal
+            pub z_crystal: Option<ZCrystal>,
+            /// Forme the holder is forced into (Plates, Memories, Rusted Sword, ...)
+            pub forced_forme: Option<crate::species::SpeciesId>,
+            /// Species that can use the item's effect (empty = any)
+            pub item_user: &'static [crate::species::SpeciesId],
+        }
+
+        /// Z-Move granted by a Z-Crystal
+        #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+        pub enum ZCrystal {
+            /// Turns damaging moves of this type into its Z-Move
+            Type(crate::types::Type),
+            /// Turns `base` into the signature `z_move` (item_user only)
+            Signature {
+                base: crate::moves::MoveId,
+                z_move: crate::moves::MoveId,
+            },
         }
 
         impl ItemId {
             /// Total number of items
             pub const COUNT: usize = #count;
 
+            /// Every item, in ID order (`ALL[id as usize] == id`)
+            pub const ALL: [ItemId; #count] = [ItemId::None, #(ItemId::#all_idents),*];
+
+            /// Convert a numeric ID (`id as u16`) back, e.g. across FFI
+            #[inline]
+            pub fn from_u16(id: u16) -> Option<Self> {
+                Self::ALL.get(id as usize).copied()
+            }
+
             /// Look up item by key string
             #[inline]
             pub fn from_str(s: &str) -> Option<Self> {
@@ -108,7 +311,23 @@ pub fn generate(out_dir: &Path, data_dir: &Path) {
 
         /// Static item data array
         pub static ITEMS: [Item; #count] = [
-            Item { fling_power: 0, is_unremovable: false }, // None
+            Item {
+                name: "",
+                fling_power: 0,
+                is_unremovable: false,
+                gen: 0,
+                nonstandard: None,
+                boosted_type: None,
+                plate_type: None,
+                memory_type: None,
+                drive_type: None,
+                is_berry: false,
+                natural_gift: None,
+                mega_stone: None,
+                z_crystal: None,
+                forced_forme: None,
+                item_user: &[],
+            }, // None
             #(#item_data),*
         ];
     };
@@ -0,0 +1,158 @@
+//! Learnset table generation.
+//!
+//! `learnsets.json` is optional: without it the tables are empty and
+//! `LEARNSETS_LOADED` is false, so the engine still builds from a checkout
+//! that only has the core data files.
+
+use crate::helpers::to_id;
+use crate::models::{LearnsetEntry, MoveData, PokedexEntry};
+use quote::quote;
+use std::collections::{BTreeMap, HashMap};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::{fs, println, writeln};
+
+/// Showdown source letters, in `LearnMethod` discriminant order
+const METHODS: [char; 8] = ['L', 'M', 'T', 'E', 'S', 'D', 'V', 'R'];
+
+/// Formes whose learnsets are their base species' (Showdown's `learnsetParent`)
+const BASE_LEARNSET_FORMES: [&str; 4] = ["Gastrodon", "Pumpkaboo", "Sinistea", "Tatsugiri"];
+
+/// Pack a source like "9L15" as `gen << 12 | method << 8 | level`.
+fn pack_source(source: &str) -> Option<u16> {
+    let mut chars = source.chars();
+    let gen = chars.next()?.to_digit(10)? as u16;
+    let letter = chars.next()?;
+    let method = METHODS.iter().position(|&m| m == letter)? as u16;
+    let arg: u16 = chars.as_str().parse().unwrap_or(0);
+    Some(gen << 12 | method << 8 | arg.min(255))
+}
+
+/// Generate learnset tables
+pub fn generate(out_dir: &Path, data_dir: &Path) {
+    let path = data_dir.join("learnsets.json");
+    let learnsets: BTreeMap<String, LearnsetEntry> = match fs::read_to_string(&path) {
+        Ok(json) => serde_json::from_str(&json).expect("parse learnsets"),
+        Err(_) => {
+            println!(
+                "cargo:warning=data/learnsets.json not found; learnset lookups are disabled \
+                 (run scripts/sync_showdown_data.mts)"
+            );
+            BTreeMap::new()
+        }
+    };
+    let loaded = !learnsets.is_empty();
+
+    // Same species and move orderings as species.rs and moves.rs
+    let json = fs::read_to_string(data_dir.join("pokedex.json")).expect("pokedex.json");
+    let pokedex: BTreeMap<String, PokedexEntry> =
+        serde_json::from_str(&json).expect("parse pokedex");
+    let valid_species: Vec<(&String, &PokedexEntry)> = pokedex
+        .iter()
+        .filter(|(_, entry)| {
+            entry.num.map(|n| n > 0).unwrap_or(false)
+                && entry.base_stats.is_some()
+                && entry.types.is_some()
+        })
+        .collect();
+    let species_to_idx: HashMap<&str, u16> = valid_species
+        .iter()
+        .enumerate()
+        .map(|(i, (key, _))| (key.as_str(), i as u16))
+        .collect();
+
+    let json = fs::read_to_string(data_dir.join("moves.json")).expect("moves.json");
+    let moves: BTreeMap<String, MoveData> = serde_json::from_str(&json).expect("parse moves");
+    let mut move_list: Vec<(&String, &MoveData)> = moves.iter().collect();
+    move_list.sort_by_key(|(_, data)| data.num);
+    let move_to_idx: HashMap<&str, u16> = move_list
+        .into_iter()
+        .filter(|(_, data)| data.num >= 0)
+        .enumerate()
+        .map(|(i, (key, _))| (key.as_str(), i as u16))
+        .collect();
+
+    let mut ranges = Vec::with_capacity(valid_species.len());
+    let mut parents = Vec::with_capacity(valid_species.len());
+    let mut entries = Vec::new();
+    let mut sources = Vec::new();
+
+    for (key, entry) in &valid_species {
+        let own = learnsets
+            .get(key.as_str())
+            .and_then(|l| l.learnset.as_ref());
+
+        // Moves sorted by ID for binary search, sources newest gen first
+        let mut learnset: Vec<(u16, Vec<u16>)> = own
+            .into_iter()
+            .flatten()
+            .filter_map(|(move_key, move_sources)| {
+                let move_idx = *move_to_idx.get(move_key.as_str())?;
+                let mut packed: Vec<u16> =
+                    move_sources.iter().filter_map(|s| pack_source(s)).collect();
+                packed.sort_unstable_by(|a, b| b.cmp(a));
+                Some((move_idx, packed))
+            })
+            .collect();
+        learnset.sort_by_key(|(move_idx, _)| *move_idx);
+
+        ranges.push((entries.len() as u32, learnset.len() as u16));
+        for (move_idx, packed) in learnset {
+            entries.push((move_idx, sources.len() as u32, packed.len() as u8));
+            sources.extend(packed);
+        }
+
+        let base = entry.base_species.as_deref();
+        let is_forme = base.is_some_and(|base| to_id(base) != key.as_str());
+        let parent = if own.is_none() && is_forme {
+            // Formes without data of their own (Megas, cosmetic formes)
+            entry.changes_from.as_deref().or(base)
+        } else if is_forme && BASE_LEARNSET_FORMES.contains(&base.unwrap_or_default()) {
+            base
+        } else if key.as_str() == "lycanrocdusk" {
+            Some("Rockruff-Dusk")
+        } else if let Some(prevo) = &entry.prevo {
+            Some(prevo.as_str())
+        } else if base != Some("Kyurem") {
+            // Rotom and Necrozma formes extend their base forme's learnset
+            entry.changes_from.as_deref()
+        } else {
+            None
+        };
+        let parent = parent
+            .and_then(|name| species_to_idx.get(to_id(name).as_str()))
+            .map_or(0, |idx| idx + 1);
+        parents.push(parent);
+    }
+
+    let count = valid_species.len();
+    let range_tokens = ranges.iter().map(|(start, len)| quote! { (#start, #len) });
+    let entry_count = entries.len();
+    let entry_tokens = entries
+        .iter()
+        .map(|(move_idx, start, len)| quote! { (#move_idx, #start, #len) });
+    let source_count = sources.len();
+
+    let code = quote! {
+        /// Whether `data/learnsets.json` was available at build time
+        pub const LEARNSETS_LOADED: bool = #loaded;
+
+        /// Per species: (first entry, entry count) into `LEARNSET_ENTRIES`
+        static LEARNSET_RANGES: [(u32, u16); #count] = [#(#range_tokens),*];
+
+        /// Learnset parent (prevolution or base forme) + 1 (0 = none)
+        static LEARNSET_PARENTS: [u16; #count] = [#(#parents),*];
+
+        /// (move ID, first source, source count), sorted by move within a species
+        static LEARNSET_ENTRIES: [(u16, u32, u8); #entry_count] = [#(#entry_tokens),*];
+
+        /// Packed sources, `gen << 12 | method << 8 | level or event index`,
+        /// newest generation first
+        static LEARNSET_SOURCES: [u16; #source_count] = [#(#sources),*];
+    };
+
+    let dest = out_dir.join("learnsets.rs");
+    let mut file = BufWriter::new(File::create(&dest).expect("create learnsets.rs"));
+    writeln!(file, "{}", code).unwrap();
+}
@@ -4,8 +4,10 @@
 //! and generates optimized Rust types for the battle engine.
 
 mod abilities;
+mod gen_mods;
 mod helpers;
 mod items;
+mod learnsets;
 mod models;
 mod moves;
 mod natures;
@@ -31,6 +33,23 @@ pub fn generate_all(out_dir: &Path, data_dir: &Path) {
     ] {
         println!("cargo:rerun-if-changed={}", data_dir.join(file).display());
     }
+    // learnsets.json and formats-data.json are optional; while one is
+    // missing, watch the directory so adding it triggers a rebuild
+    for file in &["learnsets.json", "formats-data.json"] {
+        let path = data_dir.join(file);
+        if path.exists() {
+            println!("cargo:rerun-if-changed={}", path.display());
+        } else {
+            println!("cargo:rerun-if-changed={}", data_dir.display());
+        }
+    }
+    // Generation mods are optional too
+    let mods = data_dir.join("mods");
+    if mods.exists() {
+        println!("cargo:rerun-if-changed={}", mods.display());
+    } else {
+        println!("cargo:rerun-if-changed={}", data_dir.display());
+    }
 
     // Generate each module
     types::generate(out_dir, data_dir);
@@ -40,4 +59,5 @@ pub fn generate_all(out_dir: &Path, data_dir: &Path) {
     moves::generate(out_dir, data_dir);
     items::generate(out_dir, data_dir);
     terrains::generate(out_dir, data_dir);
+    learnsets::generate(out_dir, data_dir);
 }
@@ -1,7 +1,7 @@
 //! JSON deserialization structures for Showdown data files.
 
 use serde::Deserialize;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 #[derive(Deserialize)]
 pub struct NatureData {
@@ -48,13 +48,20 @@ pub struct PokedexEntry {
     #[serde(rename = "otherFormes")]
     pub other_formes: Option<Vec<String>>,
     pub evos: Option<Vec<String>>,
+    pub prevo: Option<String>,
+    #[serde(rename = "changesFrom")]
+    pub changes_from: Option<String>,
+    pub forme: Option<String>,
+    pub gen: Option<u8>,
 }
 
 #[derive(Deserialize)]
 pub struct AbilityData {
-    #[allow(dead_code)]
     pub name: String,
     pub num: i16,
+    pub gen: Option<u8>,
+    #[serde(rename = "isNonstandard")]
+    pub is_nonstandard: Option<String>,
 }
 
 #[derive(Deserialize)]
@@ -91,9 +98,31 @@ pub struct MoveData {
     pub has_sheer_force: Option<bool>,
 
     pub ohko: Option<serde_json::Value>, // can be true or string "Ice"
+    #[serde(rename = "ignoreAbility")]
+    pub ignore_ability: Option<bool>,
+    #[serde(rename = "volatileStatus")]
+    pub volatile_status: Option<String>,
+    #[serde(rename = "critRatio")]
+    pub crit_ratio: Option<u8>,
 
     pub target: Option<String>,
     pub multihit: Option<serde_json::Value>,
+
+    #[serde(rename = "zMove")]
+    pub z_move: Option<ZMoveData>,
+
+    pub gen: Option<u8>,
+    #[serde(rename = "isNonstandard")]
+    pub is_nonstandard: Option<String>,
+}
+
+/// A move's `zMove` entry: Z-Power override or Z-status effect
+#[derive(Deserialize)]
+pub struct ZMoveData {
+    #[serde(rename = "basePower")]
+    pub base_power: Option<u16>,
+    pub boost: Option<BTreeMap<String, i8>>,
+    pub effect: Option<String>,
 }
 
 #[derive(Deserialize)]
@@ -102,15 +131,22 @@ pub struct Fling {
     pub base_power: u8,
 }
 
+#[derive(Deserialize)]
+pub struct NaturalGift {
+    #[serde(rename = "basePower")]
+    pub base_power: u8,
+    #[serde(rename = "type")]
+    pub gift_type: String,
+}
+
 #[derive(Deserialize)]
 pub struct ItemData {
     pub name: String,
     pub num: Option<i16>,
-    #[allow(dead_code)]
     #[serde(default)]
     pub gen: u8,
     #[serde(rename = "isNonstandard")]
-    pub _is_nonstandard: Option<String>,
+    pub is_nonstandard: Option<String>,
     pub fling: Option<Fling>,
     #[serde(default)]
     #[serde(rename = "megaStone")]
@@ -130,4 +166,30 @@ pub struct ItemData {
     #[serde(default)]
     #[serde(rename = "forcedForme")]
     pub forced_forme: Option<String>,
+    #[serde(default)]
+    #[serde(rename = "isBerry")]
+    pub is_berry: bool,
+    #[serde(rename = "naturalGift")]
+    pub natural_gift: Option<NaturalGift>,
+    #[serde(default)]
+    #[serde(rename = "itemUser")]
+    pub item_user: Vec<String>,
+    #[serde(rename = "zMoveType")]
+    pub z_move_type: Option<String>,
+    #[serde(rename = "zMoveFrom")]
+    pub z_move_from: Option<String>,
+}
+
+/// One species' entry in formats-data.json (tiers are ignored)
+#[derive(Deserialize)]
+pub struct FormatsDataEntry {
+    #[serde(rename = "isNonstandard")]
+    pub is_nonstandard: Option<String>,
+}
+
+/// One species' entry in learnsets.json (`eventData` etc. are ignored)
+#[derive(Deserialize)]
+pub struct LearnsetEntry {
+    /// Move key -> sources such as "9L1", "8M", "7E", "6S0"
+    pub learnset: Option<BTreeMap<String, Vec<String>>>,
 }
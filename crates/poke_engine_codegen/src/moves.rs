@@ -1,6 +1,7 @@
 //! MoveId enum and move data generation.
 
-use crate::helpers::{has_secondary_effects, to_valid_ident};
+use crate::gen_mods;
+use crate::helpers::{has_secondary_effects, move_gen, nonstandard_tokens, to_valid_ident};
 use crate::models::MoveData;
 use heck::{ToPascalCase, ToShoutySnakeCase};
 use proc_macro2::TokenStream;
@@ -27,6 +28,13 @@ pub fn generate(out_dir: &Path, data_dir: &Path) {
 
     let count = valid_moves.len();
 
+    // IDs past COUNT reserved for runtime custom moves (`custom` module)
+    let custom_slots = 256usize;
+    let custom_idents: Vec<_> = (0..custom_slots)
+        .map(|i| format_ident!("Custom{}", i))
+        .collect();
+    let custom_ids: Vec<u16> = (0..custom_slots).map(|i| (count + i) as u16).collect();
+
     // 1. Collect Flags and Targets
     let mut flag_names = BTreeSet::new();
     let mut target_names = BTreeSet::new();
@@ -78,6 +86,18 @@ pub fn generate(out_dir: &Path, data_dir: &Path) {
             flag_names.insert("Ohko".to_string());
         }
 
+        if data.ignore_ability.unwrap_or(false) {
+            flag_names.insert("IgnoresAbility".to_string());
+        }
+
+        if data.volatile_status.as_deref() == Some("partiallytrapped") {
+            flag_names.insert("PartiallyTraps".to_string());
+        }
+
+        if data.crit_ratio.unwrap_or(1) > 1 {
+            flag_names.insert("HighCrit".to_string());
+        }
+
         if let Some(target) = &data.target {
             target_names.insert(target.clone());
         }
@@ -103,6 +123,7 @@ pub fn generate(out_dir: &Path, data_dir: &Path) {
         })
         .collect();
 
+    let target_strs: Vec<&String> = target_names.iter().collect();
     let target_variants: Vec<TokenStream> = target_names
         .iter()
         .map(|name| {
@@ -117,6 +138,10 @@ pub fn generate(out_dir: &Path, data_dir: &Path) {
         .collect();
 
     // 2. Generate Enum Variants
+    let all_idents: Vec<_> = valid_moves
+        .iter()
+        .map(|(key, _)| format_ident!("{}", to_valid_ident(key)))
+        .collect();
     let variants: Vec<TokenStream> = valid_moves
         .iter()
         .enumerate()
@@ -128,126 +153,199 @@ pub fn generate(out_dir: &Path, data_dir: &Path) {
         .collect();
 
     // 3. Generate Move Data Entries
-    let move_data_entries: Vec<TokenStream> = valid_moves
-        .iter()
-        .map(|(_, data)| {
-            let name = &data.name;
-            let type_str = data.move_type.as_deref().unwrap_or("Normal");
-            let type_ident = format_ident!("{}", type_str);
-
-            let cat_str = data.category.as_deref().unwrap_or("Status");
-            let cat_ident = format_ident!("{}", cat_str);
-
-            let power = data.base_power.unwrap_or(0);
-
-            let accuracy = match &data.accuracy {
-                Some(serde_json::Value::Bool(true)) => 0,
-                Some(serde_json::Value::Number(n)) => n.as_u64().unwrap_or(0) as u8,
-                _ => 0,
-            };
-
-            let pp = data.pp.unwrap_or(0);
-            let priority = data.priority.unwrap_or(0);
-
-            let target_str = data.target.as_deref().unwrap_or("Normal");
-            let target_ident_str = to_valid_ident(target_str);
-            let target_ident = if target_ident_str == "Self" {
-                format_ident!("User")
-            } else {
-                format_ident!("{}", target_ident_str)
-            };
-
-            let (min_hits, max_hits) = match &data.multihit {
-                Some(serde_json::Value::Number(n)) => {
-                    let v = n.as_u64().unwrap_or(1) as u8;
-                    (v, v)
-                }
-                Some(serde_json::Value::Array(arr)) => {
-                    if arr.len() >= 2 {
-                        (
-                            arr[0].as_u64().unwrap_or(1) as u8,
-                            arr[1].as_u64().unwrap_or(1) as u8,
-                        )
-                    } else {
-                        (1, 1)
-                    }
+    let encode = |data: &MoveData| -> TokenStream {
+        let name = &data.name;
+        let type_str = data.move_type.as_deref().unwrap_or("Normal");
+        let type_ident = format_ident!("{}", type_str);
+
+        let cat_str = data.category.as_deref().unwrap_or("Status");
+        let cat_ident = format_ident!("{}", cat_str);
+
+        let power = data.base_power.unwrap_or(0);
+
+        let accuracy = match &data.accuracy {
+            Some(serde_json::Value::Bool(true)) => 0,
+            Some(serde_json::Value::Number(n)) => n.as_u64().unwrap_or(0) as u8,
+            _ => 0,
+        };
+
+        let pp = data.pp.unwrap_or(0);
+        let priority = data.priority.unwrap_or(0);
+
+        let target_str = data.target.as_deref().unwrap_or("Normal");
+        let target_ident_str = to_valid_ident(target_str);
+        let target_ident = if target_ident_str == "Self" {
+            format_ident!("User")
+        } else {
+            format_ident!("{}", target_ident_str)
+        };
+
+        let (min_hits, max_hits) = match &data.multihit {
+            Some(serde_json::Value::Number(n)) => {
+                let v = n.as_u64().unwrap_or(1) as u8;
+                (v, v)
+            }
+            Some(serde_json::Value::Array(arr)) => {
+                if arr.len() >= 2 {
+                    (
+                        arr[0].as_u64().unwrap_or(1) as u8,
+                        arr[1].as_u64().unwrap_or(1) as u8,
+                    )
+                } else {
+                    (1, 1)
                 }
-                _ => (0, 0), // 0,0 indicates standard single hit (handled by default logic)
-            };
+            }
+            _ => (0, 0), // 0,0 indicates standard single hit (handled by default logic)
+        };
+
+        // Flags
+        let mut flag_bits = 0u64;
+        for (flag_key, _) in &data.flags {
+            if let Some(pos) = flag_names.iter().position(|x| x == flag_key) {
+                flag_bits |= 1 << pos;
+            }
+        }
 
-            // Flags
-            let mut flag_bits = 0u64;
-            for (flag_key, _) in &data.flags {
-                if let Some(pos) = flag_names.iter().position(|x| x == flag_key) {
-                    flag_bits |= 1 << pos;
-                }
+        // Inject Recoil flag bit
+        if data.recoil.is_some()
+            || data.has_crash_damage.unwrap_or(false)
+            || data.mind_blown_recoil.unwrap_or(false)
+        {
+            if let Some(pos) = flag_names.iter().position(|x| x == "Recoil") {
+                flag_bits |= 1 << pos;
             }
+        }
 
-            // Inject Recoil flag bit
-            if data.recoil.is_some()
-                || data.has_crash_damage.unwrap_or(false)
-                || data.mind_blown_recoil.unwrap_or(false)
-            {
-                if let Some(pos) = flag_names.iter().position(|x| x == "Recoil") {
-                    flag_bits |= 1 << pos;
-                }
+        // Inject HasSecondaryEffects flag bit
+        if has_secondary_effects(data) {
+            if let Some(pos) = flag_names.iter().position(|x| x == "HasSecondaryEffects") {
+                flag_bits |= 1 << pos;
             }
+        }
 
-            // Inject HasSecondaryEffects flag bit
-            if has_secondary_effects(data) {
-                if let Some(pos) = flag_names.iter().position(|x| x == "HasSecondaryEffects") {
-                    flag_bits |= 1 << pos;
-                }
+        if data.ohko.is_some() {
+            if let Some(pos) = flag_names.iter().position(|x| x == "Ohko") {
+                flag_bits |= 1 << pos;
             }
+        }
 
-            if data.ohko.is_some() {
-                if let Some(pos) = flag_names.iter().position(|x| x == "Ohko") {
-                    flag_bits |= 1 << pos;
-                }
+        if data.ignore_ability.unwrap_or(false) {
+            if let Some(pos) = flag_names.iter().position(|x| x == "IgnoresAbility") {
+                flag_bits |= 1 << pos;
             }
+        }
 
-            if breaks_screens_moves.contains(&data.name.as_str()) {
-                if let Some(pos) = flag_names.iter().position(|x| x == "BreaksScreens") {
-                    flag_bits |= 1 << pos;
-                }
+        if data.volatile_status.as_deref() == Some("partiallytrapped") {
+            if let Some(pos) = flag_names.iter().position(|x| x == "PartiallyTraps") {
+                flag_bits |= 1 << pos;
             }
+        }
 
-            if variable_power_moves.contains(&data.name.as_str()) {
-                if let Some(pos) = flag_names.iter().position(|x| x == "VariablePower") {
-                    flag_bits |= 1 << pos;
-                }
+        if data.crit_ratio.unwrap_or(1) > 1 {
+            if let Some(pos) = flag_names.iter().position(|x| x == "HighCrit") {
+                flag_bits |= 1 << pos;
             }
+        }
 
-            let flag_bits_lit = if use_u64 {
-                quote! { #flag_bits }
-            } else {
-                let val = flag_bits as u32;
-                quote! { #val }
-            };
+        if breaks_screens_moves.contains(&data.name.as_str()) {
+            if let Some(pos) = flag_names.iter().position(|x| x == "BreaksScreens") {
+                flag_bits |= 1 << pos;
+            }
+        }
 
-            // Terrain
-            let terrain_ident = if let Some(t) = &data.terrain {
-                let t_ident = format_ident!("{}", t.replace("terrain", "").to_pascal_case());
-                quote! { TerrainId::#t_ident }
-            } else {
-                quote! { TerrainId::None }
-            };
+        if variable_power_moves.contains(&data.name.as_str()) {
+            if let Some(pos) = flag_names.iter().position(|x| x == "VariablePower") {
+                flag_bits |= 1 << pos;
+            }
+        }
 
-            quote! {
-                Move {
-                    name: #name,
-                    primary_type: Type::#type_ident,
-                    category: MoveCategory::#cat_ident,
-                    power: #power,
-                    accuracy: #accuracy,
-                    pp: #pp,
-                    priority: #priority,
-                    flags: MoveFlags::from_bits_truncate(#flag_bits_lit),
-                    terrain: #terrain_ident,
-                    target: MoveTarget::#target_ident,
-                    multihit: (#min_hits, #max_hits),
+        let flag_bits_lit = if use_u64 {
+            quote! { #flag_bits }
+        } else {
+            let val = flag_bits as u32;
+            quote! { #val }
+        };
+
+        // Terrain
+        let terrain_ident = if let Some(t) = &data.terrain {
+            let t_ident = format_ident!("{}", t.replace("terrain", "").to_pascal_case());
+            quote! { TerrainId::#t_ident }
+        } else {
+            quote! { TerrainId::None }
+        };
+
+        // Z-Power: explicit power override or Z-status effect
+        let z_power = match &data.z_move {
+            Some(z) => match (z.base_power, &z.boost, z.effect.as_deref()) {
+                (Some(power), _, _) => quote! { Some(ZPower::BasePower(#power)) },
+                (_, Some(boost), _) => {
+                    let stages = ["atk", "def", "spa", "spd", "spe", "accuracy", "evasion"]
+                        .map(|stat| boost.get(stat).copied().unwrap_or(0));
+                    quote! { Some(ZPower::Boost([#(#stages),*])) }
                 }
+                (_, _, Some(effect)) => {
+                    let ident = match effect {
+                        "clearnegativeboost" => "ClearNegativeBoost",
+                        "heal" => "Heal",
+                        "crit2" => "Crit2",
+                        "redirect" => "Redirect",
+                        "healreplacement" => "HealReplacement",
+                        "curse" => "Curse",
+                        other => panic!("unknown Z-Move effect: {}", other),
+                    };
+                    let ident = format_ident!("{}", ident);
+                    quote! { Some(ZPower::#ident) }
+                }
+                _ => quote! { None },
+            },
+            None => quote! { None },
+        };
+
+        let gen = move_gen(data);
+        let nonstandard = nonstandard_tokens(data.is_nonstandard.as_deref());
+
+        quote! {
+            Move {
+                name: #name,
+                primary_type: Type::#type_ident,
+                category: MoveCategory::#cat_ident,
+                power: #power,
+                accuracy: #accuracy,
+                pp: #pp,
+                priority: #priority,
+                flags: MoveFlags::from_bits_truncate(#flag_bits_lit),
+                terrain: #terrain_ident,
+                target: MoveTarget::#target_ident,
+                multihit: (#min_hits, #max_hits),
+                z_power: #z_power,
+                gen: #gen,
+                nonstandard: #nonstandard,
             }
+        }
+    };
+    let move_data_entries: Vec<TokenStream> =
+        valid_moves.iter().map(|(_, data)| encode(data)).collect();
+
+    // 4. Per-generation overlays: only moves whose generated data differs
+    let base_values: BTreeMap<String, serde_json::Value> =
+        serde_json::from_str(&json).expect("parse moves");
+    let base_tokens: Vec<String> = move_data_entries.iter().map(|t| t.to_string()).collect();
+    let overlays: Vec<TokenStream> = gen_mods::resolve(data_dir, "moves.json", &base_values)
+        .iter()
+        .map(|resolved| {
+            let entries = valid_moves
+                .iter()
+                .enumerate()
+                .filter_map(|(idx, (key, _))| {
+                    let old: MoveData = serde_json::from_value(resolved.get(*key)?.clone()).ok()?;
+                    let tokens = encode(&old);
+                    if tokens.to_string() == base_tokens[idx] {
+                        return None;
+                    }
+                    let idx = idx as u16;
+                    Some(quote! { (#idx, #tokens) })
+                });
+            quote! { &[#(#entries),*] }
         })
         .collect();
 
@@ -269,7 +367,11 @@ pub fn generate(out_dir: &Path, data_dir: &Path) {
         #[repr(u16)]
         pub enum MoveId {
             #[default]
-            #(#variants),*
+            #(#variants,)*
+            #(
+                /// Reserved for a runtime custom move (see `custom`)
+                #custom_idents = #custom_ids,
+            )*
         }
 
         #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -292,6 +394,37 @@ pub fn generate(out_dir: &Path, data_dir: &Path) {
             #(#target_variants),*
         }
 
+        impl MoveTarget {
+            /// Parse a Showdown target (e.g. "allAdjacentFoes")
+            pub fn from_str(s: &str) -> Option<Self> {
+                match s {
+                    #(#target_strs => Some(MoveTarget::#target_variants),)*
+                    _ => None,
+                }
+            }
+        }
+
+
+        /// What a move gains when used with its Z-Crystal
+        #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+        pub enum ZPower {
+            /// Z-Move power, overriding the base power table
+            BasePower(u16),
+            /// Stat stages the user gains (`BattleState::boosts` order)
+            Boost([i8; 7]),
+            /// Clears the user's negative stat stages
+            ClearNegativeBoost,
+            /// Fully heals the user
+            Heal,
+            /// Raises the user's critical hit ratio by 2 stages
+            Crit2,
+            /// Makes the user the center of attention
+            Redirect,
+            /// Fully heals the Pokémon that switches in
+            HealReplacement,
+            /// Ghost-type users fully heal, others gain +1 Attack
+            Curse,
+        }
 
         /// Static move data
         #[derive(Clone, Copy, Debug)]
@@ -307,6 +440,13 @@ pub fn generate(out_dir: &Path, data_dir: &Path) {
             pub terrain: TerrainId,
             pub target: MoveTarget,
             pub multihit: (u8, u8),
+            /// Z-Power override or Z-status effect (`None` = power from the
+            /// base power table, or no Z-effect for status moves)
+            pub z_power: Option<ZPower>,
+            /// Generation that introduced the move
+            pub gen: u8,
+            /// Showdown's `isNonstandard` category (`None` = standard)
+            pub nonstandard: Option<crate::dex::Nonstandard>,
         }
 
 
@@ -314,19 +454,65 @@ pub fn generate(out_dir: &Path, data_dir: &Path) {
             /// Total number of moves
             pub const COUNT: usize = #count;
 
-            /// Look up move by key string
+            /// Every move, in ID order (`ALL[id as usize] == id`)
+            pub const ALL: [MoveId; #count] = [#(MoveId::#all_idents),*];
+
+            /// Number of IDs reserved past `COUNT` for custom moves
+            pub const CUSTOM_SLOTS: usize = #custom_slots;
+
+            /// The reserved custom move IDs (`CUSTOM[i] as usize == COUNT + i`)
+            pub const CUSTOM: [MoveId; #custom_slots] = [#(MoveId::#custom_idents),*];
+
+            /// Convert a numeric ID (`id as u16`) back, e.g. across FFI.
+            /// Custom IDs convert only while a data mod defines them.
+            #[inline]
+            pub fn from_u16(id: u16) -> Option<Self> {
+                match Self::ALL.get(id as usize) {
+                    Some(&id) => Some(id),
+                    None => crate::custom::move_from_u16(id),
+                }
+            }
+
+            /// Look up move by key string (including installed custom
+            /// moves, see `custom`)
             #[inline]
             pub fn from_str(s: &str) -> Option<Self> {
-                MOVE_LOOKUP.get(s).copied()
+                match MOVE_LOOKUP.get(s) {
+                    Some(&id) => Some(id),
+                    None => crate::custom::move_id(s),
+                }
             }
 
-            /// Get move data
+            /// Get move data (custom data when a data mod overrides or adds
+            /// this move)
             #[inline]
             pub fn data(self) -> &'static Move {
-                &MOVES[self as usize]
+                match crate::custom::move_data(self) {
+                    Some(data) => data,
+                    None => &MOVES[self as usize],
+                }
+            }
+
+            /// Move data as of generation `gen` (1-9): power, accuracy, type,
+            /// category and PP as they were in that generation's games.
+            /// Same as `data()` when built without `data/mods`.
+            #[inline]
+            pub fn data_in(self, gen: u8) -> &'static Move {
+                let overlay = match gen {
+                    1..=8 => MOVE_OVERLAYS[gen as usize - 1],
+                    _ => return self.data(),
+                };
+                match overlay.binary_search_by_key(&(self as u16), |(id, _)| *id) {
+                    Ok(i) => &overlay[i].1,
+                    Err(_) => self.data(),
+                }
             }
         }
 
+        /// Moves whose data differs from the current generation's in Gens
+        /// 1-8 (`MOVE_OVERLAYS[gen - 1]`, sorted by move ID)
+        static MOVE_OVERLAYS: [&[(u16, Move)]; 8] = [#(#overlays),*];
+
         /// Static move data array
         pub static MOVES: [Move; #count] = [
             #(#move_data_entries),*
@@ -1,8 +1,10 @@
 //! Species data and lookup map generation.
 
-use crate::models::{AbilityData, PokedexEntry};
+use crate::gen_mods;
+use crate::helpers::{nonstandard_tokens, species_gen};
+use crate::models::{AbilityData, FormatsDataEntry, PokedexEntry};
 use proc_macro2::TokenStream;
-use quote::quote;
+use quote::{format_ident, quote};
 use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fs::File;
 use std::io::{BufWriter, Write};
@@ -15,6 +17,13 @@ pub fn generate(out_dir: &Path, data_dir: &Path) {
     let pokedex: BTreeMap<String, PokedexEntry> =
         serde_json::from_str(&json).expect("parse pokedex");
 
+    // Species availability lives in formats-data.json, which is optional
+    let formats_data: BTreeMap<String, FormatsDataEntry> =
+        match fs::read_to_string(data_dir.join("formats-data.json")) {
+            Ok(json) => serde_json::from_str(&json).expect("parse formats-data"),
+            Err(_) => BTreeMap::new(),
+        };
+
     // Load abilities for lookup
     let abilities_json =
         fs::read_to_string(data_dir.join("abilities.json")).expect("abilities.json");
@@ -81,142 +90,181 @@ pub fn generate(out_dir: &Path, data_dir: &Path) {
     // Generate species data array
     let count = valid_species.len();
 
-    let species_data: Vec<TokenStream> = valid_species
-        .iter()
-        .map(|(_key, entry)| {
-            let stats = entry.base_stats.as_ref().unwrap();
-            let hp = stats.hp;
-            let atk = stats.atk;
-            let def = stats.def;
-            let spa = stats.spa;
-            let spd = stats.spd;
-            let spe = stats.spe;
-
-            // Types
-            let types = entry.types.as_ref().unwrap();
-            let type1 = types
-                .first()
-                .and_then(|t| type_to_idx.get(t.to_lowercase().as_str()))
-                .copied()
-                .unwrap_or(0);
-            let type2 = types
-                .get(1)
-                .and_then(|t| type_to_idx.get(t.to_lowercase().as_str()))
-                .map(|&t| t + 1) // +1 so 0 means "no second type"
-                .unwrap_or(0);
-
-            // Weight (fixed-point: kg * 10)
-            let weight = (entry.weightkg * 10.0).round() as u16;
-
-            // Abilities (up to 3: slot 0, slot 1, hidden)
-            let ability_key = |slot: &str| -> u16 {
-                entry
-                    .abilities
-                    .get(slot)
-                    .and_then(|name| {
-                        // Convert ability name to key format
-                        let key = name
-                            .to_lowercase()
-                            .chars()
-                            .filter(|c| c.is_alphanumeric())
-                            .collect::<String>();
-                        ability_to_idx.get(key.as_str()).copied()
-                    })
-                    .unwrap_or(0)
-            };
-            let ability0 = ability_key("0");
-            let ability1 = ability_key("1");
-            let hidden = ability_key("H");
-
-            // Base species (for forms)
-            let base = entry
-                .base_species
-                .as_ref()
+    let encode = |key: &str, entry: &PokedexEntry| -> TokenStream {
+        let stats = entry.base_stats.as_ref().unwrap();
+        let hp = stats.hp;
+        let atk = stats.atk;
+        let def = stats.def;
+        let spa = stats.spa;
+        let spd = stats.spd;
+        let spe = stats.spe;
+
+        // Types
+        let types = entry.types.as_ref().unwrap();
+        let type1 = types
+            .first()
+            .and_then(|t| type_to_idx.get(t.to_lowercase().as_str()))
+            .copied()
+            .unwrap_or(0);
+        let type2 = types
+            .get(1)
+            .and_then(|t| type_to_idx.get(t.to_lowercase().as_str()))
+            .map(|&t| t + 1) // +1 so 0 means "no second type"
+            .unwrap_or(0);
+
+        // Weight (fixed-point: kg * 10)
+        let weight = (entry.weightkg * 10.0).round() as u16;
+
+        // Abilities (up to 3: slot 0, slot 1, hidden)
+        let ability_key = |slot: &str| -> u16 {
+            entry
+                .abilities
+                .get(slot)
                 .and_then(|name| {
-                    let base_key = name
+                    // Convert ability name to key format
+                    let key = name
                         .to_lowercase()
                         .chars()
                         .filter(|c| c.is_alphanumeric())
                         .collect::<String>();
-                    key_to_idx.get(base_key.as_str()).copied()
+                    ability_to_idx.get(key.as_str()).copied()
                 })
-                .map(|i| i + 1) // +1 so 0 means "is base species"
-                .unwrap_or(0);
+                .unwrap_or(0)
+        };
+        let ability0 = ability_key("0");
+        let ability1 = ability_key("1");
+        let hidden = ability_key("H");
+
+        // Base species (for forms)
+        let base = entry
+            .base_species
+            .as_ref()
+            .and_then(|name| {
+                let base_key = name
+                    .to_lowercase()
+                    .chars()
+                    .filter(|c| c.is_alphanumeric())
+                    .collect::<String>();
+                key_to_idx.get(base_key.as_str()).copied()
+            })
+            .map(|i| i + 1) // +1 so 0 means "is base species"
+            .unwrap_or(0);
 
-            // Flags
-            // Shedinja always has 1 HP (mechanics/stats.md)
-            let mut flags: u8 = if entry.name == "Shedinja" { 1 << 0 } else { 0 };
+        // Flags
+        // Shedinja always has 1 HP (mechanics/stats.md)
+        let mut flags: u8 = if entry.name == "Shedinja" { 1 << 0 } else { 0 };
 
-            // NFE flag
-            if entry.evos.as_ref().map(|v| !v.is_empty()).unwrap_or(false) {
-                flags |= 1 << 1;
-            }
+        // NFE flag
+        if entry.evos.as_ref().map(|v| !v.is_empty()).unwrap_or(false) {
+            flags |= 1 << 1;
+        }
 
-            // Gender Ratio
-            let gender_ratio_tokens = match entry.gender.as_deref() {
-                Some("N") => quote! { GenderRatio::Genderless },
-                Some("M") => quote! { GenderRatio::AlwaysMale },
-                Some("F") => quote! { GenderRatio::AlwaysFemale },
-                _ => {
-                    match &entry.gender_ratio {
-                        Some(ratio) => {
-                             let m = ratio.get("M").copied().unwrap_or(0.5);
-                             if m >= 0.875 { quote! { GenderRatio::SevenToOne } }
-                             else if m >= 0.75 { quote! { GenderRatio::ThreeToOne } }
-                             else if m >= 0.5 { quote! { GenderRatio::OneToOne } }
-                             else if m >= 0.25 { quote! { GenderRatio::OneToThree } }
-                             else if m >= 0.125 { quote! { GenderRatio::OneToSeven } }
-                             else { quote! { GenderRatio::AlwaysFemale } }
-                        },
-                        None => quote! { GenderRatio::OneToOne },
-                    }
-                }
-            };
+        // Evolved flag (Little Cup eligibility)
+        if entry.prevo.is_some() {
+            flags |= 1 << 2;
+        }
 
-            // Forme Lookups
-            let mut mega = 0u16;
-            let mut mega_y = 0u16;
-            let mut primal = 0u16;
+        // Gender Ratio
+        let gender_ratio_tokens = match entry.gender.as_deref() {
+            Some("N") => quote! { GenderRatio::Genderless },
+            Some("M") => quote! { GenderRatio::AlwaysMale },
+            Some("F") => quote! { GenderRatio::AlwaysFemale },
+            _ => {
+                match &entry.gender_ratio {
+                    Some(ratio) => {
+                         let m = ratio.get("M").copied().unwrap_or(0.5);
+                         if m >= 0.875 { quote! { GenderRatio::SevenToOne } }
+                         else if m >= 0.75 { quote! { GenderRatio::ThreeToOne } }
+                         else if m >= 0.5 { quote! { GenderRatio::OneToOne } }
+                         else if m >= 0.25 { quote! { GenderRatio::OneToThree } }
+                         else if m >= 0.125 { quote! { GenderRatio::OneToSeven } }
+                         else { quote! { GenderRatio::AlwaysFemale } }
+                    },
+                    None => quote! { GenderRatio::OneToOne },
+                }
+            }
+        };
 
-            if let Some(formes) = &entry.other_formes {
-                 for forme_name in formes {
-                     let forme_key = forme_name
-                        .to_lowercase()
-                        .chars()
-                        .filter(|c| c.is_alphanumeric())
-                        .collect::<String>();
+        // Forme Lookups
+        let mut mega = 0u16;
+        let mut mega_y = 0u16;
+        let mut primal = 0u16;
 
-                     if let Some(&idx) = key_to_idx.get(forme_key.as_str()) {
-                         if forme_key.ends_with("megax") {
-                             mega = idx + 1;
-                         } else if forme_key.ends_with("megay") {
-                             mega_y = idx + 1;
-                         } else if forme_key.ends_with("mega") {
-                             mega = idx + 1;
-                         } else if forme_key.ends_with("primal") {
-                             primal = idx + 1;
-                         }
+        if let Some(formes) = &entry.other_formes {
+             for forme_name in formes {
+                 let forme_key = forme_name
+                    .to_lowercase()
+                    .chars()
+                    .filter(|c| c.is_alphanumeric())
+                    .collect::<String>();
+
+                 if let Some(&idx) = key_to_idx.get(forme_key.as_str()) {
+                     if forme_key.ends_with("megax") {
+                         mega = idx + 1;
+                     } else if forme_key.ends_with("megay") {
+                         mega_y = idx + 1;
+                     } else if forme_key.ends_with("mega") {
+                         mega = idx + 1;
+                     } else if forme_key.ends_with("primal") {
+                         primal = idx + 1;
                      }
                  }
+             }
+        }
+
+        let name = &entry.name;
+        let gen = species_gen(entry);
+        let nonstandard = nonstandard_tokens(
+            formats_data
+                .get(key)
+                .and_then(|data| data.is_nonstandard.as_deref()),
+        );
+
+        quote! {
+            Species {
+                name: #name,
+                base_stats: [#hp, #atk, #def, #spa, #spd, #spe],
+                type1: #type1,
+                type2: #type2,
+                weight: #weight,
+                ability0: #ability0,
+                ability1: #ability1,
+                hidden_ability: #hidden,
+                base_species: #base,
+                flags: #flags,
+                gender_ratio: #gender_ratio_tokens,
+                mega_forme: #mega,
+                mega_forme_y: #mega_y,
+                primal_forme: #primal,
+                gen: #gen,
+                nonstandard: #nonstandard,
             }
+        }
+    };
+    let species_data: Vec<TokenStream> =
+        valid_species.iter().map(|(key, entry)| encode(key, entry)).collect();
 
-            quote! {
-                Species {
-                    base_stats: [#hp, #atk, #def, #spa, #spd, #spe],
-                    type1: #type1,
-                    type2: #type2,
-                    weight: #weight,
-                    ability0: #ability0,
-                    ability1: #ability1,
-                    hidden_ability: #hidden,
-                    base_species: #base,
-                    flags: #flags,
-                    gender_ratio: #gender_ratio_tokens,
-                    mega_forme: #mega,
-                    mega_forme_y: #mega_y,
-                    primal_forme: #primal,
+    // Per-generation overlays: only species whose generated data differs
+    // (base stats, types, abilities)
+    let base_values: BTreeMap<String, serde_json::Value> =
+        serde_json::from_str(&json).expect("parse pokedex");
+    let base_tokens: Vec<String> = species_data.iter().map(|t| t.to_string()).collect();
+    let overlays: Vec<TokenStream> = gen_mods::resolve(data_dir, "pokedex.json", &base_values)
+        .iter()
+        .map(|resolved| {
+            let entries = valid_species.iter().enumerate().filter_map(|(idx, (key, _))| {
+                let old: PokedexEntry =
+                    serde_json::from_value(resolved.get(*key)?.clone()).ok()?;
+                old.base_stats.as_ref()?;
+                old.types.as_ref()?;
+                let tokens = encode(key, &old);
+                if tokens.to_string() == base_tokens[idx] {
+                    return None;
                 }
-            }
+                let idx = idx as u16;
+                Some(quote! { (#idx, #tokens) })
+            });
+            quote! { &[#(#entries),*] }
         })
         .collect();
 
@@ -227,6 +275,14 @@ pub fn generate(out_dir: &Path, data_dir: &Path) {
     }
     let phf_str = phf_map.build().to_string();
 
+    // Named constants (`SpeciesId::CUBONE`) so hooks can match species
+    // without a runtime lookup
+    let const_names: Vec<_> = species_keys
+        .iter()
+        .map(|key| format_ident!("{}", key.to_uppercase()))
+        .collect();
+    let const_ids: Vec<u16> = (0..species_keys.len() as u16).collect();
+
     // Generate base species lookup map
     let mut base_phf = phf_codegen::Map::new();
     for (key, entry) in &valid_species {
@@ -263,6 +319,8 @@ pub fn generate(out_dir: &Path, data_dir: &Path) {
         #[derive(Clone, Copy, Debug)]
         #[repr(C)]
         pub struct Species {
+            /// Display name (e.g. "Charizard-Mega-X")
+            pub name: &'static str,
             /// Base stats: [hp, atk, def, spa, spd, spe]
             pub base_stats: [u8; 6],
             /// Primary type index
@@ -289,33 +347,64 @@ pub fn generate(out_dir: &Path, data_dir: &Path) {
             pub mega_forme_y: u16,
             /// Primal Forme ID + 1 (0 = none)
             pub primal_forme: u16,
+            /// Generation that introduced the species or forme
+            pub gen: u8,
+            /// Showdown's `isNonstandard` category (`None` = standard)
+            pub nonstandard: Option<crate::dex::Nonstandard>,
         }
 
         /// Flag: Shedinja's HP is always 1
         pub const FLAG_FORCE_1_HP: u8 = 1 << 0;
         /// Flag: Species is Not Fully Evolved (NFE)
         pub const FLAG_NFE: u8 = 1 << 1;
+        /// Flag: Species evolves from another (has a prevolution)
+        pub const FLAG_EVOLVED: u8 = 1 << 2;
 
         impl SpeciesId {
             /// Total number of species
             pub const COUNT: usize = #count;
 
-            /// Look up species by key string
+            #(pub const #const_names: SpeciesId = SpeciesId(#const_ids);)*
+
+            /// Look up species by key string (including installed custom
+            /// species, see `custom`)
             #[inline]
             pub fn from_str(s: &str) -> Option<Self> {
-                SPECIES_LOOKUP.get(s).copied()
+                match SPECIES_LOOKUP.get(s) {
+                    Some(&id) => Some(id),
+                    None => crate::custom::species_id(s),
+                }
             }
 
-            /// Get species data
+            /// Get species data (custom data when a data mod overrides or
+            /// adds this species)
             #[inline]
             pub fn data(self) -> &'static Species {
-                &SPECIES[self.0 as usize]
+                match crate::custom::species(self) {
+                    Some(data) => data,
+                    None => &SPECIES[self.0 as usize],
+                }
+            }
+
+            /// Species data as of generation `gen` (1-9): base stats, types
+            /// and abilities as they were in that generation's games. Same as
+            /// `data()` when built without `data/mods`.
+            #[inline]
+            pub fn data_in(self, gen: u8) -> &'static Species {
+                let overlay = match gen {
+                    1..=8 => SPECIES_OVERLAYS[gen as usize - 1],
+                    _ => return self.data(),
+                };
+                match overlay.binary_search_by_key(&self.0, |(id, _)| *id) {
+                    Ok(i) => &overlay[i].1,
+                    Err(_) => self.data(),
+                }
             }
 
             /// Get base species (returns self if already base)
             #[inline]
             pub fn base(self) -> Self {
-                let base = SPECIES[self.0 as usize].base_species;
+                let base = self.data().base_species;
                 if base == 0 {
                     self
                 } else {
@@ -370,6 +459,10 @@ pub fn generate(out_dir: &Path, data_dir: &Path) {
         pub static SPECIES: [Species; #count] = [
             #(#species_data),*
         ];
+
+        /// Species whose data differs from the current generation's in Gens
+        /// 1-8 (`SPECIES_OVERLAYS[gen - 1]`, sorted by species ID)
+        static SPECIES_OVERLAYS: [&[(u16, Species)]; 8] = [#(#overlays),*];
     };
 
     let dest = out_dir.join("species.rs");
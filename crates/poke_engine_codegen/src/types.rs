@@ -1,5 +1,6 @@
 //! Type enum and type chart generation.
 
+use crate::gen_mods;
 use crate::models::TypeChartEntry;
 use heck::ToPascalCase;
 use proc_macro2::TokenStream;
@@ -86,33 +87,64 @@ pub fn generate(out_dir: &Path, data_dir: &Path) {
 
     // Build effectiveness matrix
     // Matrix[defender][attacker] = effectiveness
-    let mut matrix: Vec<Vec<u8>> = vec![vec![0; type_count]; type_count];
-    for (def_idx, def_name) in type_names.iter().enumerate() {
-        let entry = &chart[*def_name];
-        for (atk_idx, atk_name) in type_names.iter().enumerate() {
-            let atk_pascal = atk_name.to_pascal_case();
-            let eff = entry.damage_taken.get(&atk_pascal).copied().unwrap_or(0);
-            matrix[def_idx][atk_idx] = eff;
+    let build_matrix = |chart: &BTreeMap<String, TypeChartEntry>| -> Vec<Vec<u8>> {
+        let mut matrix: Vec<Vec<u8>> = vec![vec![0; type_count]; type_count];
+        for (def_idx, def_name) in type_names.iter().enumerate() {
+            let Some(entry) = chart.get(*def_name) else {
+                continue;
+            };
+            for (atk_idx, atk_name) in type_names.iter().enumerate() {
+                let atk_pascal = atk_name.to_pascal_case();
+                let eff = entry.damage_taken.get(&atk_pascal).copied().unwrap_or(0);
+                matrix[def_idx][atk_idx] = eff;
+            }
         }
-    }
+        matrix
+    };
+    let matrix = build_matrix(&chart);
 
-    let matrix_rows: Vec<TokenStream> = matrix
-        .iter()
-        .map(|row| {
-            let cells: Vec<TokenStream> = row
-                .iter()
-                .map(|&v| {
-                    let ident = match v {
-                        0 => format_ident!("Normal"),
-                        1 => format_ident!("SuperEffective"),
-                        2 => format_ident!("Resistant"),
-                        3 => format_ident!("Immune"),
-                        _ => format_ident!("Normal"),
-                    };
-                    quote! { TypeEffectiveness::#ident }
-                })
-                .collect();
-            quote! { [#(#cells),*] }
+    let matrix_tokens = |matrix: &[Vec<u8>]| -> Vec<TokenStream> {
+        matrix
+            .iter()
+            .map(|row| {
+                let cells: Vec<TokenStream> = row
+                    .iter()
+                    .map(|&v| {
+                        let ident = match v {
+                            0 => format_ident!("Normal"),
+                            1 => format_ident!("SuperEffective"),
+                            2 => format_ident!("Resistant"),
+                            3 => format_ident!("Immune"),
+                            _ => format_ident!("Normal"),
+                        };
+                        quote! { TypeEffectiveness::#ident }
+                    })
+                    .collect();
+                quote! { [#(#cells),*] }
+            })
+            .collect()
+    };
+    let matrix_rows = matrix_tokens(&matrix);
+
+    // Older generations' charts (Gen 1 quirks, Steel resisting Ghost/Dark
+    // before Gen 6, no Fairy before Gen 6), where they differ
+    let gen_charts_loaded = gen_mods::any_present(data_dir, "typechart.json");
+    let base_values: BTreeMap<String, serde_json::Value> =
+        serde_json::from_str(&json).expect("parse typechart");
+    let gen_charts: Vec<TokenStream> = gen_mods::resolve(data_dir, "typechart.json", &base_values)
+        .into_iter()
+        .map(|resolved| {
+            let mut values = base_values.clone();
+            values.extend(resolved);
+            let gen_chart: BTreeMap<String, TypeChartEntry> =
+                serde_json::from_value(serde_json::to_value(values).unwrap())
+                    .expect("parse typechart mod");
+            let gen_matrix = build_matrix(&gen_chart);
+            if gen_matrix == matrix {
+                return quote! { None };
+            }
+            let rows = matrix_tokens(&gen_matrix);
+            quote! { Some(&[#(#rows),*]) }
         })
         .collect();
 
@@ -198,6 +230,35 @@ pub fn generate(out_dir: &Path, data_dir: &Path) {
             }
             mult
         }
+
+        /// Whether older generations' type charts (`data/mods`) were
+        /// available at build time
+        pub const GEN_TYPE_CHARTS_LOADED: bool = #gen_charts_loaded;
+
+        /// Type charts of Gens 1-8 where they differ from `TYPE_CHART`
+        static GEN_TYPE_CHARTS: [Option<&[[TypeEffectiveness; #type_count_lit]; #type_count_lit]>; 8] = [
+            #(#gen_charts),*
+        ];
+
+        /// `type_effectiveness` with generation `gen`'s (1-9) type chart.
+        /// Same as `type_effectiveness` when built without `data/mods`.
+        #[inline]
+        pub fn type_effectiveness_in(
+            gen: u8,
+            attacker: Type,
+            defender1: Type,
+            defender2: Option<Type>,
+        ) -> u8 {
+            let chart = match gen {
+                1..=8 => GEN_TYPE_CHARTS[gen as usize - 1].unwrap_or(&TYPE_CHART),
+                _ => &TYPE_CHART,
+            };
+            let mut mult = chart[defender1 as usize][attacker as usize].multiplier();
+            if let Some(t2) = defender2 {
+                mult = mult * chart[t2 as usize][attacker as usize].multiplier() / 4;
+            }
+            mult
+        }
     };
 
     let dest = out_dir.join("types.rs");
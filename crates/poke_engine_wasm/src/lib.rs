@@ -0,0 +1,11 @@
+//! WebAssembly build of `poke_engine`.
+//!
+//! The bindings live in `poke_engine::wasm`; this crate only exists to be
+//! the `cdylib` that wasm-pack builds, so `poke_engine` itself stays an
+//! rlib usable from `no_std` crates.
+//!
+//! ```sh
+//! wasm-pack build crates/poke_engine_wasm
+//! ```
+
+pub use poke_engine::wasm::*;
@@ -74,6 +74,7 @@ interface CapturedCase {
     };
     move: {
         name: string;
+        originalName?: string;
         useZ?: boolean;
         isCrit?: boolean;
         hits?: number;
@@ -135,6 +136,8 @@ function serializePokemon(p: any): CapturedCase['attacker'] {
 
 function serializeMove(m: any): CapturedCase['move'] {
     const result: CapturedCase['move'] = { name: m.name };
+    // Z-Moves are renamed; keep the base move so the engine can derive them
+    if (m.originalName && m.originalName !== m.name) result.originalName = m.originalName;
     if (m.useZ) result.useZ = m.useZ;
     if (m.isCrit) result.isCrit = m.isCrit;
     if (m.hits && m.hits > 1) result.hits = m.hits;
@@ -5,6 +5,7 @@
 //
 // Output:
 //   data/*.json
+//   data/mods/genN/{pokedex,moves,typechart}.json (Gens 1-8 deltas)
 
 import fs from 'fs';
 import path from 'path';
@@ -35,6 +36,9 @@ const naturesModule = await import(
 const learnsetsModule = await import(
     pathToFileURL(path.join(showdownRoot, 'data/learnsets.ts')).href
 );
+const formatsDataModule = await import(
+    pathToFileURL(path.join(showdownRoot, 'data/formats-data.ts')).href
+);
 
 const { Pokedex } = pokedexModule as { Pokedex: unknown };
 const { Moves } = movesModule as { Moves: unknown };
@@ -43,6 +47,7 @@ const { Items } = itemsModule as { Items: unknown };
 const { Abilities } = abilitiesModule as { Abilities: unknown };
 const { Natures } = naturesModule as { Natures: unknown };
 const { Learnsets } = learnsetsModule as { Learnsets: unknown };
+const { FormatsData } = formatsDataModule as { FormatsData: unknown };
 
 const save = (name: string, data: unknown) => {
     if (data === undefined) {
@@ -67,5 +72,28 @@ save('items', Items);
 save('abilities', Abilities);
 save('natures', Natures);
 save('learnsets', Learnsets);
+// Species `isNonstandard` and tiers live here rather than in the pokedex
+save('formats-data', FormatsData);
+
+// Generation mods are deltas on the next generation (entries with
+// `inherit: true` patch the inherited entry); codegen resolves the chain.
+const modExports: [string, string][] = [
+    ['pokedex', 'Pokedex'],
+    ['moves', 'Moves'],
+    ['typechart', 'TypeChart'],
+];
+for (let gen = 1; gen <= 8; gen++) {
+    const modDir = path.join(showdownRoot, `data/mods/gen${gen}`);
+    const outDir = path.join(outputDir, `mods/gen${gen}`);
+    fs.mkdirSync(outDir, { recursive: true });
+    for (const [file, exportName] of modExports) {
+        const source = path.join(modDir, `${file}.ts`);
+        if (!fs.existsSync(source)) continue;
+        const module = await import(pathToFileURL(source).href);
+        const filePath = path.join(outDir, `${file}.json`);
+        fs.writeFileSync(filePath, JSON.stringify(module[exportName] ?? {}, null, 2));
+        console.log(`Saved: ${filePath}`);
+    }
+}
 console.log('Extraction complete.');
 // FIXME: Add version metadata from pokemon-showdown to the output.